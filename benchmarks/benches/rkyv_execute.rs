@@ -0,0 +1,41 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use openvm_benchmarks::utils::build_bench_program;
+use openvm_circuit::arch::{instructions::exe::VmExe, VmExecutor};
+use openvm_rv32im_circuit::Rv32ImConfig;
+use openvm_rv32im_transpiler::{
+    Rv32ITranspilerExtension, Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
+};
+use openvm_sdk::StdIn;
+use openvm_stark_sdk::p3_baby_bear::BabyBear;
+use openvm_transpiler::{transpiler::Transpiler, FromElf};
+
+fn benchmark_function(c: &mut Criterion) {
+    let elf = build_bench_program("rkyv").unwrap();
+    let exe = VmExe::from_elf(
+        elf,
+        Transpiler::<BabyBear>::default()
+            .with_extension(Rv32ITranspilerExtension)
+            .with_extension(Rv32MTranspilerExtension)
+            .with_extension(Rv32IoTranspilerExtension),
+    )
+    .unwrap();
+
+    let mut group = c.benchmark_group("rkyv");
+    group.sample_size(10);
+    let config = Rv32ImConfig::default();
+    let executor = VmExecutor::<BabyBear, Rv32ImConfig>::new(config);
+
+    let data = include_bytes!("../programs/rkyv/minecraft_savedata.bin");
+    group.bench_function("execute", |b| {
+        b.iter(|| {
+            executor
+                .execute(exe.clone(), StdIn::from_bytes(data))
+                .unwrap();
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_function);
+criterion_main!(benches);
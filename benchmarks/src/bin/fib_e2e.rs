@@ -64,6 +64,8 @@ async fn main() -> Result<()> {
             internal_fri_params,
             root_fri_params,
             compiler_options,
+            composition: None,
+            batch: None,
         },
         halo2_config: Halo2Config {
             verifier_k: 24,
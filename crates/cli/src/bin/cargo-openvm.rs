@@ -1,5 +1,8 @@
 use cargo_openvm::{
-    commands::{BenchCmd, BuildCmd, EvmProvingSetupCmd, KeygenCmd, ProveCmd, RunCmd, VerifyCmd},
+    commands::{
+        BenchCmd, BuildCmd, DebugCmd, EvmProvingSetupCmd, KeygenCmd, ProveCmd, RunCmd, VerifyCmd,
+        WorkerCmd,
+    },
     OPENVM_VERSION_MESSAGE,
 };
 use clap::{Parser, Subcommand};
@@ -25,11 +28,13 @@ pub struct VmCli {
 pub enum VmCliCommands {
     Bench(BenchCmd),
     Build(BuildCmd),
+    Debug(DebugCmd),
     Keygen(KeygenCmd),
     Prove(ProveCmd),
     Run(RunCmd),
     Setup(EvmProvingSetupCmd),
     Verify(VerifyCmd),
+    Worker(WorkerCmd),
 }
 
 #[tokio::main]
@@ -40,10 +45,12 @@ async fn main() -> Result<()> {
     match command {
         VmCliCommands::Bench(cmd) => cmd.run(),
         VmCliCommands::Build(cmd) => cmd.run(),
+        VmCliCommands::Debug(cmd) => cmd.run(),
         VmCliCommands::Run(cmd) => cmd.run(),
         VmCliCommands::Keygen(cmd) => cmd.run(),
         VmCliCommands::Prove(cmd) => cmd.run(),
         VmCliCommands::Setup(cmd) => cmd.run().await,
         VmCliCommands::Verify(cmd) => cmd.run(),
+        VmCliCommands::Worker(cmd) => cmd.run(),
     }
 }
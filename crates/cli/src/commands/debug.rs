@@ -0,0 +1,292 @@
+use std::{
+    collections::BTreeSet,
+    io::{stdin, stdout, BufRead, Write},
+    ops::ControlFlow,
+    path::PathBuf,
+};
+
+use clap::Parser;
+use eyre::{eyre, Result};
+use openvm_circuit::arch::{
+    instructions::exe::{FnBound, FnBounds},
+    ExecutionError, ExecutionHook, ExecutionStep, InstructionContext,
+};
+use openvm_sdk::{fs::read_exe_from_file, Sdk};
+use openvm_stark_sdk::{
+    openvm_stark_backend::p3_field::{AbstractField, PrimeField32},
+    p3_baby_bear::BabyBear,
+};
+
+use crate::{
+    default::{DEFAULT_APP_CONFIG_PATH, DEFAULT_APP_EXE_PATH},
+    util::{read_config_toml_or_default, read_to_stdin, Input},
+};
+
+/// RISC-V registers live in address space 1, 4 bytes per register.
+const REGISTER_ADDRESS_SPACE: u32 = 1;
+const NUM_REGISTERS: u32 = 32;
+
+const HELP: &str = "\
+commands:
+  s, step                  execute one instruction
+  c, continue              run until the next breakpoint
+  b, break <pc|symbol>     set a breakpoint at a pc (e.g. 0x200c) or at the start of a function
+  d, delete <pc|symbol>    remove a breakpoint
+  l, list                  list breakpoints
+  r, regs                  print the RISC-V registers
+  x <as> <ptr> [n]         print n (default 1) memory cells of address space <as> from <ptr>
+  last                     print the previously executed instruction and its memory accesses
+  q, quit                  abort the program
+  h, help                  print this message";
+
+#[derive(Parser)]
+#[command(
+    name = "debug",
+    about = "Run an OpenVM program in an interactive step debugger"
+)]
+pub struct DebugCmd {
+    #[clap(long, action, help = "Path to OpenVM executable", default_value = DEFAULT_APP_EXE_PATH)]
+    exe: PathBuf,
+
+    #[clap(long, action, help = "Path to app config TOML file", default_value = DEFAULT_APP_CONFIG_PATH)]
+    config: PathBuf,
+
+    #[clap(long, value_parser, help = "Input to OpenVM program")]
+    input: Option<Input>,
+
+    #[clap(
+        long = "break",
+        short = 'b',
+        help = "Initial breakpoint at a pc or function symbol, may be repeated. If none are given, execution pauses at the first instruction"
+    )]
+    breakpoints: Vec<String>,
+}
+
+impl DebugCmd {
+    pub fn run(&self) -> Result<()> {
+        let exe = read_exe_from_file(&self.exe)?;
+        let app_config = read_config_toml_or_default(&self.config)?;
+        let mut debugger = Debugger::new(exe.fn_bounds.clone());
+        for location in &self.breakpoints {
+            let pc = debugger.resolve(location).ok_or_else(|| {
+                eyre!("Breakpoint location {location} is neither a pc nor a known function")
+            })?;
+            debugger.breakpoints.insert(pc);
+        }
+        debugger.stepping = self.breakpoints.is_empty();
+        println!("{HELP}");
+
+        let output = Sdk
+            .execute_with_hook(
+                exe,
+                app_config.app_vm_config,
+                read_to_stdin(&self.input)?,
+                &mut debugger,
+            )
+            .map_err(|e| match e {
+                ExecutionError::Aborted { pc } => eyre!("Debugger quit at pc {pc:#x}"),
+                e => e.into(),
+            })?;
+        println!("Execution output: {:?}", output);
+        Ok(())
+    }
+}
+
+struct Debugger {
+    fn_bounds: FnBounds,
+    breakpoints: BTreeSet<u32>,
+    /// Pause before every instruction.
+    stepping: bool,
+    last_step: Option<ExecutionStep>,
+}
+
+impl Debugger {
+    fn new(fn_bounds: FnBounds) -> Self {
+        Self {
+            fn_bounds,
+            breakpoints: BTreeSet::new(),
+            stepping: true,
+            last_step: None,
+        }
+    }
+
+    /// Resolves a pc literal (decimal or 0x-prefixed hex) or a function symbol to a pc.
+    fn resolve(&self, location: &str) -> Option<u32> {
+        if let Some(pc) = parse_u32(location) {
+            return Some(pc);
+        }
+        self.fn_bounds
+            .values()
+            .find(|f| f.name == location || demangled(&f.name) == location)
+            .or_else(|| {
+                self.fn_bounds
+                    .values()
+                    .find(|f| demangled(&f.name).contains(location))
+            })
+            .map(|f| f.start)
+    }
+
+    fn function_at(&self, pc: u32) -> Option<&FnBound> {
+        self.fn_bounds
+            .range(..=pc)
+            .next_back()
+            .map(|(_, f)| f)
+            .filter(|f| pc <= f.end)
+    }
+
+    /// Handles one command line. Returns `Some` once execution should resume or abort.
+    fn handle_command(
+        &mut self,
+        line: &str,
+        ctx: &InstructionContext<'_, BabyBear>,
+    ) -> Option<ControlFlow<()>> {
+        let args: Vec<&str> = line.split_whitespace().collect();
+        match args.as_slice() {
+            ["s" | "step"] => {
+                self.stepping = true;
+                return Some(ControlFlow::Continue(()));
+            }
+            ["c" | "continue"] => {
+                self.stepping = false;
+                return Some(ControlFlow::Continue(()));
+            }
+            ["b" | "break", location] => match self.resolve(location) {
+                Some(pc) => {
+                    self.breakpoints.insert(pc);
+                    println!("breakpoint at {pc:#x}");
+                }
+                None => println!("unknown location {location}"),
+            },
+            ["d" | "delete", location] => match self.resolve(location) {
+                Some(pc) if self.breakpoints.remove(&pc) => {
+                    println!("removed breakpoint at {pc:#x}")
+                }
+                _ => println!("no breakpoint at {location}"),
+            },
+            ["l" | "list"] => {
+                for pc in &self.breakpoints {
+                    println!("{pc:#x} {}", self.describe_pc(*pc));
+                }
+            }
+            ["r" | "regs"] => {
+                for reg in 0..NUM_REGISTERS {
+                    let bytes: [u8; 4] = std::array::from_fn(|i| {
+                        read_cell(ctx, REGISTER_ADDRESS_SPACE, 4 * reg + i as u32) as u8
+                    });
+                    println!("x{reg:<2} = {:#010x}", u32::from_le_bytes(bytes));
+                }
+            }
+            ["x", address_space, pointer, rest @ ..] if rest.len() <= 1 => {
+                let parsed = (
+                    parse_u32(address_space),
+                    parse_u32(pointer),
+                    rest.first().map_or(Some(1), |n| parse_u32(n)),
+                );
+                match parsed {
+                    (Some(address_space), Some(pointer), Some(n)) => {
+                        for ptr in pointer..pointer.saturating_add(n) {
+                            println!(
+                                "[{address_space}][{ptr:#x}] = {}",
+                                read_cell(ctx, address_space, ptr)
+                            );
+                        }
+                    }
+                    _ => println!("usage: x <as> <ptr> [n]"),
+                }
+            }
+            ["last"] => match &self.last_step {
+                Some(step) => print_step(step),
+                None => println!("no instruction executed yet"),
+            },
+            ["q" | "quit"] => return Some(ControlFlow::Break(())),
+            ["h" | "help"] => println!("{HELP}"),
+            [] => {}
+            _ => println!("unknown command, type `help` for a list of commands"),
+        }
+        None
+    }
+
+    fn describe_pc(&self, pc: u32) -> String {
+        match self.function_at(pc) {
+            Some(f) => format!("<{}+{:#x}>", demangled(&f.name), pc - f.start),
+            None => String::new(),
+        }
+    }
+}
+
+impl ExecutionHook<BabyBear> for Debugger {
+    fn on_segment_start(&mut self, segment_idx: usize) {
+        if segment_idx > 0 {
+            println!("starting segment {segment_idx}");
+        }
+    }
+
+    fn before_instruction(&mut self, ctx: &InstructionContext<'_, BabyBear>) -> ControlFlow<()> {
+        if !self.stepping && !self.breakpoints.contains(&ctx.pc) {
+            return ControlFlow::Continue(());
+        }
+        println!(
+            "segment {} | pc {:#x} {} | time {} | {:?}",
+            ctx.segment_idx,
+            ctx.pc,
+            self.describe_pc(ctx.pc),
+            ctx.timestamp,
+            ctx.instruction
+        );
+        let mut line = String::new();
+        loop {
+            print!("(openvm-debug) ");
+            stdout().flush().ok();
+            line.clear();
+            match stdin().lock().read_line(&mut line) {
+                Ok(0) | Err(_) => {
+                    // Stdin closed: run to completion without stopping again.
+                    self.stepping = false;
+                    self.breakpoints.clear();
+                    return ControlFlow::Continue(());
+                }
+                Ok(_) => {}
+            }
+            if let Some(control) = self.handle_command(line.trim(), ctx) {
+                return control;
+            }
+        }
+    }
+
+    fn after_instruction(&mut self, step: ExecutionStep) {
+        self.last_step = Some(step);
+    }
+}
+
+fn read_cell(ctx: &InstructionContext<'_, BabyBear>, address_space: u32, pointer: u32) -> u32 {
+    ctx.memory
+        .unsafe_read_cell(
+            BabyBear::from_canonical_u32(address_space),
+            BabyBear::from_canonical_u32(pointer),
+        )
+        .as_canonical_u32()
+}
+
+fn print_step(step: &ExecutionStep) {
+    println!(
+        "segment {} | pc {:#x} | time {} | {} {:?}",
+        step.segment_idx, step.pc, step.timestamp, step.opcode, step.operands
+    );
+    for access in &step.accesses {
+        println!(
+            "  {:?} [{}][{:#x}] = {:?}",
+            access.kind, access.address_space, access.pointer, access.data
+        );
+    }
+}
+
+fn parse_u32(s: &str) -> Option<u32> {
+    match s.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+fn demangled(name: &str) -> String {
+    format!("{:#}", rustc_demangle::demangle(name))
+}
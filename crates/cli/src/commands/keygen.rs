@@ -2,13 +2,21 @@ use std::path::PathBuf;
 
 use clap::Parser;
 use eyre::Result;
+use openvm_circuit::arch::VmConfig;
 use openvm_sdk::{
-    fs::{write_app_pk_to_file, write_app_vk_to_file},
+    config::AggStarkConfig,
+    fs::{
+        write_agg_stark_pk_to_file, write_app_pk_to_file, write_app_vk_to_file,
+        write_root_verifier_vk_to_file,
+    },
     Sdk,
 };
 
 use crate::{
-    default::{DEFAULT_APP_CONFIG_PATH, DEFAULT_APP_PK_PATH, DEFAULT_APP_VK_PATH},
+    default::{
+        DEFAULT_AGG_STARK_PK_PATH, DEFAULT_APP_CONFIG_PATH, DEFAULT_APP_PK_PATH,
+        DEFAULT_APP_VK_PATH, DEFAULT_ROOT_VK_PATH,
+    },
     util::read_config_toml_or_default,
 };
 
@@ -33,6 +41,29 @@ pub struct KeygenCmd {
         default_value = DEFAULT_APP_VK_PATH
     )]
     vk_output: PathBuf,
+
+    #[clap(
+        long,
+        action,
+        help = "Also generate the STARK aggregation proving key used by `prove stark`"
+    )]
+    agg_stark: bool,
+
+    #[clap(
+        long,
+        action,
+        help = "Path to output STARK aggregation proving key file",
+        default_value = DEFAULT_AGG_STARK_PK_PATH
+    )]
+    agg_stark_output: PathBuf,
+
+    #[clap(
+        long,
+        action,
+        help = "Path to output root verifier verifying key file, used by `verify stark`",
+        default_value = DEFAULT_ROOT_VK_PATH
+    )]
+    root_vk_output: PathBuf,
 }
 
 impl KeygenCmd {
@@ -40,6 +71,20 @@ impl KeygenCmd {
         let app_config = read_config_toml_or_default(&self.config)?;
         let app_pk = Sdk.app_keygen(app_config)?;
         write_app_vk_to_file(app_pk.get_vk(), &self.vk_output)?;
+        if self.agg_stark {
+            // The aggregation VMs must accept the leaf proofs and public values of this app VM.
+            let agg_stark_config = AggStarkConfig {
+                max_num_user_public_values: app_pk.app_vm_pk.vm_config.system().num_public_values,
+                leaf_fri_params: app_pk.leaf_fri_params,
+                ..Default::default()
+            };
+            let agg_stark_pk = Sdk.agg_stark_keygen(agg_stark_config)?;
+            write_root_verifier_vk_to_file(
+                agg_stark_pk.root_verifier_pk.get_vk(),
+                &self.root_vk_output,
+            )?;
+            write_agg_stark_pk_to_file(agg_stark_pk, &self.agg_stark_output)?;
+        }
         write_app_pk_to_file(app_pk, &self.output)?;
         Ok(())
     }
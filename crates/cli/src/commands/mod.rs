@@ -4,6 +4,9 @@ pub use bench::*;
 mod build;
 pub use build::*;
 
+mod debug;
+pub use debug::*;
+
 mod keygen;
 pub use keygen::*;
 
@@ -18,3 +21,6 @@ pub use setup::*;
 
 mod verify;
 pub use verify::*;
+
+mod worker;
+pub use worker::*;
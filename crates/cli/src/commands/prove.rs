@@ -7,19 +7,23 @@ use openvm_sdk::{
     commit::AppExecutionCommit,
     config::SdkVmConfig,
     fs::{
-        read_agg_pk_from_file, read_app_pk_from_file, read_exe_from_file, write_app_proof_to_file,
+        read_agg_pk_from_file, read_agg_stark_pk_from_file, read_app_pk_from_file,
+        read_exe_from_file, write_agg_stark_proof_to_file, write_app_proof_to_file,
         write_evm_proof_to_file,
     },
     keygen::AppProvingKey,
+    prover::distributed::{DistributedProver, WorkerAddress},
     NonRootCommittedExe, Sdk, StdIn,
 };
+use openvm_stark_sdk::p3_baby_bear::BabyBear;
 
 use crate::{
     default::{
-        DEFAULT_AGG_PK_PATH, DEFAULT_APP_EXE_PATH, DEFAULT_APP_PK_PATH, DEFAULT_APP_PROOF_PATH,
-        DEFAULT_EVM_PROOF_PATH, DEFAULT_PARAMS_DIR,
+        DEFAULT_AGG_PK_PATH, DEFAULT_AGG_STARK_PK_PATH, DEFAULT_AGG_STARK_PROOF_PATH,
+        DEFAULT_APP_EXE_PATH, DEFAULT_APP_PK_PATH, DEFAULT_APP_PROOF_PATH, DEFAULT_EVM_PROOF_PATH,
+        DEFAULT_PARAMS_DIR,
     },
-    util::{read_to_stdin, Input},
+    util::{commit_to_hex, read_to_stdin, Input},
 };
 
 #[derive(Parser)]
@@ -43,6 +47,36 @@ enum ProveSubCommand {
 
         #[clap(long, action, help = "Path to output proof", default_value = DEFAULT_APP_PROOF_PATH)]
         output: PathBuf,
+
+        #[clap(
+            long,
+            value_delimiter = ',',
+            help = "Comma-separated addresses of `cargo openvm worker` processes to prove segments on, e.g. 127.0.0.1:7878 or unix:/tmp/worker.sock"
+        )]
+        workers: Vec<WorkerAddress>,
+    },
+    Stark {
+        #[clap(long, action, help = "Path to app proving key", default_value = DEFAULT_APP_PK_PATH)]
+        app_pk: PathBuf,
+
+        #[clap(long, action, help = "Path to OpenVM executable", default_value = DEFAULT_APP_EXE_PATH)]
+        exe: PathBuf,
+
+        #[clap(long, action, help = "Path to STARK aggregation proving key", default_value = DEFAULT_AGG_STARK_PK_PATH)]
+        agg_stark_pk: PathBuf,
+
+        #[clap(long, value_parser, help = "Input to OpenVM program")]
+        input: Option<Input>,
+
+        #[clap(long, action, help = "Path to output proof", default_value = DEFAULT_AGG_STARK_PROOF_PATH)]
+        output: PathBuf,
+
+        #[clap(
+            long,
+            value_delimiter = ',',
+            help = "Comma-separated addresses of `cargo openvm worker` processes to prove segments and aggregation nodes on"
+        )]
+        workers: Vec<WorkerAddress>,
     },
     Evm {
         #[clap(long, action, help = "Path to app proving key", default_value = DEFAULT_APP_PK_PATH)]
@@ -67,10 +101,39 @@ impl ProveCmd {
                 exe,
                 input,
                 output,
+                workers,
             } => {
-                let (app_pk, committed_exe, input) = Self::prepare_execution(app_pk, exe, input)?;
-                let app_proof = Sdk.generate_app_proof(app_pk, committed_exe, input)?;
-                write_app_proof_to_file(app_proof, output)?;
+                let (app_pk, committed_exe, commits, input) =
+                    Self::prepare_execution(app_pk, exe, input)?;
+                let app_proof = if workers.is_empty() {
+                    Sdk.generate_app_proof(app_pk, committed_exe, input)?
+                } else {
+                    DistributedProver::new(app_pk, committed_exe, workers.clone())?
+                        .generate_app_proof(input)?
+                };
+                write_app_proof_to_file(app_proof, &commits, output)?;
+            }
+            ProveSubCommand::Stark {
+                app_pk,
+                exe,
+                agg_stark_pk,
+                input,
+                output,
+                workers,
+            } => {
+                let (app_pk, committed_exe, commits, input) =
+                    Self::prepare_execution(app_pk, exe, input)?;
+                let agg_stark_pk = read_agg_stark_pk_from_file(agg_stark_pk).map_err(|e| {
+                    eyre::eyre!("Failed to read STARK aggregation proving key: {}\nPlease run 'cargo openvm keygen --agg-stark' first", e)
+                })?;
+                let stark_proof = if workers.is_empty() {
+                    Sdk.generate_agg_stark_proof(app_pk, committed_exe, agg_stark_pk, input)?
+                } else {
+                    DistributedProver::new(app_pk, committed_exe, workers.clone())?
+                        .with_agg_stark_pk(agg_stark_pk)
+                        .generate_agg_proof(input)?
+                };
+                write_agg_stark_proof_to_file(stark_proof, &commits, output)?;
             }
             ProveSubCommand::Evm {
                 app_pk,
@@ -79,14 +142,15 @@ impl ProveCmd {
                 output,
             } => {
                 let params_reader = CacheHalo2ParamsReader::new(DEFAULT_PARAMS_DIR);
-                let (app_pk, committed_exe, input) = Self::prepare_execution(app_pk, exe, input)?;
+                let (app_pk, committed_exe, commits, input) =
+                    Self::prepare_execution(app_pk, exe, input)?;
                 println!("Generating EVM proof, this may take a lot of compute and memory...");
                 let agg_pk = read_agg_pk_from_file(DEFAULT_AGG_PK_PATH).map_err(|e| {
                     eyre::eyre!("Failed to read aggregation proving key: {}\nPlease run 'cargo openvm setup' first", e)
                 })?;
                 let evm_proof =
                     Sdk.generate_evm_proof(&params_reader, app_pk, committed_exe, agg_pk, input)?;
-                write_evm_proof_to_file(evm_proof, output)?;
+                write_evm_proof_to_file(evm_proof, &commits, output)?;
             }
         }
         Ok(())
@@ -99,6 +163,7 @@ impl ProveCmd {
     ) -> Result<(
         Arc<AppProvingKey<SdkVmConfig>>,
         Arc<NonRootCommittedExe>,
+        AppExecutionCommit<BabyBear>,
         StdIn,
     )> {
         let app_pk: Arc<AppProvingKey<SdkVmConfig>> = Arc::new(read_app_pk_from_file(app_pk)?);
@@ -110,10 +175,13 @@ impl ProveCmd {
             &committed_exe,
             &app_pk.leaf_committed_exe,
         );
-        println!("app_pk commit: {:?}", commits.app_config_commit_to_bn254());
-        println!("exe commit: {:?}", commits.exe_commit_to_bn254());
+        println!(
+            "app_pk commit: {}",
+            commit_to_hex(&commits.leaf_vm_verifier_commit)
+        );
+        println!("exe commit: {}", commit_to_hex(&commits.exe_commit));
 
         let input = read_to_stdin(input)?;
-        Ok((app_pk, committed_exe, input))
+        Ok((app_pk, committed_exe, commits, input))
     }
 }
@@ -1,11 +1,18 @@
-use std::path::PathBuf;
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
 
 use clap::Parser;
 use eyre::Result;
+use openvm_circuit::arch::{ExecutionError, ExecutionHook, ExecutionStep, ExitCode};
 use openvm_sdk::{fs::read_exe_from_file, Sdk};
+use openvm_stark_sdk::p3_baby_bear::BabyBear;
 
 use crate::{
     default::{DEFAULT_APP_CONFIG_PATH, DEFAULT_APP_EXE_PATH},
+    profiler::Profiler,
     util::{read_config_toml_or_default, read_to_stdin, Input},
 };
 
@@ -20,14 +27,104 @@ pub struct RunCmd {
 
     #[clap(long, value_parser, help = "Input to OpenVM program")]
     input: Option<Input>,
+
+    #[clap(
+        long,
+        action,
+        help = "Record a per-instruction execution trace to this file, as JSON lines"
+    )]
+    trace: Option<PathBuf>,
+
+    #[clap(
+        long,
+        action,
+        conflicts_with = "trace",
+        help = "Write folded-stack profiles weighted by cycles and by trace cells to this directory"
+    )]
+    profile: Option<PathBuf>,
 }
 
 impl RunCmd {
     pub fn run(&self) -> Result<()> {
         let exe = read_exe_from_file(&self.exe)?;
         let app_config = read_config_toml_or_default(&self.config)?;
-        let output = Sdk.execute(exe, app_config.app_vm_config, read_to_stdin(&self.input)?)?;
+        let inputs = read_to_stdin(&self.input)?;
+        let output = if let Some(trace_path) = &self.trace {
+            let mut tracer = JsonLinesTracer::new(BufWriter::new(File::create(trace_path)?));
+            let output = Sdk.execute_with_hook(exe, app_config.app_vm_config, inputs, &mut tracer);
+            // Write out the trace even if execution failed, since that is when it is most useful.
+            let num_steps = tracer.finish()?;
+            println!(
+                "Wrote {} execution steps to {}",
+                num_steps,
+                trace_path.display()
+            );
+            output?
+        } else if let Some(profile_dir) = &self.profile {
+            let mut profiler = Profiler::new(exe.fn_bounds.clone());
+            let output =
+                Sdk.execute_with_hook(exe, app_config.app_vm_config, inputs, &mut profiler);
+            profiler.write_folded(profile_dir)?;
+            println!("Wrote profiles to {}", profile_dir.display());
+            output?
+        } else {
+            let result = Sdk.execute_pure(exe, app_config.app_vm_config, inputs)?;
+            println!(
+                "Executed {} instructions, estimated {} continuation segments",
+                result.instret,
+                result.segments.len()
+            );
+            if result.exit_code != ExitCode::Success as u32 {
+                return Err(ExecutionError::FailedWithExitCode {
+                    exit_code: result.exit_code,
+                }
+                .into());
+            }
+            result.public_values
+        };
         println!("Execution output: {:?}", output);
         Ok(())
     }
 }
+
+/// Writes every [ExecutionStep] as one line of JSON.
+pub(crate) struct JsonLinesTracer<W: Write> {
+    writer: W,
+    num_steps: usize,
+    // The first write error, reported once execution is over.
+    error: Option<std::io::Error>,
+}
+
+impl<W: Write> JsonLinesTracer<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        Self {
+            writer,
+            num_steps: 0,
+            error: None,
+        }
+    }
+
+    /// Flushes the writer and returns the number of steps written.
+    pub(crate) fn finish(mut self) -> Result<usize> {
+        if let Some(e) = self.error.take() {
+            return Err(e.into());
+        }
+        self.writer.flush()?;
+        Ok(self.num_steps)
+    }
+}
+
+impl<W: Write> ExecutionHook<BabyBear> for JsonLinesTracer<W> {
+    fn after_instruction(&mut self, step: ExecutionStep) {
+        if self.error.is_some() {
+            return;
+        }
+        let res = serde_json::to_writer(&mut self.writer, &step)
+            .map_err(std::io::Error::from)
+            .and_then(|_| self.writer.write_all(b"\n"));
+        match res {
+            Ok(()) => self.num_steps += 1,
+            Err(e) => self.error = Some(e),
+        }
+    }
+}
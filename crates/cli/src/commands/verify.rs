@@ -3,15 +3,21 @@ use std::path::PathBuf;
 use clap::Parser;
 use eyre::{eyre, Result};
 use openvm_sdk::{
+    commit::{compute_app_exe_commit, AppExecutionCommit},
     fs::{
-        read_app_proof_from_file, read_app_vk_from_file, read_evm_proof_from_file,
-        read_evm_verifier_from_file,
+        read_agg_stark_proof_from_file, read_app_proof_from_file, read_app_vk_from_file,
+        read_evm_proof_from_file, read_evm_verifier_from_file, read_exe_from_file,
+        read_root_verifier_vk_from_file,
     },
     Sdk,
 };
 
-use crate::default::{
-    DEFAULT_APP_PROOF_PATH, DEFAULT_APP_VK_PATH, DEFAULT_EVM_PROOF_PATH, DEFAULT_VERIFIER_PATH,
+use crate::{
+    default::{
+        DEFAULT_AGG_STARK_PROOF_PATH, DEFAULT_APP_EXE_PATH, DEFAULT_APP_PROOF_PATH,
+        DEFAULT_APP_VK_PATH, DEFAULT_EVM_PROOF_PATH, DEFAULT_ROOT_VK_PATH, DEFAULT_VERIFIER_PATH,
+    },
+    util::parse_commit,
 };
 
 #[derive(Parser)]
@@ -29,6 +35,26 @@ enum VerifySubCommand {
 
         #[clap(long, action, help = "Path to app proof", default_value = DEFAULT_APP_PROOF_PATH)]
         proof: PathBuf,
+
+        #[clap(long, action, help = "Path to OpenVM executable the proof should be for", default_value = DEFAULT_APP_EXE_PATH)]
+        exe: PathBuf,
+    },
+    Stark {
+        #[clap(long, action, help = "Path to root verifier verifying key", default_value = DEFAULT_ROOT_VK_PATH)]
+        root_vk: PathBuf,
+
+        #[clap(long, action, help = "Path to STARK proof", default_value = DEFAULT_AGG_STARK_PROOF_PATH)]
+        proof: PathBuf,
+
+        #[clap(long, action, help = "Expected exe commit, as printed by `prove`")]
+        exe_commit: String,
+
+        #[clap(
+            long,
+            action,
+            help = "Expected app config commit, as printed by `prove`"
+        )]
+        app_config_commit: String,
     },
     Evm {
         #[clap(long, action, help = "Path to EVM proof", default_value = DEFAULT_EVM_PROOF_PATH)]
@@ -39,19 +65,55 @@ enum VerifySubCommand {
 impl VerifyCmd {
     pub fn run(&self) -> Result<()> {
         match &self.command {
-            VerifySubCommand::App { app_vk, proof } => {
+            VerifySubCommand::App { app_vk, proof, exe } => {
                 let app_vk = read_app_vk_from_file(app_vk)?;
-                let app_proof = read_app_proof_from_file(proof)?;
-                Sdk.verify_app_proof(&app_vk, &app_proof)?;
+                let exe = read_exe_from_file(exe)?;
+                let committed_exe = Sdk.commit_app_exe(app_vk.fri_params, exe)?;
+                let expected_exe_commit =
+                    compute_app_exe_commit(app_vk.memory_dimensions, &committed_exe);
+
+                let (header, app_proof) = read_app_proof_from_file(proof)?;
+                header.check_app_commit(&AppExecutionCommit {
+                    leaf_vm_verifier_commit: app_vk.leaf_vm_verifier_commit,
+                    exe_commit: expected_exe_commit,
+                })?;
+                let payload = Sdk.verify_app_proof(&app_vk, &app_proof)?;
+                if payload.exe_commit != expected_exe_commit {
+                    return Err(eyre!("App proof is not for the given executable"));
+                }
+            }
+            VerifySubCommand::Stark {
+                root_vk,
+                proof,
+                exe_commit,
+                app_config_commit,
+            } => {
+                let expected_exe_commit = parse_commit(exe_commit)?;
+                let expected_app_config_commit = parse_commit(app_config_commit)?;
+
+                let (header, stark_proof) = read_agg_stark_proof_from_file(proof)?;
+                header.check_app_config_commit(&expected_app_config_commit)?;
+                header.check_exe_commit(&expected_exe_commit)?;
+                let root_vk = read_root_verifier_vk_from_file(root_vk)?;
+                let pvs = Sdk
+                    .verify_agg_stark_proof(&root_vk, &stark_proof)
+                    .map_err(|e| eyre!("STARK proof verification failed: {}", e))?;
+                if pvs.leaf_verifier_commit != expected_app_config_commit {
+                    return Err(eyre!("STARK proof is not for the given app VM"));
+                }
+                if pvs.exe_commit != expected_exe_commit {
+                    return Err(eyre!("STARK proof is not for the given executable"));
+                }
             }
             VerifySubCommand::Evm { proof } => {
                 let evm_verifier = read_evm_verifier_from_file(DEFAULT_VERIFIER_PATH).map_err(|e| {
                     eyre::eyre!("Failed to read EVM verifier: {}\nPlease run 'cargo openvm evm-proving-setup' first", e)
                 })?;
-                let evm_proof = read_evm_proof_from_file(proof)?;
-                if !Sdk.verify_evm_proof(&evm_verifier, &evm_proof) {
-                    return Err(eyre!("EVM proof verification failed"));
-                }
+                let (_, evm_proof) = read_evm_proof_from_file(proof)?;
+                let gas_used = Sdk
+                    .verify_evm_proof(&evm_verifier, &evm_proof)
+                    .map_err(|e| eyre!("EVM proof verification failed: {}", e))?;
+                println!("EVM proof verified, gas used: {}", gas_used);
             }
         }
         Ok(())
@@ -0,0 +1,55 @@
+use std::{path::PathBuf, sync::Arc};
+
+use clap::Parser;
+use eyre::Result;
+use openvm_sdk::{
+    config::SdkVmConfig,
+    fs::{read_agg_stark_pk_from_file, read_app_pk_from_file, read_exe_from_file},
+    keygen::AppProvingKey,
+    prover::distributed::{ProverWorker, WorkerAddress},
+    Sdk,
+};
+
+use crate::default::{DEFAULT_APP_EXE_PATH, DEFAULT_APP_PK_PATH};
+
+#[derive(Parser)]
+#[command(
+    name = "worker",
+    about = "Serve segment and aggregation proving requests from `cargo openvm prove --workers`"
+)]
+pub struct WorkerCmd {
+    #[clap(
+        long,
+        help = "Address to listen on, either a TCP host:port or unix:<path> for a Unix domain socket. Connections are not authenticated, so only listen on trusted interfaces"
+    )]
+    listen: WorkerAddress,
+
+    #[clap(long, action, help = "Path to app proving key", default_value = DEFAULT_APP_PK_PATH)]
+    app_pk: PathBuf,
+
+    #[clap(long, action, help = "Path to OpenVM executable", default_value = DEFAULT_APP_EXE_PATH)]
+    exe: PathBuf,
+
+    #[clap(
+        long,
+        action,
+        help = "Path to STARK aggregation proving key, required to prove aggregation nodes"
+    )]
+    agg_stark_pk: Option<PathBuf>,
+}
+
+impl WorkerCmd {
+    pub fn run(&self) -> Result<()> {
+        let app_pk: Arc<AppProvingKey<SdkVmConfig>> =
+            Arc::new(read_app_pk_from_file(&self.app_pk)?);
+        let app_exe = read_exe_from_file(&self.exe)?;
+        let committed_exe = Sdk.commit_app_exe(app_pk.app_fri_params(), app_exe)?;
+        let mut worker = ProverWorker::new(app_pk, committed_exe);
+        if let Some(agg_stark_pk) = &self.agg_stark_pk {
+            worker = worker.with_agg_stark_pk(&read_agg_stark_pk_from_file(agg_stark_pk)?);
+        }
+        let listener = self.listen.bind()?;
+        println!("Listening on {}", listener.local_addr()?);
+        worker.serve(&listener)
+    }
+}
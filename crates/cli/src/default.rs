@@ -12,6 +12,9 @@ pub const DEFAULT_APP_EXE_PATH: &str = "./openvm/app.vmexe";
 pub const DEFAULT_APP_PK_PATH: &str = "./openvm/app.pk";
 pub const DEFAULT_APP_VK_PATH: &str = "./openvm/app.vk";
 pub const DEFAULT_APP_PROOF_PATH: &str = "./openvm/app.proof";
+pub const DEFAULT_AGG_STARK_PK_PATH: &str = "./openvm/agg_stark.pk";
+pub const DEFAULT_ROOT_VK_PATH: &str = "./openvm/root.vk";
+pub const DEFAULT_AGG_STARK_PROOF_PATH: &str = "./openvm/stark.proof";
 pub const DEFAULT_EVM_PROOF_PATH: &str = "./openvm/evm.proof";
 
 pub fn default_app_config() -> AppConfig<SdkVmConfig> {
@@ -1,5 +1,6 @@
 pub mod commands;
 pub mod default;
+mod profiler;
 mod util;
 
 use std::process::{Command, Stdio};
@@ -0,0 +1,190 @@
+use std::{collections::BTreeMap, fs::File, io::Write, path::Path};
+
+use eyre::Result;
+use openvm_circuit::arch::{instructions::exe::FnBounds, ExecutionHook, ExecutionStep};
+use openvm_stark_sdk::p3_baby_bear::BabyBear;
+
+/// Register pointer of the RISC-V return address register `ra` (x1).
+const RA_REGISTER_PTR: u32 = 4;
+
+/// Attributes every executed instruction to the guest call stack and accumulates cycles and
+/// trace cells per stack, to be written out in the folded-stack format used by flamegraph tools.
+///
+/// The call stack is reconstructed from the ELF symbol table by following the RISC-V calling
+/// convention: a `jal`/`jalr` that links into `ra` is a call, and a `jalr x0, ra` is a return.
+/// Any other `jal x0`/`jalr x0` that lands on the start of a function is a tail call, which
+/// replaces the current frame instead of pushing a new one.
+///
+/// The same profiler sees all segments, so the stack, and a call whose callee starts in the
+/// next segment, carry over segment boundaries.
+pub(crate) struct Profiler {
+    fn_bounds: FnBounds,
+    stack: Vec<String>,
+    /// `stack` joined by `;`, cached since the stack changes rarely.
+    stack_key: String,
+    /// Set by a call, the callee is pushed once we see the pc it jumped to.
+    pending_call: bool,
+    /// Set by a jump that does not link, which is a tail call if it lands on a function start.
+    pending_jump: bool,
+    cycles: BTreeMap<String, u64>,
+    /// Keyed by the stack with the AIR name appended as the leaf frame.
+    trace_cells: BTreeMap<String, u64>,
+}
+
+impl Profiler {
+    pub(crate) fn new(fn_bounds: FnBounds) -> Self {
+        Self {
+            fn_bounds,
+            stack: vec![],
+            stack_key: String::new(),
+            pending_call: false,
+            pending_jump: false,
+            cycles: BTreeMap::new(),
+            trace_cells: BTreeMap::new(),
+        }
+    }
+
+    /// Writes `cycles.folded` and `trace_cells.folded` into `dir`.
+    pub(crate) fn write_folded(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+        write_folded_file(&dir.join("cycles.folded"), &self.cycles)?;
+        write_folded_file(&dir.join("trace_cells.folded"), &self.trace_cells)?;
+        Ok(())
+    }
+
+    fn function_name(&self, pc: u32) -> String {
+        self.fn_bounds
+            .range(..=pc)
+            .next_back()
+            .map(|(_, f)| f)
+            .filter(|f| pc <= f.end)
+            .map(|f| format!("{:#}", rustc_demangle::demangle(&f.name)))
+            .unwrap_or_else(|| format!("{pc:#x}"))
+    }
+
+    fn push(&mut self, frame: String) {
+        self.stack.push(frame);
+        self.stack_key = self.stack.join(";");
+    }
+
+    fn pop(&mut self) {
+        // Never pop the entry frame, returns out of it are not calls we have seen.
+        if self.stack.len() > 1 {
+            self.stack.pop();
+            self.stack_key = self.stack.join(";");
+        }
+    }
+
+    fn replace_top(&mut self, frame: String) {
+        self.stack.pop();
+        self.push(frame);
+    }
+}
+
+impl ExecutionHook<BabyBear> for Profiler {
+    fn after_instruction(&mut self, step: ExecutionStep) {
+        if self.stack.is_empty() || self.pending_call {
+            self.push(self.function_name(step.pc));
+        } else if self.pending_jump && self.fn_bounds.contains_key(&step.pc) {
+            self.replace_top(self.function_name(step.pc));
+        }
+        self.pending_call = false;
+        self.pending_jump = false;
+
+        *self.cycles.entry(self.stack_key.clone()).or_default() += 1;
+        for (air_name, cells) in &step.trace_cells {
+            *self
+                .trace_cells
+                .entry(format!("{};{}", self.stack_key, air_name))
+                .or_default() += *cells as u64;
+        }
+
+        let [rd, rs1, ..] = step.operands;
+        match step.opcode.as_str() {
+            "JAL" | "JALR" if rd == RA_REGISTER_PTR => self.pending_call = true,
+            "JALR" if rd == 0 && rs1 == RA_REGISTER_PTR => self.pop(),
+            "JAL" | "JALR" if rd == 0 => self.pending_jump = true,
+            _ => {}
+        }
+    }
+
+    fn collect_trace_cells(&self) -> bool {
+        true
+    }
+}
+
+fn write_folded_file(path: &Path, weights: &BTreeMap<String, u64>) -> Result<()> {
+    let mut file = std::io::BufWriter::new(File::create(path)?);
+    for (stack, weight) in weights {
+        writeln!(file, "{stack} {weight}")?;
+    }
+    file.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use openvm_circuit::arch::instructions::exe::FnBound;
+
+    use super::*;
+
+    const RA: u32 = RA_REGISTER_PTR;
+
+    fn fn_bounds() -> FnBounds {
+        [("main", 0x100), ("f", 0x200), ("g", 0x300), ("h", 0x400)]
+            .into_iter()
+            .map(|(name, start)| {
+                let bound = FnBound {
+                    start,
+                    end: start + 0xfc,
+                    name: name.to_string(),
+                };
+                (start, bound)
+            })
+            .collect()
+    }
+
+    fn step(segment_idx: usize, pc: u32, opcode: &str, rd: u32, rs1: u32) -> ExecutionStep {
+        ExecutionStep {
+            segment_idx,
+            pc,
+            timestamp: 0,
+            opcode: opcode.to_string(),
+            operands: [rd, rs1, 0, 1, 0, 0, 0],
+            accesses: vec![],
+            trace_cells: vec![("Rv32Air".to_string(), 2)],
+        }
+    }
+
+    #[test]
+    fn test_profiler_call_graph() {
+        // main calls f, f tail calls g, g calls h. The segments end in the middle of g and right
+        // after the call to h.
+        let steps = [
+            step(0, 0x100, "ADD", 8, 8),
+            step(0, 0x104, "JAL", RA, 0),
+            step(0, 0x200, "ADD", 8, 8),
+            // A jump within f is not a tail call.
+            step(0, 0x204, "JAL", 0, 0),
+            step(0, 0x20c, "JAL", 0, 0),
+            step(0, 0x300, "ADD", 8, 8),
+            step(1, 0x304, "JAL", RA, 0),
+            step(2, 0x400, "ADD", 8, 8),
+            step(2, 0x404, "JALR", 0, RA),
+            // g returns to main, since f is no longer on the stack.
+            step(2, 0x308, "JALR", 0, RA),
+            step(2, 0x108, "ADD", 8, 8),
+        ];
+        let mut profiler = Profiler::new(fn_bounds());
+        for step in steps {
+            profiler.after_instruction(step);
+        }
+
+        let expected_cycles = BTreeMap::from(
+            [("main", 3), ("main;f", 3), ("main;g", 3), ("main;g;h", 2)]
+                .map(|(stack, cycles)| (stack.to_string(), cycles)),
+        );
+        assert_eq!(profiler.cycles, expected_cycles);
+        assert_eq!(profiler.trace_cells["main;g;h;Rv32Air"], 4);
+    }
+}
@@ -5,11 +5,16 @@ use std::{
     str::FromStr,
 };
 
-use eyre::Result;
+use eyre::{bail, eyre, Result};
+use num_bigint_dig::BigUint;
 use openvm_sdk::{
     config::{AppConfig, SdkVmConfig},
     StdIn,
 };
+use openvm_stark_sdk::{
+    openvm_stark_backend::p3_field::{AbstractField, PrimeField32},
+    p3_baby_bear::BabyBear,
+};
 use serde::de::DeserializeOwned;
 
 use crate::default::default_app_config;
@@ -93,3 +98,34 @@ pub(crate) fn read_config_toml_or_default(config: &PathBuf) -> Result<AppConfig<
         Ok(default_app_config())
     }
 }
+
+/// Formats a commitment as the hex encoding of the BN254 field element it is exposed as in EVM
+/// proofs, i.e. `sum(commit[i] * p^i)` where `p` is the BabyBear modulus.
+pub(crate) fn commit_to_hex<const N: usize>(commit: &[BabyBear; N]) -> String {
+    let order = BigUint::from(BabyBear::ORDER_U32);
+    let value = commit.iter().rev().fold(BigUint::default(), |acc, x| {
+        acc * &order + BigUint::from(x.as_canonical_u32())
+    });
+    format!("0x{value:064x}")
+}
+
+/// Parses a commitment formatted by [commit_to_hex].
+pub(crate) fn parse_commit<const N: usize>(s: &str) -> Result<[BabyBear; N]> {
+    let hex = s.strip_prefix("0x").unwrap_or(s);
+    let mut value = BigUint::parse_bytes(hex.as_bytes(), 16)
+        .ok_or_else(|| eyre!("Invalid commit {s}: expected a hex string"))?;
+    let order = BigUint::from(BabyBear::ORDER_U32);
+    let commit = std::array::from_fn(|_| {
+        let limb = (&value % &order)
+            .to_bytes_le()
+            .iter()
+            .rev()
+            .fold(0u32, |acc, &byte| (acc << 8) | byte as u32);
+        value = &value / &order;
+        BabyBear::from_canonical_u32(limb)
+    });
+    if value != BigUint::default() {
+        bail!("Invalid commit {s}: out of range");
+    }
+    Ok(commit)
+}
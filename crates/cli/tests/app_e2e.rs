@@ -77,6 +77,8 @@ fn test_cli_app_e2e() -> Result<()> {
             temp_vk.to_str().unwrap(),
             "--proof",
             temp_proof.to_str().unwrap(),
+            "--exe",
+            temp_exe.to_str().unwrap(),
         ],
     )?;
 
@@ -122,8 +122,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     )?;
 
     // 14. Verify the EVM proof
-    let success = sdk.verify_evm_proof(&verifier, &proof);
-    assert!(success);
+    sdk.verify_evm_proof(&verifier, &proof)?;
     // ANCHOR_END: evm_verification
 
     Ok(())
@@ -0,0 +1,19 @@
+#![cfg_attr(target_os = "zkvm", no_main)]
+#![cfg_attr(target_os = "zkvm", no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use openvm::io::{read, read_vec};
+
+openvm::entry!(main);
+
+/// Relies on another OpenVM execution, whose commits and public values are read from the input.
+/// The aggregation proves this program only together with a proof of that execution.
+pub fn main() {
+    let exe_commit: [u32; 8] = read();
+    let vm_config_commit: [u32; 8] = read();
+    let public_values: Vec<u8> = read_vec();
+    openvm::verify_openvm_stark(&exe_commit, &vm_config_commit, &public_values);
+}
@@ -7,7 +7,7 @@ use openvm_circuit::{
         VmConfig,
     },
     system::{
-        memory::{memory_image_to_equipartition, tree::MemoryNode},
+        memory::{dimensions::MemoryDimensions, memory_image_to_equipartition, tree::MemoryNode},
         program::trace::VmCommittedExe,
     },
 };
@@ -53,33 +53,16 @@ impl AppExecutionCommit<F> {
         assert!(
             app_exe.exe.program.max_num_public_values <= app_vm_config.system().num_public_values
         );
-        let hasher = vm_poseidon2_hasher();
         let memory_dimensions = app_vm_config.system().memory_config.memory_dimensions();
-        let app_program_commit: [F; DIGEST_SIZE] =
-            app_exe.committed_program.prover_data.commit.into();
         let leaf_verifier_program_commit: [F; DIGEST_SIZE] = leaf_vm_verifier_exe
             .committed_program
             .prover_data
             .commit
             .into();
 
-        let init_memory_commit = MemoryNode::tree_from_memory(
-            memory_dimensions,
-            &memory_image_to_equipartition(app_exe.exe.init_memory.clone()),
-            &hasher,
-        )
-        .hash();
-        let mut padded_pc_start = [F::ZERO; DIGEST_SIZE];
-        padded_pc_start[0] = F::from_canonical_u32(app_exe.exe.pc_start);
-        let app_hash = hasher.hash(&app_program_commit);
-        let init_memory_hash = hasher.hash(&init_memory_commit);
-        let pc_start_hash = hasher.hash(&padded_pc_start);
-        let compress_1 = hasher.compress(&app_hash, &init_memory_hash);
-        let user_commit = hasher.compress(&compress_1, &pc_start_hash);
-
         Self {
             leaf_vm_verifier_commit: leaf_verifier_program_commit,
-            exe_commit: user_commit,
+            exe_commit: compute_app_exe_commit(memory_dimensions, app_exe),
         }
     }
 
@@ -92,6 +75,65 @@ impl AppExecutionCommit<F> {
     }
 }
 
+/// Computes `exe_commit` of [AppExecutionCommit] for `app_exe`.
+pub fn compute_app_exe_commit(
+    memory_dimensions: MemoryDimensions,
+    app_exe: &NonRootCommittedExe,
+) -> [F; DIGEST_SIZE] {
+    let hasher = vm_poseidon2_hasher();
+    let app_program_commit: [F; DIGEST_SIZE] = app_exe.committed_program.prover_data.commit.into();
+    let init_memory_commit = MemoryNode::tree_from_memory(
+        memory_dimensions,
+        &memory_image_to_equipartition(app_exe.exe.init_memory.clone()),
+        &hasher,
+    )
+    .hash();
+    compute_exe_commit(
+        &hasher,
+        &app_program_commit,
+        &init_memory_commit,
+        app_exe.exe.pc_start,
+    )
+}
+
+/// Computes `exe_commit` of [AppExecutionCommit] from its components.
+pub fn compute_exe_commit(
+    hasher: &impl Hasher<DIGEST_SIZE, F>,
+    app_program_commit: &[F; DIGEST_SIZE],
+    init_memory_commit: &[F; DIGEST_SIZE],
+    pc_start: u32,
+) -> [F; DIGEST_SIZE] {
+    let mut padded_pc_start = [F::ZERO; DIGEST_SIZE];
+    padded_pc_start[0] = F::from_canonical_u32(pc_start);
+    let app_hash = hasher.hash(app_program_commit);
+    let init_memory_hash = hasher.hash(init_memory_commit);
+    let pc_start_hash = hasher.hash(&padded_pc_start);
+    let compress_1 = hasher.compress(&app_hash, &init_memory_hash);
+    hasher.compress(&compress_1, &pc_start_hash)
+}
+
+/// Computes the `batch_commit` exposed by a batch root verifier, see
+/// [BatchConfig](crate::config::BatchConfig). `executions` are the `(exe_commit, public_values)`
+/// of the executions in the order they are aggregated. Each execution is the leaf
+/// `compress(exe_commit, merkle_root(public_values))`, the leaves are padded with zeros to
+/// `max_num_executions` and `batch_commit` is their Merkle root. The root verifier exposes the
+/// number of executions separately, so padding cannot be mistaken for executions.
+pub fn compute_batch_commit(
+    hasher: &impl Hasher<DIGEST_SIZE, F>,
+    executions: &[([F; DIGEST_SIZE], Vec<F>)],
+    max_num_executions: usize,
+) -> [F; DIGEST_SIZE] {
+    assert!(max_num_executions.is_power_of_two());
+    assert!(!executions.is_empty() && executions.len() <= max_num_executions);
+    let mut leaves = vec![F::ZERO; max_num_executions * DIGEST_SIZE];
+    for (leaf, (exe_commit, public_values)) in leaves.chunks_exact_mut(DIGEST_SIZE).zip(executions)
+    {
+        let public_values_commit = hasher.merkle_root(public_values);
+        leaf.copy_from_slice(&hasher.compress(exe_commit, &public_values_commit));
+    }
+    hasher.merkle_root(&leaves)
+}
+
 pub(crate) fn babybear_digest_to_bn254(digest: &[F; DIGEST_SIZE]) -> Bn254Fr {
     let mut ret = Bn254Fr::ZERO;
     let order = Bn254Fr::from_canonical_u32(BabyBear::ORDER_U32);
@@ -5,8 +5,10 @@ use openvm_algebra_circuit::{
     ModularExtensionExecutor, ModularExtensionPeriphery,
 };
 use openvm_algebra_transpiler::{Fp2TranspilerExtension, ModularTranspilerExtension};
-use openvm_bigint_circuit::{Int256, Int256Executor, Int256Periphery};
-use openvm_bigint_transpiler::Int256TranspilerExtension;
+use openvm_bigint_circuit::{
+    Int256, Int256Executor, Int256Periphery, WideUint, WideUintExecutor, WideUintPeriphery,
+};
+use openvm_bigint_transpiler::{Int256TranspilerExtension, WideUintTranspilerExtension};
 use openvm_circuit::{
     arch::{
         SystemConfig, SystemExecutor, SystemPeriphery, VmChipComplex, VmConfig, VmInventoryError,
@@ -15,9 +17,10 @@ use openvm_circuit::{
     derive::{AnyEnum, InstructionExecutor},
 };
 use openvm_ecc_circuit::{
-    WeierstrassExtension, WeierstrassExtensionExecutor, WeierstrassExtensionPeriphery,
+    EdwardsExtension, EdwardsExtensionExecutor, EdwardsExtensionPeriphery, WeierstrassExtension,
+    WeierstrassExtensionExecutor, WeierstrassExtensionPeriphery,
 };
-use openvm_ecc_transpiler::EccTranspilerExtension;
+use openvm_ecc_transpiler::{EccTranspilerExtension, EdwardsTranspilerExtension};
 use openvm_keccak256_circuit::{Keccak256, Keccak256Executor, Keccak256Periphery};
 use openvm_keccak256_transpiler::Keccak256TranspilerExtension;
 use openvm_native_circuit::{Native, NativeExecutor, NativePeriphery};
@@ -32,6 +35,8 @@ use openvm_rv32im_circuit::{
 use openvm_rv32im_transpiler::{
     Rv32ITranspilerExtension, Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
 };
+use openvm_sha256_circuit::{Sha256, Sha256Executor, Sha256Periphery};
+use openvm_sha256_transpiler::Sha256TranspilerExtension;
 use openvm_stark_backend::p3_field::PrimeField32;
 use openvm_transpiler::transpiler::Transpiler;
 use serde::{Deserialize, Serialize};
@@ -46,14 +51,17 @@ pub struct SdkVmConfig {
     pub rv32i: Option<UnitStruct>,
     pub io: Option<UnitStruct>,
     pub keccak: Option<UnitStruct>,
+    pub sha256: Option<UnitStruct>,
     pub native: Option<UnitStruct>,
 
     pub rv32m: Option<Rv32M>,
     pub bigint: Option<Int256>,
+    pub wide_uint: Option<WideUint>,
     pub modular: Option<ModularExtension>,
     pub fp2: Option<Fp2Extension>,
     pub pairing: Option<PairingExtension>,
     pub ecc: Option<WeierstrassExtension>,
+    pub edwards: Option<EdwardsExtension>,
 }
 
 #[derive(ChipUsageGetter, Chip, InstructionExecutor, From, AnyEnum)]
@@ -67,12 +75,16 @@ pub enum SdkVmConfigExecutor<F: PrimeField32> {
     #[any_enum]
     Keccak(Keccak256Executor<F>),
     #[any_enum]
+    Sha256(Sha256Executor<F>),
+    #[any_enum]
     Native(NativeExecutor<F>),
     #[any_enum]
     Rv32m(Rv32MExecutor<F>),
     #[any_enum]
     BigInt(Int256Executor<F>),
     #[any_enum]
+    WideUint(WideUintExecutor<F>),
+    #[any_enum]
     Modular(ModularExtensionExecutor<F>),
     #[any_enum]
     Fp2(Fp2ExtensionExecutor<F>),
@@ -80,6 +92,8 @@ pub enum SdkVmConfigExecutor<F: PrimeField32> {
     Pairing(PairingExtensionExecutor<F>),
     #[any_enum]
     Ecc(WeierstrassExtensionExecutor<F>),
+    #[any_enum]
+    Edwards(EdwardsExtensionExecutor<F>),
 }
 
 #[derive(From, ChipUsageGetter, Chip, AnyEnum)]
@@ -93,12 +107,16 @@ pub enum SdkVmConfigPeriphery<F: PrimeField32> {
     #[any_enum]
     Keccak(Keccak256Periphery<F>),
     #[any_enum]
+    Sha256(Sha256Periphery<F>),
+    #[any_enum]
     Native(NativePeriphery<F>),
     #[any_enum]
     Rv32m(Rv32MPeriphery<F>),
     #[any_enum]
     BigInt(Int256Periphery<F>),
     #[any_enum]
+    WideUint(WideUintPeriphery<F>),
+    #[any_enum]
     Modular(ModularExtensionPeriphery<F>),
     #[any_enum]
     Fp2(Fp2ExtensionPeriphery<F>),
@@ -106,6 +124,8 @@ pub enum SdkVmConfigPeriphery<F: PrimeField32> {
     Pairing(PairingExtensionPeriphery<F>),
     #[any_enum]
     Ecc(WeierstrassExtensionPeriphery<F>),
+    #[any_enum]
+    Edwards(EdwardsExtensionPeriphery<F>),
 }
 
 impl SdkVmConfig {
@@ -120,12 +140,18 @@ impl SdkVmConfig {
         if self.keccak.is_some() {
             transpiler = transpiler.with_extension(Keccak256TranspilerExtension);
         }
+        if self.sha256.is_some() {
+            transpiler = transpiler.with_extension(Sha256TranspilerExtension);
+        }
         if self.rv32m.is_some() {
             transpiler = transpiler.with_extension(Rv32MTranspilerExtension);
         }
         if self.bigint.is_some() {
             transpiler = transpiler.with_extension(Int256TranspilerExtension);
         }
+        if self.wide_uint.is_some() {
+            transpiler = transpiler.with_extension(WideUintTranspilerExtension);
+        }
         if self.modular.is_some() {
             transpiler = transpiler.with_extension(ModularTranspilerExtension);
         }
@@ -138,6 +164,9 @@ impl SdkVmConfig {
         if self.ecc.is_some() {
             transpiler = transpiler.with_extension(EccTranspilerExtension);
         }
+        if self.edwards.is_some() {
+            transpiler = transpiler.with_extension(EdwardsTranspilerExtension);
+        }
         transpiler
     }
 }
@@ -168,6 +197,9 @@ impl<F: PrimeField32> VmConfig<F> for SdkVmConfig {
         if self.keccak.is_some() {
             complex = complex.extend(&Keccak256)?;
         }
+        if self.sha256.is_some() {
+            complex = complex.extend(&Sha256)?;
+        }
         if self.native.is_some() {
             complex = complex.extend(&Native)?;
         }
@@ -192,6 +224,9 @@ impl<F: PrimeField32> VmConfig<F> for SdkVmConfig {
             }
             complex = complex.extend(&bigint)?;
         }
+        if let Some(ref wide_uint) = self.wide_uint {
+            complex = complex.extend(wide_uint)?;
+        }
         if let Some(ref modular) = self.modular {
             complex = complex.extend(modular)?;
         }
@@ -204,6 +239,9 @@ impl<F: PrimeField32> VmConfig<F> for SdkVmConfig {
         if let Some(ref ecc) = self.ecc {
             complex = complex.extend(ecc)?;
         }
+        if let Some(ref edwards) = self.edwards {
+            complex = complex.extend(edwards)?;
+        }
 
         Ok(complex)
     }
@@ -251,6 +289,12 @@ impl From<Keccak256> for UnitStruct {
     }
 }
 
+impl From<Sha256> for UnitStruct {
+    fn from(_: Sha256) -> Self {
+        UnitStruct {}
+    }
+}
+
 impl From<Native> for UnitStruct {
     fn from(_: Native) -> Self {
         UnitStruct {}
@@ -1,3 +1,4 @@
+use openvm::composition::{ASSUMPTIONS_OFFSET, ASSUMPTION_HEADER_SIZE};
 use openvm_circuit::arch::instructions::program::DEFAULT_MAX_NUM_PUBLIC_VALUES;
 use openvm_native_compiler::conversion::CompilerOptions;
 use openvm_stark_sdk::config::FriParameters;
@@ -39,6 +40,50 @@ pub struct AggStarkConfig {
     pub root_fri_params: FriParameters,
     /// Only for AggVM debugging.
     pub compiler_options: CompilerOptions,
+    /// If set, the aggregation resolves the assumptions recorded by `openvm::verify_openvm_stark`
+    /// and the root verifier rejects executions with unresolved assumptions. The first 4 bytes of
+    /// the public values of every app aggregated with such a key are reserved for the number of
+    /// assumptions.
+    #[serde(default)]
+    pub composition: Option<CompositionConfig>,
+    /// If set, the root verifier aggregates a batch of executions instead of a single one, see
+    /// [BatchConfig].
+    #[serde(default)]
+    pub batch: Option<BatchConfig>,
+}
+
+/// Layout of the assumptions recorded by `openvm::verify_openvm_stark`, see
+/// [openvm::composition].
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct CompositionConfig {
+    /// Maximum number of assumptions an execution can record.
+    pub max_num_assumptions: usize,
+    /// Number of public values of the referenced executions. Must be a power of two times
+    /// `DIGEST_SIZE`.
+    pub num_referenced_public_values: usize,
+}
+
+impl CompositionConfig {
+    /// Number of public values taken by one assumption.
+    pub fn assumption_size(&self) -> usize {
+        ASSUMPTION_HEADER_SIZE + self.num_referenced_public_values
+    }
+
+    /// Minimum number of public values of an app that records assumptions.
+    pub fn min_num_public_values(&self) -> usize {
+        ASSUMPTIONS_OFFSET + self.max_num_assumptions * self.assumption_size()
+    }
+}
+
+/// Batch aggregation of executions of possibly different executables on the same app VM. The root
+/// verifier exposes the leaf verifier commit shared by all executions and a commitment to the
+/// `(exe_commit, public_values)` of each execution, computed by
+/// [compute_batch_commit](crate::commit::compute_batch_commit), followed by the number of
+/// executions, instead of a single execution's exe commit and public values.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct BatchConfig {
+    /// Maximum number of executions in a batch. Must be a power of two.
+    pub max_num_executions: usize,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -87,6 +132,8 @@ impl Default for AggStarkConfig {
                 DEFAULT_ROOT_BLOWUP,
             ),
             compiler_options: Default::default(),
+            composition: None,
+            batch: None,
         }
     }
 }
@@ -1,73 +1,337 @@
 use std::{
-    fs::{create_dir_all, read, write},
+    fs::{create_dir_all, read, write, File},
+    io::{BufReader, Read},
     path::Path,
 };
 
-use eyre::Result;
-use openvm_circuit::arch::{instructions::exe::VmExe, VmConfig};
+use eyre::{bail, eyre, Result};
+use openvm_circuit::arch::{instructions::exe::VmExe, VmCheckpoint, VmConfig};
+use openvm_native_compiler::ir::DIGEST_SIZE;
 use openvm_native_recursion::halo2::{wrapper::EvmVerifier, EvmProof};
-use serde::{de::DeserializeOwned, Serialize};
+use openvm_stark_sdk::openvm_stark_backend::{p3_field::PrimeField32, prover::types::Proof};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::{
-    keygen::{AggProvingKey, AppProvingKey, AppVerifyingKey},
+    commit::AppExecutionCommit,
+    keygen::{
+        AggProvingKey, AggStarkProvingKey, AppProvingKey, AppVerifyingKey, RootVerifierVerifyingKey,
+    },
     prover::vm::ContinuationVmProof,
-    F, SC,
+    RootSC, F, SC,
 };
 
+/// Magic bytes at the start of every artifact file.
+pub const ARTIFACT_MAGIC: [u8; 8] = *b"OPENVMAF";
+/// Version of the artifact file layout. Files with a different version are rejected.
+pub const ARTIFACT_FORMAT_VERSION: u32 = 1;
+/// Version of the SDK writing the artifacts. Payloads are `bitcode` encodings of SDK types, which
+/// are only compatible within the same SDK version.
+pub const SDK_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Type of the payload of an artifact file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    Exe,
+    AppProvingKey,
+    AppVerifyingKey,
+    AppProof,
+    Checkpoint,
+    AggProvingKey,
+    AggStarkProvingKey,
+    RootVerifierVerifyingKey,
+    AggStarkProof,
+    EvmProof,
+}
+
+/// Header of an artifact file. An artifact file is laid out as:
+/// - [ARTIFACT_MAGIC]
+/// - length of the header in bytes, as a little-endian `u32`
+/// - the header, encoded as JSON so it can be read without the SDK
+/// - the `bitcode` encoding of the artifact
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactHeader {
+    pub kind: ArtifactKind,
+    pub format_version: u32,
+    pub sdk_version: String,
+    /// [AppExecutionCommit::leaf_vm_verifier_commit] of the app the artifact belongs to, if known.
+    pub app_config_commit: Option<[u32; DIGEST_SIZE]>,
+    /// [AppExecutionCommit::exe_commit] of the executable the artifact belongs to, if known.
+    pub exe_commit: Option<[u32; DIGEST_SIZE]>,
+}
+
+impl ArtifactHeader {
+    pub fn new(kind: ArtifactKind) -> Self {
+        Self {
+            kind,
+            format_version: ARTIFACT_FORMAT_VERSION,
+            sdk_version: SDK_VERSION.to_string(),
+            app_config_commit: None,
+            exe_commit: None,
+        }
+    }
+
+    pub fn with_app_config_commit(mut self, app_config_commit: &[F; DIGEST_SIZE]) -> Self {
+        self.app_config_commit = Some(digest_to_u32(app_config_commit));
+        self
+    }
+
+    pub fn with_app_commit(self, app_commit: &AppExecutionCommit<F>) -> Self {
+        let mut ret = self.with_app_config_commit(&app_commit.leaf_vm_verifier_commit);
+        ret.exe_commit = Some(digest_to_u32(&app_commit.exe_commit));
+        ret
+    }
+
+    /// Checks that the artifact can be decoded as `kind` by this SDK.
+    pub fn check_compatibility(&self, kind: ArtifactKind) -> Result<()> {
+        if self.format_version != ARTIFACT_FORMAT_VERSION {
+            bail!(
+                "Unsupported artifact format version {}, expected {}",
+                self.format_version,
+                ARTIFACT_FORMAT_VERSION
+            );
+        }
+        if self.kind != kind {
+            bail!("Artifact is {:?}, expected {:?}", self.kind, kind);
+        }
+        if self.sdk_version != SDK_VERSION {
+            bail!(
+                "Artifact was written by SDK version {}, but this is version {}",
+                self.sdk_version,
+                SDK_VERSION
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks that the commitments recorded in the header, if any, match `app_commit`.
+    pub fn check_app_commit(&self, app_commit: &AppExecutionCommit<F>) -> Result<()> {
+        self.check_app_config_commit(&app_commit.leaf_vm_verifier_commit)?;
+        self.check_exe_commit(&app_commit.exe_commit)
+    }
+
+    /// Checks that the app VM commitment recorded in the header, if any, matches
+    /// `app_config_commit`.
+    pub fn check_app_config_commit(&self, app_config_commit: &[F; DIGEST_SIZE]) -> Result<()> {
+        if self
+            .app_config_commit
+            .is_some_and(|commit| commit != digest_to_u32(app_config_commit))
+        {
+            bail!("Artifact is for a different app VM");
+        }
+        Ok(())
+    }
+
+    /// Checks that the executable commitment recorded in the header, if any, matches
+    /// `exe_commit`. Used when only the executable is known.
+    pub fn check_exe_commit(&self, exe_commit: &[F; DIGEST_SIZE]) -> Result<()> {
+        if self
+            .exe_commit
+            .is_some_and(|commit| commit != digest_to_u32(exe_commit))
+        {
+            bail!("Artifact is for a different executable");
+        }
+        Ok(())
+    }
+}
+
+/// An artifact exported to JSON for consumers without the SDK.
+#[derive(Serialize)]
+struct JsonArtifact<'a, T> {
+    header: ArtifactHeader,
+    artifact: &'a T,
+}
+
 pub fn read_exe_from_file<P: AsRef<Path>>(path: P) -> Result<VmExe<F>> {
-    read_from_file_bitcode(path)
+    let (_, exe) = read_artifact_from_file(path, ArtifactKind::Exe)?;
+    Ok(exe)
 }
 
 pub fn write_exe_to_file<P: AsRef<Path>>(exe: VmExe<F>, path: P) -> Result<()> {
-    write_to_file_bitcode(path, exe)
+    write_artifact_to_file(path, ArtifactHeader::new(ArtifactKind::Exe), exe)
 }
 
+/// Reads an app proving key and checks it against the app VM commitment in its header.
 pub fn read_app_pk_from_file<VC: VmConfig<F>, P: AsRef<Path>>(
     path: P,
 ) -> Result<AppProvingKey<VC>> {
-    read_from_file_bitcode(path)
+    let (header, app_pk): (_, AppProvingKey<VC>) =
+        read_artifact_from_file(path, ArtifactKind::AppProvingKey)?;
+    header.check_app_config_commit(&app_pk.leaf_committed_exe.get_program_commit().into())?;
+    Ok(app_pk)
 }
 
 pub fn write_app_pk_to_file<VC: VmConfig<F>, P: AsRef<Path>>(
     app_pk: AppProvingKey<VC>,
     path: P,
 ) -> Result<()> {
-    write_to_file_bitcode(path, app_pk)
+    let header = ArtifactHeader::new(ArtifactKind::AppProvingKey)
+        .with_app_config_commit(&app_pk.leaf_committed_exe.get_program_commit().into());
+    write_artifact_to_file(path, header, app_pk)
 }
 
+/// Reads an app verifying key and checks it against the app VM commitment in its header.
 pub fn read_app_vk_from_file<P: AsRef<Path>>(path: P) -> Result<AppVerifyingKey> {
-    read_from_file_bitcode(path)
+    let (header, app_vk): (_, AppVerifyingKey) =
+        read_artifact_from_file(path, ArtifactKind::AppVerifyingKey)?;
+    header.check_app_config_commit(&app_vk.leaf_vm_verifier_commit)?;
+    Ok(app_vk)
 }
 
 pub fn write_app_vk_to_file<P: AsRef<Path>>(app_vk: AppVerifyingKey, path: P) -> Result<()> {
-    write_to_file_bitcode(path, app_vk)
+    let header = ArtifactHeader::new(ArtifactKind::AppVerifyingKey)
+        .with_app_config_commit(&app_vk.leaf_vm_verifier_commit);
+    write_artifact_to_file(path, header, app_vk)
+}
+
+pub fn write_app_vk_to_json<P: AsRef<Path>>(app_vk: &AppVerifyingKey, path: P) -> Result<()> {
+    let header = ArtifactHeader::new(ArtifactKind::AppVerifyingKey)
+        .with_app_config_commit(&app_vk.leaf_vm_verifier_commit);
+    write_artifact_to_json(path, header, app_vk)
 }
 
-pub fn read_app_proof_from_file<P: AsRef<Path>>(path: P) -> Result<ContinuationVmProof<SC>> {
-    read_from_file_bitcode(path)
+/// Reads an app proof together with its header. The caller should check the header against the
+/// expected app with [ArtifactHeader::check_app_commit].
+pub fn read_app_proof_from_file<P: AsRef<Path>>(
+    path: P,
+) -> Result<(ArtifactHeader, ContinuationVmProof<SC>)> {
+    read_artifact_from_file(path, ArtifactKind::AppProof)
 }
 
 pub fn write_app_proof_to_file<P: AsRef<Path>>(
     proof: ContinuationVmProof<SC>,
+    app_commit: &AppExecutionCommit<F>,
     path: P,
 ) -> Result<()> {
-    write_to_file_bitcode(path, proof)
+    let header = ArtifactHeader::new(ArtifactKind::AppProof).with_app_commit(app_commit);
+    write_artifact_to_file(path, header, proof)
+}
+
+pub fn write_app_proof_to_json<P: AsRef<Path>>(
+    proof: &ContinuationVmProof<SC>,
+    app_commit: &AppExecutionCommit<F>,
+    path: P,
+) -> Result<()> {
+    let header = ArtifactHeader::new(ArtifactKind::AppProof).with_app_commit(app_commit);
+    write_artifact_to_json(path, header, proof)
+}
+
+/// Reads a checkpoint and checks that it was taken from an execution of the expected app, so a
+/// segment is never resumed with a different executable or app VM.
+pub fn read_checkpoint_from_file<P: AsRef<Path>>(
+    path: P,
+    app_commit: &AppExecutionCommit<F>,
+) -> Result<VmCheckpoint<F>> {
+    let (header, checkpoint) = read_artifact_from_file(path, ArtifactKind::Checkpoint)?;
+    header.check_app_commit(app_commit)?;
+    Ok(checkpoint)
+}
+
+pub fn write_checkpoint_to_file<P: AsRef<Path>>(
+    checkpoint: VmCheckpoint<F>,
+    app_commit: &AppExecutionCommit<F>,
+    path: P,
+) -> Result<()> {
+    let header = ArtifactHeader::new(ArtifactKind::Checkpoint).with_app_commit(app_commit);
+    write_artifact_to_file(path, header, checkpoint)
 }
 
 pub fn read_agg_pk_from_file<P: AsRef<Path>>(path: P) -> Result<AggProvingKey> {
-    read_from_file_bitcode(path)
+    let (_, agg_pk) = read_artifact_from_file(path, ArtifactKind::AggProvingKey)?;
+    Ok(agg_pk)
 }
 
 pub fn write_agg_pk_to_file<P: AsRef<Path>>(agg_pk: AggProvingKey, path: P) -> Result<()> {
-    write_to_file_bitcode(path, agg_pk)
+    write_artifact_to_file(
+        path,
+        ArtifactHeader::new(ArtifactKind::AggProvingKey),
+        agg_pk,
+    )
+}
+
+pub fn read_agg_stark_pk_from_file<P: AsRef<Path>>(path: P) -> Result<AggStarkProvingKey> {
+    let (_, agg_stark_pk) = read_artifact_from_file(path, ArtifactKind::AggStarkProvingKey)?;
+    Ok(agg_stark_pk)
+}
+
+pub fn write_agg_stark_pk_to_file<P: AsRef<Path>>(
+    agg_stark_pk: AggStarkProvingKey,
+    path: P,
+) -> Result<()> {
+    write_artifact_to_file(
+        path,
+        ArtifactHeader::new(ArtifactKind::AggStarkProvingKey),
+        agg_stark_pk,
+    )
 }
 
-pub fn read_evm_proof_from_file<P: AsRef<Path>>(path: P) -> Result<EvmProof> {
-    read_from_file_bitcode(path)
+pub fn read_root_verifier_vk_from_file<P: AsRef<Path>>(
+    path: P,
+) -> Result<RootVerifierVerifyingKey> {
+    let (_, root_verifier_vk) =
+        read_artifact_from_file(path, ArtifactKind::RootVerifierVerifyingKey)?;
+    Ok(root_verifier_vk)
+}
+
+pub fn write_root_verifier_vk_to_file<P: AsRef<Path>>(
+    root_verifier_vk: RootVerifierVerifyingKey,
+    path: P,
+) -> Result<()> {
+    write_artifact_to_file(
+        path,
+        ArtifactHeader::new(ArtifactKind::RootVerifierVerifyingKey),
+        root_verifier_vk,
+    )
+}
+
+/// Reads an aggregated STARK proof together with its header. The caller should check the header
+/// against the expected app with [ArtifactHeader::check_app_commit].
+pub fn read_agg_stark_proof_from_file<P: AsRef<Path>>(
+    path: P,
+) -> Result<(ArtifactHeader, Proof<RootSC>)> {
+    read_artifact_from_file(path, ArtifactKind::AggStarkProof)
+}
+
+pub fn write_agg_stark_proof_to_file<P: AsRef<Path>>(
+    proof: Proof<RootSC>,
+    app_commit: &AppExecutionCommit<F>,
+    path: P,
+) -> Result<()> {
+    let header = ArtifactHeader::new(ArtifactKind::AggStarkProof).with_app_commit(app_commit);
+    write_artifact_to_file(path, header, proof)
+}
+
+pub fn write_agg_stark_proof_to_json<P: AsRef<Path>>(
+    proof: &Proof<RootSC>,
+    app_commit: &AppExecutionCommit<F>,
+    path: P,
+) -> Result<()> {
+    let header = ArtifactHeader::new(ArtifactKind::AggStarkProof).with_app_commit(app_commit);
+    write_artifact_to_json(path, header, proof)
+}
+
+/// Reads an EVM proof together with its header. The caller should check the header against the
+/// expected app with [ArtifactHeader::check_app_commit].
+pub fn read_evm_proof_from_file<P: AsRef<Path>>(path: P) -> Result<(ArtifactHeader, EvmProof)> {
+    read_artifact_from_file(path, ArtifactKind::EvmProof)
+}
+
+pub fn write_evm_proof_to_file<P: AsRef<Path>>(
+    proof: EvmProof,
+    app_commit: &AppExecutionCommit<F>,
+    path: P,
+) -> Result<()> {
+    let header = ArtifactHeader::new(ArtifactKind::EvmProof).with_app_commit(app_commit);
+    write_artifact_to_file(path, header, proof)
 }
 
-pub fn write_evm_proof_to_file<P: AsRef<Path>>(proof: EvmProof, path: P) -> Result<()> {
-    write_to_file_bitcode(path, proof)
+pub fn write_evm_proof_to_json<P: AsRef<Path>>(
+    proof: &EvmProof,
+    app_commit: &AppExecutionCommit<F>,
+    path: P,
+) -> Result<()> {
+    let header = ArtifactHeader::new(ArtifactKind::EvmProof).with_app_commit(app_commit);
+    write_artifact_to_json(path, header, proof)
 }
 
 pub fn read_evm_verifier_from_file<P: AsRef<Path>>(path: P) -> Result<EvmVerifier> {
@@ -78,19 +342,76 @@ pub fn write_evm_verifier_to_file<P: AsRef<Path>>(verifier: EvmVerifier, path: P
     write_to_file_bytes(path, verifier)
 }
 
-pub(crate) fn read_from_file_bitcode<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T> {
-    let data = std::fs::read(path)?;
-    let ret = bitcode::deserialize(&data)?;
-    Ok(ret)
+/// Reads only the header of an artifact file, without reading the payload.
+pub fn read_artifact_header<P: AsRef<Path>>(path: P) -> Result<ArtifactHeader> {
+    read_header(&mut BufReader::new(File::open(path)?))
 }
 
-pub(crate) fn write_to_file_bitcode<T: Serialize, P: AsRef<Path>>(path: P, data: T) -> Result<()> {
-    let bytes = bitcode::serialize(&data)?;
-    if let Some(parent) = path.as_ref().parent() {
-        create_dir_all(parent)?;
+/// Reads an artifact file and decodes its payload after checking the header is compatible with
+/// `kind`. Returns the header so the caller can check the commitments recorded in it.
+pub fn read_artifact_from_file<T: DeserializeOwned, P: AsRef<Path>>(
+    path: P,
+    kind: ArtifactKind,
+) -> Result<(ArtifactHeader, T)> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let header = read_header(&mut reader)?;
+    header.check_compatibility(kind)?;
+    let mut payload = Vec::new();
+    reader.read_to_end(&mut payload)?;
+    let ret = bitcode::deserialize(&payload)?;
+    Ok((header, ret))
+}
+
+pub fn write_artifact_to_file<T: Serialize, P: AsRef<Path>>(
+    path: P,
+    header: ArtifactHeader,
+    data: T,
+) -> Result<()> {
+    let header = serde_json::to_vec(&header)?;
+    let payload = bitcode::serialize(&data)?;
+    let mut bytes = Vec::with_capacity(ARTIFACT_MAGIC.len() + 4 + header.len() + payload.len());
+    bytes.extend_from_slice(&ARTIFACT_MAGIC);
+    bytes.extend_from_slice(&u32::try_from(header.len())?.to_le_bytes());
+    bytes.extend_from_slice(&header);
+    bytes.extend_from_slice(&payload);
+    write_to_file_bytes(path, bytes)
+}
+
+/// Exports an artifact to JSON as an object with the fields `header` and `artifact`.
+pub fn write_artifact_to_json<T: Serialize, P: AsRef<Path>>(
+    path: P,
+    header: ArtifactHeader,
+    data: &T,
+) -> Result<()> {
+    let json = serde_json::to_vec_pretty(&JsonArtifact {
+        header,
+        artifact: data,
+    })?;
+    write_to_file_bytes(path, json)
+}
+
+/// Reads the magic bytes, the header length and the header, leaving `reader` at the payload.
+fn read_header(reader: &mut impl Read) -> Result<ArtifactHeader> {
+    let mut magic = [0u8; ARTIFACT_MAGIC.len()];
+    if reader.read_exact(&mut magic).is_err() || magic != ARTIFACT_MAGIC {
+        bail!("Not an OpenVM artifact file");
     }
-    write(path, bytes)?;
-    Ok(())
+    let mut len = [0u8; 4];
+    reader
+        .read_exact(&mut len)
+        .map_err(|_| eyre!("Truncated artifact header"))?;
+    let len = u32::from_le_bytes(len) as u64;
+    // Read through `take` so a corrupted length does not allocate more than the file holds.
+    let mut header = Vec::new();
+    reader.take(len).read_to_end(&mut header)?;
+    if header.len() as u64 != len {
+        bail!("Truncated artifact header");
+    }
+    Ok(serde_json::from_slice(&header)?)
+}
+
+fn digest_to_u32(digest: &[F; DIGEST_SIZE]) -> [u32; DIGEST_SIZE] {
+    digest.map(|x| x.as_canonical_u32())
 }
 
 pub(crate) fn read_from_file_bytes<T: From<Vec<u8>>, P: AsRef<Path>>(path: P) -> Result<T> {
@@ -4,7 +4,7 @@ use derivative::Derivative;
 use dummy::{compute_root_proof_heights, dummy_internal_proof_riscv_app_vm};
 use openvm_circuit::{
     arch::{VirtualMachine, VmConfig},
-    system::program::trace::VmCommittedExe,
+    system::{memory::dimensions::MemoryDimensions, program::trace::VmCommittedExe},
 };
 use openvm_native_circuit::NativeConfig;
 use openvm_native_compiler::ir::DIGEST_SIZE;
@@ -52,6 +52,10 @@ pub struct AppProvingKey<VC> {
 pub struct AppVerifyingKey {
     pub fri_params: FriParameters,
     pub app_vm_vk: MultiStarkVerifyingKey<SC>,
+    pub memory_dimensions: MemoryDimensions,
+    /// Commitment of the leaf verifier program for this app VM, see
+    /// [AppExecutionCommit::leaf_vm_verifier_commit](crate::commit::AppExecutionCommit::leaf_vm_verifier_commit).
+    pub leaf_vm_verifier_commit: [F; DIGEST_SIZE],
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -126,6 +130,13 @@ where
         AppVerifyingKey {
             fri_params: self.app_vm_pk.fri_params,
             app_vm_vk: self.app_vm_pk.vm_pk.get_vk(),
+            memory_dimensions: self
+                .app_vm_pk
+                .vm_config
+                .system()
+                .memory_config
+                .memory_dimensions(),
+            leaf_vm_verifier_commit: self.leaf_committed_exe.get_program_commit().into(),
         }
     }
 
@@ -149,6 +160,16 @@ impl AggStarkProvingKey {
     }
 
     pub fn dummy_proof_and_keygen(config: AggStarkConfig) -> (Self, Proof<SC>) {
+        if let Some(batch) = config.batch {
+            assert!(
+                batch.max_num_executions.is_power_of_two(),
+                "max_num_executions must be a power of two"
+            );
+            assert!(
+                config.composition.is_none(),
+                "Proof composition is not supported with batch aggregation"
+            );
+        }
         let leaf_vm_config = config.leaf_vm_config();
         let internal_vm_config = config.internal_vm_config();
         let root_vm_config = config.root_verifier_vm_config();
@@ -184,6 +205,8 @@ impl AggStarkProvingKey {
         let internal_program = InternalVmVerifierConfig {
             leaf_fri_params: config.leaf_fri_params,
             internal_fri_params: config.internal_fri_params,
+            num_public_values: config.max_num_user_public_values,
+            composition: config.composition,
             compiler_options: config.compiler_options,
         }
         .build_program(&leaf_vm_vk, &internal_vm_vk);
@@ -206,6 +229,8 @@ impl AggStarkProvingKey {
                 internal_fri_params: config.internal_fri_params,
                 num_public_values: config.max_num_user_public_values,
                 internal_vm_verifier_commit: internal_committed_exe.get_program_commit().into(),
+                composition: config.composition,
+                batch: config.batch,
                 compiler_options: config.compiler_options,
             }
             .build_program(&leaf_vm_vk, &internal_vm_vk);
@@ -218,10 +243,13 @@ impl AggStarkProvingKey {
             let mut vm_pk = vm.keygen();
             assert!(vm_pk.max_constraint_degree <= config.root_fri_params.max_constraint_degree());
 
+            // A batch root verifier must fit a full batch in its fixed trace heights.
+            let num_root_input_proofs = config.batch.map_or(1, |batch| batch.max_num_executions);
             let (air_heights, _internal_heights) = compute_root_proof_heights(
                 root_vm_config.clone(),
                 root_committed_exe.exe.clone(),
                 &internal_proof,
+                num_root_input_proofs,
             );
             let root_air_perm = AirIdPermutation::compute(&air_heights);
             root_air_perm.permute(&mut vm_pk.per_air);
@@ -287,6 +315,37 @@ impl RootVerifierProvingKey {
     pub fn air_id_permutation(&self) -> AirIdPermutation {
         AirIdPermutation::compute(&self.air_heights)
     }
+
+    pub fn get_vk(&self) -> RootVerifierVerifyingKey {
+        RootVerifierVerifyingKey {
+            fri_params: self.vm_pk.fri_params,
+            vm_vk: self.vm_pk.vm_pk.get_vk(),
+            program_commit: self.root_committed_exe.get_program_commit(),
+            air_heights: self.air_heights.clone(),
+            num_public_values: self.vm_pk.vm_config.system.num_public_values,
+        }
+    }
+}
+
+/// Verifying key for proofs of the root verifier. Much smaller than [RootVerifierProvingKey], and
+/// all that is needed to verify an aggregated STARK proof natively.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RootVerifierVerifyingKey {
+    pub fri_params: FriParameters,
+    /// AIRs are ordered by trace height, like in [RootVerifierProvingKey::vm_pk].
+    pub vm_vk: MultiStarkVerifyingKey<RootSC>,
+    /// Commitment of the root verifier program, which fixes the internal verifier it accepts.
+    pub program_commit: Com<RootSC>,
+    /// The constant trace heights, ordered by AIR ID.
+    pub air_heights: Vec<usize>,
+    /// Number of public values of the root verifier, including the exe and leaf verifier commits.
+    pub num_public_values: usize,
+}
+
+impl RootVerifierVerifyingKey {
+    pub fn air_id_permutation(&self) -> AirIdPermutation {
+        AirIdPermutation::compute(&self.air_heights)
+    }
 }
 
 impl AggProvingKey {
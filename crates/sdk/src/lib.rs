@@ -1,8 +1,8 @@
 extern crate core;
 
-use std::{fs::read, panic::catch_unwind, path::Path, sync::Arc};
+use std::{borrow::Borrow, fs::read, path::Path, sync::Arc};
 
-use commit::commit_app_exe;
+use commit::{babybear_digest_to_bn254, commit_app_exe, compute_exe_commit, AppExecutionCommit};
 use config::AppConfig;
 use eyre::Result;
 use keygen::{AppProvingKey, AppVerifyingKey};
@@ -10,27 +10,42 @@ use openvm_build::{
     build_guest_package, find_unique_executable, get_package, GuestOptions, TargetFilter,
 };
 use openvm_circuit::{
-    arch::{instructions::exe::VmExe, ExecutionError, VmConfig, VmExecutor},
-    system::{memory::tree::public_values::extract_public_values, program::trace::VmCommittedExe},
+    arch::{
+        hasher::poseidon2::vm_poseidon2_hasher, instructions::exe::VmExe, verify_segments,
+        ExecutionError, ExecutionHook, ExitCode, PureExecutionResult, VerifiedExecutionPayload,
+        VmConfig, VmExecutor, VmMemoryState, VmVerificationError, PROGRAM_CACHED_TRACE_INDEX,
+    },
+    system::{
+        connector::VmConnectorPvs, memory::tree::public_values::extract_public_values,
+        program::trace::VmCommittedExe,
+    },
 };
+use openvm_native_compiler::{constraints::halo2::compiler::convert_fr, ir::DIGEST_SIZE};
 use openvm_native_recursion::{
     halo2::{
         utils::Halo2ParamsReader,
-        wrapper::{EvmVerifier, Halo2WrapperProvingKey},
+        wrapper::{
+            EvmVerificationError, EvmVerifier, Halo2WrapperProvingKey,
+            NUM_ACCUMULATOR_PUBLIC_VALUES,
+        },
         EvmProof,
     },
     types::InnerConfig,
 };
-use openvm_stark_backend::engine::StarkEngine;
 use openvm_stark_sdk::{
     config::{
         baby_bear_poseidon2::{BabyBearPoseidon2Config, BabyBearPoseidon2Engine},
-        baby_bear_poseidon2_root::BabyBearPoseidon2RootConfig,
+        baby_bear_poseidon2_root::{BabyBearPoseidon2RootConfig, BabyBearPoseidon2RootEngine},
         FriParameters,
     },
-    engine::StarkFriEngine,
-    openvm_stark_backend::{verifier::VerificationError, Chip},
+    engine::{StarkEngine, StarkFriEngine},
+    openvm_stark_backend::{
+        p3_field::{AbstractField, PrimeField32},
+        prover::types::Proof,
+        Chip,
+    },
     p3_baby_bear::BabyBear,
+    p3_bn254_fr::Bn254Fr,
 };
 use openvm_transpiler::{
     elf::Elf,
@@ -53,9 +68,10 @@ pub use stdin::*;
 pub mod fs;
 
 use crate::{
-    config::AggConfig,
-    keygen::AggProvingKey,
-    prover::{AppProver, ContinuationProver},
+    config::{AggConfig, AggStarkConfig},
+    keygen::{AggProvingKey, AggStarkProvingKey, RootVerifierVerifyingKey},
+    prover::{AggStarkProver, AppProver, ContinuationProver, Halo2Prover, StarkProver},
+    verifier::root::types::{BatchRootVmVerifierPvs, RootVmVerifierPvs},
 };
 
 pub(crate) type SC = BabyBearPoseidon2Config;
@@ -64,6 +80,13 @@ pub(crate) type F = BabyBear;
 pub(crate) type RootSC = BabyBearPoseidon2RootConfig;
 pub type NonRootCommittedExe = VmCommittedExe<SC>;
 
+/// The payload of a verified app proof.
+pub struct VerifiedContinuationVmPayload {
+    /// Commitment of the executable, in the same format as [commit::AppExecutionCommit::exe_commit].
+    pub exe_commit: [F; DIGEST_SIZE],
+    pub user_public_values: Vec<F>,
+}
+
 pub struct Sdk;
 
 impl Sdk {
@@ -105,18 +128,58 @@ impl Sdk {
         vm_config: VC,
         inputs: StdIn,
     ) -> Result<Vec<F>, ExecutionError>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let result = self.execute_pure(exe, vm_config, inputs)?;
+        if result.exit_code != ExitCode::Success as u32 {
+            return Err(ExecutionError::FailedWithExitCode {
+                exit_code: result.exit_code,
+            });
+        }
+        Ok(result.public_values)
+    }
+
+    /// Executes without generating anything needed for proving, and returns the exit code,
+    /// instruction count and estimated continuation segments along with the output.
+    pub fn execute_pure<VC: VmConfig<F>>(
+        &self,
+        exe: VmExe<F>,
+        vm_config: VC,
+        inputs: StdIn,
+    ) -> Result<PureExecutionResult<F>, ExecutionError> {
+        VmExecutor::new(vm_config).execute_pure(exe, inputs)
+    }
+
+    /// Same as [Self::execute], but calls `hook` around every executed instruction. Used for
+    /// execution tracing and debugging.
+    pub fn execute_with_hook<VC: VmConfig<F>>(
+        &self,
+        exe: VmExe<F>,
+        vm_config: VC,
+        inputs: StdIn,
+        hook: &mut dyn ExecutionHook<F>,
+    ) -> Result<Vec<F>, ExecutionError>
     where
         VC::Executor: Chip<SC>,
         VC::Periphery: Chip<SC>,
     {
         let vm = VmExecutor::new(vm_config);
-        let final_memory = vm.execute(exe, inputs)?;
+        let final_memory = vm.execute_with_hook(exe, inputs, hook)?;
+        Ok(Self::extract_app_public_values(&vm, final_memory))
+    }
+
+    fn extract_app_public_values<VC: VmConfig<F>>(
+        vm: &VmExecutor<F, VC>,
+        final_memory: Option<VmMemoryState<F>>,
+    ) -> Vec<F> {
         let public_values = extract_public_values(
             &vm.config.system().memory_config.memory_dimensions(),
             vm.config.system().num_public_values,
             final_memory.as_ref().unwrap(),
         );
-        Ok(public_values)
+        public_values
     }
 
     pub fn commit_app_exe(
@@ -152,17 +215,201 @@ impl Sdk {
         Ok(proof)
     }
 
+    /// Verifies the segment proofs of an app proof natively, together with the continuation
+    /// conditions between segments and the user public values.
+    ///
+    /// The returned `exe_commit` must be checked against the expected executable, e.g. via
+    /// [commit::compute_app_exe_commit].
     pub fn verify_app_proof(
         &self,
         app_vk: &AppVerifyingKey,
         proof: &ContinuationVmProof<SC>,
-    ) -> Result<(), VerificationError> {
+    ) -> Result<VerifiedContinuationVmPayload, VmVerificationError> {
         let e = BabyBearPoseidon2Engine::new(app_vk.fri_params);
-        for seg_proof in &proof.per_segment {
-            e.verify(&app_vk.app_vm_vk, seg_proof)?
+        let VerifiedExecutionPayload {
+            program_commit,
+            pc_start,
+            initial_memory_root,
+            final_memory_root,
+        } = verify_segments(&e, &app_vk.app_vm_vk, &proof.per_segment)?;
+
+        let hasher = vm_poseidon2_hasher();
+        proof
+            .user_public_values
+            .verify(&hasher, app_vk.memory_dimensions, final_memory_root)?;
+        let exe_commit = compute_exe_commit(
+            &hasher,
+            &program_commit.into(),
+            &initial_memory_root,
+            pc_start,
+        );
+        Ok(VerifiedContinuationVmPayload {
+            exe_commit,
+            user_public_values: proof.user_public_values.public_values.clone(),
+        })
+    }
+
+    pub fn agg_stark_keygen(&self, config: AggStarkConfig) -> Result<AggStarkProvingKey> {
+        let agg_stark_pk = AggStarkProvingKey::keygen(config);
+        Ok(agg_stark_pk)
+    }
+
+    /// Generates an app proof and aggregates it into a single STARK proof of the root verifier,
+    /// without wrapping it in Halo2.
+    pub fn generate_agg_stark_proof<VC: VmConfig<F>>(
+        &self,
+        app_pk: Arc<AppProvingKey<VC>>,
+        app_exe: Arc<NonRootCommittedExe>,
+        agg_stark_pk: AggStarkProvingKey,
+        inputs: StdIn,
+    ) -> Result<Proof<RootSC>>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let stark_prover = StarkProver::new(app_pk, app_exe, agg_stark_pk);
+        let proof = stark_prover.generate_proof_for_outer_recursion(inputs);
+        Ok(proof)
+    }
+
+    /// Like [Self::generate_agg_stark_proof], for programs that recorded assumptions with
+    /// [openvm::verify_openvm_stark]. `assumptions` are proofs generated by
+    /// [Self::generate_assumption_proof] with the same `agg_stark_pk`, in the order the
+    /// assumptions were recorded.
+    pub fn generate_agg_stark_proof_with_assumptions<VC: VmConfig<F>>(
+        &self,
+        app_pk: Arc<AppProvingKey<VC>>,
+        app_exe: Arc<NonRootCommittedExe>,
+        agg_stark_pk: AggStarkProvingKey,
+        inputs: StdIn,
+        assumptions: Vec<Proof<SC>>,
+    ) -> Result<Proof<RootSC>>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let stark_prover = StarkProver::new(app_pk, app_exe, agg_stark_pk);
+        let proof =
+            stark_prover.generate_proof_for_outer_recursion_with_assumptions(inputs, assumptions);
+        Ok(proof)
+    }
+
+    /// Generates a proof of an execution that other programs can reference with
+    /// [openvm::verify_openvm_stark]. The number of public values of the app must be the
+    /// `num_referenced_public_values` of the proof composition config of `agg_stark_pk`.
+    pub fn generate_assumption_proof<VC: VmConfig<F>>(
+        &self,
+        app_pk: Arc<AppProvingKey<VC>>,
+        app_exe: Arc<NonRootCommittedExe>,
+        agg_stark_pk: AggStarkProvingKey,
+        inputs: StdIn,
+    ) -> Result<Proof<SC>>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        assert_eq!(
+            app_pk.leaf_fri_params, agg_stark_pk.leaf_vm_pk.fri_params,
+            "App VM is incompatible with Agg VM because of leaf FRI parameters"
+        );
+        let app_prover = AppProver::new(app_pk.app_vm_pk.clone(), app_exe);
+        let agg_prover = AggStarkProver::new(agg_stark_pk, app_pk.leaf_committed_exe.clone());
+        let app_proof = app_prover.generate_app_proof(inputs);
+        Ok(agg_prover.generate_internal_proof(app_proof))
+    }
+
+    /// Verifies a proof generated by [Self::generate_agg_stark_proof] natively. This performs the
+    /// same checks as the static verifier: the proof is for the root verifier program of
+    /// `root_verifier_vk` and the root verifier exited successfully. `root_verifier_vk` is obtained
+    /// from the proving key with `agg_stark_pk.root_verifier_pk.get_vk()`.
+    ///
+    /// The returned `exe_commit` and `leaf_verifier_commit` must be checked against the expected
+    /// [AppExecutionCommit].
+    pub fn verify_agg_stark_proof(
+        &self,
+        root_verifier_vk: &RootVerifierVerifyingKey,
+        proof: &Proof<RootSC>,
+    ) -> Result<RootVmVerifierPvs<F>, VmVerificationError> {
+        let public_values = verify_root_verifier_proof(root_verifier_vk, proof)?;
+        let expected = root_verifier_vk.num_public_values;
+        if public_values.len() != expected {
+            return Err(VmVerificationError::NumPublicValuesMismatch {
+                expected,
+                actual: public_values.len(),
+            });
         }
-        // TODO: verify continuation.
-        Ok(())
+        Ok(RootVmVerifierPvs::from_flatten(public_values))
+    }
+
+    /// Generates app proofs of a batch of executions on the same app VM and aggregates them into a
+    /// single STARK proof of the root verifier. `agg_stark_pk` must be generated with batch
+    /// aggregation enabled, see [config::BatchConfig].
+    pub fn generate_batch_agg_stark_proof<VC: VmConfig<F>>(
+        &self,
+        app_pk: Arc<AppProvingKey<VC>>,
+        executions: Vec<(Arc<NonRootCommittedExe>, StdIn)>,
+        agg_stark_pk: AggStarkProvingKey,
+    ) -> Result<Proof<RootSC>>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        assert_eq!(
+            app_pk.leaf_fri_params, agg_stark_pk.leaf_vm_pk.fri_params,
+            "App VM is incompatible with Agg VM because of leaf FRI parameters"
+        );
+        let app_proofs = executions
+            .into_iter()
+            .map(|(app_exe, inputs)| {
+                AppProver::new(app_pk.app_vm_pk.clone(), app_exe).generate_app_proof(inputs)
+            })
+            .collect();
+        let agg_prover = AggStarkProver::new(agg_stark_pk, app_pk.leaf_committed_exe.clone());
+        Ok(agg_prover.generate_batch_agg_proof(app_proofs))
+    }
+
+    /// Like [Self::generate_batch_agg_stark_proof], and wraps the root proof in Halo2 for EVM
+    /// verification. The EVM proof exposes the `batch_commit` in place of the exe commit and the
+    /// number of executions as the only user public value.
+    pub fn generate_batch_evm_proof<VC: VmConfig<F>>(
+        &self,
+        reader: &impl Halo2ParamsReader,
+        app_pk: Arc<AppProvingKey<VC>>,
+        executions: Vec<(Arc<NonRootCommittedExe>, StdIn)>,
+        agg_pk: AggProvingKey,
+    ) -> Result<EvmProof>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let AggProvingKey {
+            agg_stark_pk,
+            halo2_pk,
+        } = agg_pk;
+        let root_proof = self.generate_batch_agg_stark_proof(app_pk, executions, agg_stark_pk)?;
+        Ok(Halo2Prover::new(reader, halo2_pk).prove_for_evm(&root_proof))
+    }
+
+    /// Verifies a proof generated by [Self::generate_batch_agg_stark_proof] natively, with the
+    /// same checks as [Self::verify_agg_stark_proof].
+    ///
+    /// The returned `batch_commit` and `num_executions` must be checked against
+    /// [compute_batch_commit](commit::compute_batch_commit) and the number of the expected
+    /// executions.
+    pub fn verify_batch_agg_stark_proof(
+        &self,
+        root_verifier_vk: &RootVerifierVerifyingKey,
+        proof: &Proof<RootSC>,
+    ) -> Result<BatchRootVmVerifierPvs<F>, VmVerificationError> {
+        let public_values = verify_root_verifier_proof(root_verifier_vk, proof)?;
+        let expected = 2 * DIGEST_SIZE + 1;
+        if public_values.len() != expected {
+            return Err(VmVerificationError::NumPublicValuesMismatch {
+                expected,
+                actual: public_values.len(),
+            });
+        }
+        Ok(BatchRootVmVerifierPvs::from_flatten(public_values))
     }
 
     pub fn agg_keygen(
@@ -201,11 +448,109 @@ impl Sdk {
         Ok(evm_verifier)
     }
 
-    pub fn verify_evm_proof(&self, evm_verifier: &EvmVerifier, evm_proof: &EvmProof) -> bool {
-        // FIXME: we should return the concrete error.
-        catch_unwind(|| {
-            Halo2WrapperProvingKey::evm_verify(evm_verifier, evm_proof);
-        })
-        .is_ok()
+    /// Verifies an EVM proof by executing the EVM verifier in a local EVM. Returns the gas used by
+    /// the verification call.
+    pub fn verify_evm_proof(
+        &self,
+        evm_verifier: &EvmVerifier,
+        evm_proof: &EvmProof,
+    ) -> Result<u64, EvmVerificationError> {
+        Halo2WrapperProvingKey::evm_verify(evm_verifier, evm_proof)
+    }
+
+    /// Same as [Self::verify_evm_proof], but also checks that the EVM proof is for the app VM and
+    /// executable committed by `app_commit`.
+    pub fn verify_evm_proof_with_commit(
+        &self,
+        evm_verifier: &EvmVerifier,
+        evm_proof: &EvmProof,
+        app_commit: &AppExecutionCommit<F>,
+    ) -> Result<u64, EvmVerificationError> {
+        check_evm_public_values(
+            evm_proof,
+            &[
+                app_commit.exe_commit_to_bn254(),
+                app_commit.app_config_commit_to_bn254(),
+            ],
+        )?;
+        self.verify_evm_proof(evm_verifier, evm_proof)
+    }
+
+    /// Verifies a proof generated by [Self::generate_batch_evm_proof] and checks that it is for
+    /// `num_executions` executions with `batch_commit` on the app VM with `leaf_verifier_commit`.
+    pub fn verify_batch_evm_proof_with_commit(
+        &self,
+        evm_verifier: &EvmVerifier,
+        evm_proof: &EvmProof,
+        batch_commit: &[F; DIGEST_SIZE],
+        leaf_verifier_commit: &[F; DIGEST_SIZE],
+        num_executions: usize,
+    ) -> Result<u64, EvmVerificationError> {
+        check_evm_public_values(
+            evm_proof,
+            &[
+                babybear_digest_to_bn254(batch_commit),
+                babybear_digest_to_bn254(leaf_verifier_commit),
+                Bn254Fr::from_canonical_usize(num_executions),
+            ],
+        )?;
+        self.verify_evm_proof(evm_verifier, evm_proof)
     }
 }
+
+/// Checks that the public values of `evm_proof`, after the accumulator, start with `expected`.
+fn check_evm_public_values(
+    evm_proof: &EvmProof,
+    expected: &[Bn254Fr],
+) -> Result<(), EvmVerificationError> {
+    for (i, expected) in expected.iter().enumerate() {
+        let index = NUM_ACCUMULATOR_PUBLIC_VALUES + i;
+        let actual = evm_proof.instances.first().and_then(|pvs| pvs.get(index));
+        if actual != Some(&convert_fr(expected)) {
+            return Err(EvmVerificationError::PublicInputMismatch { index });
+        }
+    }
+    Ok(())
+}
+
+/// Verifies a proof of the root verifier of `root_verifier_vk` and returns its public values.
+fn verify_root_verifier_proof(
+    root_verifier_vk: &RootVerifierVerifyingKey,
+    proof: &Proof<RootSC>,
+) -> Result<Vec<F>, VmVerificationError> {
+    let e = BabyBearPoseidon2RootEngine::new(root_verifier_vk.fri_params);
+    e.verify(&root_verifier_vk.vm_vk, proof)?;
+
+    let program_commit = proof.commitments.main_trace.get(PROGRAM_CACHED_TRACE_INDEX);
+    if program_commit != Some(&root_verifier_vk.program_commit) {
+        return Err(VmVerificationError::ProgramCommitMismatch { index: 0 });
+    }
+
+    let special_air_ids = root_verifier_vk.air_id_permutation().get_special_air_ids();
+    let air_pvs = |air_id: usize| {
+        proof
+            .per_air
+            .iter()
+            .find(|air_proof_data| air_proof_data.air_id == air_id)
+            .map(|air_proof_data| &air_proof_data.public_values)
+            .ok_or(VmVerificationError::SystemAirMissing { air_id })
+    };
+
+    let connector_pvs: &VmConnectorPvs<_> = air_pvs(special_air_ids.connector_air_id)?
+        .as_slice()
+        .borrow();
+    if connector_pvs.is_terminate != F::ONE {
+        return Err(VmVerificationError::IsTerminateMismatch {
+            expected: true,
+            actual: false,
+        });
+    }
+    if connector_pvs.exit_code != F::from_canonical_u32(ExitCode::Success as u32) {
+        return Err(VmVerificationError::ExitCodeMismatch {
+            expected: ExitCode::Success as u32,
+            actual: connector_pvs.exit_code.as_canonical_u32(),
+        });
+    }
+
+    Ok(air_pvs(special_air_ids.public_values_air_id)?.clone())
+}
@@ -1,13 +1,17 @@
 use std::sync::Arc;
 
+use eyre::Result;
+use openvm::composition::{ASSUMPTIONS_OFFSET, NUM_ASSUMPTIONS_OFFSET};
 #[cfg(feature = "bench-metrics")]
 use openvm_circuit::arch::SingleSegmentVmExecutor;
 use openvm_circuit::arch::Streams;
 use openvm_native_circuit::NativeConfig;
+use openvm_native_compiler::ir::DIGEST_SIZE;
 use openvm_native_recursion::hints::Hintable;
 use openvm_stark_sdk::{
-    config::baby_bear_poseidon2::BabyBearPoseidon2Engine, engine::StarkFriEngine,
-    openvm_stark_backend::prover::types::Proof,
+    config::baby_bear_poseidon2::BabyBearPoseidon2Engine,
+    engine::StarkFriEngine,
+    openvm_stark_backend::{p3_field::AbstractField, prover::types::Proof},
 };
 use tracing::info_span;
 
@@ -20,7 +24,8 @@ use crate::{
         RootVerifierLocalProver,
     },
     verifier::{
-        internal::types::InternalVmVerifierInput, leaf::types::LeafVmVerifierInput,
+        internal::types::{AssumptionsInput, InternalVmVerifierInput},
+        leaf::types::LeafVmVerifierInput,
         root::types::RootVmVerifierInput,
     },
     NonRootCommittedExe, RootSC, F, SC,
@@ -31,7 +36,7 @@ const DEFAULT_NUM_CHILDREN_INTERNAL: usize = 2;
 const DEFAULT_MAX_INTERNAL_WRAPPER_LAYERS: usize = 4;
 
 pub struct AggStarkProver {
-    leaf_prover: LeafProver,
+    pub(crate) leaf_prover: LeafProver,
     internal_prover: VmLocalProver<SC, NativeConfig, BabyBearPoseidon2Engine>,
     root_prover: RootVerifierLocalProver,
 
@@ -96,25 +101,199 @@ impl AggStarkProver {
     pub fn generate_agg_proof(&self, app_proofs: ContinuationVmProof<SC>) -> Proof<RootSC> {
         let leaf_proofs = self.leaf_prover.generate_proof(&app_proofs);
         let public_values = app_proofs.user_public_values.public_values;
-        let internal_proof = self.generate_internal_proof_impl(leaf_proofs, &public_values);
+        let internal_proof = self.generate_internal_proof_impl(leaf_proofs, &public_values, None);
         self.generate_root_proof_impl(RootVmVerifierInput {
             proofs: vec![internal_proof],
             public_values,
         })
     }
 
+    /// Generate a proof to aggregate app proofs of an execution that recorded assumptions with
+    /// [openvm::verify_openvm_stark]. `assumptions` are proofs of the referenced executions
+    /// generated by [Self::generate_internal_proof], in the order the assumptions were recorded.
+    /// The aggregation key must be generated with proof composition enabled.
+    pub fn generate_agg_proof_with_assumptions(
+        &self,
+        app_proofs: ContinuationVmProof<SC>,
+        assumptions: Vec<Proof<SC>>,
+    ) -> Proof<RootSC> {
+        let leaf_proofs = self.leaf_prover.generate_proof(&app_proofs);
+        let public_values = app_proofs.user_public_values.public_values;
+        // The internal verifier resolving the assumptions clears their number in the public values.
+        let mut resolved_public_values = public_values.clone();
+        resolved_public_values[NUM_ASSUMPTIONS_OFFSET..ASSUMPTIONS_OFFSET].fill(F::ZERO);
+        let internal_proof = self.generate_internal_proof_impl(
+            leaf_proofs,
+            &resolved_public_values,
+            Some(AssumptionsInput {
+                public_values,
+                proofs: assumptions,
+            }),
+        );
+        self.generate_root_proof_impl(RootVmVerifierInput {
+            proofs: vec![internal_proof],
+            public_values: resolved_public_values,
+        })
+    }
+
+    /// Generate a single internal verifier proof of app proofs, which other executions can
+    /// reference with [openvm::verify_openvm_stark]. Unlike [Self::generate_agg_proof], the number
+    /// of public values of the app does not need to match the aggregation key.
+    pub fn generate_internal_proof(&self, app_proofs: ContinuationVmProof<SC>) -> Proof<SC> {
+        let mut proofs = self.leaf_prover.generate_proof(&app_proofs);
+        let mut internal_node_height = 0;
+        let mut internal_node_idx = -1;
+        while proofs.len() > 1 {
+            let internal_inputs = InternalVmVerifierInput::chunk_leaf_or_internal_proofs(
+                self.internal_program_commit(),
+                &proofs,
+                self.num_children_internal,
+            );
+            proofs = self.prove_internal_layer(
+                internal_node_height,
+                internal_inputs,
+                &mut internal_node_idx,
+            );
+            internal_node_height += 1;
+        }
+        proofs.pop().unwrap()
+    }
+
+    /// Generate a proof to aggregate the app proofs of a batch of executions. The aggregation key
+    /// must be generated with batch aggregation enabled, see [crate::config::BatchConfig]. The
+    /// executions are committed in the order of `app_proofs`.
+    pub fn generate_batch_agg_proof(
+        &self,
+        app_proofs: Vec<ContinuationVmProof<SC>>,
+    ) -> Proof<RootSC> {
+        assert!(
+            !app_proofs.is_empty(),
+            "Batch must contain at least one execution"
+        );
+        let mut proofs: Vec<_> = app_proofs
+            .into_iter()
+            .map(|app_proofs| self.generate_internal_proof(app_proofs))
+            .collect();
+        let mut internal_node_height = 0;
+        let mut internal_node_idx = -1;
+        let mut wrapper_layers = 0;
+        loop {
+            let actual_air_heights =
+                self.root_prover
+                    .execute_for_air_heights(RootVmVerifierInput {
+                        proofs: proofs.clone(),
+                        public_values: vec![],
+                    });
+            if heights_le(
+                &actual_air_heights,
+                &self.root_prover.root_verifier_pk.air_heights,
+            ) {
+                break;
+            }
+            if wrapper_layers >= self.max_internal_wrapper_layers {
+                panic!("The heights of the root verifier still exceed the required heights after {} wrapper layers", self.max_internal_wrapper_layers);
+            }
+            wrapper_layers += 1;
+            // Wrap each execution separately so that the root verifier still sees one proof per
+            // execution.
+            let internal_inputs = proofs
+                .iter()
+                .flat_map(|proof| {
+                    InternalVmVerifierInput::chunk_leaf_or_internal_proofs(
+                        self.internal_program_commit(),
+                        std::slice::from_ref(proof),
+                        self.num_children_internal,
+                    )
+                })
+                .collect();
+            proofs = self.prove_internal_layer(
+                internal_node_height,
+                internal_inputs,
+                &mut internal_node_idx,
+            );
+            internal_node_height += 1;
+        }
+        self.generate_root_proof_impl(RootVmVerifierInput {
+            proofs,
+            public_values: vec![],
+        })
+    }
+
     fn generate_internal_proof_impl(
         &self,
         leaf_proofs: Vec<Proof<SC>>,
         public_values: &[F],
+        assumptions: Option<AssumptionsInput<SC>>,
     ) -> Proof<SC> {
         let mut internal_node_idx = -1;
+        self.aggregate_internal_proofs(
+            leaf_proofs,
+            public_values,
+            assumptions,
+            |internal_node_height, internal_inputs| {
+                Ok(self.prove_internal_layer(
+                    internal_node_height,
+                    internal_inputs,
+                    &mut internal_node_idx,
+                ))
+            },
+        )
+        .unwrap()
+    }
+
+    fn prove_internal_layer(
+        &self,
+        internal_node_height: usize,
+        internal_inputs: Vec<InternalVmVerifierInput<SC>>,
+        internal_node_idx: &mut i32,
+    ) -> Vec<Proof<SC>> {
+        info_span!("internal verifier", group = "internal").in_scope(|| {
+            #[cfg(feature = "bench-metrics")]
+            metrics::counter!("fri.log_blowup")
+                .absolute(self.internal_prover.pk.fri_params.log_blowup as u64);
+            internal_inputs
+                .into_iter()
+                .map(|input| {
+                    *internal_node_idx += 1;
+                    info_span!(
+                        "Internal verifier proof",
+                        idx = *internal_node_idx,
+                        hgt = internal_node_height
+                    )
+                    .in_scope(|| {
+                        single_segment_prove(&self.internal_prover, input.write(), self.profile)
+                    })
+                })
+                .collect()
+        })
+    }
+
+    fn internal_program_commit(&self) -> [F; DIGEST_SIZE] {
+        self.internal_prover
+            .committed_exe
+            .get_program_commit()
+            .into()
+    }
+
+    /// Aggregates `leaf_proofs` layer by layer until the root verifier can verify the single
+    /// remaining internal proof. `prove_layer` proves the inputs of the internal verifier at the
+    /// given height of the aggregation tree.
+    ///
+    /// If `assumptions` is set, they are resolved by the first internal layer, so `public_values`
+    /// must be the public values after resolution.
+    pub(crate) fn aggregate_internal_proofs(
+        &self,
+        leaf_proofs: Vec<Proof<SC>>,
+        public_values: &[F],
+        mut assumptions: Option<AssumptionsInput<SC>>,
+        mut prove_layer: impl FnMut(usize, Vec<InternalVmVerifierInput<SC>>) -> Result<Vec<Proof<SC>>>,
+    ) -> Result<Proof<SC>> {
         let mut internal_node_height = 0;
         let mut proofs = leaf_proofs;
         let mut wrapper_layers = 0;
         loop {
             // TODO: what's a good test case for the wrapping logic?
-            if proofs.len() == 1 {
+            if proofs.len() == 1 && assumptions.is_none() {
                 // TODO: record execution time as a part of root verifier execution time.
                 let actual_air_heights =
                     self.root_prover
@@ -134,39 +313,23 @@ impl AggStarkProver {
                 }
                 wrapper_layers += 1;
             }
-            let internal_inputs = InternalVmVerifierInput::chunk_leaf_or_internal_proofs(
-                self.internal_prover
-                    .committed_exe
-                    .get_program_commit()
-                    .into(),
+            let mut internal_inputs = InternalVmVerifierInput::chunk_leaf_or_internal_proofs(
+                self.internal_program_commit(),
                 &proofs,
                 self.num_children_internal,
             );
-            proofs = info_span!("internal verifier", group = "internal").in_scope(|| {
-                #[cfg(feature = "bench-metrics")]
-                metrics::counter!("fri.log_blowup")
-                    .absolute(self.internal_prover.pk.fri_params.log_blowup as u64);
-                internal_inputs
-                    .into_iter()
-                    .map(|input| {
-                        internal_node_idx += 1;
-                        info_span!(
-                            "Internal verifier proof",
-                            idx = internal_node_idx,
-                            hgt = internal_node_height
-                        )
-                        .in_scope(|| {
-                            single_segment_prove(&self.internal_prover, input.write(), self.profile)
-                        })
-                    })
-                    .collect()
-            });
+            // Only the node aggregating the last segment knows the final public values.
+            internal_inputs.last_mut().unwrap().assumptions = assumptions.take();
+            proofs = prove_layer(internal_node_height, internal_inputs)?;
             internal_node_height += 1;
         }
-        proofs.pop().unwrap()
+        Ok(proofs.pop().unwrap())
     }
 
-    fn generate_root_proof_impl(&self, root_input: RootVmVerifierInput<SC>) -> Proof<RootSC> {
+    pub(crate) fn generate_root_proof_impl(
+        &self,
+        root_input: RootVmVerifierInput<SC>,
+    ) -> Proof<RootSC> {
         info_span!("root verifier", group = "root").in_scope(|| {
             let input = root_input.write();
             #[cfg(feature = "bench-metrics")]
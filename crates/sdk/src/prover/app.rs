@@ -55,6 +55,22 @@ impl<VC> AppProver<VC> {
         self.set_program_name(program_name);
         self
     }
+    /// See [VmLocalProver::set_max_in_flight_segments].
+    pub fn set_max_in_flight_segments(&mut self, max_in_flight_segments: usize) -> &mut Self
+    where
+        VC: VmConfig<F>,
+    {
+        self.app_prover
+            .set_max_in_flight_segments(max_in_flight_segments);
+        self
+    }
+    pub fn with_max_in_flight_segments(mut self, max_in_flight_segments: usize) -> Self
+    where
+        VC: VmConfig<F>,
+    {
+        self.set_max_in_flight_segments(max_in_flight_segments);
+        self
+    }
 
     pub fn generate_app_proof(&self, input: StdIn) -> ContinuationVmProof<SC>
     where
@@ -0,0 +1,294 @@
+use std::{
+    sync::{
+        mpsc::{self, Receiver, Sender, SyncSender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use eyre::{eyre, Result, WrapErr};
+use openvm_circuit::{
+    arch::{hasher::poseidon2::vm_poseidon2_hasher, VmConfig, VmExecutor},
+    system::memory::tree::public_values::UserPublicValuesProof,
+};
+use openvm_native_recursion::hints::Hintable;
+use openvm_stark_sdk::openvm_stark_backend::{prover::types::Proof, Chip};
+
+use super::protocol::{
+    read_message, write_message, WorkerAddress, WorkerCommits, WorkerRequest, WorkerResponse,
+    WorkerStream,
+};
+use crate::{
+    keygen::{AggStarkProvingKey, AppProvingKey},
+    prover::{vm::ContinuationVmProof, AggStarkProver},
+    stdin::StdIn,
+    verifier::{leaf::types::LeafVmVerifierInput, root::types::RootVmVerifierInput},
+    NonRootCommittedExe, RootSC, F, SC,
+};
+
+/// Coordinator of distributed proving. The coordinator executes the app program and cuts it into
+/// segments, while the segments and the leaf/internal aggregation nodes are proven by
+/// [ProverWorker](super::ProverWorker)s. Only the root verifier proof is generated locally.
+pub struct DistributedProver<VC> {
+    app_pk: Arc<AppProvingKey<VC>>,
+    app_committed_exe: Arc<NonRootCommittedExe>,
+    agg_prover: Option<AggStarkProver>,
+    workers: Vec<WorkerAddress>,
+    expected_commits: WorkerCommits,
+}
+
+impl<VC: VmConfig<F>> DistributedProver<VC>
+where
+    VC::Executor: Chip<SC>,
+    VC::Periphery: Chip<SC>,
+{
+    pub fn new(
+        app_pk: Arc<AppProvingKey<VC>>,
+        app_committed_exe: Arc<NonRootCommittedExe>,
+        workers: Vec<WorkerAddress>,
+    ) -> Result<Self> {
+        if workers.is_empty() {
+            return Err(eyre!("At least one worker is required"));
+        }
+        let expected_commits = WorkerCommits {
+            app_program_commit: app_committed_exe.get_program_commit().into(),
+            leaf_program_commit: None,
+            internal_program_commit: None,
+        };
+        Ok(Self {
+            app_pk,
+            app_committed_exe,
+            agg_prover: None,
+            workers,
+            expected_commits,
+        })
+    }
+
+    /// Enables [Self::generate_agg_proof]. The workers must be started with the same aggregation
+    /// key.
+    pub fn with_agg_stark_pk(mut self, agg_stark_pk: AggStarkProvingKey) -> Self {
+        self.expected_commits.leaf_program_commit =
+            Some(self.app_pk.leaf_committed_exe.get_program_commit().into());
+        self.expected_commits.internal_program_commit =
+            Some(agg_stark_pk.internal_program_commit());
+        self.agg_prover = Some(AggStarkProver::new(
+            agg_stark_pk,
+            self.app_pk.leaf_committed_exe.clone(),
+        ));
+        self
+    }
+
+    pub fn generate_app_proof(&self, input: StdIn) -> Result<ContinuationVmProof<SC>> {
+        let mut pool = WorkerPool::connect(&self.workers, &self.expected_commits)?;
+        self.generate_app_proof_impl(&mut pool, input)
+    }
+
+    /// Generates the app proof and aggregates it into a root verifier proof, the same proof as
+    /// [Sdk::generate_agg_stark_proof](crate::Sdk::generate_agg_stark_proof).
+    pub fn generate_agg_proof(&self, input: StdIn) -> Result<Proof<RootSC>> {
+        let agg_prover = self
+            .agg_prover
+            .as_ref()
+            .ok_or_else(|| eyre!("DistributedProver was created without an aggregation key"))?;
+        let mut pool = WorkerPool::connect(&self.workers, &self.expected_commits)?;
+        let app_proof = self.generate_app_proof_impl(&mut pool, input)?;
+
+        let leaf_inputs = LeafVmVerifierInput::chunk_continuation_vm_proof(
+            &app_proof,
+            agg_prover.leaf_prover.num_children_leaf,
+        );
+        for leaf_input in leaf_inputs {
+            pool.submit(WorkerRequest::Leaf(leaf_input.write_to_stream()))?;
+        }
+        let leaf_proofs = pool.collect().wrap_err("Failed to prove leaf verifier")?;
+
+        let public_values = app_proof.user_public_values.public_values;
+        let internal_proof = agg_prover.aggregate_internal_proofs(
+            leaf_proofs,
+            &public_values,
+            None,
+            |_, internal_inputs| {
+                for internal_input in internal_inputs {
+                    pool.submit(WorkerRequest::Internal(internal_input.write()))?;
+                }
+                pool.collect().wrap_err("Failed to prove internal verifier")
+            },
+        )?;
+        Ok(agg_prover.generate_root_proof_impl(RootVmVerifierInput {
+            proofs: vec![internal_proof],
+            public_values,
+        }))
+    }
+
+    fn generate_app_proof_impl(
+        &self,
+        pool: &mut WorkerPool,
+        input: StdIn,
+    ) -> Result<ContinuationVmProof<SC>> {
+        let vm_config = &self.app_pk.app_vm_pk.vm_config;
+        assert!(vm_config.system().continuation_enabled);
+        let executor = VmExecutor::<F, VC>::new(vm_config.clone());
+        // Segments are submitted while execution continues. A failed submission means every
+        // worker is gone, which `collect` reports below.
+        let mut submit_result = Ok(());
+        let final_memory = executor.execute_checkpoints_streaming(
+            self.app_committed_exe.exe.clone(),
+            input,
+            |checkpoint| {
+                if submit_result.is_ok() {
+                    submit_result = pool.submit(WorkerRequest::AppSegment(checkpoint));
+                }
+            },
+        )?;
+        let per_segment = pool.collect().wrap_err("Failed to prove app segment")?;
+        submit_result?;
+        let user_public_values = UserPublicValuesProof::compute(
+            vm_config.system().memory_config.memory_dimensions(),
+            vm_config.system().num_public_values,
+            &vm_poseidon2_hasher(),
+            final_memory.as_ref().unwrap(),
+        );
+        Ok(ContinuationVmProof {
+            per_segment,
+            user_public_values,
+        })
+    }
+}
+
+/// Number of times a job is sent to a worker before proving fails.
+pub const MAX_JOB_ATTEMPTS: usize = 3;
+
+/// Result of a job. A failed job hands the request back so it can be queued again.
+type JobResult = (usize, Result<Proof<SC>, (WorkerRequest, eyre::Report)>);
+
+/// One thread per worker connection. The threads take jobs from a shared bounded queue, so at
+/// most two jobs per worker are pending at any time and faster workers take more jobs. A worker
+/// that fails a job is dropped and the job is queued again for the remaining workers.
+struct WorkerPool {
+    job_sender: SyncSender<(usize, WorkerRequest)>,
+    result_receiver: Receiver<JobResult>,
+    num_jobs: usize,
+}
+
+impl WorkerPool {
+    fn connect(workers: &[WorkerAddress], expected_commits: &WorkerCommits) -> Result<Self> {
+        let (job_sender, job_receiver) = mpsc::sync_channel(workers.len());
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+        let (result_sender, result_receiver) = mpsc::channel();
+        for address in workers {
+            let stream = handshake(address, expected_commits)
+                .wrap_err_with(|| format!("Failed to connect to worker {address}"))?;
+            let address = address.clone();
+            let job_receiver = job_receiver.clone();
+            let result_sender = result_sender.clone();
+            thread::spawn(move || run_connection(address, stream, job_receiver, result_sender));
+        }
+        Ok(Self {
+            job_sender,
+            result_receiver,
+            num_jobs: 0,
+        })
+    }
+
+    /// Queues a job, blocking while the queue is full.
+    fn submit(&mut self, request: WorkerRequest) -> Result<()> {
+        self.job_sender
+            .send((self.num_jobs, request))
+            .map_err(|_| eyre!("All workers disconnected"))?;
+        self.num_jobs += 1;
+        Ok(())
+    }
+
+    /// Waits for the proofs of all jobs submitted since the last call, in submission order. Failed
+    /// jobs are queued again until they have been attempted [MAX_JOB_ATTEMPTS] times.
+    fn collect(&mut self) -> Result<Vec<Proof<SC>>> {
+        let mut proofs: Vec<Option<Proof<SC>>> = (0..self.num_jobs).map(|_| None).collect();
+        let mut attempts = vec![1; self.num_jobs];
+        let mut num_pending = self.num_jobs;
+        while num_pending > 0 {
+            let (idx, result) = self
+                .result_receiver
+                .recv()
+                .map_err(|_| eyre!("All workers disconnected"))?;
+            match result {
+                Ok(proof) => {
+                    proofs[idx] = Some(proof);
+                    num_pending -= 1;
+                }
+                Err((_, e)) if attempts[idx] >= MAX_JOB_ATTEMPTS => {
+                    return Err(e.wrap_err(format!("Job failed {MAX_JOB_ATTEMPTS} times")));
+                }
+                Err((request, e)) => {
+                    tracing::warn!("{e:#}, retrying the job on another worker");
+                    attempts[idx] += 1;
+                    self.job_sender
+                        .send((idx, request))
+                        .map_err(|_| e.wrap_err("All workers disconnected"))?;
+                }
+            }
+        }
+        self.num_jobs = 0;
+        Ok(proofs.into_iter().map(Option::unwrap).collect())
+    }
+}
+
+fn handshake(address: &WorkerAddress, expected_commits: &WorkerCommits) -> Result<WorkerStream> {
+    let mut stream = address.connect()?;
+    write_message(&mut stream, &WorkerRequest::Handshake)?;
+    let commits = match read_message(&mut stream)? {
+        Some(WorkerResponse::Handshake(commits)) => commits,
+        Some(WorkerResponse::Error(e)) => return Err(eyre!(e)),
+        _ => return Err(eyre!("Unexpected handshake response")),
+    };
+    let commit_matches =
+        |expected: Option<_>, actual: Option<_>| expected.is_none() || expected == actual;
+    if commits.app_program_commit != expected_commits.app_program_commit
+        || !commit_matches(
+            expected_commits.leaf_program_commit,
+            commits.leaf_program_commit,
+        )
+        || !commit_matches(
+            expected_commits.internal_program_commit,
+            commits.internal_program_commit,
+        )
+    {
+        return Err(eyre!(
+            "Worker program commitments {commits:?} do not match {expected_commits:?}"
+        ));
+    }
+    Ok(stream)
+}
+
+/// Proves jobs on one worker connection until the job queue is closed or a job fails. A failed
+/// job is returned with its request and the worker is not used again.
+fn run_connection(
+    address: WorkerAddress,
+    mut stream: WorkerStream,
+    job_receiver: Arc<Mutex<Receiver<(usize, WorkerRequest)>>>,
+    result_sender: Sender<JobResult>,
+) {
+    loop {
+        let job = job_receiver.lock().unwrap().recv();
+        let Ok((idx, request)) = job else {
+            return;
+        };
+        let result = match request_proof(&mut stream, &request) {
+            Ok(proof) => Ok(proof),
+            Err(e) => Err((request, e.wrap_err(format!("Worker {address} failed")))),
+        };
+        let failed = result.is_err();
+        if result_sender.send((idx, result)).is_err() || failed {
+            return;
+        }
+    }
+}
+
+fn request_proof(stream: &mut WorkerStream, request: &WorkerRequest) -> Result<Proof<SC>> {
+    write_message(stream, request)?;
+    match read_message(stream)? {
+        Some(WorkerResponse::Proof(proof)) => Ok(proof),
+        Some(WorkerResponse::Error(e)) => Err(eyre!(e)),
+        Some(WorkerResponse::Handshake(_)) => Err(eyre!("Unexpected handshake response")),
+        None => Err(eyre!("Worker closed the connection")),
+    }
+}
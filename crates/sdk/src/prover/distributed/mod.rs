@@ -0,0 +1,20 @@
+//! Distributed proving over a local socket or TCP. A [DistributedProver] executes the app program
+//! and sends the starting checkpoint (memory and input streams) of each segment to
+//! [ProverWorker]s, which prove the segment and the leaf/internal verifier nodes of the
+//! aggregation tree. The coordinator assembles the [ContinuationVmProof](super::vm::ContinuationVmProof)
+//! and the aggregation tree from the returned proofs.
+//!
+//! Each message is a little-endian `u64` length followed by the bitcode encoding of a
+//! [WorkerRequest] or [WorkerResponse], at most [MAX_MESSAGE_LEN] bytes long. A worker answers
+//! every request with one response.
+//!
+//! The protocol is neither authenticated nor encrypted: anyone who can connect to a worker can use
+//! it to prove, and anyone on the path can read the inputs. Workers must only listen on trusted
+//! interfaces, e.g. a Unix domain socket or a private network.
+
+mod coordinator;
+pub use coordinator::*;
+mod protocol;
+pub use protocol::*;
+mod worker;
+pub use worker::*;
@@ -0,0 +1,216 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    io::{self, Read, Write},
+    net::{TcpListener, TcpStream},
+    str::FromStr,
+};
+#[cfg(unix)]
+use std::{
+    os::unix::net::{UnixListener, UnixStream},
+    path::PathBuf,
+};
+
+use eyre::{eyre, Result};
+use openvm_circuit::arch::VmCheckpoint;
+use openvm_native_compiler::ir::DIGEST_SIZE;
+use openvm_stark_sdk::openvm_stark_backend::prover::types::Proof;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{F, SC};
+
+/// Request sent by the coordinator to a worker. The worker answers each request with exactly one
+/// [WorkerResponse].
+#[derive(Serialize, Deserialize)]
+pub enum WorkerRequest {
+    /// Asks for the program commitments of the worker, to check that it was started with the same
+    /// keys and executable as the coordinator.
+    Handshake,
+    /// Prove the app VM segment starting at this checkpoint.
+    AppSegment(VmCheckpoint<F>),
+    /// Prove the leaf verifier on these input streams.
+    Leaf(Vec<Vec<F>>),
+    /// Prove the internal verifier on these input streams.
+    Internal(Vec<Vec<F>>),
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum WorkerResponse {
+    Handshake(WorkerCommits),
+    Proof(Proof<SC>),
+    Error(String),
+}
+
+/// Commitments of the programs a worker proves.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkerCommits {
+    pub app_program_commit: [F; DIGEST_SIZE],
+    /// `None` if the worker only proves app segments.
+    pub leaf_program_commit: Option<[F; DIGEST_SIZE]>,
+    /// `None` if the worker only proves app segments.
+    pub internal_program_commit: Option<[F; DIGEST_SIZE]>,
+}
+
+/// Maximum length of an encoded message, enough for the checkpoint of a segment with a fully
+/// touched memory. Messages are read into memory whole, so a peer must not be able to make the
+/// reader allocate an arbitrary amount.
+pub const MAX_MESSAGE_LEN: u64 = 1 << 30;
+
+/// Writes `message` as a little-endian `u64` length followed by its bitcode encoding.
+pub fn write_message<T: Serialize>(stream: &mut impl Write, message: &T) -> Result<()> {
+    let bytes = bitcode::serialize(message)?;
+    if bytes.len() as u64 > MAX_MESSAGE_LEN {
+        return Err(eyre!(
+            "Message of {} bytes exceeds the maximum of {MAX_MESSAGE_LEN} bytes",
+            bytes.len()
+        ));
+    }
+    stream.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    stream.write_all(&bytes)?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Reads a message written by [write_message]. Returns `None` if the stream was closed before
+/// the message started, and an error if the message is longer than [MAX_MESSAGE_LEN].
+pub fn read_message<T: DeserializeOwned>(stream: &mut impl Read) -> Result<Option<T>> {
+    let mut len = [0u8; 8];
+    match stream.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    let len = u64::from_le_bytes(len);
+    if len > MAX_MESSAGE_LEN {
+        return Err(eyre!(
+            "Message of {len} bytes exceeds the maximum of {MAX_MESSAGE_LEN} bytes"
+        ));
+    }
+    // The buffer grows with the bytes actually received rather than with the announced length.
+    let mut bytes = Vec::new();
+    stream.take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(Some(bitcode::deserialize(&bytes)?))
+}
+
+/// Address of a worker: `unix:<path>` for a Unix domain socket, otherwise a TCP `host:port`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WorkerAddress {
+    Tcp(String),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl FromStr for WorkerAddress {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.strip_prefix("unix:") {
+            #[cfg(unix)]
+            Some(path) => Ok(Self::Unix(path.into())),
+            #[cfg(not(unix))]
+            Some(_) => Err(eyre!(
+                "Unix domain sockets are not supported on this platform"
+            )),
+            None => Ok(Self::Tcp(s.to_string())),
+        }
+    }
+}
+
+impl Display for WorkerAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "{addr}"),
+            #[cfg(unix)]
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+impl WorkerAddress {
+    pub fn connect(&self) -> Result<WorkerStream> {
+        let stream = match self {
+            Self::Tcp(addr) => WorkerStream::Tcp(TcpStream::connect(addr)?),
+            #[cfg(unix)]
+            Self::Unix(path) => WorkerStream::Unix(UnixStream::connect(path)?),
+        };
+        Ok(stream)
+    }
+
+    pub fn bind(&self) -> Result<WorkerListener> {
+        let listener = match self {
+            Self::Tcp(addr) => WorkerListener::Tcp(TcpListener::bind(addr)?),
+            #[cfg(unix)]
+            Self::Unix(path) => WorkerListener::Unix(UnixListener::bind(path)?),
+        };
+        Ok(listener)
+    }
+}
+
+pub enum WorkerStream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Read for WorkerStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for WorkerStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.flush(),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+pub enum WorkerListener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl WorkerListener {
+    pub fn accept(&self) -> Result<WorkerStream> {
+        let stream = match self {
+            Self::Tcp(listener) => WorkerStream::Tcp(listener.accept()?.0),
+            #[cfg(unix)]
+            Self::Unix(listener) => WorkerStream::Unix(listener.accept()?.0),
+        };
+        Ok(stream)
+    }
+
+    /// The address coordinators should connect to, e.g. the actual port when bound to port 0.
+    pub fn local_addr(&self) -> Result<WorkerAddress> {
+        let addr = match self {
+            Self::Tcp(listener) => WorkerAddress::Tcp(listener.local_addr()?.to_string()),
+            #[cfg(unix)]
+            Self::Unix(listener) => WorkerAddress::Unix(
+                listener
+                    .local_addr()?
+                    .as_pathname()
+                    .ok_or_else(|| eyre!("Unix socket is not bound to a path"))?
+                    .to_path_buf(),
+            ),
+        };
+        Ok(addr)
+    }
+}
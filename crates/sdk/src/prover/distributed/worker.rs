@@ -0,0 +1,142 @@
+use std::{
+    io::{Read, Write},
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::Arc,
+};
+
+use eyre::Result;
+use openvm_circuit::arch::VmConfig;
+use openvm_native_circuit::NativeConfig;
+use openvm_native_compiler::ir::DIGEST_SIZE;
+use openvm_stark_sdk::{
+    config::baby_bear_poseidon2::BabyBearPoseidon2Engine,
+    openvm_stark_backend::{prover::types::Proof, Chip},
+};
+
+use super::protocol::{
+    read_message, write_message, WorkerCommits, WorkerListener, WorkerRequest, WorkerResponse,
+};
+use crate::{
+    keygen::{AggStarkProvingKey, AppProvingKey},
+    prover::vm::{local::VmLocalProver, SingleSegmentVmProver},
+    NonRootCommittedExe, F, SC,
+};
+
+/// Proves app segments and leaf/internal aggregation nodes on behalf of a
+/// [DistributedProver](super::DistributedProver). A worker must be created from the same keys and
+/// executable as the coordinator.
+pub struct ProverWorker<VC> {
+    app_prover: VmLocalProver<SC, VC, BabyBearPoseidon2Engine>,
+    leaf_committed_exe: Arc<NonRootCommittedExe>,
+    leaf_prover: Option<VmLocalProver<SC, NativeConfig, BabyBearPoseidon2Engine>>,
+    internal_prover: Option<VmLocalProver<SC, NativeConfig, BabyBearPoseidon2Engine>>,
+}
+
+impl<VC: VmConfig<F>> ProverWorker<VC>
+where
+    VC::Executor: Chip<SC>,
+    VC::Periphery: Chip<SC>,
+{
+    /// Creates a worker that only proves app segments.
+    pub fn new(
+        app_pk: Arc<AppProvingKey<VC>>,
+        app_committed_exe: Arc<NonRootCommittedExe>,
+    ) -> Self {
+        Self {
+            app_prover: VmLocalProver::new(app_pk.app_vm_pk.clone(), app_committed_exe),
+            leaf_committed_exe: app_pk.leaf_committed_exe.clone(),
+            leaf_prover: None,
+            internal_prover: None,
+        }
+    }
+
+    /// Also proves leaf and internal aggregation nodes.
+    pub fn with_agg_stark_pk(mut self, agg_stark_pk: &AggStarkProvingKey) -> Self {
+        self.leaf_prover = Some(VmLocalProver::new(
+            agg_stark_pk.leaf_vm_pk.clone(),
+            self.leaf_committed_exe.clone(),
+        ));
+        self.internal_prover = Some(VmLocalProver::new(
+            agg_stark_pk.internal_vm_pk.clone(),
+            agg_stark_pk.internal_committed_exe.clone(),
+        ));
+        self
+    }
+
+    pub fn commits(&self) -> WorkerCommits {
+        let program_commit = |prover: &VmLocalProver<SC, NativeConfig, _>| -> [F; DIGEST_SIZE] {
+            prover.committed_exe.get_program_commit().into()
+        };
+        WorkerCommits {
+            app_program_commit: self.app_prover.committed_exe.get_program_commit().into(),
+            leaf_program_commit: self.leaf_prover.as_ref().map(program_commit),
+            internal_program_commit: self.internal_prover.as_ref().map(program_commit),
+        }
+    }
+
+    /// Serves coordinator connections accepted on `listener`, one at a time, until accepting
+    /// fails.
+    pub fn serve(&self, listener: &WorkerListener) -> Result<()> {
+        loop {
+            let stream = listener.accept()?;
+            if let Err(e) = self.handle_connection(stream) {
+                tracing::warn!("Coordinator connection failed: {e}");
+            }
+        }
+    }
+
+    /// Answers the requests on `stream` until the coordinator closes it.
+    pub fn handle_connection(&self, mut stream: impl Read + Write) -> Result<()> {
+        while let Some(request) = read_message(&mut stream)? {
+            let response = self.handle_request(request);
+            write_message(&mut stream, &response)?;
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: WorkerRequest) -> WorkerResponse {
+        let proof = match request {
+            WorkerRequest::Handshake => return WorkerResponse::Handshake(self.commits()),
+            WorkerRequest::AppSegment(checkpoint) => {
+                let segment_idx = checkpoint.segment_idx;
+                tracing::info_span!("app segment", segment = segment_idx).in_scope(|| {
+                    prove_catching_panics(|| {
+                        self.app_prover
+                            .prove_from_checkpoint(checkpoint)
+                            .map_err(Into::into)
+                    })
+                })
+            }
+            WorkerRequest::Leaf(input) => tracing::info_span!("leaf verifier proof")
+                .in_scope(|| prove_single_segment(self.leaf_prover.as_ref(), input)),
+            WorkerRequest::Internal(input) => tracing::info_span!("internal verifier proof")
+                .in_scope(|| prove_single_segment(self.internal_prover.as_ref(), input)),
+        };
+        match proof {
+            Ok(proof) => WorkerResponse::Proof(proof),
+            Err(e) => WorkerResponse::Error(e.to_string()),
+        }
+    }
+}
+
+fn prove_single_segment(
+    prover: Option<&VmLocalProver<SC, NativeConfig, BabyBearPoseidon2Engine>>,
+    input: Vec<Vec<F>>,
+) -> Result<Proof<SC>> {
+    let prover =
+        prover.ok_or_else(|| eyre::eyre!("Worker was started without an aggregation key"))?;
+    prove_catching_panics(|| Ok(SingleSegmentVmProver::prove(prover, input)))
+}
+
+/// The local provers panic on invalid inputs. A panic must not take down the worker, so it is
+/// reported to the coordinator instead.
+fn prove_catching_panics(prove: impl FnOnce() -> Result<Proof<SC>>) -> Result<Proof<SC>> {
+    catch_unwind(AssertUnwindSafe(prove)).unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(eyre::eyre!("Proving panicked: {message}"))
+    })
+}
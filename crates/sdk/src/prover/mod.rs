@@ -10,6 +10,7 @@ mod agg;
 pub use agg::*;
 mod app;
 pub use app::*;
+pub mod distributed;
 use openvm_native_recursion::halo2::utils::Halo2ParamsReader;
 
 mod halo2;
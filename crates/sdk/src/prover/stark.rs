@@ -55,4 +55,19 @@ impl<VC> StarkProver<VC> {
         let app_proof = self.app_prover.generate_app_proof(input);
         self.agg_prover.generate_agg_proof(app_proof)
     }
+    /// See [AggStarkProver::generate_agg_proof_with_assumptions].
+    pub fn generate_proof_for_outer_recursion_with_assumptions(
+        &self,
+        input: StdIn,
+        assumptions: Vec<Proof<SC>>,
+    ) -> Proof<RootSC>
+    where
+        VC: VmConfig<F>,
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let app_proof = self.app_prover.generate_app_proof(input);
+        self.agg_prover
+            .generate_agg_proof_with_assumptions(app_proof, assumptions)
+    }
 }
@@ -3,15 +3,17 @@ use std::{marker::PhantomData, sync::Arc};
 use async_trait::async_trait;
 use openvm_circuit::{
     arch::{
-        hasher::poseidon2::vm_poseidon2_hasher, Streams, VirtualMachine, VmComplexTraceHeights,
-        VmConfig,
+        hasher::poseidon2::vm_poseidon2_hasher, ExecutionError, Streams, VirtualMachine,
+        VmCheckpoint, VmComplexTraceHeights, VmConfig, VmExecutor,
     },
     system::{memory::tree::public_values::UserPublicValuesProof, program::trace::VmCommittedExe},
 };
 use openvm_stark_backend::{
     config::{StarkGenericConfig, Val},
+    engine::StarkEngine,
+    keygen::types::MultiStarkProvingKey,
     p3_field::PrimeField32,
-    prover::types::Proof,
+    prover::types::{Proof, ProofInput},
     Chip,
 };
 use openvm_stark_sdk::engine::StarkFriEngine;
@@ -21,10 +23,15 @@ use crate::prover::vm::{
     ContinuationVmProof, ContinuationVmProver, SingleSegmentVmProver,
 };
 
+/// Default for [VmLocalProver::set_max_in_flight_segments]: one segment is proven while the
+/// next one is executed.
+pub const DEFAULT_MAX_IN_FLIGHT_SEGMENTS: usize = 2;
+
 pub struct VmLocalProver<SC: StarkGenericConfig, VC, E: StarkFriEngine<SC>> {
     pub pk: Arc<VmProvingKey<SC, VC>>,
     pub committed_exe: Arc<VmCommittedExe<SC>>,
     overridden_heights: Option<VmComplexTraceHeights>,
+    max_in_flight_segments: usize,
     _marker: PhantomData<E>,
 }
 
@@ -37,6 +44,7 @@ where
             pk,
             committed_exe,
             overridden_heights: None,
+            max_in_flight_segments: DEFAULT_MAX_IN_FLIGHT_SEGMENTS,
             _marker: PhantomData,
         }
     }
@@ -50,6 +58,7 @@ where
             pk,
             committed_exe,
             overridden_heights,
+            max_in_flight_segments: DEFAULT_MAX_IN_FLIGHT_SEGMENTS,
             _marker: PhantomData,
         }
     }
@@ -57,6 +66,37 @@ where
     pub fn set_override_trace_heights(&mut self, overridden_heights: VmComplexTraceHeights) {
         self.overridden_heights = Some(overridden_heights);
     }
+
+    /// Sets the maximum number of segments whose traces are kept in memory at once by
+    /// [ContinuationVmProver::prove]. With 1, segments are executed and proven one after another.
+    /// With `n > 1`, the next segments are executed while the current one is being proven, and
+    /// execution pauses once `n` segments are waiting to be proven or being proven.
+    pub fn set_max_in_flight_segments(&mut self, max_in_flight_segments: usize) {
+        assert!(max_in_flight_segments > 0);
+        self.max_in_flight_segments = max_in_flight_segments;
+    }
+
+    /// Proves the single continuation segment starting at `checkpoint`, e.g. a checkpoint from
+    /// [VmExecutor::execute_checkpoints] produced on another machine.
+    pub fn prove_from_checkpoint(
+        &self,
+        checkpoint: VmCheckpoint<Val<SC>>,
+    ) -> Result<Proof<SC>, ExecutionError>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        assert!(self.pk.vm_config.system().continuation_enabled);
+        let executor = VmExecutor::new_with_overridden_trace_heights(
+            self.pk.vm_config.clone(),
+            self.overridden_heights.clone(),
+        );
+        let seg_idx = checkpoint.segment_idx;
+        let proof_input = executor
+            .execute_and_generate_from_checkpoint(self.committed_exe.clone(), checkpoint)?;
+        let e = E::new(self.pk.fri_params);
+        Ok(prove_segment(&e, &self.pk.vm_pk, seg_idx, proof_input))
+    }
 }
 
 impl<SC: StarkGenericConfig, VC: VmConfig<Val<SC>>, E: StarkFriEngine<SC>> ContinuationVmProver<SC>
@@ -65,25 +105,68 @@ where
     Val<SC>: PrimeField32,
     VC::Executor: Chip<SC>,
     VC::Periphery: Chip<SC>,
+    MultiStarkProvingKey<SC>: Sync,
+    ProofInput<SC>: Send,
+    Proof<SC>: Send,
 {
     fn prove(&self, input: impl Into<Streams<Val<SC>>>) -> ContinuationVmProof<SC> {
         assert!(self.pk.vm_config.system().continuation_enabled);
-        let e = E::new(self.pk.fri_params);
-        let vm = VirtualMachine::new_with_overridden_trace_heights(
-            e,
+        let executor = VmExecutor::new_with_overridden_trace_heights(
             self.pk.vm_config.clone(),
             self.overridden_heights.clone(),
         );
-        let results = vm
-            .execute_and_generate_with_cached_program(self.committed_exe.clone(), input)
-            .unwrap();
+        let mut per_segment = vec![];
+        let final_memory = if self.max_in_flight_segments == 1 {
+            let e = E::new(self.pk.fri_params);
+            executor.execute_and_generate_with_cached_program_streaming(
+                self.committed_exe.clone(),
+                input,
+                |seg_idx, proof_input| {
+                    per_segment.push(prove_segment(&e, &self.pk.vm_pk, seg_idx, proof_input));
+                },
+            )
+        } else {
+            // Besides the segments queued in the channel, one segment is being proven and one is
+            // waiting to be sent.
+            let (sender, receiver) = std::sync::mpsc::sync_channel(self.max_in_flight_segments - 2);
+            let span = tracing::Span::current();
+            let (fri_params, vm_pk) = (self.pk.fri_params, &self.pk.vm_pk);
+            std::thread::scope(|s| {
+                let prover = s.spawn(move || {
+                    span.in_scope(|| {
+                        let e = E::new(fri_params);
+                        receiver
+                            .into_iter()
+                            .map(|(seg_idx, proof_input)| {
+                                prove_segment(&e, vm_pk, seg_idx, proof_input)
+                            })
+                            .collect()
+                    })
+                });
+                let final_memory = executor.execute_and_generate_with_cached_program_streaming(
+                    self.committed_exe.clone(),
+                    input,
+                    |seg_idx, proof_input| {
+                        sender
+                            .send((seg_idx, proof_input))
+                            .expect("segment prover thread stopped");
+                    },
+                );
+                drop(sender);
+                per_segment = prover.join().unwrap();
+                final_memory
+            })
+        }
+        .unwrap();
+        #[cfg(feature = "bench-metrics")]
+        metrics::counter!("num_segments").absolute(per_segment.len() as u64);
+
         let user_public_values = UserPublicValuesProof::compute(
             self.pk.vm_config.system().memory_config.memory_dimensions(),
             self.pk.vm_config.system().num_public_values,
             &vm_poseidon2_hasher(),
-            results.final_memory.as_ref().unwrap(),
+            final_memory.as_ref().unwrap(),
         );
-        let per_segment = vm.prove(&self.pk.vm_pk, results);
         ContinuationVmProof {
             per_segment,
             user_public_values,
@@ -91,6 +174,15 @@ where
     }
 }
 
+fn prove_segment<SC: StarkGenericConfig, E: StarkEngine<SC>>(
+    e: &E,
+    pk: &MultiStarkProvingKey<SC>,
+    seg_idx: usize,
+    proof_input: ProofInput<SC>,
+) -> Proof<SC> {
+    tracing::info_span!("prove_segment", segment = seg_idx).in_scope(|| e.prove(pk, proof_input))
+}
+
 #[async_trait]
 impl<SC: StarkGenericConfig, VC: VmConfig<Val<SC>>, E: StarkFriEngine<SC>>
     AsyncContinuationVmProver<SC> for VmLocalProver<SC, VC, E>
@@ -99,6 +191,9 @@ where
     Val<SC>: PrimeField32,
     VC::Executor: Chip<SC>,
     VC::Periphery: Chip<SC>,
+    MultiStarkProvingKey<SC>: Sync,
+    ProofInput<SC>: Send,
+    Proof<SC>: Send,
 {
     async fn prove(
         &self,
@@ -1,14 +1,26 @@
 use std::collections::VecDeque;
 
-use openvm_circuit::arch::Streams;
+use openvm_circuit::arch::{Streams, DEFAULT_RNG_SEED};
 use openvm_stark_backend::p3_field::AbstractField;
 use serde::{Deserialize, Serialize};
 
 use crate::F;
 
-#[derive(Clone, Default, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct StdIn {
     pub buffer: VecDeque<Vec<F>>,
+    /// Seed for the random number generator that backs guest `getrandom` calls.
+    #[serde(default)]
+    pub rng_seed: u64,
+}
+
+impl Default for StdIn {
+    fn default() -> Self {
+        Self {
+            buffer: VecDeque::new(),
+            rng_seed: DEFAULT_RNG_SEED,
+        }
+    }
 }
 
 impl StdIn {
@@ -36,6 +48,10 @@ impl StdIn {
     pub fn write_field(&mut self, data: &[F]) {
         self.buffer.push_back(data.to_vec());
     }
+
+    pub fn set_rng_seed(&mut self, seed: u64) {
+        self.rng_seed = seed;
+    }
 }
 
 impl From<StdIn> for Streams<F> {
@@ -44,7 +60,7 @@ impl From<StdIn> for Streams<F> {
         while let Some(input) = std_in.read() {
             data.push(input);
         }
-        Streams::new(data)
+        Streams::new(data).with_rng_seed(std_in.rng_seed)
     }
 }
 
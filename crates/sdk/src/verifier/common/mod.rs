@@ -11,7 +11,7 @@ use openvm_native_compiler::{ir::Config, prelude::*};
 use openvm_native_recursion::{digest::DigestVariable, vars::StarkProofVariable};
 use openvm_stark_sdk::openvm_stark_backend::p3_field::AbstractField;
 
-use crate::verifier::internal::types::InternalVmVerifierPvs;
+use crate::verifier::{internal::types::InternalVmVerifierPvs, utils::VariableP2Hasher};
 
 pub mod non_leaf;
 pub mod types;
@@ -195,3 +195,24 @@ pub fn assert_required_air_for_app_vm_present<C: Config>(
     let public_values_air = builder.get(&proof.per_air, MERKLE_AIR_ID);
     builder.assert_eq::<Usize<_>>(public_values_air.air_id, RVar::from(MERKLE_AIR_ID));
 }
+
+/// Computes the exe commit the same way as [AppExecutionCommit](crate::commit::AppExecutionCommit).
+pub(crate) fn compute_exe_commit<C: Config>(
+    builder: &mut Builder<C>,
+    hasher: &VariableP2Hasher<C>,
+    app_commit: [Felt<C::F>; DIGEST_SIZE],
+    init_memory: [Felt<C::F>; DIGEST_SIZE],
+    pc_start: Felt<C::F>,
+) -> [Felt<C::F>; DIGEST_SIZE] {
+    let app_commit_hash = hasher.hash(builder, &app_commit);
+    let init_memory_hash = hasher.hash(builder, &init_memory);
+    let const_zero = hasher.const_zero;
+    let padded_pc_start = array::from_fn(|i| if i == 0 { pc_start } else { const_zero });
+    let pc_start_hash = hasher.hash(builder, &padded_pc_start);
+    let compress_1 = hasher
+        .compressor
+        .compress(builder, &app_commit_hash, &init_memory_hash);
+    hasher
+        .compressor
+        .compress(builder, &compress_1, &pc_start_hash)
+}
@@ -89,7 +89,7 @@ impl<C: Config> NonLeafVerifierVariables<C> {
         });
         (pvs, leaf_verifier_commit)
     }
-    fn verify_internal_or_leaf_verifier_proof(
+    pub(crate) fn verify_internal_or_leaf_verifier_proof(
         &self,
         builder: &mut Builder<C>,
         proof: &StarkProofVariable<C>,
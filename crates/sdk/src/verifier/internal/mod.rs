@@ -1,3 +1,4 @@
+use openvm::composition::{ASSUMPTIONS_OFFSET, ASSUMPTION_HEADER_SIZE, NUM_ASSUMPTIONS_OFFSET};
 use openvm_circuit::arch::instructions::program::Program;
 use openvm_native_compiler::{conversion::CompilerOptions, prelude::*};
 use openvm_native_recursion::{
@@ -6,16 +7,24 @@ use openvm_native_recursion::{
 };
 use openvm_stark_sdk::{
     config::{baby_bear_poseidon2::BabyBearPoseidon2Config, FriParameters},
-    openvm_stark_backend::keygen::types::MultiStarkVerifyingKey,
+    openvm_stark_backend::{keygen::types::MultiStarkVerifyingKey, p3_field::AbstractField},
 };
 
 use crate::{
+    config::CompositionConfig,
     verifier::{
-        common::non_leaf::NonLeafVerifierVariables,
+        common::{
+            assert_required_air_for_agg_vm_present, assert_single_segment_vm_exit_successfully,
+            compute_exe_commit, non_leaf::NonLeafVerifierVariables, types::VmVerifierPvs,
+        },
         internal::{
-            types::{InternalVmVerifierExtraPvs, InternalVmVerifierInput, InternalVmVerifierPvs},
-            vars::InternalVmVerifierInputVariable,
+            types::{
+                AssumptionsInput, InternalVmVerifierExtraPvs, InternalVmVerifierInput,
+                InternalVmVerifierPvs,
+            },
+            vars::{AssumptionsInputVariable, InternalVmVerifierInputVariable},
         },
+        utils::VariableP2Hasher,
     },
     C, F,
 };
@@ -27,6 +36,11 @@ mod vars;
 pub struct InternalVmVerifierConfig {
     pub leaf_fri_params: FriParameters,
     pub internal_fri_params: FriParameters,
+    /// Number of public values of the aggregated app. Only used with proof composition.
+    pub num_public_values: usize,
+    /// If set, the internal verifier can resolve the assumptions recorded by the aggregated
+    /// execution.
+    pub composition: Option<CompositionConfig>,
     pub compiler_options: CompilerOptions,
 }
 
@@ -65,6 +79,18 @@ impl InternalVmVerifierConfig {
             let (vm_verifier_pvs, leaf_verifier_commit) =
                 non_leaf_verifier.verify_internal_or_leaf_verifier_proofs(&mut builder, &proofs);
             builder.cycle_tracker_end("VerifyProofs");
+            if let Some(composition) = &self.composition {
+                builder.cycle_tracker_start("ResolveAssumptions");
+                let assumptions = AssumptionsInput::<BabyBearPoseidon2Config>::read(&mut builder);
+                self.resolve_assumptions(
+                    &mut builder,
+                    composition,
+                    &non_leaf_verifier,
+                    &assumptions,
+                    &vm_verifier_pvs,
+                );
+                builder.cycle_tracker_end("ResolveAssumptions");
+            }
             let pvs = InternalVmVerifierPvs {
                 vm_verifier_pvs,
                 extra_pvs: InternalVmVerifierExtraPvs {
@@ -81,4 +107,129 @@ impl InternalVmVerifierConfig {
 
         builder.compile_isa_with_options(self.compiler_options)
     }
+
+    /// If public values are provided, verifies a proof of every assumption recorded in them and
+    /// replaces the public values commit of `pvs` with the commit of the public values with
+    /// their number of assumptions set to 0. The root verifier only accepts public values without
+    /// assumptions, so an execution with assumptions must be resolved by an internal verifier.
+    fn resolve_assumptions(
+        &self,
+        builder: &mut Builder<C>,
+        composition: &CompositionConfig,
+        non_leaf_verifier: &NonLeafVerifierVariables<C>,
+        assumptions: &AssumptionsInputVariable<C>,
+        pvs: &VmVerifierPvs<Felt<F>>,
+    ) {
+        let num_public_values = self.num_public_values;
+        assert!(
+            num_public_values >= composition.min_num_public_values(),
+            "Not enough public values for {} assumptions",
+            composition.max_num_assumptions
+        );
+        let assumption_size = composition.assumption_size();
+        let AssumptionsInputVariable {
+            public_values,
+            proofs,
+        } = assumptions;
+
+        builder
+            .if_ne(public_values.len(), RVar::zero())
+            .then(|builder| {
+                // The public values commit is only final once the execution terminated.
+                builder.assert_felt_eq(pvs.connector.is_terminate, F::ONE);
+                builder.assert_eq::<Usize<_>>(public_values.len(), RVar::from(num_public_values));
+                let public_values_vec: Vec<Felt<F>> = (0..num_public_values)
+                    .map(|i| builder.get(public_values, i))
+                    .collect();
+                let hasher = VariableP2Hasher::new(builder);
+                let pv_commit = hasher.merkle_root(builder, &public_values_vec);
+                builder.assert_eq::<[_; DIGEST_SIZE]>(pvs.public_values_commit, pv_commit);
+
+                let num_assumptions = compose_u32(
+                    builder,
+                    &public_values_vec[NUM_ASSUMPTIONS_OFFSET..ASSUMPTIONS_OFFSET],
+                );
+                let num_assumptions = builder.cast_felt_to_var(num_assumptions);
+                builder.assert_var_eq(proofs.len(), num_assumptions);
+                // All assumptions must fit in the public values.
+                let num_assumptions_in_range: Var<_> = builder.eval(F::ZERO);
+                for k in 0..=composition.max_num_assumptions {
+                    builder
+                        .if_eq(num_assumptions, RVar::from(k))
+                        .then(|builder| builder.assign(&num_assumptions_in_range, F::ONE));
+                }
+                builder.assert_var_eq(num_assumptions_in_range, F::ONE);
+
+                builder.range(0, proofs.len()).for_each(|i, builder| {
+                    let proof = builder.get(proofs, i);
+                    assert_required_air_for_agg_vm_present(builder, &proof);
+                    let proof_pvs =
+                        non_leaf_verifier.verify_internal_or_leaf_verifier_proof(builder, &proof);
+                    assert_single_segment_vm_exit_successfully(builder, &proof);
+                    let proof_vm_pvs = &proof_pvs.vm_verifier_pvs;
+                    // The referenced execution terminated successfully.
+                    builder.assert_felt_eq(proof_vm_pvs.connector.is_terminate, F::ONE);
+                    builder.assert_felt_eq(proof_vm_pvs.connector.exit_code, F::ZERO);
+
+                    let offset: Var<_> = builder
+                        .eval(i * RVar::from(assumption_size) + RVar::from(ASSUMPTIONS_OFFSET));
+                    let assumption: Vec<Felt<F>> = (0..assumption_size)
+                        .map(|j| {
+                            let idx: Var<_> = builder.eval(offset + F::from_canonical_usize(j));
+                            builder.get(public_values, idx)
+                        })
+                        .collect();
+                    let (header, referenced_public_values) =
+                        assumption.split_at(ASSUMPTION_HEADER_SIZE);
+                    let commits: Vec<Felt<F>> = header
+                        .chunks_exact(4)
+                        .map(|bytes| compose_u32(builder, bytes))
+                        .collect();
+                    let exe_commit = compute_exe_commit(
+                        builder,
+                        &hasher,
+                        proof_vm_pvs.app_commit,
+                        proof_vm_pvs.memory.initial_root,
+                        proof_vm_pvs.connector.initial_pc,
+                    );
+                    for (expected, actual) in commits[..DIGEST_SIZE].iter().zip(exe_commit) {
+                        builder.assert_felt_eq(*expected, actual);
+                    }
+                    for (expected, actual) in commits[DIGEST_SIZE..]
+                        .iter()
+                        .zip(proof_pvs.extra_pvs.leaf_verifier_commit)
+                    {
+                        builder.assert_felt_eq(*expected, actual);
+                    }
+                    // Nested assumptions must have been resolved in the referenced proof.
+                    for &byte in
+                        &referenced_public_values[NUM_ASSUMPTIONS_OFFSET..ASSUMPTIONS_OFFSET]
+                    {
+                        builder.assert_felt_eq(byte, F::ZERO);
+                    }
+                    let referenced_pv_commit =
+                        hasher.merkle_root(builder, referenced_public_values);
+                    builder.assert_eq::<[_; DIGEST_SIZE]>(
+                        proof_vm_pvs.public_values_commit,
+                        referenced_pv_commit,
+                    );
+                });
+
+                let mut resolved_public_values = public_values_vec;
+                for pv in &mut resolved_public_values[NUM_ASSUMPTIONS_OFFSET..ASSUMPTIONS_OFFSET] {
+                    *pv = hasher.const_zero;
+                }
+                let resolved_pv_commit = hasher.merkle_root(builder, &resolved_public_values);
+                builder.assign(&pvs.public_values_commit, resolved_pv_commit);
+            });
+    }
+}
+
+/// Composes 4 little-endian bytes into a field element.
+fn compose_u32(builder: &mut Builder<C>, bytes: &[Felt<F>]) -> Felt<F> {
+    let ret: Felt<F> = builder.eval(F::ZERO);
+    for (i, &byte) in bytes.iter().enumerate() {
+        builder.assign(&ret, ret + byte * F::from_canonical_u32(1 << (8 * i)));
+    }
+    ret
 }
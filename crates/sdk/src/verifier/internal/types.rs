@@ -27,6 +27,9 @@ pub struct InternalVmVerifierInput<SC: StarkGenericConfig> {
     pub self_program_commit: [Val<SC>; DIGEST_SIZE],
     /// The proofs of leaf verifier or internal verifier in the execution order.
     pub proofs: Vec<Proof<SC>>,
+    /// Only read by internal verifiers with proof composition enabled. `None` if this node does
+    /// not resolve the assumptions of the execution.
+    pub assumptions: Option<AssumptionsInput<SC>>,
 }
 assert_impl_all!(InternalVmVerifierInput<BabyBearPoseidon2Config>: Serialize, DeserializeOwned);
 
@@ -41,10 +44,23 @@ impl InternalVmVerifierInput<SC> {
             .map(|chunk| Self {
                 self_program_commit,
                 proofs: chunk.to_vec(),
+                assumptions: None,
             })
             .collect()
     }
 }
+
+/// Resolves the assumptions recorded by the aggregated execution, see [openvm::composition].
+#[derive(Serialize, Deserialize, Derivative)]
+#[serde(bound = "")]
+#[derivative(Clone(bound = "Com<SC>: Clone"))]
+pub struct AssumptionsInput<SC: StarkGenericConfig> {
+    /// All public values of the aggregated execution.
+    pub public_values: Vec<Val<SC>>,
+    /// Internal verifier proofs of the referenced executions, in the order the assumptions were
+    /// recorded.
+    pub proofs: Vec<Proof<SC>>,
+}
 /// Aggregated state of all segments
 #[derive(Debug, Clone, Copy, AlignedBorrow)]
 #[repr(C)]
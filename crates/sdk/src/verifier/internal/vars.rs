@@ -5,8 +5,11 @@ use openvm_native_recursion::{hints::Hintable, vars::StarkProofVariable};
 use openvm_stark_sdk::openvm_stark_backend::prover::types::Proof;
 
 use crate::{
-    verifier::{internal::types::InternalVmVerifierInput, utils::write_field_slice},
-    C, SC,
+    verifier::{
+        internal::types::{AssumptionsInput, InternalVmVerifierInput},
+        utils::write_field_slice,
+    },
+    C, F, SC,
 };
 
 #[derive(DslVariable, Clone)]
@@ -16,6 +19,13 @@ pub struct InternalVmVerifierInputVariable<C: Config> {
     pub proofs: Array<C, StarkProofVariable<C>>,
 }
 
+#[derive(DslVariable, Clone)]
+pub struct AssumptionsInputVariable<C: Config> {
+    /// Empty if this node does not resolve assumptions.
+    pub public_values: Array<C, Felt<C::F>>,
+    pub proofs: Array<C, StarkProofVariable<C>>,
+}
+
 impl Hintable<C> for InternalVmVerifierInput<SC> {
     type HintVariable = InternalVmVerifierInputVariable<C>;
 
@@ -31,6 +41,33 @@ impl Hintable<C> for InternalVmVerifierInput<SC> {
     fn write(&self) -> Vec<Vec<<C as Config>::N>> {
         let mut stream = write_field_slice(&self.self_program_commit);
         stream.extend(self.proofs.write());
+        // Internal verifiers without proof composition ignore the trailing input.
+        match &self.assumptions {
+            Some(assumptions) => stream.extend(assumptions.write()),
+            None => {
+                stream.extend(Vec::<F>::new().write());
+                stream.extend(Vec::<Proof<SC>>::new().write());
+            }
+        }
+        stream
+    }
+}
+
+impl Hintable<C> for AssumptionsInput<SC> {
+    type HintVariable = AssumptionsInputVariable<C>;
+
+    fn read(builder: &mut Builder<C>) -> Self::HintVariable {
+        let public_values = Vec::<F>::read(builder);
+        let proofs = Vec::<Proof<SC>>::read(builder);
+        Self::HintVariable {
+            public_values,
+            proofs,
+        }
+    }
+
+    fn write(&self) -> Vec<Vec<<C as Config>::N>> {
+        let mut stream = self.public_values.write();
+        stream.extend(self.proofs.write());
         stream
     }
 }
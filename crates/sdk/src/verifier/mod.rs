@@ -26,9 +26,14 @@ impl AggStarkConfig {
         )
     }
     pub fn root_verifier_vm_config(&self) -> NativeConfig {
-        NativeConfig::aggregation(
+        let num_public_values = match self.batch {
+            // batch_commit + leaf_verifier_commit + num_executions
+            Some(_) => DIGEST_SIZE * 2 + 1,
             // app_commit + leaf_verifier_commit + public_values
-            DIGEST_SIZE * 2 + self.max_num_user_public_values,
+            None => DIGEST_SIZE * 2 + self.max_num_user_public_values,
+        };
+        NativeConfig::aggregation(
+            num_public_values,
             SBOX_SIZE.min(self.root_fri_params.max_constraint_degree()),
         )
     }
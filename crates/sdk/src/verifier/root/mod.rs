@@ -1,10 +1,11 @@
 use std::array;
 
+use openvm::composition::{ASSUMPTIONS_OFFSET, NUM_ASSUMPTIONS_OFFSET};
 use openvm_circuit::arch::instructions::program::Program;
 use openvm_native_compiler::{conversion::CompilerOptions, prelude::*};
 use openvm_native_recursion::{
     fri::TwoAdicFriPcsVariable, hints::Hintable, types::new_from_inner_multi_vk,
-    utils::const_fri_config,
+    utils::const_fri_config, vars::StarkProofVariable,
 };
 use openvm_stark_sdk::{
     config::FriParameters,
@@ -12,10 +13,14 @@ use openvm_stark_sdk::{
 };
 
 use crate::{
+    config::{BatchConfig, CompositionConfig},
     verifier::{
-        common::non_leaf::NonLeafVerifierVariables,
+        common::{
+            assert_required_air_for_agg_vm_present, assert_single_segment_vm_exit_successfully,
+            compute_exe_commit, non_leaf::NonLeafVerifierVariables,
+        },
         root::{
-            types::{RootVmVerifierInput, RootVmVerifierPvs},
+            types::{BatchRootVmVerifierPvs, RootVmVerifierInput, RootVmVerifierPvs},
             vars::RootVmVerifierInputVariable,
         },
         utils::VariableP2Hasher,
@@ -32,6 +37,10 @@ pub struct RootVmVerifierConfig {
     pub internal_fri_params: FriParameters,
     pub num_public_values: usize,
     pub internal_vm_verifier_commit: [F; DIGEST_SIZE],
+    /// If set, the public values must not contain unresolved assumptions.
+    pub composition: Option<CompositionConfig>,
+    /// If set, verifies a batch of executions instead of a single one.
+    pub batch: Option<BatchConfig>,
     pub compiler_options: CompilerOptions,
 }
 impl RootVmVerifierConfig {
@@ -59,7 +68,6 @@ impl RootVmVerifierConfig {
                 config: const_fri_config(&mut builder, &self.internal_fri_params),
             };
             builder.cycle_tracker_end("InitializePcsConst");
-            builder.cycle_tracker_start("VerifyProofs");
             let internal_program_commit =
                 array::from_fn(|i| builder.eval(self.internal_vm_verifier_commit[i]));
             let non_leaf_verifier = NonLeafVerifierVariables {
@@ -69,63 +77,151 @@ impl RootVmVerifierConfig {
                 internal_pcs,
                 internal_advice,
             };
-            let (merged_pvs, expected_leaf_commit) =
-                non_leaf_verifier.verify_internal_or_leaf_verifier_proofs(&mut builder, &proofs);
-            builder.cycle_tracker_end("VerifyProofs");
-
-            // App Program should terminate
-            builder.assert_felt_eq(merged_pvs.connector.is_terminate, F::ONE);
-            // App Program should exit successfully
-            builder.assert_felt_eq(merged_pvs.connector.exit_code, F::ZERO);
-
-            builder.cycle_tracker_start("ExtractPublicValues");
-            builder.assert_eq::<Usize<_>>(public_values.len(), RVar::from(self.num_public_values));
-            let public_values_vec: Vec<Felt<F>> = (0..self.num_public_values)
-                .map(|i| builder.get(&public_values, i))
-                .collect();
-            let hasher = VariableP2Hasher::new(&mut builder);
-            let pv_commit = hasher.merkle_root(&mut builder, &public_values_vec);
-            builder.assert_eq::<[_; DIGEST_SIZE]>(merged_pvs.public_values_commit, pv_commit);
-            builder.cycle_tracker_end("ExtractPublicValues");
-
-            let pvs = RootVmVerifierPvs {
-                exe_commit: compute_exe_commit(
-                    &mut builder,
-                    &hasher,
-                    merged_pvs.app_commit,
-                    merged_pvs.memory.initial_root,
-                    merged_pvs.connector.initial_pc,
-                ),
-                leaf_verifier_commit: expected_leaf_commit,
-                public_values: public_values_vec,
+            let pvs = match &self.batch {
+                Some(batch) => self.verify_batch(&mut builder, batch, &non_leaf_verifier, &proofs),
+                None => {
+                    self.verify_execution(&mut builder, &non_leaf_verifier, &proofs, &public_values)
+                }
             };
-            pvs.flatten()
-                .into_iter()
-                .for_each(|v| builder.commit_public_value(v));
+            pvs.into_iter().for_each(|v| builder.commit_public_value(v));
 
             builder.halt();
         }
 
         builder.compile_isa_with_options(self.compiler_options)
     }
-}
 
-fn compute_exe_commit<C: Config>(
-    builder: &mut Builder<C>,
-    hasher: &VariableP2Hasher<C>,
-    app_commit: [Felt<C::F>; DIGEST_SIZE],
-    init_memory: [Felt<C::F>; DIGEST_SIZE],
-    pc_start: Felt<C::F>,
-) -> [Felt<C::F>; DIGEST_SIZE] {
-    let app_commit_hash = hasher.hash(builder, &app_commit);
-    let init_memory_hash = hasher.hash(builder, &init_memory);
-    let const_zero = hasher.const_zero;
-    let padded_pc_start = array::from_fn(|i| if i == 0 { pc_start } else { const_zero });
-    let pc_start_hash = hasher.hash(builder, &padded_pc_start);
-    let compress_1 = hasher
-        .compressor
-        .compress(builder, &app_commit_hash, &init_memory_hash);
-    hasher
-        .compressor
-        .compress(builder, &compress_1, &pc_start_hash)
+    /// Verifies the proofs of a single execution and returns the flattened [RootVmVerifierPvs].
+    fn verify_execution(
+        &self,
+        builder: &mut Builder<C>,
+        non_leaf_verifier: &NonLeafVerifierVariables<C>,
+        proofs: &Array<C, StarkProofVariable<C>>,
+        public_values: &Array<C, Felt<F>>,
+    ) -> Vec<Felt<F>> {
+        builder.cycle_tracker_start("VerifyProofs");
+        let (merged_pvs, expected_leaf_commit) =
+            non_leaf_verifier.verify_internal_or_leaf_verifier_proofs(builder, proofs);
+        builder.cycle_tracker_end("VerifyProofs");
+
+        // App Program should terminate
+        builder.assert_felt_eq(merged_pvs.connector.is_terminate, F::ONE);
+        // App Program should exit successfully
+        builder.assert_felt_eq(merged_pvs.connector.exit_code, F::ZERO);
+
+        builder.cycle_tracker_start("ExtractPublicValues");
+        builder.assert_eq::<Usize<_>>(public_values.len(), RVar::from(self.num_public_values));
+        let public_values_vec: Vec<Felt<F>> = (0..self.num_public_values)
+            .map(|i| builder.get(public_values, i))
+            .collect();
+        let hasher = VariableP2Hasher::new(builder);
+        let pv_commit = hasher.merkle_root(builder, &public_values_vec);
+        builder.assert_eq::<[_; DIGEST_SIZE]>(merged_pvs.public_values_commit, pv_commit);
+        if self.composition.is_some() {
+            // Assumptions are resolved by the internal verifier, which clears their number.
+            for &pv in &public_values_vec[NUM_ASSUMPTIONS_OFFSET..ASSUMPTIONS_OFFSET] {
+                builder.assert_felt_eq(pv, F::ZERO);
+            }
+        }
+        builder.cycle_tracker_end("ExtractPublicValues");
+
+        let pvs = RootVmVerifierPvs {
+            exe_commit: compute_exe_commit(
+                builder,
+                &hasher,
+                merged_pvs.app_commit,
+                merged_pvs.memory.initial_root,
+                merged_pvs.connector.initial_pc,
+            ),
+            leaf_verifier_commit: expected_leaf_commit,
+            public_values: public_values_vec,
+        };
+        pvs.flatten()
+    }
+
+    /// Verifies one proof of each execution in a batch and returns the flattened
+    /// [BatchRootVmVerifierPvs].
+    fn verify_batch(
+        &self,
+        builder: &mut Builder<C>,
+        batch: &BatchConfig,
+        non_leaf_verifier: &NonLeafVerifierVariables<C>,
+        proofs: &Array<C, StarkProofVariable<C>>,
+    ) -> Vec<Felt<F>> {
+        let max_num_executions = batch.max_num_executions;
+        builder.cycle_tracker_start("VerifyProofs");
+        // 1 <= number of executions <= max_num_executions
+        let num_executions_in_range: Var<_> = builder.eval(F::ZERO);
+        let num_executions: Felt<F> = builder.eval(F::ZERO);
+        for k in 1..=max_num_executions {
+            builder.if_eq(proofs.len(), RVar::from(k)).then(|builder| {
+                builder.assign(&num_executions_in_range, F::ONE);
+                builder.assign(&num_executions, F::from_canonical_usize(k));
+            });
+        }
+        builder.assert_var_eq(num_executions_in_range, F::ONE);
+
+        let hasher = VariableP2Hasher::new(builder);
+        // Executions missing from the batch are zero leaves.
+        let leaves = builder.array(max_num_executions * DIGEST_SIZE);
+        for i in 0..max_num_executions * DIGEST_SIZE {
+            builder.set_value(&leaves, i, hasher.const_zero);
+        }
+        let leaf_verifier_commit: [Felt<F>; DIGEST_SIZE] = array::from_fn(|_| builder.uninit());
+        builder.range(0, proofs.len()).for_each(|i, builder| {
+            let proof = builder.get(proofs, i);
+            assert_required_air_for_agg_vm_present(builder, &proof);
+            let proof_pvs =
+                non_leaf_verifier.verify_internal_or_leaf_verifier_proof(builder, &proof);
+            assert_single_segment_vm_exit_successfully(builder, &proof);
+            let vm_pvs = &proof_pvs.vm_verifier_pvs;
+            // App Program should terminate
+            builder.assert_felt_eq(vm_pvs.connector.is_terminate, F::ONE);
+            // App Program should exit successfully
+            builder.assert_felt_eq(vm_pvs.connector.exit_code, F::ZERO);
+            // All executions run on the same App VM.
+            builder.if_eq(i, RVar::zero()).then_or_else(
+                |builder| {
+                    builder.assign(
+                        &leaf_verifier_commit,
+                        proof_pvs.extra_pvs.leaf_verifier_commit,
+                    );
+                },
+                |builder| {
+                    builder.assert_eq::<[_; DIGEST_SIZE]>(
+                        leaf_verifier_commit,
+                        proof_pvs.extra_pvs.leaf_verifier_commit,
+                    );
+                },
+            );
+
+            let exe_commit = compute_exe_commit(
+                builder,
+                &hasher,
+                vm_pvs.app_commit,
+                vm_pvs.memory.initial_root,
+                vm_pvs.connector.initial_pc,
+            );
+            let leaf =
+                hasher
+                    .compressor
+                    .compress(builder, &exe_commit, &vm_pvs.public_values_commit);
+            let offset: Var<_> = builder.eval(i * RVar::from(DIGEST_SIZE));
+            for (j, felt) in leaf.into_iter().enumerate() {
+                let idx: Var<_> = builder.eval(offset + F::from_canonical_usize(j));
+                builder.set_value(&leaves, idx, felt);
+            }
+        });
+        builder.cycle_tracker_end("VerifyProofs");
+
+        let leaves: Vec<Felt<F>> = (0..max_num_executions * DIGEST_SIZE)
+            .map(|i| builder.get(&leaves, i))
+            .collect();
+        BatchRootVmVerifierPvs {
+            batch_commit: hasher.merkle_root(builder, &leaves),
+            leaf_verifier_commit,
+            num_executions,
+        }
+        .flatten()
+    }
 }
@@ -23,6 +23,20 @@ pub struct RootVmVerifierPvs<T> {
     pub public_values: Vec<T>,
 }
 
+/// Public values of the root VM verifier in batch mode. The layout is the same as
+/// [RootVmVerifierPvs] with `batch_commit` in place of `exe_commit` and the number of executions
+/// as the only user public value.
+#[derive(Debug)]
+pub struct BatchRootVmVerifierPvs<T> {
+    /// The commitment of the executables and public values of all executions in the batch.
+    pub batch_commit: [T; DIGEST_SIZE],
+    /// The commitment of the leaf verifier program, which commits the VM config of App VM.
+    pub leaf_verifier_commit: [T; DIGEST_SIZE],
+    /// The number of executions in the batch. Distinguishes the executions from the zero leaves
+    /// padding `batch_commit`.
+    pub num_executions: T,
+}
+
 /// Input for the root VM verifier.
 /// Note: Root verifier is proven in Root SC, but it usually verifies proofs in SC. So
 /// usually only RootVmVerifierInput<SC> is needed.
@@ -30,9 +44,10 @@ pub struct RootVmVerifierPvs<T> {
 #[serde(bound = "")]
 #[derivative(Clone(bound = "Com<SC>: Clone"))]
 pub struct RootVmVerifierInput<SC: StarkGenericConfig> {
-    /// The proofs of leaf verifier or internal verifier in the execution order.
+    /// The proofs of leaf verifier or internal verifier in the execution order. In batch mode,
+    /// one proof of each execution.
     pub proofs: Vec<Proof<SC>>,
-    /// Public values to expose directly
+    /// Public values to expose directly. Empty in batch mode.
     pub public_values: Vec<Val<SC>>,
 }
 assert_impl_all!(RootVmVerifierInput<BabyBearPoseidon2Config>: Serialize, DeserializeOwned);
@@ -65,3 +80,22 @@ impl<F: Copy> RootVmVerifierPvs<F> {
         }
     }
 }
+
+impl<F: Copy> BatchRootVmVerifierPvs<F> {
+    pub fn flatten(self) -> Vec<F> {
+        let mut ret = self.batch_commit.to_vec();
+        ret.extend(self.leaf_verifier_commit);
+        ret.push(self.num_executions);
+        ret
+    }
+    pub fn from_flatten(flatten: Vec<F>) -> Self {
+        let batch_commit = flatten[..DIGEST_SIZE].try_into().unwrap();
+        let leaf_verifier_commit = flatten[DIGEST_SIZE..2 * DIGEST_SIZE].try_into().unwrap();
+        let num_executions = flatten[2 * DIGEST_SIZE];
+        Self {
+            batch_commit,
+            leaf_verifier_commit,
+            num_executions,
+        }
+    }
+}
@@ -1,20 +1,55 @@
-use std::{borrow::Borrow, path::PathBuf, sync::Arc};
+use std::{
+    borrow::Borrow,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::Arc,
+};
 
+use openvm::composition::{ASSUMPTIONS_OFFSET, NUM_ASSUMPTIONS_OFFSET};
 use openvm_build::GuestOptions;
 use openvm_circuit::{
     arch::{
-        hasher::poseidon2::vm_poseidon2_hasher, ExecutionError, SingleSegmentVmExecutor,
-        SystemConfig, VmConfig, VmExecutor,
+        hasher::poseidon2::vm_poseidon2_hasher,
+        instructions::{instruction::Instruction, program::Program, SystemOpcode, VmOpcode},
+        ExecutionError, SingleSegmentVmExecutor, SystemConfig, VmConfig, VmExecutor,
+        VmVerificationError,
+    },
+    system::{
+        memory::tree::public_values::{UserPublicValuesProof, PUBLIC_VALUES_ADDRESS_SPACE_OFFSET},
+        program::trace::VmCommittedExe,
     },
-    system::{memory::tree::public_values::UserPublicValuesProof, program::trace::VmCommittedExe},
 };
 use openvm_native_circuit::{Native, NativeConfig};
-use openvm_native_compiler::{conversion::CompilerOptions, prelude::*};
-use openvm_native_recursion::{halo2::utils::CacheHalo2ParamsReader, types::InnerConfig};
+use openvm_native_compiler::{conversion::CompilerOptions, prelude::*, NativeLoadStoreOpcode};
+use openvm_native_recursion::{
+    halo2::{
+        utils::CacheHalo2ParamsReader,
+        wrapper::{EvmVerificationError, NUM_ACCUMULATOR_PUBLIC_VALUES},
+    },
+    types::InnerConfig,
+};
 use openvm_rv32im_transpiler::{Rv32ITranspilerExtension, Rv32MTranspilerExtension};
 use openvm_sdk::{
-    config::{AggConfig, AggStarkConfig, AppConfig, Halo2Config},
+    commit::{compute_app_exe_commit, compute_batch_commit, AppExecutionCommit},
+    config::{
+        AggConfig, AggStarkConfig, AppConfig, BatchConfig, CompositionConfig, Halo2Config,
+        SdkVmConfig,
+    },
+    fs::{
+        read_app_proof_from_file, read_app_vk_from_file, read_artifact_header,
+        read_checkpoint_from_file, read_root_verifier_vk_from_file, write_app_proof_to_file,
+        write_app_proof_to_json, write_app_vk_to_file, write_artifact_to_file,
+        write_checkpoint_to_file, write_root_verifier_vk_to_file, ArtifactHeader, ArtifactKind,
+        ARTIFACT_FORMAT_VERSION, SDK_VERSION,
+    },
     keygen::AppProvingKey,
+    prover::{
+        distributed::{
+            read_message, write_message, DistributedProver, ProverWorker, WorkerAddress,
+            WorkerRequest, WorkerResponse,
+        },
+        AppProver,
+    },
     verifier::{
         common::types::VmVerifierPvs,
         leaf::types::{LeafVmVerifierInput, UserPublicValuesRootProof},
@@ -27,7 +62,10 @@ use openvm_stark_sdk::{
         fri_params::standard_fri_params_with_100_bits_conjectured_security,
     },
     engine::{StarkEngine, StarkFriEngine},
-    openvm_stark_backend::{p3_field::AbstractField, Chip},
+    openvm_stark_backend::{
+        p3_field::{AbstractField, PrimeField32},
+        Chip,
+    },
     p3_baby_bear::BabyBear,
 };
 use openvm_transpiler::transpiler::Transpiler;
@@ -63,8 +101,11 @@ where
 }
 
 fn app_committed_exe_for_test(app_log_blowup: usize) -> Arc<VmCommittedExe<SC>> {
+    fib_committed_exe_for_test(app_log_blowup, 200)
+}
+
+fn fib_committed_exe_for_test(app_log_blowup: usize, n: usize) -> Arc<VmCommittedExe<SC>> {
     let program = {
-        let n = 200;
         let mut builder = Builder::<C>::default();
         let a: Felt<F> = builder.eval(F::ZERO);
         let b: Felt<F> = builder.eval(F::ONE);
@@ -84,6 +125,27 @@ fn app_committed_exe_for_test(app_log_blowup: usize) -> Arc<VmCommittedExe<SC>>
     .unwrap()
 }
 
+/// Public values that `openvm::verify_openvm_stark` records for one assumption about the
+/// execution with `referenced_commit` and `referenced_public_values`.
+fn assumption_public_values_for_test(
+    referenced_commit: &AppExecutionCommit<F>,
+    referenced_public_values: &[u8],
+    num_public_values: usize,
+) -> Vec<F> {
+    let mut public_values = vec![F::ZERO; num_public_values];
+    public_values[NUM_ASSUMPTIONS_OFFSET] = F::ONE;
+    let bytes = referenced_commit
+        .exe_commit
+        .iter()
+        .chain(&referenced_commit.leaf_vm_verifier_commit)
+        .flat_map(|f| f.as_canonical_u32().to_le_bytes())
+        .chain(referenced_public_values.iter().copied());
+    for (i, byte) in bytes.enumerate() {
+        public_values[ASSUMPTIONS_OFFSET + i] = F::from_canonical_u8(byte);
+    }
+    public_values
+}
+
 fn agg_config_for_test() -> AggConfig {
     AggConfig {
         agg_stark_config: agg_stark_config_for_test(),
@@ -107,6 +169,8 @@ fn agg_stark_config_for_test() -> AggStarkConfig {
             compile_prints: true,
             ..Default::default()
         },
+        composition: None,
+        batch: None,
     }
 }
 
@@ -253,10 +317,700 @@ fn test_public_values_and_leaf_verification() {
 }
 
 #[test]
-fn test_e2e_proof_generation_and_verification() {
+fn test_verify_app_proof_continuations() {
+    let app_log_blowup = 3;
+    let app_config = small_test_app_config(app_log_blowup);
+    let app_pk = Arc::new(Sdk.app_keygen(app_config).unwrap());
+    let app_vk = app_pk.get_vk();
+    let app_committed_exe = app_committed_exe_for_test(app_log_blowup);
+
+    let app_proof = Sdk
+        .generate_app_proof(app_pk, app_committed_exe.clone(), StdIn::default())
+        .unwrap();
+    assert!(app_proof.per_segment.len() > 2);
+
+    let payload = Sdk.verify_app_proof(&app_vk, &app_proof).unwrap();
+    assert_eq!(
+        payload.exe_commit,
+        compute_app_exe_commit(app_vk.memory_dimensions, &app_committed_exe)
+    );
+    assert_eq!(
+        payload.user_public_values,
+        app_proof.user_public_values.public_values
+    );
+
+    // Dropping a segment in the middle breaks the continuation.
+    let mut dropped = app_proof.clone();
+    dropped.per_segment.remove(1);
+    assert!(Sdk.verify_app_proof(&app_vk, &dropped).is_err());
+
+    // Reordering segments breaks the continuation.
+    let mut reordered = app_proof.clone();
+    reordered.per_segment.swap(0, 1);
+    assert!(Sdk.verify_app_proof(&app_vk, &reordered).is_err());
+
+    // The last segment must terminate.
+    let mut truncated = app_proof.clone();
+    truncated.per_segment.pop();
+    assert!(matches!(
+        Sdk.verify_app_proof(&app_vk, &truncated),
+        Err(VmVerificationError::IsTerminateMismatch { .. })
+    ));
+
+    // Public values must be committed in the final memory state.
+    let mut tampered = app_proof;
+    tampered.user_public_values.public_values[0] += F::ONE;
+    assert!(matches!(
+        Sdk.verify_app_proof(&app_vk, &tampered),
+        Err(VmVerificationError::UserPublicValuesError(_))
+    ));
+}
+
+#[test]
+fn test_checkpoint_files() {
     let app_log_blowup = 1;
     let app_config = small_test_app_config(app_log_blowup);
     let app_pk = Sdk.app_keygen(app_config).unwrap();
+    let app_committed_exe = app_committed_exe_for_test(app_log_blowup);
+    let app_commit = AppExecutionCommit::compute(
+        &app_pk.app_vm_pk.vm_config,
+        &app_committed_exe,
+        &app_pk.leaf_committed_exe,
+    );
+    let executor = VmExecutor::<F, _>::new(app_pk.app_vm_pk.vm_config.clone());
+    let exe = app_committed_exe.exe.clone();
+    let checkpoints = executor
+        .execute_checkpoints(exe.clone(), StdIn::default())
+        .unwrap();
+    assert!(checkpoints.len() > 1);
+
+    let dir = std::env::temp_dir().join(format!("openvm-sdk-checkpoints-{}", std::process::id()));
+    let path = dir.join("last.checkpoint");
+    let last = checkpoints.last().unwrap().clone();
+    write_checkpoint_to_file(last, &app_commit, &path).unwrap();
+    let header = read_artifact_header(&path).unwrap();
+    assert_eq!(header.kind, ArtifactKind::Checkpoint);
+    header.check_app_commit(&app_commit).unwrap();
+
+    // Resuming from the checkpoint file ends in the same state as executing from the start.
+    let resumed = executor
+        .execute_from_checkpoint(
+            exe.clone(),
+            read_checkpoint_from_file(&path, &app_commit).unwrap(),
+        )
+        .unwrap();
+    let final_memory = executor.execute(exe, StdIn::default()).unwrap();
+    assert!(resumed.final_memory.is_some());
+    assert_eq!(resumed.final_memory, final_memory);
+
+    // A checkpoint of another executable is rejected.
+    let other_commit = AppExecutionCommit {
+        leaf_vm_verifier_commit: app_commit.leaf_vm_verifier_commit,
+        exe_commit: app_commit.exe_commit.map(|x| x + F::ONE),
+    };
+    assert!(read_checkpoint_from_file(&path, &other_commit).is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_artifact_files() {
+    let app_log_blowup = 1;
+    let app_config = small_test_app_config(app_log_blowup);
+    let app_pk = Arc::new(Sdk.app_keygen(app_config).unwrap());
+    let app_vk = app_pk.get_vk();
+    let app_committed_exe = app_committed_exe_for_test(app_log_blowup);
+    let app_commit = AppExecutionCommit::compute(
+        &app_pk.app_vm_pk.vm_config,
+        &app_committed_exe,
+        &app_pk.leaf_committed_exe,
+    );
+    let app_proof = Sdk
+        .generate_app_proof(app_pk, app_committed_exe, StdIn::default())
+        .unwrap();
+
+    let dir = std::env::temp_dir().join(format!("openvm-sdk-artifacts-{}", std::process::id()));
+    let proof_path = dir.join("app.proof");
+    write_app_proof_to_file(app_proof.clone(), &app_commit, &proof_path).unwrap();
+    let header = read_artifact_header(&proof_path).unwrap();
+    assert_eq!(header.kind, ArtifactKind::AppProof);
+    assert_eq!(header.format_version, ARTIFACT_FORMAT_VERSION);
+    assert_eq!(header.sdk_version, SDK_VERSION);
+    header.check_app_commit(&app_commit).unwrap();
+    let (read_header, read_proof) = read_app_proof_from_file(&proof_path).unwrap();
+    assert_eq!(read_header, header);
+    let payload = Sdk.verify_app_proof(&app_vk, &read_proof).unwrap();
+    assert_eq!(payload.exe_commit, app_commit.exe_commit);
+
+    // The header records the executable the proof is for.
+    let other_commit = AppExecutionCommit {
+        leaf_vm_verifier_commit: app_commit.leaf_vm_verifier_commit,
+        exe_commit: app_commit.exe_commit.map(|x| x + F::ONE),
+    };
+    assert!(header.check_app_commit(&other_commit).is_err());
+    assert!(header.check_exe_commit(&other_commit.exe_commit).is_err());
+
+    // Reading an artifact as another kind fails instead of misdecoding.
+    assert!(read_app_vk_from_file(&proof_path).is_err());
+
+    // The app verifying key records the app VM it is for and is checked against it.
+    let vk_path = dir.join("app.vk");
+    write_app_vk_to_file(app_vk.clone(), &vk_path).unwrap();
+    let vk_header = read_artifact_header(&vk_path).unwrap();
+    vk_header
+        .check_app_config_commit(&app_commit.leaf_vm_verifier_commit)
+        .unwrap();
+    let read_vk = read_app_vk_from_file(&vk_path).unwrap();
+    assert_eq!(
+        read_vk.leaf_vm_verifier_commit,
+        app_commit.leaf_vm_verifier_commit
+    );
+    let other_vk_header = ArtifactHeader::new(ArtifactKind::AppVerifyingKey)
+        .with_app_config_commit(&app_commit.leaf_vm_verifier_commit.map(|x| x + F::ONE));
+    write_artifact_to_file(&vk_path, other_vk_header, app_vk.clone()).unwrap();
+    assert!(read_app_vk_from_file(&vk_path).is_err());
+
+    // Artifacts written by another SDK version are rejected.
+    let old_path = dir.join("old.proof");
+    let old_header = ArtifactHeader {
+        sdk_version: "0.0.0".to_string(),
+        ..ArtifactHeader::new(ArtifactKind::AppProof)
+    };
+    write_artifact_to_file(&old_path, old_header, app_proof.clone()).unwrap();
+    assert!(read_app_proof_from_file(&old_path).is_err());
+
+    // Files without the magic bytes are rejected.
+    let raw_path = dir.join("raw.proof");
+    std::fs::write(&raw_path, bitcode::serialize(&app_proof).unwrap()).unwrap();
+    assert!(read_app_proof_from_file(&raw_path).is_err());
+
+    let json_path = dir.join("app_proof.json");
+    write_app_proof_to_json(&app_proof, &app_commit, &json_path).unwrap();
+    let json: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&json_path).unwrap()).unwrap();
+    assert_eq!(json["header"]["kind"], "app_proof");
+    assert_eq!(
+        json["header"]["exe_commit"],
+        serde_json::to_value(app_commit.exe_commit.map(|x| x.as_canonical_u32())).unwrap()
+    );
+    assert!(json["artifact"].is_object());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_app_proof_max_in_flight_segments() {
+    let app_log_blowup = 3;
+    let app_config = small_test_app_config(app_log_blowup);
+    let app_pk = Arc::new(Sdk.app_keygen(app_config).unwrap());
+    let app_vk = app_pk.get_vk();
+    let app_committed_exe = app_committed_exe_for_test(app_log_blowup);
+    let expected_exe_commit = compute_app_exe_commit(app_vk.memory_dimensions, &app_committed_exe);
+
+    let mut num_segments = vec![];
+    for max_in_flight_segments in [1, 2, 4] {
+        let app_proof = AppProver::new(app_pk.app_vm_pk.clone(), app_committed_exe.clone())
+            .with_max_in_flight_segments(max_in_flight_segments)
+            .generate_app_proof(StdIn::default());
+        let payload = Sdk.verify_app_proof(&app_vk, &app_proof).unwrap();
+        assert_eq!(payload.exe_commit, expected_exe_commit);
+        num_segments.push(app_proof.per_segment.len());
+    }
+    assert!(num_segments[0] > 2);
+    assert!(num_segments.iter().all(|&n| n == num_segments[0]));
+}
+
+#[test]
+fn test_agg_stark_proof_generation_and_verification() {
+    let app_log_blowup = 1;
+    let app_config = small_test_app_config(app_log_blowup);
+    let app_pk = Arc::new(Sdk.app_keygen(app_config).unwrap());
+    let app_committed_exe = app_committed_exe_for_test(app_log_blowup);
+    let app_commit = AppExecutionCommit::compute(
+        &app_pk.app_vm_pk.vm_config,
+        &app_committed_exe,
+        &app_pk.leaf_committed_exe,
+    );
+    let agg_stark_pk = Sdk.agg_stark_keygen(agg_stark_config_for_test()).unwrap();
+
+    let stark_proof = Sdk
+        .generate_agg_stark_proof(
+            app_pk,
+            app_committed_exe,
+            agg_stark_pk.clone(),
+            StdIn::default(),
+        )
+        .unwrap();
+    // The proof can be verified without the proving key.
+    let vk_path = std::env::temp_dir().join(format!("openvm-root-vk-{}", std::process::id()));
+    write_root_verifier_vk_to_file(agg_stark_pk.root_verifier_pk.get_vk(), &vk_path).unwrap();
+    let root_verifier_vk = read_root_verifier_vk_from_file(&vk_path).unwrap();
+    std::fs::remove_file(&vk_path).unwrap();
+    let pvs = Sdk
+        .verify_agg_stark_proof(&root_verifier_vk, &stark_proof)
+        .unwrap();
+    assert_eq!(pvs.exe_commit, app_commit.exe_commit);
+    assert_eq!(pvs.leaf_verifier_commit, app_commit.leaf_vm_verifier_commit);
+    assert_eq!(pvs.public_values.len(), NUM_PUB_VALUES);
+
+    let mut bad_stark_proof = stark_proof;
+    for air_proof_data in bad_stark_proof.per_air.iter_mut() {
+        if let Some(pv) = air_proof_data.public_values.first_mut() {
+            *pv += F::ONE;
+        }
+    }
+    assert!(matches!(
+        Sdk.verify_agg_stark_proof(&root_verifier_vk, &bad_stark_proof),
+        Err(VmVerificationError::StarkError(_))
+    ));
+}
+
+#[test]
+fn test_agg_stark_proof_with_assumptions() {
+    let app_log_blowup = 1;
+    let composition = CompositionConfig {
+        max_num_assumptions: 1,
+        num_referenced_public_values: NUM_PUB_VALUES,
+    };
+    let agg_stark_config = AggStarkConfig {
+        max_num_user_public_values: 128,
+        composition: Some(composition),
+        ..agg_stark_config_for_test()
+    };
+    let agg_stark_pk = Sdk.agg_stark_keygen(agg_stark_config).unwrap();
+
+    // The referenced execution.
+    let referenced_app_pk = Arc::new(
+        Sdk.app_keygen(small_test_app_config(app_log_blowup))
+            .unwrap(),
+    );
+    let referenced_exe = app_committed_exe_for_test(app_log_blowup);
+    let referenced_commit = AppExecutionCommit::compute(
+        &referenced_app_pk.app_vm_pk.vm_config,
+        &referenced_exe,
+        &referenced_app_pk.leaf_committed_exe,
+    );
+    let assumption_proof = Sdk
+        .generate_assumption_proof(
+            referenced_app_pk.clone(),
+            referenced_exe,
+            agg_stark_pk.clone(),
+            StdIn::default(),
+        )
+        .unwrap();
+
+    // The referencing execution writes the public values `openvm::verify_openvm_stark` would
+    // write for the referenced execution, which has no public values set.
+    let mut app_config = small_test_app_config(app_log_blowup);
+    app_config.app_vm_config.system = app_config.app_vm_config.system.with_public_values(128);
+    let pv_as = PUBLIC_VALUES_ADDRESS_SPACE_OFFSET
+        + app_config.app_vm_config.system.memory_config.as_offset;
+    let app_pk = Arc::new(Sdk.app_keygen(app_config).unwrap());
+    let referencing_exe = |public_values: &[F]| {
+        let instructions: Vec<_> = public_values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| **value != F::ZERO)
+            .map(|(i, value)| {
+                Instruction::from_isize(
+                    VmOpcode::with_default_offset(NativeLoadStoreOpcode::STOREW),
+                    value.as_canonical_u32() as isize,
+                    i as isize,
+                    0,
+                    0,
+                    pv_as as isize,
+                )
+            })
+            .chain([Instruction::from_isize(
+                VmOpcode::with_default_offset(SystemOpcode::TERMINATE),
+                0,
+                0,
+                0,
+                0,
+                0,
+            )])
+            .collect();
+        Sdk.commit_app_exe(
+            standard_fri_params_with_100_bits_conjectured_security(app_log_blowup),
+            Program::from_instructions(&instructions).into(),
+        )
+        .unwrap()
+    };
+    let public_values =
+        assumption_public_values_for_test(&referenced_commit, &[0; NUM_PUB_VALUES], 128);
+    let mut expected_public_values = public_values.clone();
+    expected_public_values[NUM_ASSUMPTIONS_OFFSET] = F::ZERO;
+    let app_committed_exe = referencing_exe(&public_values);
+    let app_commit = AppExecutionCommit::compute(
+        &app_pk.app_vm_pk.vm_config,
+        &app_committed_exe,
+        &app_pk.leaf_committed_exe,
+    );
+
+    let stark_proof = Sdk
+        .generate_agg_stark_proof_with_assumptions(
+            app_pk.clone(),
+            app_committed_exe.clone(),
+            agg_stark_pk.clone(),
+            StdIn::default(),
+            vec![assumption_proof.clone()],
+        )
+        .unwrap();
+    let pvs = Sdk
+        .verify_agg_stark_proof(&agg_stark_pk.root_verifier_pk.get_vk(), &stark_proof)
+        .unwrap();
+    assert_eq!(pvs.exe_commit, app_commit.exe_commit);
+    assert_eq!(pvs.leaf_verifier_commit, app_commit.leaf_vm_verifier_commit);
+    assert_eq!(pvs.public_values, expected_public_values);
+
+    // The root verifier rejects unresolved assumptions.
+    let unresolved = panic::catch_unwind(AssertUnwindSafe(|| {
+        Sdk.generate_agg_stark_proof(
+            app_pk.clone(),
+            app_committed_exe.clone(),
+            agg_stark_pk.clone(),
+            StdIn::default(),
+        )
+    }));
+    assert!(unresolved.is_err());
+
+    // A proof of a different executable does not resolve the assumption.
+    let other_assumption_proof = Sdk
+        .generate_assumption_proof(
+            referenced_app_pk,
+            fib_committed_exe_for_test(app_log_blowup, 100),
+            agg_stark_pk.clone(),
+            StdIn::default(),
+        )
+        .unwrap();
+    let other_exe = panic::catch_unwind(AssertUnwindSafe(|| {
+        Sdk.generate_agg_stark_proof_with_assumptions(
+            app_pk.clone(),
+            app_committed_exe,
+            agg_stark_pk.clone(),
+            StdIn::default(),
+            vec![other_assumption_proof],
+        )
+    }));
+    assert!(other_exe.is_err());
+
+    // Nor does a proof of the referenced executable with different public values.
+    let mut other_public_values = [0; NUM_PUB_VALUES];
+    other_public_values[ASSUMPTIONS_OFFSET] = 1;
+    let other_pvs_exe = referencing_exe(&assumption_public_values_for_test(
+        &referenced_commit,
+        &other_public_values,
+        128,
+    ));
+    let other_pvs = panic::catch_unwind(AssertUnwindSafe(|| {
+        Sdk.generate_agg_stark_proof_with_assumptions(
+            app_pk,
+            other_pvs_exe,
+            agg_stark_pk,
+            StdIn::default(),
+            vec![assumption_proof],
+        )
+    }));
+    assert!(other_pvs.is_err());
+}
+
+#[test]
+fn test_composition_guest_build_and_execute() {
+    // A referenced execution with public values.
+    let referenced_commit = AppExecutionCommit {
+        exe_commit: std::array::from_fn(|i| F::from_canonical_usize(i + 1)),
+        leaf_vm_verifier_commit: std::array::from_fn(|i| F::from_canonical_usize(100 + i)),
+    };
+    let referenced_public_values: Vec<u8> = (0..NUM_PUB_VALUES as u8).collect();
+
+    let vm_config = SdkVmConfig::builder()
+        .system(
+            SystemConfig::default()
+                .with_continuations()
+                .with_public_values(128)
+                .into(),
+        )
+        .rv32i(Default::default())
+        .rv32m(Default::default())
+        .io(Default::default())
+        .build();
+    let pkg_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("guest-composition");
+    let elf = Sdk
+        .build(GuestOptions::default(), &pkg_dir, &Default::default())
+        .unwrap();
+    let exe = Sdk.transpile(elf, vm_config.transpiler()).unwrap();
+
+    let mut stdin = StdIn::default();
+    stdin.write(&referenced_commit.exe_commit.map(|f| f.as_canonical_u32()));
+    stdin.write(
+        &referenced_commit
+            .leaf_vm_verifier_commit
+            .map(|f| f.as_canonical_u32()),
+    );
+    stdin.write_bytes(&referenced_public_values);
+    let public_values = Sdk.execute(exe, vm_config, stdin).unwrap();
+    assert_eq!(
+        public_values,
+        assumption_public_values_for_test(&referenced_commit, &referenced_public_values, 128)
+    );
+}
+
+#[test]
+fn test_batch_agg_stark_proof() {
+    let app_log_blowup = 1;
+    let app_config = small_test_app_config(app_log_blowup);
+    let app_pk = Arc::new(Sdk.app_keygen(app_config).unwrap());
+    let agg_stark_config = AggStarkConfig {
+        batch: Some(BatchConfig {
+            max_num_executions: 2,
+        }),
+        ..agg_stark_config_for_test()
+    };
+    let agg_stark_pk = Sdk.agg_stark_keygen(agg_stark_config).unwrap();
+
+    let app_exes = [
+        fib_committed_exe_for_test(app_log_blowup, 200),
+        fib_committed_exe_for_test(app_log_blowup, 300),
+    ];
+    let app_commits: Vec<_> = app_exes
+        .iter()
+        .map(|app_exe| {
+            AppExecutionCommit::compute(
+                &app_pk.app_vm_pk.vm_config,
+                app_exe,
+                &app_pk.leaf_committed_exe,
+            )
+        })
+        .collect();
+    let executions = app_exes
+        .iter()
+        .map(|app_exe| (app_exe.clone(), StdIn::default()))
+        .collect();
+
+    let stark_proof = Sdk
+        .generate_batch_agg_stark_proof(app_pk.clone(), executions, agg_stark_pk.clone())
+        .unwrap();
+    let pvs = Sdk
+        .verify_batch_agg_stark_proof(&agg_stark_pk.root_verifier_pk.get_vk(), &stark_proof)
+        .unwrap();
+    // The programs do not set any public values.
+    let expected_executions: Vec<_> = app_commits
+        .iter()
+        .map(|commit| (commit.exe_commit, vec![F::ZERO; NUM_PUB_VALUES]))
+        .collect();
+    assert_eq!(
+        pvs.batch_commit,
+        compute_batch_commit(&vm_poseidon2_hasher(), &expected_executions, 2)
+    );
+    assert_eq!(
+        pvs.leaf_verifier_commit,
+        app_commits[0].leaf_vm_verifier_commit
+    );
+    assert_eq!(pvs.num_executions, F::TWO);
+    // A batch proof is not a proof of a single execution.
+    assert!(matches!(
+        Sdk.verify_agg_stark_proof(&agg_stark_pk.root_verifier_pk.get_vk(), &stark_proof),
+        Err(VmVerificationError::NumPublicValuesMismatch { .. })
+    ));
+}
+
+#[test]
+#[ignore = "slow"]
+fn test_batch_e2e_proof_generation_and_verification() {
+    let app_log_blowup = 1;
+    let app_config = small_test_app_config(app_log_blowup);
+    let app_pk = Arc::new(Sdk.app_keygen(app_config).unwrap());
+    let agg_config = AggConfig {
+        agg_stark_config: AggStarkConfig {
+            batch: Some(BatchConfig {
+                max_num_executions: 2,
+            }),
+            ..agg_stark_config_for_test()
+        },
+        ..agg_config_for_test()
+    };
+    let params_reader = CacheHalo2ParamsReader::new_with_default_params_dir();
+    let agg_pk = Sdk.agg_keygen(agg_config, &params_reader).unwrap();
+    let evm_verifier = Sdk
+        .generate_snark_verifier_contract(&params_reader, &agg_pk)
+        .unwrap();
+
+    // A batch with fewer executions than the maximum.
+    let app_exe = app_committed_exe_for_test(app_log_blowup);
+    let app_commit = AppExecutionCommit::compute(
+        &app_pk.app_vm_pk.vm_config,
+        &app_exe,
+        &app_pk.leaf_committed_exe,
+    );
+    let evm_proof = Sdk
+        .generate_batch_evm_proof(
+            &params_reader,
+            app_pk,
+            vec![(app_exe, StdIn::default())],
+            agg_pk,
+        )
+        .unwrap();
+    let batch_commit = compute_batch_commit(
+        &vm_poseidon2_hasher(),
+        &[(app_commit.exe_commit, vec![F::ZERO; NUM_PUB_VALUES])],
+        2,
+    );
+    let gas_used = Sdk
+        .verify_batch_evm_proof_with_commit(
+            &evm_verifier,
+            &evm_proof,
+            &batch_commit,
+            &app_commit.leaf_vm_verifier_commit,
+            1,
+        )
+        .unwrap();
+    assert!(gas_used > 0);
+    // The number of executions is part of the public values.
+    assert!(matches!(
+        Sdk.verify_batch_evm_proof_with_commit(
+            &evm_verifier,
+            &evm_proof,
+            &batch_commit,
+            &app_commit.leaf_vm_verifier_commit,
+            2,
+        ),
+        Err(EvmVerificationError::PublicInputMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_distributed_proof_generation() {
+    let app_log_blowup = 1;
+    let app_config = small_test_app_config(app_log_blowup);
+    let app_pk = Arc::new(Sdk.app_keygen(app_config).unwrap());
+    let app_vk = app_pk.get_vk();
+    let app_committed_exe = app_committed_exe_for_test(app_log_blowup);
+    let agg_stark_pk = Sdk.agg_stark_keygen(agg_stark_config_for_test()).unwrap();
+
+    let socket_path = std::env::temp_dir().join(format!(
+        "openvm-sdk-test-worker-{}.sock",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&socket_path);
+    let mut workers = vec![];
+    for address in [
+        "127.0.0.1:0".to_string(),
+        format!("unix:{}", socket_path.display()),
+    ] {
+        let listener = address.parse::<WorkerAddress>().unwrap().bind().unwrap();
+        workers.push(listener.local_addr().unwrap());
+        let worker = ProverWorker::new(app_pk.clone(), app_committed_exe.clone())
+            .with_agg_stark_pk(&agg_stark_pk);
+        std::thread::spawn(move || worker.serve(&listener));
+    }
+
+    let prover = DistributedProver::new(app_pk.clone(), app_committed_exe.clone(), workers)
+        .unwrap()
+        .with_agg_stark_pk(agg_stark_pk.clone());
+    let app_proof = prover.generate_app_proof(StdIn::default()).unwrap();
+    assert!(app_proof.per_segment.len() > 2);
+    let payload = Sdk.verify_app_proof(&app_vk, &app_proof).unwrap();
+    assert_eq!(
+        payload.exe_commit,
+        compute_app_exe_commit(app_vk.memory_dimensions, &app_committed_exe)
+    );
+
+    let stark_proof = prover.generate_agg_proof(StdIn::default()).unwrap();
+    let pvs = Sdk
+        .verify_agg_stark_proof(&agg_stark_pk.root_verifier_pk.get_vk(), &stark_proof)
+        .unwrap();
+    assert_eq!(
+        pvs.public_values,
+        app_proof.user_public_values.public_values
+    );
+
+    // Workers started for a different executable are rejected during the handshake.
+    let other_exe = app_committed_exe_for_test(app_log_blowup + 1);
+    let listener = "127.0.0.1:0"
+        .parse::<WorkerAddress>()
+        .unwrap()
+        .bind()
+        .unwrap();
+    let other_worker = listener.local_addr().unwrap();
+    let worker = ProverWorker::new(app_pk.clone(), other_exe);
+    std::thread::spawn(move || worker.serve(&listener));
+    let prover = DistributedProver::new(
+        app_pk.clone(),
+        app_committed_exe.clone(),
+        vec![other_worker],
+    )
+    .unwrap();
+    assert!(prover.generate_app_proof(StdIn::default()).is_err());
+    assert!(DistributedProver::new(app_pk, app_committed_exe, vec![]).is_err());
+    let _ = std::fs::remove_file(&socket_path);
+}
+
+#[test]
+fn test_distributed_proof_generation_retries_failed_job() {
+    let app_log_blowup = 1;
+    let app_config = small_test_app_config(app_log_blowup);
+    let app_pk = Arc::new(Sdk.app_keygen(app_config).unwrap());
+    let app_vk = app_pk.get_vk();
+    let app_committed_exe = app_committed_exe_for_test(app_log_blowup);
+
+    // The worker answers the handshake on its first connection but fails the first job sent on
+    // it. It serves all later connections normally.
+    let listener = "127.0.0.1:0"
+        .parse::<WorkerAddress>()
+        .unwrap()
+        .bind()
+        .unwrap();
+    let address = listener.local_addr().unwrap();
+    let worker = ProverWorker::new(app_pk.clone(), app_committed_exe.clone());
+    let commits = worker.commits();
+    let (failed_sender, failed_receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || -> eyre::Result<()> {
+        let mut stream = listener.accept()?;
+        std::thread::spawn(move || -> eyre::Result<()> {
+            while let Some(request) = read_message::<WorkerRequest>(&mut stream)? {
+                if matches!(request, WorkerRequest::Handshake) {
+                    write_message(&mut stream, &WorkerResponse::Handshake(commits.clone()))?;
+                } else {
+                    failed_sender.send(())?;
+                    write_message(&mut stream, &WorkerResponse::Error("injected".to_string()))?;
+                }
+            }
+            Ok(())
+        });
+        loop {
+            worker.handle_connection(listener.accept()?)?;
+        }
+    });
+
+    // Both connections go to the same worker, so the job failed on the first one is retried on
+    // the second.
+    let prover = DistributedProver::new(
+        app_pk.clone(),
+        app_committed_exe.clone(),
+        vec![address.clone(), address],
+    )
+    .unwrap();
+    let app_proof = prover.generate_app_proof(StdIn::default()).unwrap();
+    assert!(failed_receiver.try_recv().is_ok());
+    assert!(app_proof.per_segment.len() > 2);
+    let payload = Sdk.verify_app_proof(&app_vk, &app_proof).unwrap();
+    assert_eq!(
+        payload.exe_commit,
+        compute_app_exe_commit(app_vk.memory_dimensions, &app_committed_exe)
+    );
+}
+
+#[test]
+fn test_e2e_proof_generation_and_verification() {
+    let app_log_blowup = 1;
+    let app_config = small_test_app_config(app_log_blowup);
+    let app_pk = Arc::new(Sdk.app_keygen(app_config).unwrap());
+    let app_committed_exe = app_committed_exe_for_test(app_log_blowup);
+    let app_commit = AppExecutionCommit::compute(
+        &app_pk.app_vm_pk.vm_config,
+        &app_committed_exe,
+        &app_pk.leaf_committed_exe,
+    );
     let params_reader = CacheHalo2ParamsReader::new_with_default_params_dir();
     let agg_pk = Sdk
         .agg_keygen(agg_config_for_test(), &params_reader)
@@ -268,13 +1022,31 @@ fn test_e2e_proof_generation_and_verification() {
     let evm_proof = Sdk
         .generate_evm_proof(
             &params_reader,
-            Arc::new(app_pk),
-            app_committed_exe_for_test(app_log_blowup),
+            app_pk,
+            app_committed_exe,
             agg_pk,
             StdIn::default(),
         )
         .unwrap();
-    assert!(Sdk.verify_evm_proof(&evm_verifier, &evm_proof));
+    let gas_used = Sdk
+        .verify_evm_proof_with_commit(&evm_verifier, &evm_proof, &app_commit)
+        .unwrap();
+    assert!(gas_used > 0);
+
+    let mut bad_evm_proof = evm_proof.clone();
+    // Swap the exe commit and the leaf verifier commit.
+    bad_evm_proof.instances[0].swap(
+        NUM_ACCUMULATOR_PUBLIC_VALUES,
+        NUM_ACCUMULATOR_PUBLIC_VALUES + 1,
+    );
+    assert!(matches!(
+        Sdk.verify_evm_proof_with_commit(&evm_verifier, &bad_evm_proof, &app_commit),
+        Err(EvmVerificationError::PublicInputMismatch { .. })
+    ));
+    assert!(matches!(
+        Sdk.verify_evm_proof(&evm_verifier, &bad_evm_proof),
+        Err(EvmVerificationError::Reverted { .. })
+    ));
 }
 
 #[test]
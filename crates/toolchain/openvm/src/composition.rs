@@ -0,0 +1,58 @@
+//! Verification of other OpenVM proofs from a guest program (proof composition).
+//!
+//! [verify_openvm_stark] does not verify anything during execution. It records an *assumption*
+//! in the public values of this execution, and the proof of this execution is conditional on the
+//! assumption until the aggregation resolves it by verifying a proof of the referenced execution.
+//! An aggregation key created with proof composition enabled rejects executions with unresolved
+//! assumptions.
+//!
+//! Public values layout, in bytes:
+//! - `[0, 4)`: the number of recorded assumptions as a little-endian `u32`.
+//! - From byte 4 on, the assumptions in the order they were recorded. Each assumption is the exe
+//!   commit, then the VM config commit, both as 8 little-endian `u32`s, followed by the public
+//!   values of the referenced execution.
+//!
+//! The rest of the public values are free for the program to reveal.
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::io::reveal;
+
+/// Byte offset of the number of recorded assumptions in the public values.
+pub const NUM_ASSUMPTIONS_OFFSET: usize = 0;
+/// Byte offset of the first assumption in the public values.
+pub const ASSUMPTIONS_OFFSET: usize = 4;
+/// Size in bytes of the exe commit and the VM config commit of an assumption.
+pub const ASSUMPTION_HEADER_SIZE: usize = 64;
+
+static NUM_ASSUMPTIONS: AtomicU32 = AtomicU32::new(0);
+
+/// Assumes that the OpenVM executable with commit `exe_commit`, run on the VM with commit
+/// `vm_config_commit`, terminated successfully with `public_values`.
+///
+/// `exe_commit` and `vm_config_commit` are the `exe_commit` and `leaf_vm_verifier_commit` of the
+/// referenced execution's `AppExecutionCommit`, with each field element as a `u32`.
+/// `public_values` must contain all public values of the referenced execution, so its length is
+/// the number of public values of the referenced VM.
+pub fn verify_openvm_stark(
+    exe_commit: &[u32; 8],
+    vm_config_commit: &[u32; 8],
+    public_values: &[u8],
+) {
+    assert_eq!(public_values.len() % 4, 0);
+    let num_assumptions = NUM_ASSUMPTIONS.load(Ordering::Relaxed);
+    let assumption_size = ASSUMPTION_HEADER_SIZE + public_values.len();
+    let start = ASSUMPTIONS_OFFSET + num_assumptions as usize * assumption_size;
+    let words = exe_commit.iter().chain(vm_config_commit).copied().chain(
+        public_values
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap())),
+    );
+    for (i, word) in words.enumerate() {
+        reveal(word, start / 4 + i);
+    }
+    // The count is revealed last, so an assumption that does not fit in the public values faults
+    // before it is counted.
+    NUM_ASSUMPTIONS.store(num_assumptions + 1, Ordering::Relaxed);
+    reveal(num_assumptions + 1, NUM_ASSUMPTIONS_OFFSET / 4);
+}
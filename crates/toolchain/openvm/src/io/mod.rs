@@ -6,7 +6,7 @@ use core::alloc::Layout;
 use core::fmt::Write;
 
 #[cfg(target_os = "zkvm")]
-use openvm_rv32im_guest::{hint_input, hint_store_u32};
+use openvm_rv32im_guest::{hint_buffer_u32, hint_input, hint_random, hint_store_u32};
 use serde::de::DeserializeOwned;
 
 #[cfg(not(target_os = "zkvm"))]
@@ -66,12 +66,10 @@ pub(crate) fn read_vec_by_len(len: usize) -> Vec<u8> {
         let layout = Layout::from_size_align(capacity, 4).expect("vec is too large");
         // SAFETY: We populate a `Vec<u8>` by hintstore-ing `num_words` 4 byte words. We set the length to `len` and don't care about the extra `capacity - len` bytes stored.
         let ptr_start = unsafe { alloc::alloc::alloc(layout) };
-        let mut ptr = ptr_start;
 
         // Note: if len % 4 != 0, this will discard some last bytes
-        for _ in 0..num_words {
-            hint_store_u32!(ptr, 0);
-            ptr = unsafe { ptr.add(4) };
+        if num_words > 0 {
+            hint_buffer_u32!(ptr_start, num_words);
         }
         unsafe { Vec::from_raw_parts(ptr_start, len, capacity) }
     }
@@ -83,6 +81,21 @@ pub(crate) fn read_vec_by_len(len: usize) -> Vec<u8> {
     }
 }
 
+/// Fills `words` words at `recv_buf` with randomness from the host. The values are hints and are
+/// not constrained. This backs `getrandom` and the std `sys_rand` ABI.
+///
+/// # Safety
+///
+/// `recv_buf` must be aligned and dereferenceable.
+#[cfg(target_os = "zkvm")]
+#[no_mangle]
+pub unsafe extern "C" fn sys_rand(recv_buf: *mut u32, words: usize) {
+    hint_random(words);
+    if words > 0 {
+        hint_buffer_u32!(recv_buf, words);
+    }
+}
+
 /// Publish `x` as the `index`-th u32 output.
 #[allow(unused_variables)]
 pub fn reveal(x: u32, index: usize) {
@@ -8,6 +8,10 @@ use openvm_platform::{fileno::*, memory::sys_alloc_aligned, rust_rt::terminate,
 use openvm_rv32im_guest::raw_print_str_from_bytes;
 
 const DIGEST_WORDS: usize = 8;
+#[cfg(feature = "sha256")]
+const DIGEST_BYTES: usize = DIGEST_WORDS * WORD_SIZE;
+#[cfg(feature = "sha256")]
+const SHA_BLOCK_BYTES: usize = 2 * DIGEST_BYTES;
 
 pub mod exit_code {
     pub const SUCCESS: u8 = 0;
@@ -45,36 +49,62 @@ pub extern "C" fn sys_output(_output_id: u32, _output_value: u32) {
 #[inline(always)]
 #[no_mangle]
 pub unsafe extern "C" fn sys_sha_compress(
-    _out_state: *mut [u32; DIGEST_WORDS],
-    _in_state: *const [u32; DIGEST_WORDS],
-    _block1_ptr: *const [u32; DIGEST_WORDS],
-    _block2_ptr: *const [u32; DIGEST_WORDS],
+    out_state: *mut [u32; DIGEST_WORDS],
+    in_state: *const [u32; DIGEST_WORDS],
+    block1_ptr: *const [u32; DIGEST_WORDS],
+    block2_ptr: *const [u32; DIGEST_WORDS],
 ) {
-    unreachable!("sha_compress should not be part of PAL")
+    #[cfg(feature = "sha256")]
+    {
+        // The two halves of the block are not contiguous in general.
+        let mut block = [0u8; SHA_BLOCK_BYTES];
+        core::ptr::copy_nonoverlapping(block1_ptr as *const u8, block.as_mut_ptr(), DIGEST_BYTES);
+        core::ptr::copy_nonoverlapping(
+            block2_ptr as *const u8,
+            block[DIGEST_BYTES..].as_mut_ptr(),
+            DIGEST_BYTES,
+        );
+        let mut state = *(in_state as *const [u8; DIGEST_BYTES]);
+        openvm_sha256_guest::sha256_compress(&mut state, &block);
+        *(out_state as *mut [u8; DIGEST_BYTES]) = state;
+    }
+    #[cfg(not(feature = "sha256"))]
+    {
+        let _ = (out_state, in_state, block1_ptr, block2_ptr);
+        unreachable!("sha_compress requires the sha256 feature")
+    }
 }
 
 /// # Safety
 ///
-/// `out_state`, `in_state`, and `buf` must be aligned and dereferenceable.
+/// `out_state` and `in_state` must be aligned and dereferenceable, and `buf` must be
+/// dereferenceable for `count` blocks of 64 bytes.
 #[inline(always)]
 #[no_mangle]
 pub unsafe extern "C" fn sys_sha_buffer(
-    _out_state: *mut [u32; DIGEST_WORDS],
-    _in_state: *const [u32; DIGEST_WORDS],
-    _buf: *const u8,
-    _count: u32,
+    out_state: *mut [u32; DIGEST_WORDS],
+    in_state: *const [u32; DIGEST_WORDS],
+    buf: *const u8,
+    count: u32,
 ) {
-    unreachable!("sha_buffer should not be part of PAL")
+    #[cfg(feature = "sha256")]
+    {
+        let mut state = *(in_state as *const [u8; DIGEST_BYTES]);
+        let blocks = core::slice::from_raw_parts(buf, count as usize * SHA_BLOCK_BYTES);
+        for block in blocks.chunks_exact(SHA_BLOCK_BYTES) {
+            openvm_sha256_guest::sha256_compress(&mut state, block.try_into().unwrap());
+        }
+        *(out_state as *mut [u8; DIGEST_BYTES]) = state;
+    }
+    #[cfg(not(feature = "sha256"))]
+    {
+        let _ = (out_state, in_state, buf, count);
+        unreachable!("sha_buffer requires the sha256 feature")
+    }
 }
 
-/// # Safety
-///
-/// `recv_buf` must be aligned and dereferenceable.
-#[no_mangle]
-pub unsafe extern "C" fn sys_rand(_recv_buf: *mut u32, _words: usize) {
-    crate::io::println("sys_rand is unimplemented");
-    terminate::<{ exit_code::UNIMP }>();
-}
+// sys_rand is already extern no_mangle exported from crate::io, since the `getrandom` feature
+// needs it without std.
 
 /// # Safety
 ///
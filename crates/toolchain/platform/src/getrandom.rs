@@ -1,11 +1,45 @@
 use getrandom::{register_custom_getrandom, Error};
 
+#[cfg(feature = "getrandom")]
+extern "C" {
+    /// Fills `words` words at `recv_buf` with randomness hinted by the host. Implemented by the
+    /// `openvm` crate.
+    fn sys_rand(recv_buf: *mut u32, words: usize);
+}
+
 /// This is a getrandom handler for the zkvm. It's intended to hook into a
 /// getrandom crate or a dependent of the getrandom crate used by the guest code.
+///
+/// The randomness comes from the host and is not constrained: a malicious prover can choose it.
 #[cfg(feature = "getrandom")]
 pub fn zkvm_getrandom(dest: &mut [u8]) -> Result<(), Error> {
-    todo!()
-    // Randomness would come from the host
+    if dest.is_empty() {
+        return Ok(());
+    }
+
+    let (head, aligned, tail) = bytemuck::pod_align_to_mut::<_, u32>(dest);
+
+    // Fill the aligned portion of the dest buffer with random words.
+    if !aligned.is_empty() {
+        unsafe {
+            sys_rand(aligned.as_mut_ptr(), aligned.len());
+        }
+    }
+
+    // Up to 4 bytes may be split between the head and tail.
+    if !head.is_empty() || !tail.is_empty() {
+        assert!(head.len() < crate::WORD_SIZE);
+        assert!(tail.len() < crate::WORD_SIZE);
+
+        let mut words = [0u32; 2];
+        unsafe {
+            sys_rand(words.as_mut_ptr(), 2);
+        }
+
+        head.copy_from_slice(&words[0].to_ne_bytes()[..head.len()]);
+        tail.copy_from_slice(&words[1].to_ne_bytes()[..tail.len()]);
+    }
+    Ok(())
 }
 
 #[cfg(not(feature = "getrandom"))]
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use derive_new::new;
 use openvm_circuit::system::memory::MemoryTraceHeights;
 use openvm_instructions::program::DEFAULT_MAX_NUM_PUBLIC_VALUES;
@@ -13,8 +15,8 @@ pub use super::testing::{
     POSEIDON2_DIRECT_BUS, RANGE_TUPLE_CHECKER_BUS, READ_INSTRUCTION_BUS,
 };
 use super::{
-    AnyEnum, InstructionExecutor, SystemComplex, SystemExecutor, SystemPeriphery, VmChipComplex,
-    VmInventoryError, PUBLIC_VALUES_AIR_ID,
+    AnyEnum, InstructionExecutor, SegmentationConfig, SegmentationStrategy, SystemComplex,
+    SystemExecutor, SystemPeriphery, VmChipComplex, VmInventoryError, PUBLIC_VALUES_AIR_ID,
 };
 use crate::system::memory::BOUNDARY_AIR_OFFSET;
 
@@ -52,7 +54,7 @@ pub struct MemoryConfig {
     pub clk_max_bits: usize,
     /// Limb size used by the range checker
     pub decomp: usize,
-    /// Maximum N AccessAdapter AIR to support.
+    /// Maximum N AccessAdapter AIR to support. Must be a power of two, at most 256.
     pub max_access_adapter_n: usize,
 }
 
@@ -86,6 +88,14 @@ pub struct SystemConfig {
     pub num_public_values: usize,
     /// When continuations are enabled, a heuristic used to determine when to segment execution.
     pub max_segment_len: usize,
+    /// When continuations are enabled, decides when to segment execution. By default, execution
+    /// is segmented once any chip's height exceeds `max_segment_len`.
+    #[serde(default)]
+    pub segmentation: SegmentationConfig,
+    /// A strategy that is not built in, which replaces `segmentation` if set. Only for
+    /// programmatic use: it is not serialized, so it must be set again on a deserialized config.
+    #[serde(skip)]
+    pub custom_segmentation_strategy: Option<Arc<dyn SegmentationStrategy>>,
     /// Whether to collect metrics.
     /// **Warning**: this slows down the runtime.
     pub collect_metrics: bool,
@@ -109,6 +119,8 @@ impl SystemConfig {
             memory_config,
             num_public_values,
             max_segment_len: DEFAULT_MAX_SEGMENT_LEN,
+            segmentation: SegmentationConfig::Default,
+            custom_segmentation_strategy: None,
             collect_metrics: false,
         }
     }
@@ -138,6 +150,19 @@ impl SystemConfig {
         self
     }
 
+    pub fn with_segmentation(mut self, segmentation: SegmentationConfig) -> Self {
+        self.segmentation = segmentation;
+        self
+    }
+
+    pub fn with_segmentation_strategy(
+        mut self,
+        strategy: impl SegmentationStrategy + 'static,
+    ) -> Self {
+        self.custom_segmentation_strategy = Some(Arc::new(strategy));
+        self
+    }
+
     pub fn with_metric_collection(mut self) -> Self {
         self.collect_metrics = true;
         self
@@ -7,7 +7,7 @@ use openvm_instructions::{
 use openvm_stark_backend::{interaction::InteractionBuilder, p3_field::AbstractField};
 use thiserror::Error;
 
-use super::Streams;
+use super::{SegmentationError, Streams};
 use crate::system::{memory::MemoryController, program::ProgramBus};
 
 pub type Result<T> = std::result::Result<T, ExecutionError>;
@@ -58,6 +58,16 @@ pub enum ExecutionError {
         discriminant: PhantomDiscriminant,
         inner: eyre::Error,
     },
+    #[error("at pc {pc}, segment limit was reached but continuations are not enabled")]
+    ContinuationsDisabled { pc: u32 },
+    #[error("program did not terminate")]
+    DidNotTerminate,
+    #[error("program exited with code {exit_code}")]
+    FailedWithExitCode { exit_code: u32 },
+    #[error("at pc {pc}, execution was aborted by an execution hook")]
+    Aborted { pc: u32 },
+    #[error("invalid segmentation config: {0}")]
+    Segmentation(#[from] SegmentationError),
 }
 
 pub trait InstructionExecutor<F> {
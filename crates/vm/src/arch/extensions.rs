@@ -221,6 +221,8 @@ pub enum VmInventoryError {
     PhantomSubExecutorExists { discriminant: PhantomDiscriminant },
     #[error("Chip {name} not found")]
     ChipNotFound { name: String },
+    #[error("Invalid extension config: {reason}")]
+    InvalidConfig { reason: String },
 }
 
 impl<E, P> Default for VmInventory<E, P> {
@@ -316,6 +318,12 @@ impl<E, P> VmInventory<E, P> {
         self.executors.get_mut(*id)
     }
 
+    /// Same as [Self::get_mut_executor], but also returns the index of the executor.
+    pub fn get_mut_executor_with_id(&mut self, opcode: &VmOpcode) -> Option<(ExecutorId, &mut E)> {
+        let id = *self.instruction_lookup.get(opcode)?;
+        Some((id, self.executors.get_mut(id)?))
+    }
+
     pub fn executors(&self) -> &[E] {
         &self.executors
     }
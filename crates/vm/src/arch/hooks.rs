@@ -0,0 +1,86 @@
+use std::ops::ControlFlow;
+
+use openvm_instructions::instruction::Instruction;
+use openvm_stark_backend::p3_field::PrimeField32;
+use serde::{Deserialize, Serialize};
+
+use crate::system::memory::MemoryController;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryAccessKind {
+    Read,
+    Write,
+}
+
+/// A single memory access made while executing an instruction. Immediates (address space 0)
+/// are not recorded.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryAccess {
+    pub kind: MemoryAccessKind,
+    pub address_space: u32,
+    pub pointer: u32,
+    pub data: Vec<u32>,
+}
+
+/// Record of one executed instruction, as passed to [ExecutionHook::after_instruction].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionStep {
+    pub segment_idx: usize,
+    pub pc: u32,
+    /// Timestamp before the instruction was executed. Timestamps restart in every segment.
+    pub timestamp: u32,
+    pub opcode: String,
+    /// Operands `[a, b, c, d, e, f, g]` as canonical u32s.
+    pub operands: [u32; 7],
+    pub accesses: Vec<MemoryAccess>,
+    /// Trace cells added by the instruction, per AIR name. Only AIRs that grew are listed, and
+    /// only if [ExecutionHook::collect_trace_cells] is set.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trace_cells: Vec<(String, usize)>,
+}
+
+/// State of the VM right before an instruction is executed.
+pub struct InstructionContext<'a, F> {
+    pub segment_idx: usize,
+    pub pc: u32,
+    pub timestamp: u32,
+    pub instruction: &'a Instruction<F>,
+    /// Read-only view of memory. Use [MemoryController::unsafe_read_cell] to inspect it.
+    pub memory: &'a MemoryController<F>,
+}
+
+/// Callbacks invoked by the executor around every instruction, e.g. to record an execution trace
+/// or to implement a debugger. Hooks observe execution and cannot change it, but may abort it.
+pub trait ExecutionHook<F: PrimeField32> {
+    fn on_segment_start(&mut self, _segment_idx: usize) {}
+
+    /// Returning [ControlFlow::Break] aborts execution with [ExecutionError::Aborted] before the
+    /// instruction is executed.
+    ///
+    /// [ExecutionError::Aborted]: super::ExecutionError::Aborted
+    fn before_instruction(&mut self, _ctx: &InstructionContext<'_, F>) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    fn after_instruction(&mut self, _step: ExecutionStep) {}
+
+    /// Whether to fill [ExecutionStep::trace_cells]. This measures every chip around every
+    /// instruction, so it slows execution down.
+    fn collect_trace_cells(&self) -> bool {
+        false
+    }
+}
+
+pub(crate) fn instruction_operands<F: PrimeField32>(instruction: &Instruction<F>) -> [u32; 7] {
+    [
+        instruction.a,
+        instruction.b,
+        instruction.c,
+        instruction.d,
+        instruction.e,
+        instruction.f,
+        instruction.g,
+    ]
+    .map(|x| x.as_canonical_u32())
+}
@@ -225,7 +225,9 @@ where
             output,
             &read_record,
         )?;
-        self.records.push((read_record, write_record, core_record));
+        if !memory.is_pure_execution() {
+            self.records.push((read_record, write_record, core_record));
+        }
         Ok(to_state)
     }
 
@@ -4,10 +4,14 @@ mod config;
 mod execution;
 /// Traits and builders to compose collections of chips into a virtual machine.
 mod extensions;
+/// Hooks to observe execution instruction by instruction
+mod hooks;
 /// Traits and wrappers to facilitate VM chip integration
 mod integration_api;
 /// Runtime execution and segmentation
 pub mod segment;
+/// Strategies deciding when to end a continuation segment
+mod segmentation;
 /// Top level [VirtualMachine] constructor and API.
 pub mod vm;
 
@@ -21,6 +25,8 @@ pub mod testing;
 pub use config::*;
 pub use execution::*;
 pub use extensions::*;
+pub use hooks::*;
 pub use integration_api::*;
 pub use segment::*;
+pub use segmentation::*;
 pub use vm::*;
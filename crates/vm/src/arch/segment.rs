@@ -9,9 +9,12 @@ use openvm_stark_backend::{
     prover::types::{CommittedTraceData, ProofInput},
     Chip,
 };
+use serde::{Deserialize, Serialize};
 
 use super::{
-    AnyEnum, ExecutionError, Streams, SystemConfig, VmChipComplex, VmComplexTraceHeights, VmConfig,
+    instruction_operands, AnyEnum, ExecutionError, ExecutionHook, ExecutionStep,
+    InstructionContext, SegmentationError, Streams, SystemConfig, VmChipComplex,
+    VmComplexTraceHeights, VmConfig,
 };
 #[cfg(feature = "bench-metrics")]
 use crate::metrics::VmMetrics;
@@ -25,7 +28,7 @@ use crate::{
 };
 
 /// Check segment every 100 instructions.
-const SEGMENT_CHECK_INTERVAL: usize = 100;
+pub const SEGMENT_CHECK_INTERVAL: usize = 100;
 
 pub struct ExecutionSegment<F, VC>
 where
@@ -53,15 +56,26 @@ pub struct ExecutionSegmentState {
     pub is_terminated: bool,
 }
 
+/// A continuation segment as estimated by pure execution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SegmentEstimate {
+    pub start_pc: u32,
+    /// Number of instructions executed before the segment.
+    pub start_instret: u64,
+    pub num_instructions: u64,
+}
+
 impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
-    /// Creates a new execution segment from a program and initial state, using parent VM config
+    /// Creates a new execution segment from a program and initial state, using parent VM config.
+    ///
+    /// Fails if the segmentation config does not fit the chips of `config`.
     pub fn new(
         config: &VC,
         program: Program<F>,
         init_streams: Streams<F>,
         initial_memory: Option<Equipartition<F, CHUNK>>,
         fn_bounds: FnBounds,
-    ) -> Self {
+    ) -> Result<Self, SegmentationError> {
         let mut chip_complex = config.create_chip_complex().unwrap();
         chip_complex.set_streams(init_streams);
         let program = if config.system().collect_metrics {
@@ -78,8 +92,9 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
                 .set_initial_memory(initial_memory);
         }
         let air_names = chip_complex.air_names();
+        config.system().segmentation.validate(air_names.len())?;
 
-        Self {
+        Ok(Self {
             chip_complex,
             final_memory: None,
             cycle_tracker: CycleTracker::new(),
@@ -88,7 +103,7 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
             fn_bounds,
             air_names,
             since_last_segment_check: 0,
-        }
+        })
     }
 
     pub fn system_config(&self) -> &SystemConfig {
@@ -103,10 +118,32 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
     }
 
     /// Stopping is triggered by should_segment()
-    pub fn execute_from_pc(
+    pub fn execute_from_pc(&mut self, pc: u32) -> Result<ExecutionSegmentState, ExecutionError> {
+        self.execute_from_pc_impl(pc, None)
+    }
+
+    /// Same as [Self::execute_from_pc], but calls `hook` around every instruction.
+    /// `segment_idx` is only used to label the steps passed to the hook.
+    pub fn execute_from_pc_with_hook(
+        &mut self,
+        pc: u32,
+        segment_idx: usize,
+        hook: &mut dyn ExecutionHook<F>,
+    ) -> Result<ExecutionSegmentState, ExecutionError> {
+        self.execute_from_pc_impl(pc, Some((segment_idx, hook)))
+    }
+
+    fn execute_from_pc_impl(
         &mut self,
         mut pc: u32,
+        mut hook: Option<(usize, &mut dyn ExecutionHook<F>)>,
     ) -> Result<ExecutionSegmentState, ExecutionError> {
+        if hook.is_some() {
+            self.chip_complex
+                .memory_controller()
+                .borrow_mut()
+                .set_access_log_enabled(true);
+        }
         let mut timestamp = self.chip_complex.memory_controller().borrow().timestamp();
 
         #[cfg(feature = "bench-metrics")]
@@ -128,6 +165,19 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
             let (instruction, debug_info) =
                 self.chip_complex.program_chip_mut().get_instruction(pc)?;
             tracing::trace!("pc: {pc:#x} | time: {timestamp} | {:?}", instruction);
+            if let Some((segment_idx, hook)) = hook.as_mut() {
+                let memory = self.chip_complex.memory_controller().borrow();
+                let control = hook.before_instruction(&InstructionContext {
+                    segment_idx: *segment_idx,
+                    pc,
+                    timestamp,
+                    instruction: &instruction,
+                    memory: &memory,
+                });
+                if control.is_break() {
+                    return Err(ExecutionError::Aborted { pc });
+                }
+            }
 
             let (dsl_instr, trace) = debug_info.map_or(
                 (None, None),
@@ -151,6 +201,17 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
                     ExecutionState::new(pc, timestamp),
                     Some(instruction.c.as_canonical_u32()),
                 );
+                if let Some((segment_idx, hook)) = hook.as_mut() {
+                    hook.after_instruction(ExecutionStep {
+                        segment_idx: *segment_idx,
+                        pc,
+                        timestamp,
+                        opcode: format!("{:?}", SystemOpcode::TERMINATE),
+                        operands: instruction_operands(&instruction),
+                        accesses: vec![],
+                        trace_cells: vec![],
+                    });
+                }
                 break;
             }
 
@@ -206,7 +267,19 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
 
             #[cfg(feature = "bench-metrics")]
             let mut opcode_name = None;
+            let (prev_pc, prev_timestamp) = (pc, timestamp);
+            let hooked_prev_trace_cells = match &hook {
+                Some((_, hook)) if hook.collect_trace_cells() => Some(self.current_trace_cells()),
+                _ => None,
+            };
+            let hooked_step;
             if let Some(executor) = self.chip_complex.inventory.get_mut_executor(&opcode) {
+                hooked_step = hook.is_some().then(|| {
+                    (
+                        executor.get_opcode_name(opcode.as_usize()),
+                        instruction_operands(&instruction),
+                    )
+                });
                 let next_state = InstructionExecutor::execute(
                     executor,
                     instruction,
@@ -225,6 +298,37 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
             } else {
                 return Err(ExecutionError::DisabledOperation { pc, opcode });
             };
+            if let (Some((segment_idx, hook)), Some((name, operands))) =
+                (hook.as_mut(), hooked_step)
+            {
+                let accesses = self
+                    .chip_complex
+                    .memory_controller()
+                    .borrow_mut()
+                    .take_access_log();
+                let trace_cells = match hooked_prev_trace_cells {
+                    Some(prev_trace_cells) => itertools::izip!(
+                        &self.air_names,
+                        self.current_trace_cells(),
+                        prev_trace_cells
+                    )
+                    .filter(|(_, now_value, prev_value)| now_value > prev_value)
+                    .map(|(air_name, now_value, prev_value)| {
+                        (air_name.clone(), now_value - prev_value)
+                    })
+                    .collect(),
+                    None => vec![],
+                };
+                hook.after_instruction(ExecutionStep {
+                    segment_idx: *segment_idx,
+                    pc: prev_pc,
+                    timestamp: prev_timestamp,
+                    opcode: name,
+                    operands,
+                    accesses,
+                    trace_cells,
+                });
+            }
 
             #[cfg(feature = "bench-metrics")]
             if collect_metrics {
@@ -292,6 +396,78 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
         })
     }
 
+    /// Executes from `pc` until termination without keeping anything needed for trace
+    /// generation, see [VmExecutor::execute_pure](super::VmExecutor::execute_pure). Returns the
+    /// exit code and estimated segments.
+    ///
+    /// The segment is never split. Instead, a segment boundary is estimated whenever some
+    /// executor has executed `max_segment_len` instructions since the last boundary.
+    pub fn execute_pure_from_pc(
+        &mut self,
+        mut pc: u32,
+    ) -> Result<(u32, Vec<SegmentEstimate>), ExecutionError> {
+        let max_segment_len = self.system_config().max_segment_len;
+        let continuation_enabled = self.system_config().continuation_enabled;
+        let memory_controller = self.chip_complex.memory_controller().clone();
+        memory_controller.borrow_mut().set_pure_execution(true);
+        let mut timestamp = memory_controller.borrow().timestamp();
+
+        let mut segments = vec![SegmentEstimate {
+            start_pc: pc,
+            start_instret: 0,
+            num_instructions: 0,
+        }];
+        let mut executor_counts = vec![0; self.chip_complex.inventory.executors().len()];
+        loop {
+            let (instruction, _) = self.chip_complex.program_chip_mut().get_instruction(pc)?;
+            let opcode = instruction.opcode;
+            segments.last_mut().unwrap().num_instructions += 1;
+
+            if opcode == VmOpcode::with_default_offset(SystemOpcode::TERMINATE) {
+                return Ok((instruction.c.as_canonical_u32(), segments));
+            }
+            if opcode == VmOpcode::with_default_offset(SystemOpcode::PHANTOM)
+                && SysPhantom::from_repr(instruction.c.as_canonical_u32() as u16)
+                    == Some(SysPhantom::DebugPanic)
+            {
+                return Err(ExecutionError::Fail { pc });
+            }
+
+            let Some((executor_id, executor)) = self
+                .chip_complex
+                .inventory
+                .get_mut_executor_with_id(&opcode)
+            else {
+                return Err(ExecutionError::DisabledOperation { pc, opcode });
+            };
+            let next_state = InstructionExecutor::execute(
+                executor,
+                instruction,
+                ExecutionState::new(pc, timestamp),
+            )?;
+            pc = next_state.pc;
+            timestamp = next_state.timestamp;
+
+            executor_counts[executor_id] += 1;
+            if executor_counts[executor_id] > max_segment_len {
+                if !continuation_enabled {
+                    return Err(ExecutionError::ContinuationsDisabled { pc });
+                }
+                let last = segments.last().unwrap();
+                segments.push(SegmentEstimate {
+                    start_pc: pc,
+                    start_instret: last.start_instret + last.num_instructions,
+                    num_instructions: 0,
+                });
+                executor_counts.fill(0);
+                // Timestamps restart in every segment, which also keeps them from overflowing.
+                let mut memory = memory_controller.borrow_mut();
+                memory.reset_timestamp();
+                timestamp = memory.timestamp();
+            }
+        }
+    }
+
     /// Generate ProofInput to prove the segment. Should be called after ::execute
     pub fn generate_proof_input<SC: StarkGenericConfig>(
         self,
@@ -315,7 +491,9 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
 
     /// Returns bool of whether to switch to next segment or not. This is called every clock cycle inside of Core trace generation.
     ///
-    /// Default config: switch if any runtime chip height exceeds 1<<20 - 100
+    /// Decided by [SystemConfig::custom_segmentation_strategy] if set, and otherwise by
+    /// [SystemConfig::segmentation], by default switch if any runtime chip height exceeds
+    /// `max_segment_len`.
     fn should_segment(&mut self) -> bool {
         // Avoid checking segment too often.
         if self.since_last_segment_check != SEGMENT_CHECK_INTERVAL {
@@ -323,19 +501,20 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
             return false;
         }
         self.since_last_segment_check = 0;
-        let heights = self.chip_complex.dynamic_trace_heights();
-        for (i, height) in heights.enumerate() {
-            if height > self.system_config().max_segment_len {
-                tracing::info!(
-                    "Should segment because chip {} has height {}",
-                    self.air_names[i],
-                    height
-                );
-                return true;
+        let heights: Vec<_> = self.chip_complex.dynamic_trace_heights().collect();
+        let system_config = self.system_config();
+        match &system_config.custom_segmentation_strategy {
+            Some(strategy) => {
+                let cells = self.current_trace_cells();
+                strategy.should_segment(&self.air_names, &heights, &cells)
             }
+            None => system_config.segmentation.should_segment(
+                system_config.max_segment_len,
+                &self.air_names,
+                &heights,
+                || self.current_trace_cells(),
+            ),
         }
-
-        false
     }
 
     pub fn current_trace_cells(&self) -> Vec<usize> {
@@ -0,0 +1,352 @@
+use std::{collections::BTreeMap, fmt::Debug};
+
+use openvm_stark_backend::{config::StarkGenericConfig, keygen::types::MultiStarkProvingKey};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Decides when to end a continuation segment. Only consulted every [SEGMENT_CHECK_INTERVAL]
+/// instructions, so budgets should leave room for the rows added in between.
+///
+/// All slices are indexed by AIR ID.
+///
+/// [SEGMENT_CHECK_INTERVAL]: super::SEGMENT_CHECK_INTERVAL
+pub trait SegmentationStrategy: Debug + Send + Sync {
+    /// `trace_heights` are the current heights of AIRs whose height depends on execution, and 0
+    /// for AIRs of constant height. `trace_cells` are the current trace cells of all AIRs.
+    fn should_segment(
+        &self,
+        air_names: &[String],
+        trace_heights: &[usize],
+        trace_cells: &[usize],
+    ) -> bool;
+}
+
+/// The built-in segmentation strategies, which are serialized with
+/// [SystemConfig](super::SystemConfig). A strategy that is not built in can only be set
+/// programmatically with
+/// [SystemConfig::with_segmentation_strategy](super::SystemConfig::with_segmentation_strategy).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub enum SegmentationConfig {
+    /// Segment when any AIR is higher than
+    /// [SystemConfig::max_segment_len](super::SystemConfig::max_segment_len).
+    #[default]
+    Default,
+    MaxHeight(MaxHeightSegmentation),
+    TotalCells(TotalCellsSegmentation),
+    ProverMemory(ProverMemorySegmentation),
+    PerAirHeight(PerAirHeightSegmentation),
+    FixedHeights(FixedHeightsSegmentation),
+}
+
+impl SegmentationConfig {
+    /// Checks the strategy against the number of AIRs of the VM, which a deserialized config
+    /// cannot check on construction.
+    pub fn validate(&self, num_airs: usize) -> Result<(), SegmentationError> {
+        match self {
+            Self::FixedHeights(strategy) => strategy.validate(num_airs),
+            _ => Ok(()),
+        }
+    }
+
+    /// Like [SegmentationStrategy::should_segment], but only computes `trace_cells`, which are
+    /// costly, for the strategies that need them.
+    pub fn should_segment(
+        &self,
+        max_segment_len: usize,
+        air_names: &[String],
+        trace_heights: &[usize],
+        trace_cells: impl FnOnce() -> Vec<usize>,
+    ) -> bool {
+        match self {
+            Self::Default => MaxHeightSegmentation {
+                max_height: max_segment_len,
+            }
+            .should_segment(air_names, trace_heights, &[]),
+            Self::MaxHeight(strategy) => strategy.should_segment(air_names, trace_heights, &[]),
+            Self::TotalCells(strategy) => {
+                strategy.should_segment(air_names, trace_heights, &trace_cells())
+            }
+            Self::ProverMemory(strategy) => {
+                strategy.should_segment(air_names, trace_heights, &trace_cells())
+            }
+            Self::PerAirHeight(strategy) => strategy.should_segment(air_names, trace_heights, &[]),
+            Self::FixedHeights(strategy) => strategy.should_segment(air_names, trace_heights, &[]),
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SegmentationError {
+    #[error("expected a max height for each of the {expected} AIRs, got {actual}")]
+    NumAirsMismatch { expected: usize, actual: usize },
+}
+
+/// Segment when any AIR is higher than `max_height`. [SegmentationConfig::Default] uses it with
+/// `max_height` set to `max_segment_len`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct MaxHeightSegmentation {
+    pub max_height: usize,
+}
+
+impl SegmentationStrategy for MaxHeightSegmentation {
+    fn should_segment(&self, air_names: &[String], trace_heights: &[usize], _: &[usize]) -> bool {
+        for (i, &height) in trace_heights.iter().enumerate() {
+            if height > self.max_height {
+                tracing::info!(
+                    "Should segment because chip {} has height {}",
+                    air_names[i],
+                    height
+                );
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Segment when the trace cells of all AIRs together exceed `max_cells`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct TotalCellsSegmentation {
+    pub max_cells: usize,
+}
+
+impl SegmentationStrategy for TotalCellsSegmentation {
+    fn should_segment(&self, _: &[String], _: &[usize], trace_cells: &[usize]) -> bool {
+        let total_cells: usize = trace_cells.iter().sum();
+        if total_cells > self.max_cells {
+            tracing::info!("Should segment because there are {total_cells} trace cells");
+            return true;
+        }
+        false
+    }
+}
+
+/// Rough number of prover bytes per main trace cell: a 4 byte field element, its low-degree
+/// extension with blowup 2, and about as much again for the commitment, quotient and
+/// permutation traces.
+pub const DEFAULT_BYTES_PER_TRACE_CELL: usize = 4 * 2 * 4;
+
+/// Segment when the estimated memory needed to prove the segment exceeds `max_memory_bytes`.
+///
+/// The estimate is the number of trace cells after padding heights to the next power of two,
+/// times `bytes_per_cell`. It ignores memory that does not scale with the trace, so
+/// `max_memory_bytes` should stay well below the memory of the proving machine.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct ProverMemorySegmentation {
+    pub max_memory_bytes: usize,
+    pub bytes_per_cell: usize,
+}
+
+impl ProverMemorySegmentation {
+    pub fn new(max_memory_bytes: usize) -> Self {
+        Self {
+            max_memory_bytes,
+            bytes_per_cell: DEFAULT_BYTES_PER_TRACE_CELL,
+        }
+    }
+
+    pub fn with_bytes_per_cell(mut self, bytes_per_cell: usize) -> Self {
+        self.bytes_per_cell = bytes_per_cell;
+        self
+    }
+}
+
+impl SegmentationStrategy for ProverMemorySegmentation {
+    fn should_segment(&self, _: &[String], trace_heights: &[usize], trace_cells: &[usize]) -> bool {
+        let padded_cells: usize = trace_heights
+            .iter()
+            .zip(trace_cells)
+            .map(|(&height, &cells)| {
+                if height == 0 {
+                    cells
+                } else {
+                    cells / height * height.next_power_of_two()
+                }
+            })
+            .sum();
+        let memory_bytes = padded_cells * self.bytes_per_cell;
+        if memory_bytes > self.max_memory_bytes {
+            tracing::info!("Should segment because proving needs about {memory_bytes} bytes");
+            return true;
+        }
+        false
+    }
+}
+
+/// Segment when any AIR is higher than its own budget. AIRs are identified by name, and AIRs
+/// without a budget are limited by `default_max_height`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PerAirHeightSegmentation {
+    pub max_heights: BTreeMap<String, usize>,
+    pub default_max_height: usize,
+}
+
+impl PerAirHeightSegmentation {
+    pub fn new(default_max_height: usize) -> Self {
+        Self {
+            max_heights: BTreeMap::new(),
+            default_max_height,
+        }
+    }
+
+    pub fn with_max_height(mut self, air_name: impl Into<String>, max_height: usize) -> Self {
+        self.max_heights.insert(air_name.into(), max_height);
+        self
+    }
+}
+
+impl SegmentationStrategy for PerAirHeightSegmentation {
+    fn should_segment(&self, air_names: &[String], trace_heights: &[usize], _: &[usize]) -> bool {
+        for (air_name, &height) in air_names.iter().zip(trace_heights) {
+            let max_height = self
+                .max_heights
+                .get(air_name)
+                .copied()
+                .unwrap_or(self.default_max_height);
+            if height > max_height {
+                tracing::info!("Should segment because chip {air_name} has height {height}");
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Segment before any AIR outgrows the heights a proving key was generated for, e.g. the
+/// constant `air_heights` of the SDK's root verifier proving key. `max_heights` is indexed by
+/// AIR ID and `headroom` rows are kept free in every AIR.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FixedHeightsSegmentation {
+    max_heights: Vec<usize>,
+    headroom: usize,
+}
+
+impl FixedHeightsSegmentation {
+    /// Fails if `max_heights` does not have a height for each AIR of `pk`.
+    pub fn from_proving_key<SC: StarkGenericConfig>(
+        pk: &MultiStarkProvingKey<SC>,
+        max_heights: Vec<usize>,
+        headroom: usize,
+    ) -> Result<Self, SegmentationError> {
+        let strategy = Self {
+            max_heights,
+            headroom,
+        };
+        strategy.validate(pk.per_air.len())?;
+        Ok(strategy)
+    }
+
+    pub fn max_heights(&self) -> &[usize] {
+        &self.max_heights
+    }
+
+    pub fn headroom(&self) -> usize {
+        self.headroom
+    }
+
+    fn validate(&self, num_airs: usize) -> Result<(), SegmentationError> {
+        if self.max_heights.len() != num_airs {
+            return Err(SegmentationError::NumAirsMismatch {
+                expected: num_airs,
+                actual: self.max_heights.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl SegmentationStrategy for FixedHeightsSegmentation {
+    fn should_segment(&self, air_names: &[String], trace_heights: &[usize], _: &[usize]) -> bool {
+        for (i, (&height, &max_height)) in trace_heights.iter().zip(&self.max_heights).enumerate() {
+            if height > max_height.saturating_sub(self.headroom) {
+                tracing::info!(
+                    "Should segment because chip {} has height {} of at most {}",
+                    air_names[i],
+                    height,
+                    max_height
+                );
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("Air{i}")).collect()
+    }
+
+    #[test]
+    fn test_builtin_strategies() {
+        let air_names = names(3);
+        let heights = [0, 100, 3];
+        let cells = [1000, 100 * 10, 3 * 5];
+
+        assert!(
+            !MaxHeightSegmentation { max_height: 100 }.should_segment(&air_names, &heights, &cells)
+        );
+        assert!(
+            MaxHeightSegmentation { max_height: 99 }.should_segment(&air_names, &heights, &cells)
+        );
+
+        assert!(!TotalCellsSegmentation { max_cells: 2015 }
+            .should_segment(&air_names, &heights, &cells));
+        assert!(
+            TotalCellsSegmentation { max_cells: 2014 }.should_segment(&air_names, &heights, &cells)
+        );
+
+        // Padded cells: 1000 + 128 * 10 + 4 * 5 = 2300.
+        let memory = ProverMemorySegmentation::new(2300).with_bytes_per_cell(1);
+        assert!(!memory.should_segment(&air_names, &heights, &cells));
+        let memory = ProverMemorySegmentation::new(2299).with_bytes_per_cell(1);
+        assert!(memory.should_segment(&air_names, &heights, &cells));
+
+        let per_air = PerAirHeightSegmentation::new(2).with_max_height("Air1", 100);
+        assert!(per_air.should_segment(&air_names, &heights, &cells));
+        let per_air = per_air.with_max_height("Air2", 3);
+        assert!(!per_air.should_segment(&air_names, &heights, &cells));
+
+        let fixed = FixedHeightsSegmentation {
+            max_heights: vec![1, 128, 4],
+            headroom: 1,
+        };
+        assert!(!fixed.should_segment(&air_names, &heights, &cells));
+        let fixed = FixedHeightsSegmentation {
+            max_heights: vec![1, 128, 4],
+            headroom: 2,
+        };
+        assert!(fixed.should_segment(&air_names, &heights, &cells));
+    }
+
+    #[test]
+    fn test_segmentation_config() {
+        let air_names = names(3);
+        let heights = [0, 100, 3];
+
+        let fixed = SegmentationConfig::FixedHeights(FixedHeightsSegmentation {
+            max_heights: vec![1, 128, 4],
+            headroom: 1,
+        });
+        assert_eq!(fixed.validate(3), Ok(()));
+        assert_eq!(
+            fixed.validate(4),
+            Err(SegmentationError::NumAirsMismatch {
+                expected: 4,
+                actual: 3
+            })
+        );
+
+        assert!(
+            !SegmentationConfig::Default.should_segment(100, &air_names, &heights, || {
+                unreachable!("trace cells are not needed")
+            })
+        );
+        assert!(SegmentationConfig::Default.should_segment(99, &air_names, &heights, Vec::new));
+        let total_cells = SegmentationConfig::TotalCells(TotalCellsSegmentation { max_cells: 10 });
+        assert!(total_cells.should_segment(100, &air_names, &heights, || vec![5, 5, 1]));
+    }
+}
@@ -2,23 +2,34 @@ use std::{borrow::Borrow, collections::VecDeque, marker::PhantomData, mem, sync:
 
 use openvm_instructions::exe::VmExe;
 use openvm_stark_backend::{
-    config::{Domain, StarkGenericConfig, Val},
+    config::{Com, Domain, StarkGenericConfig, Val},
     engine::StarkEngine,
     keygen::types::{MultiStarkProvingKey, MultiStarkVerifyingKey},
     p3_commit::PolynomialSpace,
-    p3_field::PrimeField32,
+    p3_field::{AbstractField, PrimeField32},
     prover::types::{CommittedTraceData, Proof, ProofInput},
     verifier::VerificationError,
     Chip,
 };
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use super::{ExecutionError, VmComplexTraceHeights, VmConfig, CONNECTOR_AIR_ID, MERKLE_AIR_ID};
+use super::{
+    ExecutionError, ExecutionHook, SegmentEstimate, VmComplexTraceHeights, VmConfig,
+    CONNECTOR_AIR_ID, MERKLE_AIR_ID, PROGRAM_AIR_ID, PROGRAM_CACHED_TRACE_INDEX,
+};
 use crate::{
     arch::segment::ExecutionSegment,
+    metrics::cycle_tracker::CycleTracker,
     system::{
         connector::{VmConnectorPvs, DEFAULT_SUSPEND_EXIT_CODE},
-        memory::{memory_image_to_equipartition, merkle::MemoryMerklePvs, Equipartition, CHUNK},
+        memory::{
+            memory_image_to_equipartition,
+            merkle::MemoryMerklePvs,
+            tree::public_values::{extract_public_values, UserPublicValuesProofError},
+            Equipartition, CHUNK,
+        },
         program::trace::VmCommittedExe,
     },
 };
@@ -26,10 +37,17 @@ use crate::{
 /// VM memory state for continuations.
 pub type VmMemoryState<F> = Equipartition<F, CHUNK>;
 
-#[derive(Clone, Default, Debug)]
+/// Seed of [Streams::rng] unless another one is set with [Streams::with_rng_seed].
+pub const DEFAULT_RNG_SEED: u64 = 0;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Streams<F> {
     pub input_stream: VecDeque<Vec<F>>,
     pub hint_stream: VecDeque<F>,
+    /// Host randomness served to the guest through hints. Seeded deterministically so that
+    /// execution, and therefore proving, is reproducible.
+    #[serde(with = "rng_serde")]
+    pub rng: ChaCha20Rng,
 }
 
 impl<F> Streams<F> {
@@ -37,7 +55,52 @@ impl<F> Streams<F> {
         Self {
             input_stream: input_stream.into(),
             hint_stream: VecDeque::default(),
+            rng: ChaCha20Rng::seed_from_u64(DEFAULT_RNG_SEED),
+        }
+    }
+
+    pub fn with_rng_seed(mut self, seed: u64) -> Self {
+        self.rng = ChaCha20Rng::seed_from_u64(seed);
+        self
+    }
+}
+
+/// Serializes the RNG as its seed and position, so a deserialized RNG continues where the
+/// original left off.
+mod rng_serde {
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct ChaChaState {
+        seed: [u8; 32],
+        stream: u64,
+        word_pos: u128,
+    }
+
+    pub fn serialize<S: Serializer>(rng: &ChaCha20Rng, serializer: S) -> Result<S::Ok, S::Error> {
+        ChaChaState {
+            seed: rng.get_seed(),
+            stream: rng.get_stream(),
+            word_pos: rng.get_word_pos(),
         }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ChaCha20Rng, D::Error> {
+        let state = ChaChaState::deserialize(deserializer)?;
+        let mut rng = ChaCha20Rng::from_seed(state.seed);
+        rng.set_stream(state.stream);
+        rng.set_word_pos(state.word_pos);
+        Ok(rng)
+    }
+}
+
+impl<F> Default for Streams<F> {
+    fn default() -> Self {
+        Self::new(VecDeque::new())
     }
 }
 
@@ -72,6 +135,49 @@ pub struct VmExecutorResult<SC: StarkGenericConfig> {
     pub final_memory: Option<VmMemoryState<Val<SC>>>,
 }
 
+/// State of the VM at the start of a continuation segment. Execution can be resumed from it with
+/// [VmExecutor::execute_from_checkpoint], e.g. to prove a segment on another machine without
+/// re-executing all segments before it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VmCheckpoint<F> {
+    /// Index of the segment that starts at this checkpoint.
+    pub segment_idx: usize,
+    pub pc: u32,
+    pub memory: VmMemoryState<F>,
+    /// Input and hints not yet consumed by the guest.
+    pub streams: Streams<F>,
+    pub cycle_tracker: CycleTracker,
+}
+
+impl<F: PrimeField32> VmCheckpoint<F> {
+    /// Checkpoint of the first segment.
+    pub fn initial(exe: &VmExe<F>, streams: Streams<F>) -> Self {
+        Self {
+            segment_idx: 0,
+            pc: exe.pc_start,
+            memory: memory_image_to_equipartition(exe.init_memory.clone()),
+            streams,
+            cycle_tracker: CycleTracker::new(),
+        }
+    }
+}
+
+/// Output of [VmExecutor::execute_pure].
+#[derive(Clone, Debug)]
+pub struct PureExecutionResult<F> {
+    /// All non-zero memory blocks at termination, including registers.
+    pub final_memory: VmMemoryState<F>,
+    /// Public values of the program. Public values that were never set are zero.
+    pub public_values: Vec<F>,
+    pub exit_code: u32,
+    /// Total number of instructions executed, including the final `TERMINATE`.
+    pub instret: u64,
+    /// Estimated continuation segments. Segment lengths are lower bounds: only instructions are
+    /// counted, not the rows they add to multi-row or memory chips. The estimate is always
+    /// based on `max_segment_len`, even if a segmentation strategy is set.
+    pub segments: Vec<SegmentEstimate>,
+}
+
 impl<F, VC> VmExecutor<F, VC>
 where
     F: PrimeField32,
@@ -107,93 +213,287 @@ where
         &self,
         exe: impl Into<VmExe<F>>,
         input: impl Into<Streams<F>>,
+    ) -> Result<Vec<ExecutionSegment<F, VC>>, ExecutionError> {
+        self.execute_segments_impl(exe.into(), input.into(), None)
+    }
+
+    /// Same as [Self::execute_segments], but calls `hook` around every executed instruction.
+    pub fn execute_segments_with_hook(
+        &self,
+        exe: impl Into<VmExe<F>>,
+        input: impl Into<Streams<F>>,
+        hook: &mut dyn ExecutionHook<F>,
+    ) -> Result<Vec<ExecutionSegment<F, VC>>, ExecutionError> {
+        self.execute_segments_impl(exe.into(), input.into(), Some(hook))
+    }
+
+    fn execute_segments_impl(
+        &self,
+        exe: VmExe<F>,
+        streams: Streams<F>,
+        mut hook: Option<&mut dyn ExecutionHook<F>>,
     ) -> Result<Vec<ExecutionSegment<F, VC>>, ExecutionError> {
         #[cfg(feature = "bench-metrics")]
         let start = std::time::Instant::now();
 
-        let exe = exe.into();
-        let streams = input.into();
         let mut segments = vec![];
+        let mut checkpoint = VmCheckpoint::initial(&exe, streams);
+        loop {
+            let (segment, next) = self.execute_segment(&exe, checkpoint, hook.as_deref_mut())?;
+            segments.push(segment);
+            match next {
+                Some(next) => checkpoint = next,
+                None => break,
+            }
+        }
+        tracing::debug!("Number of continuation segments: {}", segments.len());
+        #[cfg(feature = "bench-metrics")]
+        metrics::gauge!("execute_time_ms").set(start.elapsed().as_millis() as f64);
+        #[cfg(feature = "bench-metrics")]
+        tracing::info!("execute_time [all segments]: {:?}", start.elapsed());
+
+        Ok(segments)
+    }
+
+    /// Executes the program and returns the checkpoint at the start of every segment, without
+    /// keeping the segments themselves. Any of the checkpoints can be passed to
+    /// [Self::execute_from_checkpoint] to regenerate its segment.
+    pub fn execute_checkpoints(
+        &self,
+        exe: impl Into<VmExe<F>>,
+        input: impl Into<Streams<F>>,
+    ) -> Result<Vec<VmCheckpoint<F>>, ExecutionError> {
+        let mut checkpoints = vec![];
+        self.execute_checkpoints_streaming(exe, input, |checkpoint| checkpoints.push(checkpoint))?;
+        Ok(checkpoints)
+    }
+
+    /// Same as [Self::execute_checkpoints], but hands each checkpoint to `on_checkpoint` before
+    /// its segment is executed instead of collecting them, so segments can be dispatched to
+    /// provers while later segments are still executing. Returns the final memory state.
+    pub fn execute_checkpoints_streaming(
+        &self,
+        exe: impl Into<VmExe<F>>,
+        input: impl Into<Streams<F>>,
+        mut on_checkpoint: impl FnMut(VmCheckpoint<F>),
+    ) -> Result<Option<VmMemoryState<F>>, ExecutionError> {
+        let exe = exe.into();
+        let mut checkpoint = VmCheckpoint::initial(&exe, input.into());
+        loop {
+            on_checkpoint(checkpoint.clone());
+            let (mut segment, next) = self.execute_segment(&exe, checkpoint, None)?;
+            match next {
+                Some(next) => checkpoint = next,
+                None => {
+                    Self::check_terminated(&segment)?;
+                    return Ok(mem::take(&mut segment.final_memory));
+                }
+            }
+        }
+    }
+
+    /// Executes the single segment starting at `checkpoint`. The segment is identical to the
+    /// one [Self::execute_segments] produces at index `checkpoint.segment_idx`.
+    pub fn execute_from_checkpoint(
+        &self,
+        exe: impl Into<VmExe<F>>,
+        checkpoint: VmCheckpoint<F>,
+    ) -> Result<ExecutionSegment<F, VC>, ExecutionError> {
+        let (segment, _) = self.execute_segment(&exe.into(), checkpoint, None)?;
+        Ok(segment)
+    }
+
+    /// Executes the single segment starting at `checkpoint` and returns its proof input.
+    pub fn execute_and_generate_from_checkpoint<SC: StarkGenericConfig>(
+        &self,
+        committed_exe: Arc<VmCommittedExe<SC>>,
+        checkpoint: VmCheckpoint<F>,
+    ) -> Result<ProofInput<SC>, ExecutionError>
+    where
+        Domain<SC>: PolynomialSpace<Val = F>,
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let segment_idx = checkpoint.segment_idx;
+        let segment = self.execute_from_checkpoint(committed_exe.exe.clone(), checkpoint)?;
+        let proof_input = tracing::info_span!("trace_gen", segment = segment_idx).in_scope(|| {
+            segment.generate_proof_input(Some(committed_exe.committed_program.clone()))
+        });
+        Ok(proof_input)
+    }
+
+    /// Executes one segment from `checkpoint`. Returns the segment and, unless it terminated,
+    /// the checkpoint of the next segment.
+    fn execute_segment(
+        &self,
+        exe: &VmExe<F>,
+        checkpoint: VmCheckpoint<F>,
+        hook: Option<&mut dyn ExecutionHook<F>>,
+    ) -> Result<(ExecutionSegment<F, VC>, Option<VmCheckpoint<F>>), ExecutionError> {
+        let VmCheckpoint {
+            segment_idx,
+            pc,
+            memory,
+            streams,
+            cycle_tracker,
+        } = checkpoint;
         let mut segment = ExecutionSegment::new(
             &self.config,
             exe.program.clone(),
             streams,
-            Some(memory_image_to_equipartition(exe.init_memory)),
+            Some(memory),
             exe.fn_bounds.clone(),
-        );
+        )?;
         if let Some(overridden_heights) = self.overridden_heights.as_ref() {
             segment.set_override_trace_heights(overridden_heights.clone());
         }
-        let mut pc = exe.pc_start;
+        segment.cycle_tracker = cycle_tracker;
 
-        loop {
-            let state = tracing::info_span!("execute_segment", segment = segments.len())
-                .in_scope(|| segment.execute_from_pc(pc))?;
-            pc = state.pc;
-
-            if state.is_terminated {
-                break;
-            }
+        let state = tracing::info_span!("execute_segment", segment = segment_idx).in_scope(
+            || match hook {
+                Some(hook) => {
+                    hook.on_segment_start(segment_idx);
+                    segment.execute_from_pc_with_hook(pc, segment_idx, hook)
+                }
+                None => segment.execute_from_pc(pc),
+            },
+        )?;
+        let pc = state.pc;
 
-            assert!(
-                self.continuation_enabled(),
-                "multiple segments require to enable continuations"
-            );
+        if state.is_terminated {
+            return Ok((segment, None));
+        }
 
-            assert_eq!(
-                pc,
-                segment.chip_complex.connector_chip().boundary_states[1]
-                    .unwrap()
-                    .pc
-            );
+        if !self.continuation_enabled() {
+            return Err(ExecutionError::ContinuationsDisabled { pc });
+        }
 
-            let cycle_tracker = mem::take(&mut segment.cycle_tracker);
-            let final_memory = mem::take(&mut segment.final_memory)
-                .expect("final memory should be set in continuations segment");
-            let streams = segment.chip_complex.take_streams();
+        assert_eq!(
+            pc,
+            segment.chip_complex.connector_chip().boundary_states[1]
+                .unwrap()
+                .pc
+        );
 
-            segments.push(segment);
+        let next = VmCheckpoint {
+            segment_idx: segment_idx + 1,
+            pc,
+            memory: mem::take(&mut segment.final_memory)
+                .expect("final memory should be set in continuations segment"),
+            streams: segment.chip_complex.take_streams(),
+            cycle_tracker: mem::take(&mut segment.cycle_tracker),
+        };
+        Ok((segment, Some(next)))
+    }
 
-            segment = ExecutionSegment::new(
-                &self.config,
-                exe.program.clone(),
-                streams,
-                Some(final_memory),
-                exe.fn_bounds.clone(),
-            );
-            if let Some(overridden_heights) = self.overridden_heights.as_ref() {
-                segment.set_override_trace_heights(overridden_heights.clone());
-            }
-            segment.cycle_tracker = cycle_tracker;
-        }
-        segments.push(segment);
-        tracing::debug!("Number of continuation segments: {}", segments.len());
-        #[cfg(feature = "bench-metrics")]
-        metrics::gauge!("execute_time_ms").set(start.elapsed().as_millis() as f64);
-        #[cfg(feature = "bench-metrics")]
-        tracing::info!("execute_time [all segments]: {:?}", start.elapsed());
+    pub fn execute(
+        &self,
+        exe: impl Into<VmExe<F>>,
+        input: impl Into<Streams<F>>,
+    ) -> Result<Option<VmMemoryState<F>>, ExecutionError> {
+        let results = self.execute_segments(exe, input)?;
+        Self::final_memory_of_terminated(results)
+    }
 
-        Ok(segments)
+    /// Same as [Self::execute], but calls `hook` around every executed instruction.
+    pub fn execute_with_hook(
+        &self,
+        exe: impl Into<VmExe<F>>,
+        input: impl Into<Streams<F>>,
+        hook: &mut dyn ExecutionHook<F>,
+    ) -> Result<Option<VmMemoryState<F>>, ExecutionError> {
+        let results = self.execute_segments_with_hook(exe, input, hook)?;
+        Self::final_memory_of_terminated(results)
     }
 
-    pub fn execute(
+    /// Executes the program without keeping any of the data needed for trace generation: no
+    /// chip records, no memory block timestamps and no access adapter records. Much faster than
+    /// [Self::execute] when only the output of the program is needed.
+    ///
+    /// Unlike [Self::execute], a non-zero exit code is not an error and is returned in the result.
+    pub fn execute_pure(
         &self,
         exe: impl Into<VmExe<F>>,
         input: impl Into<Streams<F>>,
+    ) -> Result<PureExecutionResult<F>, ExecutionError> {
+        #[cfg(feature = "bench-metrics")]
+        let start = std::time::Instant::now();
+
+        let exe = exe.into();
+        let mut segment = ExecutionSegment::new(
+            &self.config,
+            exe.program,
+            input.into(),
+            Some(memory_image_to_equipartition(exe.init_memory)),
+            exe.fn_bounds,
+        )?;
+        let (exit_code, segments) = tracing::info_span!("execute_pure")
+            .in_scope(|| segment.execute_pure_from_pc(exe.pc_start))?;
+
+        let final_memory = segment
+            .chip_complex
+            .memory_controller()
+            .borrow()
+            .memory_image();
+        let system_config = self.config.system();
+        let public_values = if system_config.continuation_enabled {
+            extract_public_values(
+                &system_config.memory_config.memory_dimensions(),
+                system_config.num_public_values,
+                &final_memory,
+            )
+        } else {
+            segment
+                .chip_complex
+                .public_values_chip()
+                .map(|pv_chip| {
+                    pv_chip
+                        .core
+                        .get_custom_public_values()
+                        .into_iter()
+                        .map(|pv| pv.unwrap_or(F::ZERO))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let last = segments.last().unwrap();
+        let instret = last.start_instret + last.num_instructions;
+        tracing::debug!(
+            "Executed {instret} instructions, estimated {} segments",
+            segments.len()
+        );
+        #[cfg(feature = "bench-metrics")]
+        metrics::gauge!("execute_pure_time_ms").set(start.elapsed().as_millis() as f64);
+
+        Ok(PureExecutionResult {
+            final_memory,
+            public_values,
+            exit_code,
+            instret,
+            segments,
+        })
+    }
+
+    fn final_memory_of_terminated(
+        mut results: Vec<ExecutionSegment<F, VC>>,
     ) -> Result<Option<VmMemoryState<F>>, ExecutionError> {
-        let mut results = self.execute_segments(exe, input)?;
         let last = results.last_mut().unwrap();
-        let final_memory = mem::take(&mut last.final_memory);
+        Self::check_terminated(last)?;
+        Ok(mem::take(&mut last.final_memory))
+    }
+
+    fn check_terminated(last: &ExecutionSegment<F, VC>) -> Result<(), ExecutionError> {
         let end_state =
             last.chip_complex.connector_chip().boundary_states[1].expect("end state must be set");
-        // TODO[jpw]: add these as execution errors
-        assert_eq!(end_state.is_terminate, 1, "program must terminate");
-        assert_eq!(
-            end_state.exit_code,
-            ExitCode::Success as u32,
-            "program did not exit successfully"
-        );
-        Ok(final_memory)
+        if end_state.is_terminate != 1 {
+            return Err(ExecutionError::DidNotTerminate);
+        }
+        if end_state.exit_code != ExitCode::Success as u32 {
+            return Err(ExecutionError::FailedWithExitCode {
+                exit_code: end_state.exit_code,
+            });
+        }
+        Ok(())
     }
 
     pub fn execute_and_generate<SC: StarkGenericConfig>(
@@ -225,6 +525,40 @@ where
             input,
         )
     }
+
+    /// Same as [Self::execute_and_generate_with_cached_program], but hands the proof input of
+    /// each segment to `on_segment` as soon as it is generated instead of collecting all of them.
+    /// The next segment is only executed once `on_segment` returns, so a blocking `on_segment`
+    /// bounds how many proof inputs are alive at once. Returns the final memory state.
+    pub fn execute_and_generate_with_cached_program_streaming<SC: StarkGenericConfig>(
+        &self,
+        committed_exe: Arc<VmCommittedExe<SC>>,
+        input: impl Into<Streams<F>>,
+        mut on_segment: impl FnMut(usize, ProofInput<SC>),
+    ) -> Result<Option<VmMemoryState<F>>, ExecutionError>
+    where
+        Domain<SC>: PolynomialSpace<Val = F>,
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let exe = &committed_exe.exe;
+        let mut checkpoint = VmCheckpoint::initial(exe, input.into());
+        loop {
+            let segment_idx = checkpoint.segment_idx;
+            let (mut segment, next) = self.execute_segment(exe, checkpoint, None)?;
+            let final_memory = mem::take(&mut segment.final_memory);
+            let proof_input =
+                tracing::info_span!("trace_gen", segment = segment_idx).in_scope(|| {
+                    segment.generate_proof_input(Some(committed_exe.committed_program.clone()))
+                });
+            on_segment(segment_idx, proof_input);
+            match next {
+                Some(next) => checkpoint = next,
+                None => return Ok(final_memory),
+            }
+        }
+    }
+
     fn execute_and_generate_impl<SC: StarkGenericConfig>(
         &self,
         exe: VmExe<F>,
@@ -352,7 +686,7 @@ where
             input.into(),
             None,
             exe.fn_bounds,
-        );
+        )?;
         if let Some(overridden_heights) = self.overridden_heights.as_ref() {
             segment.set_override_trace_heights(overridden_heights.clone());
         }
@@ -369,6 +703,12 @@ where
 
 #[derive(Error, Debug)]
 pub enum VmVerificationError {
+    #[error("no proof is provided")]
+    ProofNotFound,
+
+    #[error("program commit mismatch (index of mismatch proof: {index})")]
+    ProgramCommitMismatch { index: usize },
+
     #[error("initial pc mismatch (initial: {initial}, prev_final: {prev_final})")]
     InitialPcMismatch { initial: u32, prev_final: u32 },
 
@@ -387,8 +727,158 @@ pub enum VmVerificationError {
     #[error("number of public values mismatch (expected: {expected}, actual: {actual})")]
     NumPublicValuesMismatch { expected: usize, actual: usize },
 
+    #[error("missing system AIR with ID {air_id}")]
+    SystemAirMissing { air_id: usize },
+
     #[error("stark verification error: {0}")]
     StarkError(#[from] VerificationError),
+
+    #[error("user public values proof error: {0}")]
+    UserPublicValuesError(#[from] UserPublicValuesProofError),
+}
+
+/// Boundary data of an execution whose segment proofs have been verified by [verify_segments].
+/// The segment proofs only attest to a valid transition between these boundaries, so callers
+/// must still check them against the expected executable.
+pub struct VerifiedExecutionPayload<SC: StarkGenericConfig> {
+    /// Commitment of the cached program trace. It is the same for all segments.
+    pub program_commit: Com<SC>,
+    /// The initial PC of the first segment.
+    pub pc_start: u32,
+    /// Merkle root of the memory state at the start of the first segment.
+    pub initial_memory_root: [Val<SC>; CHUNK],
+    /// Merkle root of the memory state at the end of the last segment.
+    pub final_memory_root: [Val<SC>; CHUNK],
+}
+
+/// Verify segment proofs of a continuation VM, checking the boundary conditions between
+/// consecutive segments:
+/// - all segments are proven against the same program,
+/// - the initial pc of a segment is the final pc of the previous segment,
+/// - the initial memory root of a segment is the final memory root of the previous segment,
+/// - only the last segment terminates, and it exits successfully.
+///
+/// Timestamps restart in each segment, so they are not linked across segments.
+pub fn verify_segments<SC, E>(
+    engine: &E,
+    vk: &MultiStarkVerifyingKey<SC>,
+    proofs: &[Proof<SC>],
+) -> Result<VerifiedExecutionPayload<SC>, VmVerificationError>
+where
+    SC: StarkGenericConfig,
+    E: StarkEngine<SC>,
+    Val<SC>: PrimeField32,
+    Com<SC>: PartialEq,
+{
+    if proofs.is_empty() {
+        return Err(VmVerificationError::ProofNotFound);
+    }
+    let mut program_commit = None;
+    let mut pc_start = None;
+    let mut initial_memory_root = None;
+    let mut prev_final_memory_root = None;
+    let mut prev_final_pc = None;
+
+    for (i, proof) in proofs.iter().enumerate() {
+        engine.verify(vk, proof)?;
+
+        let mut program_air_present = false;
+        let mut connector_air_present = false;
+        let mut merkle_air_present = false;
+
+        // Check public values.
+        for air_proof_data in proof.per_air.iter() {
+            let pvs = &air_proof_data.public_values;
+            let air_vk = &vk.per_air[air_proof_data.air_id];
+
+            if air_proof_data.air_id == PROGRAM_AIR_ID {
+                program_air_present = true;
+                let commit = &proof.commitments.main_trace[PROGRAM_CACHED_TRACE_INDEX];
+                if i == 0 {
+                    program_commit = Some(commit.clone());
+                } else if program_commit.as_ref() != Some(commit) {
+                    return Err(VmVerificationError::ProgramCommitMismatch { index: i });
+                }
+            } else if air_proof_data.air_id == CONNECTOR_AIR_ID {
+                connector_air_present = true;
+                let pvs: &VmConnectorPvs<_> = pvs.as_slice().borrow();
+
+                if i != 0 {
+                    // Check initial pc matches the previous final pc.
+                    if pvs.initial_pc != prev_final_pc.unwrap() {
+                        return Err(VmVerificationError::InitialPcMismatch {
+                            initial: pvs.initial_pc.as_canonical_u32(),
+                            prev_final: prev_final_pc.unwrap().as_canonical_u32(),
+                        });
+                    }
+                } else {
+                    pc_start = Some(pvs.initial_pc.as_canonical_u32());
+                }
+                prev_final_pc = Some(pvs.final_pc);
+
+                let expected_is_terminate = i == proofs.len() - 1;
+                if pvs.is_terminate != Val::<SC>::from_bool(expected_is_terminate) {
+                    return Err(VmVerificationError::IsTerminateMismatch {
+                        expected: expected_is_terminate,
+                        actual: pvs.is_terminate.as_canonical_u32() != 0,
+                    });
+                }
+
+                let expected_exit_code = if expected_is_terminate {
+                    ExitCode::Success as u32
+                } else {
+                    DEFAULT_SUSPEND_EXIT_CODE
+                };
+                if pvs.exit_code != Val::<SC>::from_canonical_u32(expected_exit_code) {
+                    return Err(VmVerificationError::ExitCodeMismatch {
+                        expected: expected_exit_code,
+                        actual: pvs.exit_code.as_canonical_u32(),
+                    });
+                }
+            } else if air_proof_data.air_id == MERKLE_AIR_ID {
+                merkle_air_present = true;
+                let pvs: &MemoryMerklePvs<_, CHUNK> = pvs.as_slice().borrow();
+
+                // Check that initial root matches the previous final root.
+                if i != 0 {
+                    if pvs.initial_root != prev_final_memory_root.unwrap() {
+                        return Err(VmVerificationError::InitialMemoryRootMismatch);
+                    }
+                } else {
+                    initial_memory_root = Some(pvs.initial_root);
+                }
+                prev_final_memory_root = Some(pvs.final_root);
+            } else {
+                if !pvs.is_empty() {
+                    return Err(VmVerificationError::UnexpectedPvs {
+                        expected: 0,
+                        actual: pvs.len(),
+                    });
+                }
+                if air_vk.params.num_public_values != 0 {
+                    return Err(VmVerificationError::NumPublicValuesMismatch {
+                        expected: 0,
+                        actual: air_vk.params.num_public_values,
+                    });
+                }
+            }
+        }
+        for (present, air_id) in [
+            (program_air_present, PROGRAM_AIR_ID),
+            (connector_air_present, CONNECTOR_AIR_ID),
+            (merkle_air_present, MERKLE_AIR_ID),
+        ] {
+            if !present {
+                return Err(VmVerificationError::SystemAirMissing { air_id });
+            }
+        }
+    }
+    Ok(VerifiedExecutionPayload {
+        program_commit: program_commit.unwrap(),
+        pc_start: pc_start.unwrap(),
+        initial_memory_root: initial_memory_root.unwrap(),
+        final_memory_root: prev_final_memory_root.unwrap(),
+    })
 }
 
 pub struct VirtualMachine<SC: StarkGenericConfig, E, VC> {
@@ -519,99 +1009,14 @@ where
     ) -> Result<(), VmVerificationError>
     where
         Val<SC>: PrimeField32,
+        Com<SC>: PartialEq,
     {
         if self.config().system().continuation_enabled {
-            self.verify_segments(vk, proofs)
+            verify_segments(&self.engine, vk, &proofs).map(|_| ())
         } else {
             assert_eq!(proofs.len(), 1);
             self.verify_single(vk, &proofs.into_iter().next().unwrap())
                 .map_err(VmVerificationError::StarkError)
         }
     }
-
-    /// Verify segment proofs with boundary condition checks for continuation between segments
-    fn verify_segments(
-        &self,
-        vk: &MultiStarkVerifyingKey<SC>,
-        proofs: Vec<Proof<SC>>,
-    ) -> Result<(), VmVerificationError>
-    where
-        Val<SC>: PrimeField32,
-    {
-        let mut prev_final_memory_root = None;
-        let mut prev_final_pc = None;
-
-        for (i, proof) in proofs.iter().enumerate() {
-            let res = self.engine.verify(vk, proof);
-            match res {
-                Ok(_) => (),
-                Err(e) => return Err(VmVerificationError::StarkError(e)),
-            };
-
-            // Check public values.
-            for air_proof_data in proof.per_air.iter() {
-                let pvs = &air_proof_data.public_values;
-                let air_vk = &vk.per_air[air_proof_data.air_id];
-
-                if air_proof_data.air_id == CONNECTOR_AIR_ID {
-                    let pvs: &VmConnectorPvs<_> = pvs.as_slice().borrow();
-
-                    if i != 0 {
-                        // Check initial pc matches the previous final pc.
-                        if pvs.initial_pc != prev_final_pc.unwrap() {
-                            return Err(VmVerificationError::InitialPcMismatch {
-                                initial: pvs.initial_pc.as_canonical_u32(),
-                                prev_final: prev_final_pc.unwrap().as_canonical_u32(),
-                            });
-                        }
-                    } else {
-                        // TODO: Fetch initial pc from program
-                    }
-                    prev_final_pc = Some(pvs.final_pc);
-
-                    let expected_is_terminate = i == proofs.len() - 1;
-                    if pvs.is_terminate != Val::<SC>::from_bool(expected_is_terminate) {
-                        return Err(VmVerificationError::IsTerminateMismatch {
-                            expected: expected_is_terminate,
-                            actual: pvs.is_terminate.as_canonical_u32() != 0,
-                        });
-                    }
-
-                    let expected_exit_code = if expected_is_terminate {
-                        ExitCode::Success as u32
-                    } else {
-                        DEFAULT_SUSPEND_EXIT_CODE
-                    };
-                    if pvs.exit_code != Val::<SC>::from_canonical_u32(expected_exit_code) {
-                        return Err(VmVerificationError::ExitCodeMismatch {
-                            expected: expected_exit_code,
-                            actual: pvs.exit_code.as_canonical_u32(),
-                        });
-                    }
-                } else if air_proof_data.air_id == MERKLE_AIR_ID {
-                    let pvs: &MemoryMerklePvs<_, CHUNK> = pvs.as_slice().borrow();
-
-                    // Check that initial root matches the previous final root.
-                    if i != 0 && pvs.initial_root != prev_final_memory_root.unwrap() {
-                        return Err(VmVerificationError::InitialMemoryRootMismatch);
-                    }
-                    prev_final_memory_root = Some(pvs.final_root);
-                } else {
-                    if !pvs.is_empty() {
-                        return Err(VmVerificationError::UnexpectedPvs {
-                            expected: 0,
-                            actual: pvs.len(),
-                        });
-                    }
-                    if air_vk.params.num_public_values != 0 {
-                        return Err(VmVerificationError::NumPublicValuesMismatch {
-                            expected: 0,
-                            actual: air_vk.params.num_public_values,
-                        });
-                    }
-                }
-            }
-        }
-        Ok(())
-    }
 }
@@ -52,6 +52,8 @@ impl<F> AccessAdapterInventory<F> {
             Self::create_access_adapter_chip::<16>(rc.clone(), mb, cmb, maan),
             Self::create_access_adapter_chip::<32>(rc.clone(), mb, cmb, maan),
             Self::create_access_adapter_chip::<64>(rc.clone(), mb, cmb, maan),
+            Self::create_access_adapter_chip::<128>(rc.clone(), mb, cmb, maan),
+            Self::create_access_adapter_chip::<256>(rc.clone(), mb, cmb, maan),
         ]
         .into_iter()
         .flatten()
@@ -168,6 +170,8 @@ enum GenericAccessAdapterChip<F> {
     N16(AccessAdapterChip<F, 16>),
     N32(AccessAdapterChip<F, 32>),
     N64(AccessAdapterChip<F, 64>),
+    N128(AccessAdapterChip<F, 128>),
+    N256(AccessAdapterChip<F, 256>),
 }
 
 impl<F> GenericAccessAdapterChip<F> {
@@ -186,7 +190,9 @@ impl<F> GenericAccessAdapterChip<F> {
             16 => GenericAccessAdapterChip::N16(AccessAdapterChip::new(rc, mb, cmb)),
             32 => GenericAccessAdapterChip::N32(AccessAdapterChip::new(rc, mb, cmb)),
             64 => GenericAccessAdapterChip::N64(AccessAdapterChip::new(rc, mb, cmb)),
-            _ => panic!("Only supports N in (2, 4, 8, 16, 32, 64)"),
+            128 => GenericAccessAdapterChip::N128(AccessAdapterChip::new(rc, mb, cmb)),
+            256 => GenericAccessAdapterChip::N256(AccessAdapterChip::new(rc, mb, cmb)),
+            _ => panic!("Only supports N in (2, 4, 8, 16, 32, 64, 128, 256)"),
         }
     }
 }
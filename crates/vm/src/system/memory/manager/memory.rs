@@ -1,8 +1,8 @@
-use std::{array, cmp::max, fmt::Debug};
+use std::{cmp::max, fmt::Debug};
 
 use openvm_stark_backend::p3_field::PrimeField32;
-use rustc_hash::{FxHashMap, FxHashSet};
 
+use super::paged_vec::AddressMap;
 use crate::system::memory::{
     adapter::{AccessAdapterRecord, AccessAdapterRecordKind},
     Equipartition, TimestampedEquipartition, TimestampedValues,
@@ -47,9 +47,6 @@ impl<T: Copy> MemoryReadRecord<T, 1> {
 
 pub const INITIAL_TIMESTAMP: u32 = 0;
 
-/// (address_space, pointer)
-type Address = (u32, u32);
-
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 struct BlockData {
     pointer: u32,
@@ -58,10 +55,13 @@ struct BlockData {
 }
 
 /// A partition of data into blocks where each block has size a power of two.
+///
+/// Both the block of every cell and the cell values are stored in paged maps, see [AddressMap].
 #[derive(Debug)]
 pub struct Memory<F> {
-    block_data: FxHashMap<Address, BlockData>,
-    data: FxHashMap<Address, F>,
+    /// The block containing each cell. Cells without a block are in their initial block.
+    block_data: AddressMap<Option<BlockData>>,
+    data: AddressMap<F>,
     initial_block_size: usize,
     timestamp: u32,
 }
@@ -73,8 +73,8 @@ impl<F: PrimeField32> Memory<F> {
     pub fn new<const N: usize>(initial_memory: &Equipartition<F, N>) -> Self {
         assert!(N.is_power_of_two());
 
-        let mut block_data = FxHashMap::default();
-        let mut data = FxHashMap::default();
+        let mut block_data = AddressMap::default();
+        let mut data = AddressMap::default();
         for (&(address_space, block_idx), values) in initial_memory {
             let pointer = block_idx * N as u32;
             let block = BlockData {
@@ -82,9 +82,9 @@ impl<F: PrimeField32> Memory<F> {
                 size: N,
                 timestamp: INITIAL_TIMESTAMP,
             };
-            for (i, value) in values.iter().enumerate() {
-                data.insert((address_space, pointer + i as u32), *value);
-                block_data.insert((address_space, pointer + i as u32), block);
+            data.set_range(address_space, pointer, *values);
+            for i in 0..N as u32 {
+                block_data.set(address_space, pointer + i, Some(block));
             }
         }
         Self {
@@ -124,11 +124,7 @@ impl<F: PrimeField32> Memory<F> {
 
         debug_assert!(prev_timestamp < self.timestamp);
 
-        let prev_data = array::from_fn(|i| {
-            self.data
-                .insert((address_space, pointer + i as u32), values[i])
-                .unwrap_or(F::ZERO)
-        });
+        let prev_data = self.data.set_range(address_space, pointer, values);
 
         let record = MemoryWriteRecord {
             address_space: F::from_canonical_u32(address_space),
@@ -161,28 +157,90 @@ impl<F: PrimeField32> Memory<F> {
             pointer: F::from_canonical_u32(pointer),
             timestamp: self.timestamp,
             prev_timestamp,
-            data: self.range_array::<N>(address_space, pointer),
+            data: self.data.range_array::<N>(address_space, pointer),
         };
 
         self.increment_timestamp();
         (record, adapter_records)
     }
 
+    /// Writes without updating the block partition, so no access adapter records are needed and
+    /// the returned record has `prev_timestamp` 0. Only used for pure execution, where nothing
+    /// is proven.
+    pub fn write_untracked<const N: usize>(
+        &mut self,
+        address_space: u32,
+        pointer: u32,
+        values: [F; N],
+    ) -> MemoryWriteRecord<F, N> {
+        let prev_data = self.data.set_range(address_space, pointer, values);
+        let record = MemoryWriteRecord {
+            address_space: F::from_canonical_u32(address_space),
+            pointer: F::from_canonical_u32(pointer),
+            timestamp: self.timestamp,
+            prev_timestamp: INITIAL_TIMESTAMP,
+            data: values,
+            prev_data,
+        };
+        self.increment_timestamp();
+        record
+    }
+
+    /// Reads without updating the block partition, see [Self::write_untracked].
+    pub fn read_untracked<const N: usize>(
+        &mut self,
+        address_space: u32,
+        pointer: u32,
+    ) -> MemoryReadRecord<F, N> {
+        let record = MemoryReadRecord {
+            address_space: F::from_canonical_u32(address_space),
+            pointer: F::from_canonical_u32(pointer),
+            timestamp: self.timestamp,
+            prev_timestamp: INITIAL_TIMESTAMP,
+            data: self.data.range_array::<N>(address_space, pointer),
+        };
+        self.increment_timestamp();
+        record
+    }
+
+    /// Restarts the timestamp as for a new segment. Only valid for untracked accesses, since the
+    /// block partition holds timestamps of the previous accesses.
+    pub fn reset_timestamp(&mut self) {
+        self.timestamp = INITIAL_TIMESTAMP + 1;
+    }
+
+    /// Returns the current memory values as an equipartition. Blocks that are all zero are
+    /// omitted.
+    pub fn values_equipartition<const N: usize>(&self) -> Equipartition<F, N> {
+        let mut equipartition = Equipartition::<F, N>::new();
+        for (address_space, pointer, value) in self.data.iter() {
+            if value != F::ZERO {
+                equipartition
+                    .entry((address_space, pointer / N as u32))
+                    .or_insert([F::ZERO; N])[pointer as usize % N] = value;
+            }
+        }
+        equipartition
+    }
+
     pub fn finalize<const N: usize>(
         &mut self,
     ) -> (TimestampedEquipartition<F, N>, Vec<AccessAdapterRecord<F>>) {
         let mut adapter_records = vec![];
 
         // First make sure the partition we maintain in self.block_data is an equipartition.
-        // Grab all aligned pointers that need to be re-accessed.
-        let to_access: FxHashSet<_> = self
+        // Grab all aligned pointers that need to be re-accessed. The cells are iterated in
+        // increasing order of addresses, so duplicates are consecutive.
+        let mut to_access: Vec<_> = self
             .block_data
-            .keys()
-            .map(|&(address_space, pointer)| (address_space, (pointer / N as u32) * N as u32))
+            .iter()
+            .filter(|(_, _, block)| block.is_some())
+            .map(|(address_space, pointer, _)| (address_space, (pointer / N as u32) * N as u32))
             .collect();
+        to_access.dedup();
 
         for &(address_space, pointer) in to_access.iter() {
-            let block = self.block_data.get(&(address_space, pointer)).unwrap();
+            let block = self.block_data.get(address_space, pointer).unwrap();
             if block.pointer != pointer || block.size != N {
                 self.access(address_space, pointer, N, &mut adapter_records);
             }
@@ -190,7 +248,7 @@ impl<F: PrimeField32> Memory<F> {
 
         let mut equipartition = TimestampedEquipartition::<F, N>::new();
         for (address_space, pointer) in to_access {
-            let block = self.block_data.get(&(address_space, pointer)).unwrap();
+            let block = self.block_data.get(address_space, pointer).unwrap();
 
             debug_assert_eq!(block.pointer % N as u32, 0);
             debug_assert_eq!(block.size, N);
@@ -199,7 +257,7 @@ impl<F: PrimeField32> Memory<F> {
                 (address_space, pointer / N as u32),
                 TimestampedValues {
                     timestamp: block.timestamp,
-                    values: self.range_array::<N>(address_space, pointer),
+                    values: self.data.range_array::<N>(address_space, pointer),
                 },
             );
         }
@@ -249,7 +307,7 @@ impl<F: PrimeField32> Memory<F> {
                     timestamp,
                 };
                 for i in 0..half_size_u32 {
-                    self.block_data.insert((address_space, mid_ptr + i), block);
+                    self.block_data.set(address_space, mid_ptr + i, Some(block));
                 }
             }
             if query >= cur_ptr + half_size_u32 {
@@ -260,7 +318,7 @@ impl<F: PrimeField32> Memory<F> {
                     timestamp,
                 };
                 for i in 0..half_size_u32 {
-                    self.block_data.insert((address_space, cur_ptr + i), block);
+                    self.block_data.set(address_space, cur_ptr + i, Some(block));
                 }
             }
             if mid_ptr <= query {
@@ -287,8 +345,10 @@ impl<F: PrimeField32> Memory<F> {
         for i in 0..size as u32 {
             let block = self
                 .block_data
-                .entry((address_space, pointer + i))
-                .or_insert_with(|| Self::initial_block_data(pointer + i, self.initial_block_size));
+                .get_mut(address_space, pointer + i)
+                .get_or_insert_with(|| {
+                    Self::initial_block_data(pointer + i, self.initial_block_size)
+                });
             debug_assert!(i == 0 || prev_timestamp == Some(block.timestamp));
             prev_timestamp = Some(block.timestamp);
             block.timestamp = self.timestamp;
@@ -308,8 +368,7 @@ impl<F: PrimeField32> Memory<F> {
 
         let block_data = self
             .block_data
-            .get(&(address_space, pointer))
-            .copied()
+            .get(address_space, pointer)
             .unwrap_or_else(|| Self::initial_block_data(pointer, self.initial_block_size));
 
         if block_data.pointer == pointer && block_data.size == size {
@@ -340,7 +399,7 @@ impl<F: PrimeField32> Memory<F> {
         pointer: u32,
         records: &mut Vec<AccessAdapterRecord<F>>,
     ) {
-        let left_block = self.block_data.get(&(address_space, pointer));
+        let left_block = self.block_data.get(address_space, pointer);
 
         let left_timestamp = left_block.map(|b| b.timestamp).unwrap_or(INITIAL_TIMESTAMP);
         let size = left_block
@@ -349,19 +408,20 @@ impl<F: PrimeField32> Memory<F> {
 
         let right_timestamp = self
             .block_data
-            .get(&(address_space, pointer + size as u32))
+            .get(address_space, pointer + size as u32)
             .map(|b| b.timestamp)
             .unwrap_or(INITIAL_TIMESTAMP);
 
         let timestamp = max(left_timestamp, right_timestamp);
         for i in 0..2 * size as u32 {
-            self.block_data.insert(
-                (address_space, pointer + i),
-                BlockData {
+            self.block_data.set(
+                address_space,
+                pointer + i,
+                Some(BlockData {
                     pointer,
                     size: 2 * size,
                     timestamp,
-                },
+                }),
             );
         }
         records.push(AccessAdapterRecord {
@@ -377,11 +437,9 @@ impl<F: PrimeField32> Memory<F> {
     }
 
     fn block_containing(&mut self, address_space: u32, pointer: u32) -> BlockData {
-        if let Some(block_data) = self.block_data.get(&(address_space, pointer)) {
-            *block_data
-        } else {
-            Self::initial_block_data(pointer, self.initial_block_size)
-        }
+        self.block_data
+            .get(address_space, pointer)
+            .unwrap_or_else(|| Self::initial_block_data(pointer, self.initial_block_size))
     }
 
     fn initial_block_data(pointer: u32, initial_block_size: usize) -> BlockData {
@@ -394,11 +452,7 @@ impl<F: PrimeField32> Memory<F> {
     }
 
     pub fn get(&self, address_space: u32, pointer: u32) -> F {
-        *self.data.get(&(address_space, pointer)).unwrap_or(&F::ZERO)
-    }
-
-    fn range_array<const N: usize>(&self, address_space: u32, pointer: u32) -> [F; N] {
-        array::from_fn(|i| self.get(address_space, pointer + i as u32))
+        self.data.get(address_space, pointer)
     }
 
     fn range_vec(&self, address_space: u32, pointer: u32, len: usize) -> Vec<F> {
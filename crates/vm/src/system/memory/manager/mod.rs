@@ -4,6 +4,7 @@ use std::{
     collections::BTreeMap,
     iter,
     marker::PhantomData,
+    mem,
     rc::Rc,
     sync::Arc,
 };
@@ -33,7 +34,7 @@ use serde::{Deserialize, Serialize};
 use self::interface::MemoryInterface;
 use super::{merkle::DirectCompressionBus, volatile::VolatileBoundaryChip};
 use crate::{
-    arch::{hasher::HasherChip, MemoryConfig},
+    arch::{hasher::HasherChip, MemoryAccess, MemoryAccessKind, MemoryConfig},
     system::memory::offline_checker::{
         MemoryBridge, MemoryBus, MemoryReadAuxCols, MemoryReadOrImmediateAuxCols,
         MemoryWriteAuxCols, AUX_LEN,
@@ -43,6 +44,7 @@ use crate::{
 pub mod dimensions;
 mod interface;
 pub(super) mod memory;
+mod paged_vec;
 
 use crate::system::memory::{
     adapter::AccessAdapterInventory,
@@ -102,6 +104,12 @@ pub struct MemoryController<F> {
 
     // Filled during finalization.
     final_state: Option<FinalState<F>>,
+
+    // Only collected when an execution hook is attached.
+    access_log: Option<Vec<MemoryAccess>>,
+
+    // See [Self::set_pure_execution].
+    pure_execution: bool,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -245,6 +253,8 @@ impl<F: PrimeField32> MemoryController<F> {
             range_checker,
             range_checker_bus,
             final_state: None,
+            access_log: None,
+            pure_execution: false,
         }
     }
 
@@ -287,6 +297,8 @@ impl<F: PrimeField32> MemoryController<F> {
             range_checker,
             range_checker_bus,
             final_state: None,
+            access_log: None,
+            pure_execution: false,
         }
     }
 
@@ -333,6 +345,44 @@ impl<F: PrimeField32> MemoryController<F> {
         }
     }
 
+    /// In pure execution, accesses only update memory values: no access timestamps, access
+    /// adapter records or touched addresses are kept, so the memory can not be finalized or
+    /// proven. Chips also check this to skip keeping records for trace generation.
+    pub fn set_pure_execution(&mut self, pure_execution: bool) {
+        self.pure_execution = pure_execution;
+    }
+
+    pub fn is_pure_execution(&self) -> bool {
+        self.pure_execution
+    }
+
+    /// Start or stop recording every memory access into a log, see [Self::take_access_log].
+    pub fn set_access_log_enabled(&mut self, enabled: bool) {
+        self.access_log = enabled.then(Vec::new);
+    }
+
+    /// Returns the memory accesses recorded since the last call. Empty if logging is disabled.
+    pub fn take_access_log(&mut self) -> Vec<MemoryAccess> {
+        self.access_log.as_mut().map(mem::take).unwrap_or_default()
+    }
+
+    fn log_access<const N: usize>(
+        &mut self,
+        kind: MemoryAccessKind,
+        address_space: u32,
+        pointer: u32,
+        data: &[F; N],
+    ) {
+        if let Some(log) = self.access_log.as_mut() {
+            log.push(MemoryAccess {
+                kind,
+                address_space,
+                pointer,
+                data: data.iter().map(|x| x.as_canonical_u32()).collect(),
+            });
+        }
+    }
+
     pub fn memory_bridge(&self) -> MemoryBridge {
         MemoryBridge::new(
             self.memory_bus,
@@ -368,7 +418,17 @@ impl<F: PrimeField32> MemoryController<F> {
             };
         }
 
+        if self.pure_execution {
+            return self.memory.read_untracked(address_space_u32, ptr_u32);
+        }
+
         let (record, adapter_records) = self.memory.read::<N>(address_space_u32, ptr_u32);
+        self.log_access(
+            MemoryAccessKind::Read,
+            address_space_u32,
+            ptr_u32,
+            &record.data,
+        );
         for record in adapter_records {
             self.access_adapters.add_record(record);
         }
@@ -415,7 +475,19 @@ impl<F: PrimeField32> MemoryController<F> {
             "memory out of bounds: {ptr_u32:?}",
         );
 
+        if self.pure_execution {
+            return self
+                .memory
+                .write_untracked(address_space_u32, ptr_u32, data);
+        }
+
         let (record, adapter_records) = self.memory.write(address_space_u32, ptr_u32, data);
+        self.log_access(
+            MemoryAccessKind::Write,
+            address_space_u32,
+            ptr_u32,
+            &record.data,
+        );
         for record in adapter_records {
             self.access_adapters.add_record(record);
         }
@@ -454,6 +526,20 @@ impl<F: PrimeField32> MemoryController<F> {
         self.memory.timestamp()
     }
 
+    /// Restarts the timestamp as for a new segment. Only allowed in pure execution.
+    pub fn reset_timestamp(&mut self) {
+        assert!(
+            self.pure_execution,
+            "Cannot reset timestamp of tracked memory"
+        );
+        self.memory.reset_timestamp();
+    }
+
+    /// Returns the current memory values, without finalizing memory.
+    pub fn memory_image(&self) -> Equipartition<F, CHUNK> {
+        self.memory.values_equipartition::<CHUNK>()
+    }
+
     /// Returns the final memory state if persistent.
     pub fn finalize(
         &mut self,
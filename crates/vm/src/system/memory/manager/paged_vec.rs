@@ -0,0 +1,230 @@
+use std::{array, mem};
+
+use openvm_instructions::riscv::RV32_MEMORY_AS;
+use rustc_hash::FxHashMap;
+
+/// Number of cells in a page.
+pub const PAGE_SIZE: usize = 1 << 12;
+
+/// Cells of a single address space, stored in pages of up to [PAGE_SIZE] cells. A page is
+/// allocated the first time one of its cells is accessed mutably and only grows up to the
+/// highest cell accessed mutably, so an address space with a few scattered accesses does not
+/// allocate whole pages. All cells that were never allocated are `T::default()`.
+#[derive(Clone, Debug)]
+pub enum PagedVec<T> {
+    /// Page table indexed by page number, for address spaces that are accessed densely.
+    Dense(Vec<Option<Vec<T>>>),
+    /// Page table keyed by page number, for address spaces with few scattered accesses.
+    Sparse(FxHashMap<u32, Vec<T>>),
+}
+
+impl<T: Copy + Default> PagedVec<T> {
+    pub fn dense() -> Self {
+        Self::Dense(Vec::new())
+    }
+
+    pub fn sparse() -> Self {
+        Self::Sparse(FxHashMap::default())
+    }
+
+    pub fn get(&self, ptr: u32) -> T {
+        let (page_idx, offset) = split_pointer(ptr);
+        self.page(page_idx)
+            .and_then(|page| page.get(offset).copied())
+            .unwrap_or_default()
+    }
+
+    /// Returns a mutable reference to the cell, allocating its page if needed.
+    pub fn get_mut(&mut self, ptr: u32) -> &mut T {
+        let (page_idx, offset) = split_pointer(ptr);
+        &mut self.page_mut(page_idx, offset + 1)[offset]
+    }
+
+    /// Sets the cell and returns its previous value.
+    pub fn set(&mut self, ptr: u32, value: T) -> T {
+        mem::replace(self.get_mut(ptr), value)
+    }
+
+    pub fn range_array<const N: usize>(&self, ptr: u32) -> [T; N] {
+        let (page_idx, offset) = split_pointer(ptr);
+        if offset + N > PAGE_SIZE {
+            return array::from_fn(|i| self.get(ptr + i as u32));
+        }
+        match self.page(page_idx) {
+            Some(page) if offset + N <= page.len() => array::from_fn(|i| page[offset + i]),
+            Some(page) => array::from_fn(|i| page.get(offset + i).copied().unwrap_or_default()),
+            None => [T::default(); N],
+        }
+    }
+
+    /// Sets `N` consecutive cells and returns their previous values.
+    pub fn set_range<const N: usize>(&mut self, ptr: u32, values: [T; N]) -> [T; N] {
+        let (page_idx, offset) = split_pointer(ptr);
+        if offset + N > PAGE_SIZE {
+            return array::from_fn(|i| self.set(ptr + i as u32, values[i]));
+        }
+        let page = &mut self.page_mut(page_idx, offset + N)[offset..offset + N];
+        array::from_fn(|i| mem::replace(&mut page[i], values[i]))
+    }
+
+    /// Iterates over all allocated cells, in increasing order of pointers.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, T)> + '_ {
+        let mut pages: Vec<(u32, &[T])> = match self {
+            Self::Dense(pages) => pages
+                .iter()
+                .enumerate()
+                .filter_map(|(page_idx, page)| Some((page_idx as u32, page.as_deref()?)))
+                .collect(),
+            Self::Sparse(pages) => pages
+                .iter()
+                .map(|(&page_idx, page)| (page_idx, &page[..]))
+                .collect(),
+        };
+        pages.sort_unstable_by_key(|&(page_idx, _)| page_idx);
+        pages.into_iter().flat_map(|(page_idx, page)| {
+            let start = page_idx * PAGE_SIZE as u32;
+            page.iter()
+                .enumerate()
+                .map(move |(offset, &value)| (start + offset as u32, value))
+        })
+    }
+
+    fn page(&self, page_idx: u32) -> Option<&[T]> {
+        match self {
+            Self::Dense(pages) => pages.get(page_idx as usize)?.as_deref(),
+            Self::Sparse(pages) => pages.get(&page_idx).map(|page| &page[..]),
+        }
+    }
+
+    /// Returns the page, allocated to at least `len` cells.
+    fn page_mut(&mut self, page_idx: u32, len: usize) -> &mut [T] {
+        let page = match self {
+            Self::Dense(pages) => {
+                let page_idx = page_idx as usize;
+                if page_idx >= pages.len() {
+                    pages.resize(page_idx + 1, None);
+                }
+                pages[page_idx].get_or_insert_with(Vec::new)
+            }
+            Self::Sparse(pages) => pages.entry(page_idx).or_default(),
+        };
+        if page.len() < len {
+            // Grow geometrically, but never beyond the page.
+            let capacity = len.max(2 * page.capacity()).min(PAGE_SIZE);
+            page.reserve_exact(capacity - page.len());
+            page.resize(len, T::default());
+        }
+        page
+    }
+}
+
+/// Cells of all address spaces. The RV32 register and memory address spaces are dense, so they
+/// use [PagedVec::Dense]; all other address spaces use [PagedVec::Sparse].
+#[derive(Clone, Debug)]
+pub struct AddressMap<T> {
+    /// Indexed by address space.
+    spaces: Vec<PagedVec<T>>,
+}
+
+impl<T> Default for AddressMap<T> {
+    fn default() -> Self {
+        Self { spaces: Vec::new() }
+    }
+}
+
+impl<T: Copy + Default> AddressMap<T> {
+    pub fn get(&self, address_space: u32, ptr: u32) -> T {
+        self.spaces
+            .get(address_space as usize)
+            .map_or_else(T::default, |space| space.get(ptr))
+    }
+
+    pub fn get_mut(&mut self, address_space: u32, ptr: u32) -> &mut T {
+        self.space_mut(address_space).get_mut(ptr)
+    }
+
+    pub fn set(&mut self, address_space: u32, ptr: u32, value: T) -> T {
+        self.space_mut(address_space).set(ptr, value)
+    }
+
+    pub fn range_array<const N: usize>(&self, address_space: u32, ptr: u32) -> [T; N] {
+        self.spaces
+            .get(address_space as usize)
+            .map_or_else(|| [T::default(); N], |space| space.range_array(ptr))
+    }
+
+    pub fn set_range<const N: usize>(
+        &mut self,
+        address_space: u32,
+        ptr: u32,
+        values: [T; N],
+    ) -> [T; N] {
+        self.space_mut(address_space).set_range(ptr, values)
+    }
+
+    /// Iterates over `(address_space, pointer, value)` for all cells of allocated pages, in
+    /// increasing order of addresses.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, u32, T)> + '_ {
+        self.spaces
+            .iter()
+            .enumerate()
+            .flat_map(|(address_space, space)| {
+                space
+                    .iter()
+                    .map(move |(ptr, value)| (address_space as u32, ptr, value))
+            })
+    }
+
+    fn space_mut(&mut self, address_space: u32) -> &mut PagedVec<T> {
+        while self.spaces.len() <= address_space as usize {
+            let space = if self.spaces.len() as u32 <= RV32_MEMORY_AS {
+                PagedVec::dense()
+            } else {
+                PagedVec::sparse()
+            };
+            self.spaces.push(space);
+        }
+        &mut self.spaces[address_space as usize]
+    }
+}
+
+fn split_pointer(ptr: u32) -> (u32, usize) {
+    (ptr / PAGE_SIZE as u32, ptr as usize % PAGE_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AddressMap, PagedVec, PAGE_SIZE};
+
+    #[test]
+    fn test_range_across_pages() {
+        for mut paged in [PagedVec::<u32>::dense(), PagedVec::<u32>::sparse()] {
+            let ptr = PAGE_SIZE as u32 - 2;
+            assert_eq!(paged.set_range(ptr, [1, 2, 3, 4]), [0; 4]);
+            assert_eq!(paged.range_array::<4>(ptr), [1, 2, 3, 4]);
+            assert_eq!(paged.set_range(ptr + 1, [5, 6]), [2, 3]);
+            assert_eq!(paged.range_array::<8>(ptr - 2), [0, 0, 1, 5, 6, 4, 0, 0]);
+            assert_eq!(paged.get(10 * PAGE_SIZE as u32), 0);
+            // Reads past the allocated end of a page.
+            let ptr = 3 * PAGE_SIZE as u32;
+            paged.set(ptr + 1, 7);
+            assert_eq!(paged.range_array::<4>(ptr), [0, 7, 0, 0]);
+            assert_eq!(paged.get(ptr + 100), 0);
+        }
+    }
+
+    #[test]
+    fn test_iter_order() {
+        let mut map = AddressMap::<u32>::default();
+        map.set(4, 5 * PAGE_SIZE as u32, 3);
+        map.set(4, 1, 2);
+        map.set(2, 7, 1);
+        let nonzero: Vec<_> = map.iter().filter(|&(_, _, value)| value != 0).collect();
+        assert_eq!(
+            nonzero,
+            vec![(2, 7, 1), (4, 1, 2), (4, 5 * PAGE_SIZE as u32, 3)]
+        );
+        // Pages are only allocated up to the highest cell written to.
+        assert_eq!(map.iter().count(), 8 + 2 + 1);
+    }
+}
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use openvm_stark_backend::{p3_field::PrimeField32, p3_util::log2_strict_usize};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use crate::{
     arch::hasher::Hasher,
@@ -10,6 +11,18 @@ use crate::{
 
 pub const PUBLIC_VALUES_ADDRESS_SPACE_OFFSET: u32 = 2;
 
+#[derive(Error, Debug)]
+pub enum UserPublicValuesProofError {
+    #[error("number of public values {0} is not a power of two * CHUNK")]
+    UnexpectedLength(usize),
+    #[error("incorrect proof length (expected: {expected}, actual: {actual})")]
+    IncorrectProofLength { expected: usize, actual: usize },
+    #[error("public values do not match the public values commit")]
+    UserPublicValuesCommitMismatch,
+    #[error("public values commit does not open to the final memory root")]
+    FinalMemoryRootMismatch,
+}
+
 /// Merkle proof for user public values in the memory state.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(bound(
@@ -54,6 +67,50 @@ impl<const CHUNK: usize, F: PrimeField32> UserPublicValuesProof<CHUNK, F> {
             public_values_commit,
         }
     }
+
+    /// Verifies that the public values are committed by `public_values_commit` and that
+    /// `public_values_commit` is in the memory state with root `final_memory_root`.
+    /// This verification must be consistent with the leaf VM verifier.
+    pub fn verify(
+        &self,
+        hasher: &impl Hasher<CHUNK, F>,
+        memory_dimensions: MemoryDimensions,
+        final_memory_root: [F; CHUNK],
+    ) -> Result<(), UserPublicValuesProofError> {
+        let num_public_values = self.public_values.len();
+        if num_public_values % CHUNK != 0 || !(num_public_values / CHUNK).is_power_of_two() {
+            return Err(UserPublicValuesProofError::UnexpectedLength(
+                num_public_values,
+            ));
+        }
+        let pv_height = log2_strict_usize(num_public_values / CHUNK);
+        let proof_len = memory_dimensions.overall_height() - pv_height;
+        if self.proof.len() != proof_len {
+            return Err(UserPublicValuesProofError::IncorrectProofLength {
+                expected: proof_len,
+                actual: self.proof.len(),
+            });
+        }
+        if hasher.merkle_root(&self.public_values) != self.public_values_commit {
+            return Err(UserPublicValuesProofError::UserPublicValuesCommitMismatch);
+        }
+        // The path to the public values root is fixed by the memory dimensions, so the direction
+        // bits in `proof` are not trusted.
+        let pv_as = PUBLIC_VALUES_ADDRESS_SPACE_OFFSET + memory_dimensions.as_offset;
+        let idx_prefix = memory_dimensions.label_to_index((pv_as, 0)) >> pv_height;
+        let mut curr_root = self.public_values_commit;
+        for (i, (_, sibling_hash)) in self.proof.iter().enumerate() {
+            curr_root = if idx_prefix & (1 << i) != 0 {
+                hasher.compress(sibling_hash, &curr_root)
+            } else {
+                hasher.compress(&curr_root, sibling_hash)
+            }
+        }
+        if curr_root != final_memory_root {
+            return Err(UserPublicValuesProofError::FinalMemoryRootMismatch);
+        }
+        Ok(())
+    }
 }
 
 fn compute_merkle_proof_to_user_public_values_root<const CHUNK: usize, F: PrimeField32>(
@@ -184,5 +241,14 @@ mod tests {
             }
         }
         assert_eq!(curr_root, final_memory_root.hash());
+        pv_proof
+            .verify(&hasher, memory_dimensions, final_memory_root.hash())
+            .unwrap();
+
+        let mut bad_proof = pv_proof.clone();
+        bad_proof.public_values[0] = F::ONE;
+        assert!(bad_proof
+            .verify(&hasher, memory_dimensions, final_memory_root.hash())
+            .is_err());
     }
 }
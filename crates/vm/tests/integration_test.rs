@@ -1,12 +1,14 @@
-use std::{collections::BTreeMap, sync::Arc};
+use std::{collections::BTreeMap, ops::ControlFlow, sync::Arc};
 
 use derive_more::derive::From;
 use openvm_circuit::{
     arch::{
         hasher::{poseidon2::vm_poseidon2_hasher, Hasher},
-        ChipId, ExitCode, MemoryConfig, SingleSegmentVmExecutor, SystemConfig, SystemExecutor,
-        SystemPeriphery, SystemTraceHeights, VirtualMachine, VmChipComplex, VmComplexTraceHeights,
-        VmConfig, VmInventoryError, VmInventoryTraceHeights,
+        ChipId, ExecutionError, ExecutionHook, ExecutionSegment, ExecutionStep, ExitCode,
+        InstructionContext, MemoryAccess, MemoryAccessKind, MemoryConfig, SegmentationConfig,
+        SingleSegmentVmExecutor, SystemConfig, SystemExecutor, SystemPeriphery, SystemTraceHeights,
+        TotalCellsSegmentation, VirtualMachine, VmChipComplex, VmComplexTraceHeights, VmConfig,
+        VmExecutor, VmInventoryError, VmInventoryTraceHeights,
     },
     derive::{AnyEnum, InstructionExecutor, VmConfig},
     system::{
@@ -44,6 +46,7 @@ use openvm_stark_backend::{
     config::StarkGenericConfig,
     engine::StarkEngine,
     p3_field::{AbstractField, PrimeField32},
+    prover::types::ProofInput,
 };
 use openvm_stark_sdk::{
     config::{
@@ -503,6 +506,364 @@ fn test_vm_continuations() {
     assert_eq!(pv_proof.public_values[0], expected_output);
 }
 
+#[test]
+fn test_vm_execute_exit_code() {
+    let program = Program::from_instructions(&[Instruction::from_isize(
+        VmOpcode::with_default_offset(TERMINATE),
+        0,
+        0,
+        ExitCode::Error as isize,
+        0,
+        0,
+    )]);
+    let config = NativeConfig {
+        system: SystemConfig::new(3, MemoryConfig::default(), 0),
+        native: Default::default(),
+    };
+    let executor = VmExecutor::<BabyBear, _>::new(config);
+    let result = executor.execute(program, vec![]);
+    assert!(matches!(
+        result,
+        Err(ExecutionError::FailedWithExitCode { exit_code }) if exit_code == ExitCode::Error as u32
+    ));
+}
+
+#[test]
+fn test_vm_execute_segment_without_continuations() {
+    let n = 1000;
+    let program = Program::from_instructions(&[
+        Instruction::from_isize(VmOpcode::with_default_offset(STOREW), n, 0, 0, 0, 1),
+        Instruction::large_from_isize(VmOpcode::with_default_offset(SUB), 0, 0, 1, 1, 1, 0, 0),
+        Instruction::from_isize(
+            VmOpcode::with_default_offset(NativeBranchEqualOpcode(BNE)),
+            0,
+            0,
+            -(DEFAULT_PC_STEP as isize),
+            1,
+            0,
+        ),
+        Instruction::from_isize(VmOpcode::with_default_offset(TERMINATE), 0, 0, 0, 0, 0),
+    ]);
+    let config = NativeConfig {
+        system: SystemConfig::new(3, MemoryConfig::default(), 0).with_max_segment_len(100),
+        native: Default::default(),
+    };
+    let executor = VmExecutor::<BabyBear, _>::new(config);
+    let result = executor.execute(program, vec![]);
+    assert!(matches!(
+        result,
+        Err(ExecutionError::ContinuationsDisabled { .. })
+    ));
+}
+
+/// Fibonacci as in `test_vm_continuations`, writing F_n to [0]_3.
+fn fibonacci_program(n: isize) -> Program<BabyBear> {
+    Program::from_instructions(&[
+        Instruction::from_isize(VmOpcode::with_default_offset(ADD), 0, 0, 0, 1, 0),
+        Instruction::from_isize(VmOpcode::with_default_offset(ADD), 1, 0, 0, 1, 0),
+        Instruction::from_isize(VmOpcode::with_default_offset(ADD), 2, 0, 1, 1, 0),
+        Instruction::large_from_isize(VmOpcode::with_default_offset(ADD), 3, 1, 2, 1, 1, 1, 0),
+        Instruction::large_from_isize(VmOpcode::with_default_offset(ADD), 1, 2, 0, 1, 1, 0, 0),
+        Instruction::large_from_isize(VmOpcode::with_default_offset(ADD), 2, 3, 0, 1, 1, 0, 0),
+        Instruction::large_from_isize(VmOpcode::with_default_offset(ADD), 0, 0, 1, 1, 1, 0, 0),
+        Instruction::from_isize(
+            VmOpcode::with_default_offset(NativeBranchEqualOpcode(BNE)),
+            n,
+            0,
+            -4 * DEFAULT_PC_STEP as isize,
+            0,
+            1,
+        ),
+        Instruction::from_isize(VmOpcode::with_default_offset(ADD), 0, 1, 0, 3, 1),
+        Instruction::from_isize(
+            VmOpcode::with_default_offset(TERMINATE),
+            0,
+            0,
+            ExitCode::Success as isize,
+            0,
+            0,
+        ),
+    ])
+}
+
+#[test]
+fn test_vm_execute_from_checkpoint() {
+    let program = fibonacci_program(1000);
+    let config = NativeConfig {
+        system: SystemConfig::new(3, MemoryConfig::default(), 0).with_max_segment_len(200),
+        native: Default::default(),
+    }
+    .with_continuations();
+    let executor = VmExecutor::<BabyBear, _>::new(config);
+
+    let segments = executor.execute_segments(program.clone(), vec![]).unwrap();
+    let checkpoints = executor
+        .execute_checkpoints(program.clone(), vec![])
+        .unwrap();
+    assert!(segments.len() > 2);
+    assert_eq!(checkpoints.len(), segments.len());
+
+    for (segment, checkpoint) in segments.iter().zip(checkpoints) {
+        let segment_idx = checkpoint.segment_idx;
+        let resumed = executor
+            .execute_from_checkpoint(program.clone(), checkpoint)
+            .unwrap();
+        assert_eq!(
+            resumed.chip_complex.current_trace_heights(),
+            segment.chip_complex.current_trace_heights(),
+            "segment {segment_idx}"
+        );
+        let boundaries = |segment: &ExecutionSegment<BabyBear, NativeConfig>| {
+            segment
+                .chip_complex
+                .connector_chip()
+                .boundary_states
+                .map(|state| state.map(|state| (state.pc, state.timestamp)))
+        };
+        assert_eq!(
+            boundaries(&resumed),
+            boundaries(segment),
+            "segment {segment_idx}"
+        );
+        assert_eq!(resumed.final_memory, segment.final_memory);
+    }
+}
+
+#[test]
+fn test_vm_execute_and_generate_streaming() {
+    let program = fibonacci_program(1000);
+    let config = NativeConfig {
+        system: SystemConfig::new(3, MemoryConfig::default(), 0).with_max_segment_len(200),
+        native: Default::default(),
+    }
+    .with_continuations();
+    let e = BabyBearPoseidon2Engine::new(standard_fri_params_with_100_bits_conjectured_security(1));
+    let committed_exe = Arc::new(VmCommittedExe::<BabyBearPoseidon2Config>::commit(
+        program.into(),
+        e.config().pcs(),
+    ));
+    let executor = VmExecutor::<BabyBear, _>::new(config);
+
+    let result = executor
+        .execute_and_generate_with_cached_program(committed_exe.clone(), vec![])
+        .unwrap();
+    assert!(result.per_segment.len() > 2);
+
+    let mut per_segment = vec![];
+    let final_memory = executor
+        .execute_and_generate_with_cached_program_streaming(
+            committed_exe,
+            vec![],
+            |segment_idx, proof_input| {
+                assert_eq!(segment_idx, per_segment.len());
+                per_segment.push(proof_input);
+            },
+        )
+        .unwrap();
+    assert_eq!(final_memory, result.final_memory);
+    assert_eq!(per_segment.len(), result.per_segment.len());
+    let air_heights = |proof_input: &ProofInput<BabyBearPoseidon2Config>| {
+        proof_input
+            .per_air
+            .iter()
+            .map(|(air_id, api)| (*air_id, api.main_trace_height()))
+            .collect::<Vec<_>>()
+    };
+    for (streamed, collected) in per_segment.iter().zip(&result.per_segment) {
+        assert_eq!(air_heights(streamed), air_heights(collected));
+    }
+}
+
+#[test]
+fn test_vm_segmentation_strategy() {
+    let program = fibonacci_program(1000);
+    let config = NativeConfig {
+        system: SystemConfig::new(3, MemoryConfig::default(), 0),
+        native: Default::default(),
+    }
+    .with_continuations();
+    let segments = VmExecutor::<BabyBear, _>::new(config.clone())
+        .execute_segments(program.clone(), vec![])
+        .unwrap();
+    assert_eq!(segments.len(), 1);
+    let max_cells = segments[0].current_trace_cells().into_iter().sum::<usize>() / 2;
+
+    let strategy = TotalCellsSegmentation { max_cells };
+    let mut builtin_config = config.clone();
+    builtin_config.system = builtin_config
+        .system
+        .with_segmentation(SegmentationConfig::TotalCells(strategy));
+    let mut custom_config = config;
+    custom_config.system = custom_config.system.with_segmentation_strategy(strategy);
+    for config in [builtin_config, custom_config] {
+        let segments = VmExecutor::<BabyBear, _>::new(config)
+            .execute_segments(program.clone(), vec![])
+            .unwrap();
+        assert!(segments.len() > 1);
+        // Segments are cut within `SEGMENT_CHECK_INTERVAL` instructions of exceeding the budget.
+        for segment in &segments[..segments.len() - 1] {
+            let total_cells: usize = segment.current_trace_cells().into_iter().sum();
+            assert!(total_cells > max_cells);
+        }
+    }
+}
+
+#[test]
+fn test_vm_execute_pure() {
+    let n = 1000;
+    let program = fibonacci_program(n);
+    let config = NativeConfig {
+        system: SystemConfig::new(3, MemoryConfig::default(), 8).with_max_segment_len(100),
+        native: Default::default(),
+    }
+    .with_continuations();
+    let executor = VmExecutor::<BabyBear, _>::new(config);
+
+    let result = executor.execute_pure(program.clone(), vec![]).unwrap();
+    assert_eq!(result.exit_code, ExitCode::Success as u32);
+    assert_eq!(result.instret, 3 + 5 * n as u64 + 2);
+    assert_eq!(result.public_values.len(), 8);
+    let expected_output = {
+        let mut a = 0;
+        let mut b = 1;
+        for _ in 0..n {
+            (a, b) = (b, a + b);
+            b %= BabyBear::ORDER_U32;
+        }
+        BabyBear::from_canonical_u32(a)
+    };
+    assert_eq!(result.public_values[0], expected_output);
+
+    // Segments are contiguous, and a segment is only cut once an executor is over the limit.
+    assert!(result.segments.len() > 1);
+    assert_eq!(result.segments[0].start_pc, 0);
+    for pair in result.segments.windows(2) {
+        assert_eq!(
+            pair[1].start_instret,
+            pair[0].start_instret + pair[0].num_instructions
+        );
+        assert!(pair[0].num_instructions > 100);
+    }
+
+    // Final memory agrees with the memory that continuations commit to, up to zero blocks.
+    let final_memory = executor.execute(program, vec![]).unwrap().unwrap();
+    let nonzero_final_memory: BTreeMap<_, _> = final_memory
+        .into_iter()
+        .filter(|(_, block)| block.iter().any(|x| *x != BabyBear::ZERO))
+        .collect();
+    assert_eq!(result.final_memory, nonzero_final_memory);
+}
+
+#[test]
+fn test_vm_execute_pure_exit_code() {
+    let program = Program::from_instructions(&[Instruction::from_isize(
+        VmOpcode::with_default_offset(TERMINATE),
+        0,
+        0,
+        ExitCode::Error as isize,
+        0,
+        0,
+    )]);
+    let config = NativeConfig {
+        system: SystemConfig::new(3, MemoryConfig::default(), 0),
+        native: Default::default(),
+    };
+    let executor = VmExecutor::<BabyBear, _>::new(config);
+    let result = executor.execute_pure(program, vec![]).unwrap();
+    assert_eq!(result.exit_code, ExitCode::Error as u32);
+    assert_eq!(result.instret, 1);
+}
+
+#[test]
+fn test_vm_execute_with_hook() {
+    #[derive(Default)]
+    struct RecordingHook {
+        visited_pcs: Vec<u32>,
+        steps: Vec<ExecutionStep>,
+    }
+
+    impl ExecutionHook<BabyBear> for RecordingHook {
+        fn before_instruction(
+            &mut self,
+            ctx: &InstructionContext<'_, BabyBear>,
+        ) -> ControlFlow<()> {
+            self.visited_pcs.push(ctx.pc);
+            ControlFlow::Continue(())
+        }
+
+        fn after_instruction(&mut self, step: ExecutionStep) {
+            self.steps.push(step);
+        }
+    }
+
+    let program = Program::from_instructions(&[
+        // word[0]_1 <- word[7]_0
+        Instruction::from_isize(VmOpcode::with_default_offset(STOREW), 7, 0, 0, 0, 1),
+        Instruction::from_isize(VmOpcode::with_default_offset(TERMINATE), 0, 0, 0, 0, 0),
+    ]);
+    let config = NativeConfig {
+        system: SystemConfig::new(3, MemoryConfig::default(), 0),
+        native: Default::default(),
+    };
+    let executor = VmExecutor::<BabyBear, _>::new(config);
+    let mut hook = RecordingHook::default();
+    executor
+        .execute_with_hook(program, vec![], &mut hook)
+        .unwrap();
+
+    assert_eq!(hook.visited_pcs, vec![0, DEFAULT_PC_STEP]);
+    assert_eq!(hook.steps.len(), 2);
+    let store = &hook.steps[0];
+    assert_eq!(store.segment_idx, 0);
+    assert_eq!(store.pc, 0);
+    assert_eq!(store.operands, [7, 0, 0, 0, 1, 0, 0]);
+    assert!(store.accesses.contains(&MemoryAccess {
+        kind: MemoryAccessKind::Write,
+        address_space: 1,
+        pointer: 0,
+        data: vec![7],
+    }));
+    let terminate = &hook.steps[1];
+    assert_eq!(terminate.pc, DEFAULT_PC_STEP);
+    assert!(terminate.timestamp > store.timestamp);
+    assert!(terminate.accesses.is_empty());
+}
+
+#[test]
+fn test_vm_execute_with_aborting_hook() {
+    struct AbortAt(u32);
+
+    impl ExecutionHook<BabyBear> for AbortAt {
+        fn before_instruction(
+            &mut self,
+            ctx: &InstructionContext<'_, BabyBear>,
+        ) -> ControlFlow<()> {
+            if ctx.pc == self.0 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }
+    }
+
+    let program = Program::from_instructions(&[
+        Instruction::from_isize(VmOpcode::with_default_offset(STOREW), 7, 0, 0, 0, 1),
+        Instruction::from_isize(VmOpcode::with_default_offset(TERMINATE), 0, 0, 0, 0, 0),
+    ]);
+    let config = NativeConfig {
+        system: SystemConfig::new(3, MemoryConfig::default(), 0),
+        native: Default::default(),
+    };
+    let executor = VmExecutor::<BabyBear, _>::new(config);
+    let result = executor.execute_with_hook(program, vec![], &mut AbortAt(DEFAULT_PC_STEP));
+    assert!(matches!(
+        result,
+        Err(ExecutionError::Aborted {
+            pc: DEFAULT_PC_STEP
+        })
+    ));
+}
+
 #[test]
 fn test_vm_without_field_arithmetic() {
     /*
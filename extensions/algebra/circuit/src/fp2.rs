@@ -1,27 +1,48 @@
 use std::{cell::RefCell, rc::Rc};
 
+use num_bigint_dig::BigUint;
+use num_traits::FromPrimitive;
 use openvm_mod_circuit_builder::{ExprBuilder, FieldVariable, SymbolicExpr};
 
-/// Quadratic field extension of `Fp` defined by `Fp2 = Fp[u]/(1 + u^2)`. Assumes that `-1` is not a quadratic residue in `Fp`, which is equivalent to `p` being congruent to `3 (mod 4)`.
+/// Quadratic field extension of `Fp` defined by `Fp2 = Fp[u]/(u^2 - non_residue)`, where
+/// `non_residue` is a small integer that is not a quadratic residue in `Fp`. [Fp2::new] uses
+/// `non_residue = -1`, which is a non-residue if and only if `p` is congruent to `3 (mod 4)`.
 #[derive(Clone)]
 pub struct Fp2 {
     pub c0: FieldVariable,
     pub c1: FieldVariable,
+    pub non_residue: isize,
 }
 
 impl Fp2 {
     pub fn new(builder: Rc<RefCell<ExprBuilder>>) -> Self {
+        Self::new_with_non_residue(builder, -1)
+    }
+
+    pub fn new_with_non_residue(builder: Rc<RefCell<ExprBuilder>>, non_residue: isize) -> Self {
         let c0 = ExprBuilder::new_input(builder.clone());
         let c1 = ExprBuilder::new_input(builder.clone());
-        Fp2 { c0, c1 }
+        Fp2 {
+            c0,
+            c1,
+            non_residue,
+        }
     }
 
     pub fn new_var(builder: Rc<RefCell<ExprBuilder>>) -> ((usize, usize), Fp2) {
+        Self::new_var_with_non_residue(builder, -1)
+    }
+
+    pub fn new_var_with_non_residue(
+        builder: Rc<RefCell<ExprBuilder>>,
+        non_residue: isize,
+    ) -> ((usize, usize), Fp2) {
         let (c0_idx, c0) = builder.borrow_mut().new_var();
         let (c1_idx, c1) = builder.borrow_mut().new_var();
         let fp2 = Fp2 {
             c0: FieldVariable::from_var(builder.clone(), c0),
             c1: FieldVariable::from_var(builder.clone(), c1),
+            non_residue,
         };
         ((c0_idx, c1_idx), fp2)
     }
@@ -38,37 +59,42 @@ impl Fp2 {
     }
 
     pub fn add(&mut self, other: &mut Fp2) -> Fp2 {
-        Fp2 {
-            c0: &mut self.c0 + &mut other.c0,
-            c1: &mut self.c1 + &mut other.c1,
-        }
+        let c0 = &mut self.c0 + &mut other.c0;
+        let c1 = &mut self.c1 + &mut other.c1;
+        self.with_coeffs(c0, c1)
     }
 
     pub fn sub(&mut self, other: &mut Fp2) -> Fp2 {
-        Fp2 {
-            c0: &mut self.c0 - &mut other.c0,
-            c1: &mut self.c1 - &mut other.c1,
-        }
+        let c0 = &mut self.c0 - &mut other.c0;
+        let c1 = &mut self.c1 - &mut other.c1;
+        self.with_coeffs(c0, c1)
     }
 
     pub fn mul(&mut self, other: &mut Fp2) -> Fp2 {
-        let c0 = &mut self.c0 * &mut other.c0 - &mut self.c1 * &mut other.c1;
+        debug_assert_eq!(self.non_residue, other.non_residue);
+        let c0 = add_non_residue_mul(
+            self.non_residue,
+            &mut self.c0 * &mut other.c0,
+            &mut self.c1 * &mut other.c1,
+        );
         let c1 = &mut self.c0 * &mut other.c1 + &mut self.c1 * &mut other.c0;
-        Fp2 { c0, c1 }
+        self.with_coeffs(c0, c1)
     }
 
     pub fn square(&mut self) -> Fp2 {
-        let c0 = self.c0.square() - self.c1.square();
+        let c0 = add_non_residue_mul(self.non_residue, self.c0.square(), self.c1.square());
         let c1 = (&mut self.c0 * &mut self.c1).int_mul(2);
-        Fp2 { c0, c1 }
+        self.with_coeffs(c0, c1)
     }
 
     pub fn div(&mut self, other: &mut Fp2) -> Fp2 {
+        debug_assert_eq!(self.non_residue, other.non_residue);
         let builder = self.c0.builder.borrow();
         let prime = builder.prime.clone();
         let limb_bits = builder.limb_bits;
         let num_limbs = builder.num_limbs;
         drop(builder);
+        let non_residue = self.non_residue;
 
         // These are dummy variables, will be replaced later so the index within it doesn't matter.
         // We use these to check if we need to save self/other first.
@@ -78,22 +104,33 @@ impl Fp2 {
         // Compute should not be affected by whether auto save is triggered.
         // So we must do compute first.
         // Compute z0
-        let compute_denom = &other.c0.expr * &other.c0.expr + &other.c1.expr * &other.c1.expr;
-        let compute_z0_nom = &self.c0.expr * &other.c0.expr + &self.c1.expr * &other.c1.expr;
+        let compute_denom = sub_non_residue_mul(
+            non_residue,
+            &other.c0.expr * &other.c0.expr,
+            &other.c1.expr * &other.c1.expr,
+        );
+        let compute_z0_nom = sub_non_residue_mul(
+            non_residue,
+            &self.c0.expr * &other.c0.expr,
+            &self.c1.expr * &other.c1.expr,
+        );
         let compute_z0 = &compute_z0_nom / &compute_denom;
         // Compute z1
         let compute_z1_nom = &self.c1.expr * &other.c0.expr - &self.c0.expr * &other.c1.expr;
         let compute_z1 = &compute_z1_nom / &compute_denom;
 
-        // Constraint 1: x0 = y0*z0 - y1*z1
-        let constraint1 = &self.c0.expr - &other.c0.expr * &fake_z0 + &other.c1.expr * &fake_z1;
-        let carry_bits = constraint1.constraint_carry_bits_with_pq(&prime, limb_bits, num_limbs);
+        // Constraint 1: x0 = y0*z0 + non_residue*y1*z1
+        let constraint1 = |x: &Fp2, y: &Fp2, z0: &SymbolicExpr, z1: &SymbolicExpr| {
+            sub_non_residue_mul(non_residue, &x.c0.expr - &y.c0.expr * z0, &y.c1.expr * z1)
+        };
+        let carry_bits = constraint1(self, other, &fake_z0, &fake_z1)
+            .constraint_carry_bits_with_pq(&prime, limb_bits, num_limbs);
         if carry_bits > self.c0.range_checker_bits {
             // TODO: should save the "bigger" one first (the one with higher limb_max_abs)
             self.save();
         }
-        let constraint1 = &self.c0.expr - &other.c0.expr * &fake_z0 + &other.c1.expr * &fake_z1;
-        let carry_bits = constraint1.constraint_carry_bits_with_pq(&prime, limb_bits, num_limbs);
+        let carry_bits = constraint1(self, other, &fake_z0, &fake_z1)
+            .constraint_carry_bits_with_pq(&prime, limb_bits, num_limbs);
         if carry_bits > self.c0.range_checker_bits {
             other.save();
         }
@@ -114,7 +151,7 @@ impl Fp2 {
         let mut builder = self.c0.builder.borrow_mut();
         let (z0_idx, z0) = builder.new_var();
         let (z1_idx, z1) = builder.new_var();
-        let constraint1 = &self.c0.expr - &other.c0.expr * &z0 + &other.c1.expr * &z1;
+        let constraint1 = constraint1(self, other, &z0, &z1);
         let constraint2 = &self.c1.expr - &other.c1.expr * &z0 - &other.c0.expr * &z1;
         builder.set_compute(z0_idx, compute_z0);
         builder.set_compute(z1_idx, compute_z1);
@@ -124,32 +161,30 @@ impl Fp2 {
 
         let z0_var = FieldVariable::from_var(self.c0.builder.clone(), z0);
         let z1_var = FieldVariable::from_var(self.c0.builder.clone(), z1);
-        Fp2 {
-            c0: z0_var,
-            c1: z1_var,
-        }
+        self.with_coeffs(z0_var, z1_var)
     }
 
     pub fn scalar_mul(&mut self, fp: &mut FieldVariable) -> Fp2 {
-        Fp2 {
-            c0: &mut self.c0 * fp,
-            c1: &mut self.c1 * fp,
-        }
+        let c0 = &mut self.c0 * fp;
+        let c1 = &mut self.c1 * fp;
+        self.with_coeffs(c0, c1)
     }
 
     pub fn int_add(&mut self, c: [isize; 2]) -> Fp2 {
-        Fp2 {
-            c0: self.c0.int_add(c[0]),
-            c1: self.c1.int_add(c[1]),
-        }
+        let c0 = self.c0.int_add(c[0]);
+        let c1 = self.c1.int_add(c[1]);
+        self.with_coeffs(c0, c1)
     }
 
     // c is like a Fp2, but with both c0 and c1 being very small numbers.
     pub fn int_mul(&mut self, c: [isize; 2]) -> Fp2 {
-        Fp2 {
-            c0: self.c0.int_mul(c[0]) - self.c1.int_mul(c[1]),
-            c1: self.c0.int_mul(c[1]) + self.c1.int_mul(c[0]),
-        }
+        let c0 = if self.non_residue == -1 {
+            self.c0.int_mul(c[0]) - self.c1.int_mul(c[1])
+        } else {
+            self.c0.int_mul(c[0]) + self.c1.int_mul(c[1] * self.non_residue)
+        };
+        let c1 = self.c0.int_mul(c[1]) + self.c1.int_mul(c[0]);
+        self.with_coeffs(c0, c1)
     }
 
     pub fn neg(&mut self) -> Fp2 {
@@ -157,13 +192,75 @@ impl Fp2 {
     }
 
     pub fn select(flag_id: usize, a: &Fp2, b: &Fp2) -> Fp2 {
+        debug_assert_eq!(a.non_residue, b.non_residue);
+        a.with_coeffs(
+            FieldVariable::select(flag_id, &a.c0, &b.c0),
+            FieldVariable::select(flag_id, &a.c1, &b.c1),
+        )
+    }
+
+    fn with_coeffs(&self, c0: FieldVariable, c1: FieldVariable) -> Fp2 {
         Fp2 {
-            c0: FieldVariable::select(flag_id, &a.c0, &b.c0),
-            c1: FieldVariable::select(flag_id, &a.c1, &b.c1),
+            c0,
+            c1,
+            non_residue: self.non_residue,
         }
     }
 }
 
+/// Returns whether `Fp[u]/(u^2 - non_residue)` is a field, i.e. whether `non_residue` is a
+/// quadratic non-residue mod `modulus`. By Euler's criterion this holds if and only if
+/// `non_residue^((p - 1) / 2) = -1 (mod p)`. `modulus` must be an odd prime.
+pub fn is_quadratic_non_residue(non_residue: isize, modulus: &BigUint) -> bool {
+    let abs = BigUint::from_usize(non_residue.unsigned_abs()).unwrap() % modulus;
+    let value = if non_residue < 0 {
+        (modulus - abs) % modulus
+    } else {
+        abs
+    };
+    let modulus_minus_one = modulus - 1u32;
+    value.modpow(&(&modulus_minus_one >> 1), modulus) == modulus_minus_one
+}
+
+/// Returns `lhs + non_residue * rhs`.
+fn add_non_residue_mul(
+    non_residue: isize,
+    lhs: FieldVariable,
+    mut rhs: FieldVariable,
+) -> FieldVariable {
+    if non_residue == -1 {
+        lhs - rhs
+    } else {
+        lhs + rhs.int_mul(non_residue)
+    }
+}
+
+/// Returns `lhs + non_residue * rhs`.
+pub(crate) fn add_non_residue_mul_expr(
+    non_residue: isize,
+    lhs: SymbolicExpr,
+    rhs: SymbolicExpr,
+) -> SymbolicExpr {
+    if non_residue == -1 {
+        lhs - rhs
+    } else {
+        lhs + SymbolicExpr::IntMul(Box::new(rhs), non_residue)
+    }
+}
+
+/// Returns `lhs - non_residue * rhs`.
+pub(crate) fn sub_non_residue_mul(
+    non_residue: isize,
+    lhs: SymbolicExpr,
+    rhs: SymbolicExpr,
+) -> SymbolicExpr {
+    if non_residue == -1 {
+        lhs + rhs
+    } else {
+        lhs - SymbolicExpr::IntMul(Box::new(rhs), non_residue)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use halo2curves_axiom::bn256::Fq2;
@@ -179,7 +276,7 @@ mod tests {
         p3_baby_bear::BabyBear,
     };
 
-    use super::Fp2;
+    use super::{is_quadratic_non_residue, Fp2};
 
     fn two_fp2_input(x: &Fq2, y: &Fq2) -> Vec<BigUint> {
         vec![
@@ -301,4 +398,72 @@ mod tests {
         )
         .expect("Verification failed");
     }
+
+    #[test]
+    fn test_fp2_non_residue() {
+        // Fp2 = Fp[u]/(u^2 - 3), the arithmetic is checked without requiring 3 to be a
+        // non-residue.
+        const NON_RESIDUE: isize = 3;
+        let prime = BN254_MODULUS.clone();
+        let (range_checker, builder) = setup(&prime);
+
+        let mut x_fp2 = Fp2::new_with_non_residue(builder.clone(), NON_RESIDUE);
+        let mut y_fp2 = Fp2::new_with_non_residue(builder.clone(), NON_RESIDUE);
+        let mut z_fp2 = Fp2::new_with_non_residue(builder.clone(), NON_RESIDUE);
+        let mut xy = x_fp2.mul(&mut y_fp2);
+        let _r = xy.div(&mut z_fp2);
+
+        let builder = builder.borrow().clone();
+        let air = FieldExpr::new(builder, range_checker.bus(), false);
+        let width = BaseAir::<BabyBear>::width(&air);
+
+        let inputs = [
+            bn254_fq2_random(5),
+            bn254_fq2_random(15),
+            bn254_fq2_random(95),
+        ]
+        .into_iter()
+        .flat_map(|x| [bn254_fq_to_biguint(x.c0), bn254_fq_to_biguint(x.c1)])
+        .collect::<Vec<_>>();
+        let mut row = BabyBear::zero_vec(width);
+        air.generate_subrow((&range_checker, inputs.clone(), vec![]), &mut row);
+        let FieldExprCols { vars, .. } = air.load_vars(&row);
+        let trace = RowMajorMatrix::new(row, width);
+        let range_trace = range_checker.generate_trace();
+        let r_c0 = evaluate_biguint(&vars[vars.len() - 2], LIMB_BITS);
+        let r_c1 = evaluate_biguint(&vars[vars.len() - 1], LIMB_BITS);
+
+        // r * z == x * y
+        let mul = |a: [&BigUint; 2], b: [&BigUint; 2]| {
+            let c0 = (a[0] * b[0] + BigUint::from(NON_RESIDUE as u32) * a[1] * b[1]) % &prime;
+            let c1 = (a[0] * b[1] + a[1] * b[0]) % &prime;
+            [c0, c1]
+        };
+        let xy = mul([&inputs[0], &inputs[1]], [&inputs[2], &inputs[3]]);
+        let rz = mul([&r_c0, &r_c1], [&inputs[4], &inputs[5]]);
+        assert_eq!(rz, xy);
+
+        BabyBearBlake3Engine::run_simple_test_no_pis_fast(
+            any_rap_arc_vec![air, range_checker.air],
+            vec![trace, range_trace],
+        )
+        .expect("Verification failed");
+    }
+
+    #[test]
+    fn test_is_quadratic_non_residue() {
+        // BN254 is 3 mod 4, so -1 is a non-residue.
+        let bn254 = BN254_MODULUS.clone();
+        assert!(is_quadratic_non_residue(-1, &bn254));
+        assert!(!is_quadratic_non_residue(1, &bn254));
+        assert!(!is_quadratic_non_residue(4, &bn254));
+        // BLS12-377 is 1 mod 4 and uses -5.
+        let bls12_377 = BigUint::parse_bytes(
+            b"01ae3a4617c510eac63b05c06ca1493b1a22d9f300f5138f1ef3622fba094800170b5d44300000008508c00000000001",
+            16,
+        )
+        .unwrap();
+        assert!(!is_quadratic_non_residue(-1, &bls12_377));
+        assert!(is_quadratic_non_residue(-5, &bls12_377));
+    }
 }
@@ -11,7 +11,10 @@ use openvm_mod_circuit_builder::{
 use openvm_rv32_adapters::Rv32VecHeapAdapterChip;
 use openvm_stark_backend::p3_field::PrimeField32;
 
-use crate::Fp2;
+use crate::{
+    fp2::{add_non_residue_mul_expr, sub_non_residue_mul},
+    Fp2,
+};
 
 // Input: Fp2 * 2
 // Output: Fp2
@@ -31,10 +34,14 @@ impl<F: PrimeField32, const BLOCKS: usize, const BLOCK_SIZE: usize>
         adapter: Rv32VecHeapAdapterChip<F, 2, BLOCKS, BLOCKS, BLOCK_SIZE, BLOCK_SIZE>,
         memory_controller: MemoryControllerRef<F>,
         config: ExprBuilderConfig,
+        non_residue: isize,
         offset: usize,
     ) -> Self {
-        let (expr, is_mul_flag, is_div_flag) =
-            fp2_muldiv_expr(config, memory_controller.borrow().range_checker.bus());
+        let (expr, is_mul_flag, is_div_flag) = fp2_muldiv_expr(
+            config,
+            memory_controller.borrow().range_checker.bus(),
+            non_residue,
+        );
         let core = FieldExpressionCoreChip::new(
             expr,
             offset,
@@ -52,19 +59,21 @@ impl<F: PrimeField32, const BLOCKS: usize, const BLOCK_SIZE: usize>
     }
 }
 
+/// `non_residue` is the `Fp2` non-residue: the chip computes in `Fp[u]/(u^2 - non_residue)`.
 pub fn fp2_muldiv_expr(
     config: ExprBuilderConfig,
     range_bus: VariableRangeCheckerBus,
+    non_residue: isize,
 ) -> (FieldExpr, usize, usize) {
     config.check_valid();
     let builder = ExprBuilder::new(config, range_bus.range_max_bits);
     let builder = Rc::new(RefCell::new(builder));
 
-    let x = Fp2::new(builder.clone());
-    let mut y = Fp2::new(builder.clone());
+    let x = Fp2::new_with_non_residue(builder.clone(), non_residue);
+    let mut y = Fp2::new_with_non_residue(builder.clone(), non_residue);
     let is_mul_flag = builder.borrow_mut().new_flag();
     let is_div_flag = builder.borrow_mut().new_flag();
-    let (z_idx, mut z) = Fp2::new_var(builder.clone());
+    let (z_idx, mut z) = Fp2::new_var_with_non_residue(builder.clone(), non_residue);
 
     let mut lvar = Fp2::select(is_mul_flag, &x, &z);
 
@@ -83,9 +92,21 @@ pub fn fp2_muldiv_expr(
 
     // Compute expression has to be done manually at the SymbolicExpr level.
     // Otherwise it saves the quotient and introduces new variables.
-    let compute_z0_div = (&x.c0.expr * &y.c0.expr + &x.c1.expr * &y.c1.expr)
-        / (&y.c0.expr * &y.c0.expr + &y.c1.expr * &y.c1.expr);
-    let compute_z0_mul = &x.c0.expr * &y.c0.expr - &x.c1.expr * &y.c1.expr;
+    let compute_denom = sub_non_residue_mul(
+        non_residue,
+        &y.c0.expr * &y.c0.expr,
+        &y.c1.expr * &y.c1.expr,
+    );
+    let compute_z0_div = sub_non_residue_mul(
+        non_residue,
+        &x.c0.expr * &y.c0.expr,
+        &x.c1.expr * &y.c1.expr,
+    ) / &compute_denom;
+    let compute_z0_mul = add_non_residue_mul_expr(
+        non_residue,
+        &x.c0.expr * &y.c0.expr,
+        &x.c1.expr * &y.c1.expr,
+    );
     let compute_z0 = SymbolicExpr::Select(
         is_mul_flag,
         Box::new(compute_z0_mul),
@@ -95,8 +116,7 @@ pub fn fp2_muldiv_expr(
             Box::new(x.c0.expr.clone()),
         )),
     );
-    let compute_z1_div = (&x.c1.expr * &y.c0.expr - &x.c0.expr * &y.c1.expr)
-        / (&y.c0.expr * &y.c0.expr + &y.c1.expr * &y.c1.expr);
+    let compute_z1_div = (&x.c1.expr * &y.c0.expr - &x.c0.expr * &y.c1.expr) / &compute_denom;
     let compute_z1_mul = &x.c1.expr * &y.c0.expr + &x.c0.expr * &y.c1.expr;
     let compute_z1 = SymbolicExpr::Select(
         is_mul_flag,
@@ -168,6 +188,7 @@ mod tests {
             adapter,
             tester.memory_controller(),
             config,
+            -1,
             Fp2Opcode::default_offset(),
         );
         assert_eq!(
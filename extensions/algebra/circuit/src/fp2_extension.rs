@@ -20,13 +20,35 @@ use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
 use strum::EnumCount;
 
-use crate::fp2_chip::{Fp2AddSubChip, Fp2MulDivChip};
+use crate::{
+    fp2_chip::{Fp2AddSubChip, Fp2MulDivChip},
+    is_quadratic_non_residue,
+};
 
 #[serde_as]
 #[derive(Clone, Debug, derive_new::new, Serialize, Deserialize)]
 pub struct Fp2Extension {
     #[serde_as(as = "Vec<DisplayFromStr>")]
     pub supported_modulus: Vec<BigUint>,
+    /// The non-residue `β` defining `Fp2 = Fp[u]/(u^2 - β)` for each modulus in
+    /// `supported_modulus`, in the same order. Moduli without an entry use `-1`, which is a
+    /// non-residue if and only if the modulus is 3 mod 4. It must match the `non_residue` passed
+    /// to `complex_declare!` in the guest.
+    #[serde(default)]
+    #[new(default)]
+    pub non_residues: Vec<isize>,
+}
+
+impl Fp2Extension {
+    pub fn with_non_residues(mut self, non_residues: Vec<isize>) -> Self {
+        self.non_residues = non_residues;
+        self
+    }
+
+    /// The non-residue of the `i`-th supported modulus.
+    pub fn non_residue(&self, i: usize) -> isize {
+        self.non_residues.get(i).copied().unwrap_or(-1)
+    }
 }
 
 #[derive(ChipUsageGetter, Chip, InstructionExecutor, AnyEnum, From)]
@@ -37,6 +59,12 @@ pub enum Fp2ExtensionExecutor<F: PrimeField32> {
     // 48 limbs prime
     Fp2AddSubRv32_48(Fp2AddSubChip<F, 6, 16>),
     Fp2MulDivRv32_48(Fp2MulDivChip<F, 6, 16>),
+    // 64 limbs prime
+    Fp2AddSubRv32_64(Fp2AddSubChip<F, 4, 32>),
+    Fp2MulDivRv32_64(Fp2MulDivChip<F, 4, 32>),
+    // 128 limbs prime
+    Fp2AddSubRv32_128(Fp2AddSubChip<F, 8, 32>),
+    Fp2MulDivRv32_128(Fp2MulDivChip<F, 8, 32>),
 }
 
 #[derive(ChipUsageGetter, Chip, AnyEnum, From)]
@@ -75,86 +103,88 @@ impl<F: PrimeField32> VmExtension<F> for Fp2Extension {
         let addsub_opcodes = (Fp2Opcode::ADD as usize)..=(Fp2Opcode::SETUP_ADDSUB as usize);
         let muldiv_opcodes = (Fp2Opcode::MUL as usize)..=(Fp2Opcode::SETUP_MULDIV as usize);
 
+        if self.non_residues.len() > self.supported_modulus.len() {
+            return Err(VmInventoryError::InvalidConfig {
+                reason: format!(
+                    "{} Fp2 non-residues given for {} moduli",
+                    self.non_residues.len(),
+                    self.supported_modulus.len()
+                ),
+            });
+        }
+        // The default `-1` is not checked, since existing configs also list moduli that are
+        // 1 mod 4, such as the scalar fields of the pairing curves.
+        for (&non_residue, modulus) in self.non_residues.iter().zip(&self.supported_modulus) {
+            // The chips multiply by the non-residue as a constant, so it must be small.
+            if non_residue.unsigned_abs() >= 1 << 8
+                || !is_quadratic_non_residue(non_residue, modulus)
+            {
+                return Err(VmInventoryError::InvalidConfig {
+                    reason: format!(
+                        "Fp2 non-residue {non_residue} is not a small quadratic non-residue mod {modulus}"
+                    ),
+                });
+            }
+        }
+
         for (i, modulus) in self.supported_modulus.iter().enumerate() {
+            let non_residue = self.non_residue(i);
             // determine the number of bytes needed to represent a prime field element
             let bytes = modulus.bits().div_ceil(8);
             let class_offset = Fp2Opcode::default_offset() + i * Fp2Opcode::COUNT;
 
-            let config32 = ExprBuilderConfig {
-                modulus: modulus.clone(),
-                num_limbs: 32,
-                limb_bits: 8,
-            };
-            let config48 = ExprBuilderConfig {
-                modulus: modulus.clone(),
-                num_limbs: 48,
-                limb_bits: 8,
-            };
-            let adapter_chip_32 = Rv32VecHeapAdapterChip::new(
-                execution_bus,
-                program_bus,
-                memory_controller.clone(),
-                bitwise_lu_chip.clone(),
-            );
-            let adapter_chip_48 = Rv32VecHeapAdapterChip::new(
-                execution_bus,
-                program_bus,
-                memory_controller.clone(),
-                bitwise_lu_chip.clone(),
-            );
+            macro_rules! add_fp2_chips {
+                ($num_limbs:literal, $addsub:ident, $muldiv:ident) => {{
+                    let config = ExprBuilderConfig {
+                        modulus: modulus.clone(),
+                        num_limbs: $num_limbs,
+                        limb_bits: 8,
+                    };
+                    let adapter_chip = Rv32VecHeapAdapterChip::new(
+                        execution_bus,
+                        program_bus,
+                        memory_controller.clone(),
+                        bitwise_lu_chip.clone(),
+                    );
+                    let addsub_chip = Fp2AddSubChip::new(
+                        adapter_chip.clone(),
+                        memory_controller.clone(),
+                        config.clone(),
+                        class_offset,
+                    );
+                    inventory.add_executor(
+                        Fp2ExtensionExecutor::$addsub(addsub_chip),
+                        addsub_opcodes
+                            .clone()
+                            .map(|x| VmOpcode::from_usize(x + class_offset)),
+                    )?;
+                    let muldiv_chip = Fp2MulDivChip::new(
+                        adapter_chip,
+                        memory_controller.clone(),
+                        config,
+                        non_residue,
+                        class_offset,
+                    );
+                    inventory.add_executor(
+                        Fp2ExtensionExecutor::$muldiv(muldiv_chip),
+                        muldiv_opcodes
+                            .clone()
+                            .map(|x| VmOpcode::from_usize(x + class_offset)),
+                    )?;
+                }};
+            }
 
+            // The block layout must match the one chosen by `moduli_declare!` in the guest.
             if bytes <= 32 {
-                let addsub_chip = Fp2AddSubChip::new(
-                    adapter_chip_32.clone(),
-                    memory_controller.clone(),
-                    config32.clone(),
-                    class_offset,
-                );
-                inventory.add_executor(
-                    Fp2ExtensionExecutor::Fp2AddSubRv32_32(addsub_chip),
-                    addsub_opcodes
-                        .clone()
-                        .map(|x| VmOpcode::from_usize(x + class_offset)),
-                )?;
-                let muldiv_chip = Fp2MulDivChip::new(
-                    adapter_chip_32.clone(),
-                    memory_controller.clone(),
-                    config32.clone(),
-                    class_offset,
-                );
-                inventory.add_executor(
-                    Fp2ExtensionExecutor::Fp2MulDivRv32_32(muldiv_chip),
-                    muldiv_opcodes
-                        .clone()
-                        .map(|x| VmOpcode::from_usize(x + class_offset)),
-                )?;
+                add_fp2_chips!(32, Fp2AddSubRv32_32, Fp2MulDivRv32_32);
             } else if bytes <= 48 {
-                let addsub_chip = Fp2AddSubChip::new(
-                    adapter_chip_48.clone(),
-                    memory_controller.clone(),
-                    config48.clone(),
-                    class_offset,
-                );
-                inventory.add_executor(
-                    Fp2ExtensionExecutor::Fp2AddSubRv32_48(addsub_chip),
-                    addsub_opcodes
-                        .clone()
-                        .map(|x| VmOpcode::from_usize(x + class_offset)),
-                )?;
-                let muldiv_chip = Fp2MulDivChip::new(
-                    adapter_chip_48.clone(),
-                    memory_controller.clone(),
-                    config48.clone(),
-                    class_offset,
-                );
-                inventory.add_executor(
-                    Fp2ExtensionExecutor::Fp2MulDivRv32_48(muldiv_chip),
-                    muldiv_opcodes
-                        .clone()
-                        .map(|x| VmOpcode::from_usize(x + class_offset)),
-                )?;
+                add_fp2_chips!(48, Fp2AddSubRv32_48, Fp2MulDivRv32_48);
+            } else if bytes <= 64 {
+                add_fp2_chips!(64, Fp2AddSubRv32_64, Fp2MulDivRv32_64);
+            } else if bytes <= 128 {
+                add_fp2_chips!(128, Fp2AddSubRv32_128, Fp2MulDivRv32_128);
             } else {
-                panic!("Modulus too large");
+                panic!("Modulus too large: at most 128 bytes are supported");
             }
         }
 
@@ -377,3 +377,10 @@ fn test_modular_is_equal_1x32() {
 fn test_modular_is_equal_3x16() {
     test_is_equal::<3, 16, 48>(17, BLS12_381_MODULUS.clone(), 100);
 }
+
+#[test]
+fn test_modular_is_equal_2x32() {
+    // 2^512 - 569
+    let modulus = (BigUint::from(1u32) << 512) - BigUint::from(569u32);
+    test_is_equal::<2, 32, 64>(17, modulus, 100);
+}
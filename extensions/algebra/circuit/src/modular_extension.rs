@@ -43,6 +43,14 @@ pub enum ModularExtensionExecutor<F: PrimeField32> {
     ModularAddSubRv32_48(ModularAddSubChip<F, 3, 16>),
     ModularMulDivRv32_48(ModularMulDivChip<F, 3, 16>),
     ModularIsEqualRv32_48(ModularIsEqualChip<F, 3, 16, 48>),
+    // 64 limbs prime
+    ModularAddSubRv32_64(ModularAddSubChip<F, 2, 32>),
+    ModularMulDivRv32_64(ModularMulDivChip<F, 2, 32>),
+    ModularIsEqualRv32_64(ModularIsEqualChip<F, 2, 32, 64>),
+    // 128 limbs prime
+    ModularAddSubRv32_128(ModularAddSubChip<F, 4, 32>),
+    ModularMulDivRv32_128(ModularMulDivChip<F, 4, 32>),
+    ModularIsEqualRv32_128(ModularIsEqualChip<F, 4, 32, 128>),
 }
 
 #[derive(ChipUsageGetter, Chip, AnyEnum, From)]
@@ -92,133 +100,99 @@ impl<F: PrimeField32> VmExtension<F> for ModularExtension {
             let class_offset = Rv32ModularArithmeticOpcode::default_offset()
                 + i * Rv32ModularArithmeticOpcode::COUNT;
 
-            let config32 = ExprBuilderConfig {
-                modulus: modulus.clone(),
-                num_limbs: 32,
-                limb_bits: 8,
-            };
-            let config48 = ExprBuilderConfig {
-                modulus: modulus.clone(),
-                num_limbs: 48,
-                limb_bits: 8,
-            };
-            let adapter_chip_32 = Rv32VecHeapAdapterChip::new(
-                execution_bus,
-                program_bus,
-                memory_controller.clone(),
-                bitwise_lu_chip.clone(),
-            );
-            let adapter_chip_48 = Rv32VecHeapAdapterChip::new(
-                execution_bus,
-                program_bus,
-                memory_controller.clone(),
-                bitwise_lu_chip.clone(),
-            );
-
-            if bytes <= 32 {
-                let addsub_chip = ModularAddSubChip::new(
-                    adapter_chip_32.clone(),
-                    ModularAddSubCoreChip::new(
-                        config32.clone(),
-                        range_checker.clone(),
-                        class_offset,
-                    ),
-                    memory_controller.clone(),
-                );
-                inventory.add_executor(
-                    ModularExtensionExecutor::ModularAddSubRv32_32(addsub_chip),
-                    addsub_opcodes
-                        .clone()
-                        .map(|x| VmOpcode::from_usize(x + class_offset)),
-                )?;
-                let muldiv_chip = ModularMulDivChip::new(
-                    adapter_chip_32.clone(),
-                    ModularMulDivCoreChip::new(
-                        config32.clone(),
-                        range_checker.clone(),
-                        class_offset,
-                    ),
-                    memory_controller.clone(),
-                );
-                inventory.add_executor(
-                    ModularExtensionExecutor::ModularMulDivRv32_32(muldiv_chip),
-                    muldiv_opcodes
-                        .clone()
-                        .map(|x| VmOpcode::from_usize(x + class_offset)),
-                )?;
-                let isequal_chip = ModularIsEqualChip::new(
-                    Rv32IsEqualModAdapterChip::new(
+            macro_rules! add_modular_chips {
+                ($num_limbs:literal, $addsub:ident, $muldiv:ident, $iseq:ident) => {{
+                    let config = ExprBuilderConfig {
+                        modulus: modulus.clone(),
+                        num_limbs: $num_limbs,
+                        limb_bits: 8,
+                    };
+                    let adapter_chip = Rv32VecHeapAdapterChip::new(
                         execution_bus,
                         program_bus,
                         memory_controller.clone(),
                         bitwise_lu_chip.clone(),
-                    ),
-                    ModularIsEqualCoreChip::new(
-                        modulus.clone(),
-                        bitwise_lu_chip.clone(),
-                        class_offset,
-                    ),
-                    memory_controller.clone(),
+                    );
+                    let addsub_chip = ModularAddSubChip::new(
+                        adapter_chip.clone(),
+                        ModularAddSubCoreChip::new(
+                            config.clone(),
+                            range_checker.clone(),
+                            class_offset,
+                        ),
+                        memory_controller.clone(),
+                    );
+                    inventory.add_executor(
+                        ModularExtensionExecutor::$addsub(addsub_chip),
+                        addsub_opcodes
+                            .clone()
+                            .map(|x| VmOpcode::from_usize(x + class_offset)),
+                    )?;
+                    let muldiv_chip = ModularMulDivChip::new(
+                        adapter_chip,
+                        ModularMulDivCoreChip::new(config, range_checker.clone(), class_offset),
+                        memory_controller.clone(),
+                    );
+                    inventory.add_executor(
+                        ModularExtensionExecutor::$muldiv(muldiv_chip),
+                        muldiv_opcodes
+                            .clone()
+                            .map(|x| VmOpcode::from_usize(x + class_offset)),
+                    )?;
+                    let isequal_chip = ModularIsEqualChip::new(
+                        Rv32IsEqualModAdapterChip::new(
+                            execution_bus,
+                            program_bus,
+                            memory_controller.clone(),
+                            bitwise_lu_chip.clone(),
+                        ),
+                        ModularIsEqualCoreChip::new(
+                            modulus.clone(),
+                            bitwise_lu_chip.clone(),
+                            class_offset,
+                        ),
+                        memory_controller.clone(),
+                    );
+                    inventory.add_executor(
+                        ModularExtensionExecutor::$iseq(isequal_chip),
+                        iseq_opcodes
+                            .clone()
+                            .map(|x| VmOpcode::from_usize(x + class_offset)),
+                    )?;
+                }};
+            }
+
+            // The block layout must match the one chosen by `moduli_declare!` in the guest.
+            if bytes <= 32 {
+                add_modular_chips!(
+                    32,
+                    ModularAddSubRv32_32,
+                    ModularMulDivRv32_32,
+                    ModularIsEqualRv32_32
                 );
-                inventory.add_executor(
-                    ModularExtensionExecutor::ModularIsEqualRv32_32(isequal_chip),
-                    iseq_opcodes
-                        .clone()
-                        .map(|x| VmOpcode::from_usize(x + class_offset)),
-                )?;
             } else if bytes <= 48 {
-                let addsub_chip = ModularAddSubChip::new(
-                    adapter_chip_48.clone(),
-                    ModularAddSubCoreChip::new(
-                        config48.clone(),
-                        range_checker.clone(),
-                        class_offset,
-                    ),
-                    memory_controller.clone(),
+                add_modular_chips!(
+                    48,
+                    ModularAddSubRv32_48,
+                    ModularMulDivRv32_48,
+                    ModularIsEqualRv32_48
                 );
-                inventory.add_executor(
-                    ModularExtensionExecutor::ModularAddSubRv32_48(addsub_chip),
-                    addsub_opcodes
-                        .clone()
-                        .map(|x| VmOpcode::from_usize(x + class_offset)),
-                )?;
-                let muldiv_chip = ModularMulDivChip::new(
-                    adapter_chip_48.clone(),
-                    ModularMulDivCoreChip::new(
-                        config48.clone(),
-                        range_checker.clone(),
-                        class_offset,
-                    ),
-                    memory_controller.clone(),
+            } else if bytes <= 64 {
+                add_modular_chips!(
+                    64,
+                    ModularAddSubRv32_64,
+                    ModularMulDivRv32_64,
+                    ModularIsEqualRv32_64
                 );
-                inventory.add_executor(
-                    ModularExtensionExecutor::ModularMulDivRv32_48(muldiv_chip),
-                    muldiv_opcodes
-                        .clone()
-                        .map(|x| VmOpcode::from_usize(x + class_offset)),
-                )?;
-                let isequal_chip = ModularIsEqualChip::new(
-                    Rv32IsEqualModAdapterChip::new(
-                        execution_bus,
-                        program_bus,
-                        memory_controller.clone(),
-                        bitwise_lu_chip.clone(),
-                    ),
-                    ModularIsEqualCoreChip::new(
-                        modulus.clone(),
-                        bitwise_lu_chip.clone(),
-                        class_offset,
-                    ),
-                    memory_controller.clone(),
+            } else if bytes <= 128 {
+                add_modular_chips!(
+                    128,
+                    ModularAddSubRv32_128,
+                    ModularMulDivRv32_128,
+                    ModularIsEqualRv32_128
                 );
-                inventory.add_executor(
-                    ModularExtensionExecutor::ModularIsEqualRv32_48(isequal_chip),
-                    iseq_opcodes
-                        .clone()
-                        .map(|x| VmOpcode::from_usize(x + class_offset)),
-                )?;
             } else {
-                panic!("Modulus too large");
+                panic!("Modulus too large: at most 128 bytes are supported");
             }
         }
 
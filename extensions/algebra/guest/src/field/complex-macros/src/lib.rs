@@ -14,9 +14,12 @@ use syn::{
 /// ```rust
 /// complex_declare! {
 ///     Complex1 { mod_type = Mod1 },
-///     Complex2 { mod_type = Mod2 },
+///     Complex2 { mod_type = Mod2, non_residue = -5 },
 /// }
 /// ```
+/// Each type is `Fp2 = Fp[u]/(u^2 - non_residue)`. `non_residue` is an optional small integer,
+/// defaulting to `-1`. It must be a quadratic non-residue mod the modulus of `mod_type` and match
+/// the non-residue configured for this modulus in the `Fp2Extension` of the VM.
 #[proc_macro]
 pub fn complex_declare(input: TokenStream) -> TokenStream {
     let MacroArgs { items } = parse_macro_input!(input as MacroArgs);
@@ -29,6 +32,7 @@ pub fn complex_declare(input: TokenStream) -> TokenStream {
         let struct_name = item.name.to_string();
         let struct_name = syn::Ident::new(&struct_name, span.into());
         let mut intmod_type: Option<syn::Path> = None;
+        let mut non_residue: isize = -1;
         for param in item.params {
             match param.name.to_string().as_str() {
                 "mod_type" => {
@@ -40,6 +44,14 @@ pub fn complex_declare(input: TokenStream) -> TokenStream {
                             .into();
                     }
                 }
+                "non_residue" => match parse_isize(&param.value) {
+                    Some(value) if value != 0 => non_residue = value,
+                    _ => {
+                        return syn::Error::new_spanned(param.value, "Expected a nonzero integer")
+                            .to_compile_error()
+                            .into();
+                    }
+                },
                 _ => {
                     panic!("Unknown parameter {}", param.name);
                 }
@@ -48,6 +60,19 @@ pub fn complex_declare(input: TokenStream) -> TokenStream {
 
         let intmod_type = intmod_type.expect("mod_type parameter is required");
 
+        // Host implementation of `x * non_residue`.
+        let non_residue_abs = non_residue.unsigned_abs() as u32;
+        let mul_by_non_residue = match non_residue {
+            -1 => quote::quote! { -x },
+            1 => quote::quote! { x },
+            n if n < 0 => quote::quote! {
+                -(x * &<#intmod_type as openvm_algebra_guest::IntMod>::from_u32(#non_residue_abs))
+            },
+            _ => quote::quote! {
+                x * &<#intmod_type as openvm_algebra_guest::IntMod>::from_u32(#non_residue_abs)
+            },
+        };
+
         macro_rules! create_extern_func {
             ($name:ident) => {
                 let $name = syn::Ident::new(
@@ -70,8 +95,9 @@ pub fn complex_declare(input: TokenStream) -> TokenStream {
             }
 
 
-            /// Quadratic extension field of `#intmod_type` with irreducible polynomial `X^2 + 1`.
-            /// Elements are represented as `c0 + c1 * u` where `u^2 = -1`.
+            /// Quadratic extension field of `#intmod_type` with irreducible polynomial
+            /// `X^2 - NON_RESIDUE`. Elements are represented as `c0 + c1 * u` where
+            /// `u^2 = NON_RESIDUE`.
             ///
             /// Memory alignment follows alignment of `#intmod_type`.
             /// Memory layout is concatenation of `c0` and `c1`.
@@ -97,6 +123,16 @@ pub fn complex_declare(input: TokenStream) -> TokenStream {
                 // One element (i.e. multiplicative identity)
                 pub const ONE: Self = Self::new(<#intmod_type as openvm_algebra_guest::IntMod>::ONE, <#intmod_type as openvm_algebra_guest::IntMod>::ZERO);
 
+                /// The non-residue `u^2`.
+                pub const NON_RESIDUE: isize = #non_residue;
+
+                /// Returns `x * NON_RESIDUE`.
+                #[cfg(not(target_os = "zkvm"))]
+                #[inline(always)]
+                fn mul_by_non_residue(x: #intmod_type) -> #intmod_type {
+                    #mul_by_non_residue
+                }
+
                 pub fn neg_assign(&mut self) {
                     self.c0.neg_assign();
                     self.c1.neg_assign();
@@ -150,7 +186,7 @@ pub fn complex_declare(input: TokenStream) -> TokenStream {
                         let (c0, c1) = (&self.c0, &self.c1);
                         let (d0, d1) = (&other.c0, &other.c1);
                         *self = Self::new(
-                            c0.clone() * d0 - c1.clone() * d1,
+                            c0.clone() * d0 + Self::mul_by_non_residue(c1.clone() * d1),
                             c0.clone() * d1 + c1.clone() * d0,
                         );
                     }
@@ -173,9 +209,9 @@ pub fn complex_declare(input: TokenStream) -> TokenStream {
                     {
                         let (c0, c1) = (&self.c0, &self.c1);
                         let (d0, d1) = (&other.c0, &other.c1);
-                        let denom = <#intmod_type as openvm_algebra_guest::IntMod>::ONE.div_unsafe(d0.square() + d1.square());
+                        let denom = <#intmod_type as openvm_algebra_guest::IntMod>::ONE.div_unsafe(d0.square() - Self::mul_by_non_residue(d1.square()));
                         *self = Self::new(
-                            denom.clone() * (c0.clone() * d0 + c1.clone() * d1),
+                            denom.clone() * (c0.clone() * d0 - Self::mul_by_non_residue(c1.clone() * d1)),
                             denom * &(c1.clone() * d0 - c0.clone() * d1),
                         );
                     }
@@ -641,6 +677,22 @@ pub fn complex_init(input: TokenStream) -> TokenStream {
     })
 }
 
+/// Parses an integer literal, optionally negated.
+fn parse_isize(expr: &Expr) -> Option<isize> {
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(int),
+            ..
+        }) => int.base10_parse::<isize>().ok(),
+        Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => parse_isize(expr).map(|value| -value),
+        _ => None,
+    }
+}
+
 struct ComplexSimpleItem {
     items: Vec<Path>,
 }
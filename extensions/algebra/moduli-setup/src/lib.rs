@@ -14,6 +14,22 @@ use syn::{
 
 static MOD_IDX: AtomicUsize = AtomicUsize::new(0);
 
+/// Returns the number of limbs and the memory block size used to represent elements modulo a
+/// modulus of `num_bytes` bytes. This must match the chip layouts that the modular, Fp2 and ECC
+/// circuit extensions select for the same modulus.
+fn limbs_and_block_size(num_bytes: usize) -> (usize, usize) {
+    if num_bytes < 32 {
+        proc_macro::Diagnostic::new(proc_macro::Level::Warning, "`limbs` has been set to 32 because it was too small; this is going to be changed once we support more flexible reads").emit();
+    }
+    match num_bytes {
+        0..=32 => (32, 32),
+        33..=48 => (48, 16),
+        49..=64 => (64, 32),
+        65..=128 => (128, 32),
+        _ => panic!("Modulus too large: at most 128 bytes are supported"),
+    }
+}
+
 /// This macro generates the code to setup the modulus for a given prime. Also it places the moduli into a special static variable to be later extracted from the ELF and used by the VM.
 /// Usage:
 /// ```
@@ -62,16 +78,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
 
         let modulus = modulus.expect("modulus parameter is required");
         let modulus_bytes = string_to_bytes(&modulus);
-        let mut limbs = modulus_bytes.len();
-
-        if limbs < 32 {
-            limbs = 32;
-            proc_macro::Diagnostic::new(proc_macro::Level::Warning, "`limbs` has been set to 32 because it was too small; this is going to be changed once we support more flexible reads").emit();
-        }
-
-        // The largest power of two so that at most 10% of all space is wasted
-        let block_size = 1usize << ((limbs - 1) ^ (limbs + limbs / 9)).ilog2();
-        let limbs = limbs.next_multiple_of(block_size);
+        let (limbs, block_size) = limbs_and_block_size(modulus_bytes.len());
         let modulus_bytes = modulus_bytes
             .into_iter()
             .chain(vec![0u8; limbs])
@@ -715,16 +722,7 @@ pub fn moduli_init(input: TokenStream) -> TokenStream {
 
         // TODO: chore: move all duplicated code to a function
         let modulus_bytes = string_to_bytes(&modulus);
-        let mut limbs = modulus_bytes.len();
-
-        if limbs < 32 {
-            limbs = 32;
-            proc_macro::Diagnostic::new(proc_macro::Level::Warning, "`limbs` has been set to 32 because it was too small; this is going to be changed once we support more flexible reads").emit();
-        }
-
-        // The largest power of two so that at most 10% of all space is wasted
-        let block_size = 1usize << ((limbs - 1) ^ (limbs + limbs / 9)).ilog2();
-        let limbs = limbs.next_multiple_of(block_size);
+        let (limbs, block_size) = limbs_and_block_size(modulus_bytes.len());
         let modulus_bytes = modulus_bytes
             .into_iter()
             .chain(vec![0u8; limbs])
@@ -0,0 +1,60 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use openvm_algebra_guest::{field::ComplexConjugate, DivAssignUnsafe, DivUnsafe, IntMod};
+
+openvm::entry!(main);
+
+openvm_algebra_moduli_setup::moduli_declare! {
+    Bls12_377Fp { modulus = "0x01ae3a4617c510eac63b05c06ca1493b1a22d9f300f5138f1ef3622fba094800170b5d44300000008508c00000000001" }
+}
+openvm_algebra_moduli_setup::moduli_init!(
+    "0x01ae3a4617c510eac63b05c06ca1493b1a22d9f300f5138f1ef3622fba094800170b5d44300000008508c00000000001"
+);
+
+// The BLS12-377 base field is 1 mod 4, so -1 is a square and Fp2 is defined by u^2 = -5.
+openvm_algebra_complex_macros::complex_declare! {
+    Bls12_377Fp2 { mod_type = Bls12_377Fp, non_residue = -5 }
+}
+
+openvm_algebra_complex_macros::complex_init! {
+    Bls12_377Fp2 { mod_idx = 0 },
+}
+
+pub fn main() {
+    setup_all_complex_extensions();
+    let u = Bls12_377Fp2::new(Bls12_377Fp::ZERO, Bls12_377Fp::ONE);
+    let non_residue = Bls12_377Fp2::new(-Bls12_377Fp::from_u8(5), Bls12_377Fp::ZERO);
+    if &u * &u != non_residue {
+        panic!();
+    }
+    if non_residue.clone().div_unsafe(&u) != u {
+        panic!();
+    }
+
+    let mut a = Bls12_377Fp2::new(
+        Bls12_377Fp::from_u64(0x0a0a_0a0a_0a0a_0a0a),
+        Bls12_377Fp::from_u64(0x1515_1515_1515_1515),
+    );
+    let mut b = Bls12_377Fp2::new(
+        Bls12_377Fp::from_u64(0x2020_2020_2020_2020),
+        Bls12_377Fp::from_u64(0x2f2f_2f2f_2f2f_2f2f),
+    );
+
+    for _ in 0..32 {
+        // a / b = a * conj(b) / (b * conj(b))
+        let mut res = &a * &b.clone().conjugate();
+        res.div_assign_unsafe(&b * &b.clone().conjugate());
+
+        if a.clone().div_unsafe(&b) - res != Bls12_377Fp2::ZERO {
+            panic!();
+        }
+
+        a *= &b;
+        b *= &a;
+    }
+
+    if a == b {
+        panic!();
+    }
+}
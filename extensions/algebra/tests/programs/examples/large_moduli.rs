@@ -0,0 +1,55 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use openvm_algebra_guest::{DivUnsafe, IntMod};
+
+openvm::entry!(main);
+
+openvm_algebra_moduli_setup::moduli_declare! {
+    // 2^512 - 569, uses the 64 limb layout
+    Mod512 { modulus = "0xFFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFDC7" },
+    // 2^521 - 1, uses the 128 limb layout
+    Mod521 { modulus = "0x01FF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF" },
+}
+
+openvm_algebra_moduli_setup::moduli_init!(
+    "0xFFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFDC7",
+    "0x01FF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF"
+);
+
+/// Checks that `a^(p - 2)` is the inverse of `a` by Fermat's little theorem.
+macro_rules! check_fermat {
+    ($ty:ty) => {{
+        let mut pow = <$ty>::MODULUS;
+        pow[0] -= 2;
+
+        let mut a = <$ty>::from_u32(1234);
+        let mut res = <$ty>::from_u32(1);
+        let inv = res.clone().div_unsafe(&a);
+
+        for pow_bit in pow {
+            for j in 0..8 {
+                if pow_bit & (1 << j) != 0 {
+                    res *= &a;
+                }
+                a *= a.clone();
+            }
+        }
+        assert_eq!(res, inv);
+
+        let two = <$ty>::from_u32(2);
+        let minus_two = <$ty>::from_le_bytes(&pow);
+        assert_eq!(res - &minus_two, inv + &two);
+        if two == minus_two {
+            openvm::process::panic();
+        }
+    }};
+}
+
+pub fn main() {
+    setup_all_moduli();
+    assert_eq!(<Mod512 as IntMod>::NUM_LIMBS, 64);
+    assert_eq!(<Mod521 as IntMod>::NUM_LIMBS, 128);
+    check_fermat!(Mod512);
+    check_fermat!(Mod521);
+}
@@ -53,6 +53,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_large_moduli() -> Result<()> {
+        let elf = build_example_program_at_path(get_programs_dir!(), "large_moduli")?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(ModularTranspilerExtension),
+        )?;
+        // 2^512 - 569 and 2^521 - 1
+        let moduli = [
+            (BigUint::from(1u32) << 512) - BigUint::from(569u32),
+            (BigUint::from(1u32) << 521) - BigUint::from(1u32),
+        ];
+        let config = Rv32ModularConfig::new(moduli.to_vec());
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+
     #[test]
     fn test_complex_two_moduli() -> Result<()> {
         let elf = build_example_program_at_path(get_programs_dir!(), "complex-two-modulos")?;
@@ -89,4 +110,24 @@ mod tests {
         air_test(config, openvm_exe);
         Ok(())
     }
+
+    #[test]
+    fn test_complex_non_residue() -> Result<()> {
+        let elf = build_example_program_at_path(get_programs_dir!(), "complex-bls12_377")?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(Fp2TranspilerExtension)
+                .with_extension(ModularTranspilerExtension),
+        )?;
+        // BLS12-377 base field, whose Fp2 is defined by u^2 = -5.
+        let modulus = BigUint::from_str("258664426012969094010652733694893533536393512754914660539884262666720468348340822774968888139573360124440321458177").unwrap();
+        let mut config = Rv32ModularWithFp2Config::new(vec![modulus]);
+        config.fp2 = config.fp2.with_non_residues(vec![-5]);
+        air_test(config, openvm_exe);
+        Ok(())
+    }
 }
@@ -7,7 +7,11 @@ use openvm_rv32im_circuit::{
 };
 
 mod extension;
+mod wide_extension;
+mod wide_mul;
 pub use extension::*;
+pub use wide_extension::*;
+pub use wide_mul::*;
 
 #[cfg(test)]
 mod tests;
@@ -47,3 +51,31 @@ pub type Rv32BranchLessThan256Chip<F> = VmChipWrapper<
     Rv32HeapBranchAdapterChip<F, 2, INT256_NUM_LIMBS>,
     BranchLessThanCoreChip<INT256_NUM_LIMBS, RV32_CELL_BITS>,
 >;
+
+pub const INT512_NUM_LIMBS: usize = 64;
+pub const INT1024_NUM_LIMBS: usize = 128;
+pub const INT2048_NUM_LIMBS: usize = 256;
+
+pub type Rv32BaseAluWideChip<F, const NUM_LIMBS: usize> = VmChipWrapper<
+    F,
+    Rv32HeapAdapterChip<F, 2, NUM_LIMBS, NUM_LIMBS>,
+    BaseAluCoreChip<NUM_LIMBS, RV32_CELL_BITS>,
+>;
+
+pub type Rv32LessThanWideChip<F, const NUM_LIMBS: usize> = VmChipWrapper<
+    F,
+    Rv32HeapAdapterChip<F, 2, NUM_LIMBS, NUM_LIMBS>,
+    LessThanCoreChip<NUM_LIMBS, RV32_CELL_BITS>,
+>;
+
+pub type Rv32MultiplicationWideChip<F, const NUM_LIMBS: usize> = VmChipWrapper<
+    F,
+    Rv32HeapAdapterChip<F, 2, NUM_LIMBS, NUM_LIMBS>,
+    WideMultiplicationCoreChip<NUM_LIMBS, RV32_CELL_BITS>,
+>;
+
+pub type Rv32ShiftWideChip<F, const NUM_LIMBS: usize> = VmChipWrapper<
+    F,
+    Rv32HeapAdapterChip<F, 2, NUM_LIMBS, NUM_LIMBS>,
+    ShiftCoreChip<NUM_LIMBS, RV32_CELL_BITS>,
+>;
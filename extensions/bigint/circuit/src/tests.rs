@@ -29,8 +29,10 @@ use openvm_stark_sdk::{p3_baby_bear::BabyBear, utils::create_seeded_rng};
 use rand::Rng;
 
 use super::{
-    Rv32BaseAlu256Chip, Rv32BranchEqual256Chip, Rv32BranchLessThan256Chip, Rv32LessThan256Chip,
-    Rv32Multiplication256Chip, Rv32Shift256Chip,
+    Rv32BaseAlu256Chip, Rv32BaseAluWideChip, Rv32BranchEqual256Chip, Rv32BranchLessThan256Chip,
+    Rv32LessThan256Chip, Rv32LessThanWideChip, Rv32Multiplication256Chip,
+    Rv32MultiplicationWideChip, Rv32Shift256Chip, Rv32ShiftWideChip, WideMultiplicationCoreChip,
+    INT512_NUM_LIMBS,
 };
 
 type F = BabyBear;
@@ -365,3 +367,152 @@ fn blt_256_bge_rand_test() {
 fn blt_256_bgeu_rand_test() {
     run_blt_256_rand_test(BranchLessThanOpcode::BGEU, 24);
 }
+
+fn run_int_512_rand_execute<E: InstructionExecutor<F>>(
+    opcode: usize,
+    num_ops: usize,
+    executor: &mut E,
+    tester: &mut VmChipTestBuilder<F>,
+) {
+    let mut rng = create_seeded_rng();
+    for _ in 0..num_ops {
+        let b = generate_long_number::<INT512_NUM_LIMBS, RV32_CELL_BITS>(&mut rng);
+        let c = generate_long_number::<INT512_NUM_LIMBS, RV32_CELL_BITS>(&mut rng);
+        let instruction = rv32_write_heap_default(
+            tester,
+            vec![b.map(F::from_canonical_u32)],
+            vec![c.map(F::from_canonical_u32)],
+            opcode,
+        );
+        tester.execute(executor, instruction);
+    }
+}
+
+fn run_alu_512_rand_test(opcode: BaseAluOpcode, num_ops: usize) {
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = Arc::new(BitwiseOperationLookupChip::<RV32_CELL_BITS>::new(
+        bitwise_bus,
+    ));
+
+    let mut tester = VmChipTestBuilder::default();
+    let mut chip = Rv32BaseAluWideChip::<F, INT512_NUM_LIMBS>::new(
+        Rv32HeapAdapterChip::<F, 2, INT512_NUM_LIMBS, INT512_NUM_LIMBS>::new(
+            tester.execution_bus(),
+            tester.program_bus(),
+            tester.memory_controller(),
+            bitwise_chip.clone(),
+        ),
+        BaseAluCoreChip::new(bitwise_chip.clone(), 0),
+        tester.memory_controller(),
+    );
+
+    run_int_512_rand_execute(opcode as usize, num_ops, &mut chip, &mut tester);
+    let tester = tester.build().load(chip).load(bitwise_chip).finalize();
+    tester.simple_test().expect("Verification failed");
+}
+
+#[test]
+fn alu_512_add_rand_test() {
+    run_alu_512_rand_test(BaseAluOpcode::ADD, 12);
+}
+
+#[test]
+fn alu_512_sub_rand_test() {
+    run_alu_512_rand_test(BaseAluOpcode::SUB, 12);
+}
+
+#[test]
+fn alu_512_and_rand_test() {
+    run_alu_512_rand_test(BaseAluOpcode::AND, 12);
+}
+
+#[test]
+fn lt_512_sltu_rand_test() {
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = Arc::new(BitwiseOperationLookupChip::<RV32_CELL_BITS>::new(
+        bitwise_bus,
+    ));
+
+    let mut tester = VmChipTestBuilder::default();
+    let mut chip = Rv32LessThanWideChip::<F, INT512_NUM_LIMBS>::new(
+        Rv32HeapAdapterChip::<F, 2, INT512_NUM_LIMBS, INT512_NUM_LIMBS>::new(
+            tester.execution_bus(),
+            tester.program_bus(),
+            tester.memory_controller(),
+            bitwise_chip.clone(),
+        ),
+        LessThanCoreChip::new(bitwise_chip.clone(), 0),
+        tester.memory_controller(),
+    );
+
+    run_int_512_rand_execute(LessThanOpcode::SLTU as usize, 12, &mut chip, &mut tester);
+    let tester = tester.build().load(chip).load(bitwise_chip).finalize();
+    tester.simple_test().expect("Verification failed");
+}
+
+#[test]
+fn mul_512_rand_test() {
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = Arc::new(BitwiseOperationLookupChip::<RV32_CELL_BITS>::new(
+        bitwise_bus,
+    ));
+
+    let mut tester = VmChipTestBuilder::default();
+    let mut chip = Rv32MultiplicationWideChip::<F, INT512_NUM_LIMBS>::new(
+        Rv32HeapAdapterChip::<F, 2, INT512_NUM_LIMBS, INT512_NUM_LIMBS>::new(
+            tester.execution_bus(),
+            tester.program_bus(),
+            tester.memory_controller(),
+            bitwise_chip.clone(),
+        ),
+        WideMultiplicationCoreChip::new(bitwise_chip.clone(), 0),
+        tester.memory_controller(),
+    );
+
+    run_int_512_rand_execute(MulOpcode::MUL as usize, 12, &mut chip, &mut tester);
+    let tester = tester.build().load(chip).load(bitwise_chip).finalize();
+    tester.simple_test().expect("Verification failed");
+}
+
+fn run_shift_512_rand_test(opcode: ShiftOpcode, num_ops: usize) {
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = Arc::new(BitwiseOperationLookupChip::<RV32_CELL_BITS>::new(
+        bitwise_bus,
+    ));
+
+    let mut tester = VmChipTestBuilder::default();
+    let mut chip = Rv32ShiftWideChip::<F, INT512_NUM_LIMBS>::new(
+        Rv32HeapAdapterChip::<F, 2, INT512_NUM_LIMBS, INT512_NUM_LIMBS>::new(
+            tester.execution_bus(),
+            tester.program_bus(),
+            tester.memory_controller(),
+            bitwise_chip.clone(),
+        ),
+        ShiftCoreChip::new(
+            bitwise_chip.clone(),
+            tester.memory_controller().borrow().range_checker.clone(),
+            0,
+        ),
+        tester.memory_controller(),
+    );
+
+    // The shift amount spans two limbs for 512-bit integers.
+    run_int_512_rand_execute(opcode as usize, num_ops, &mut chip, &mut tester);
+    let tester = tester.build().load(chip).load(bitwise_chip).finalize();
+    tester.simple_test().expect("Verification failed");
+}
+
+#[test]
+fn shift_512_sll_rand_test() {
+    run_shift_512_rand_test(ShiftOpcode::SLL, 12);
+}
+
+#[test]
+fn shift_512_srl_rand_test() {
+    run_shift_512_rand_test(ShiftOpcode::SRL, 12);
+}
+
+#[test]
+fn shift_512_sra_rand_test() {
+    run_shift_512_rand_test(ShiftOpcode::SRA, 12);
+}
@@ -0,0 +1,351 @@
+use std::sync::Arc;
+
+use derive_more::derive::From;
+use openvm_bigint_guest::INT_WIDTH_BITS;
+use openvm_bigint_transpiler::{
+    BigIntPhantom, Rv32BaseAluWideOpcode, Rv32LessThanWideOpcode, Rv32MulWideOpcode,
+    Rv32ShiftWideOpcode,
+};
+use openvm_circuit::{
+    arch::{
+        SystemConfig, SystemExecutor, SystemPeriphery, SystemPort, VmChipComplex, VmConfig,
+        VmExtension, VmInventory, VmInventoryBuilder, VmInventoryError,
+    },
+    system::phantom::PhantomChip,
+};
+use openvm_circuit_derive::{AnyEnum, InstructionExecutor, VmConfig};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, BitwiseOperationLookupChip,
+};
+use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
+use openvm_instructions::{riscv::RV32_CELL_BITS, PhantomDiscriminant, UsizeOpcode, VmOpcode};
+use openvm_rv32im_circuit::{
+    Rv32I, Rv32IExecutor, Rv32IPeriphery, Rv32Io, Rv32IoExecutor, Rv32IoPeriphery, Rv32M,
+    Rv32MExecutor, Rv32MPeriphery,
+};
+use openvm_rv32im_transpiler::{BaseAluOpcode, LessThanOpcode, MulOpcode, ShiftOpcode};
+use openvm_stark_backend::p3_field::PrimeField32;
+use serde::{Deserialize, Serialize};
+use strum::{EnumCount, IntoEnumIterator};
+
+use crate::*;
+
+#[derive(Clone, Debug, VmConfig, derive_new::new, Serialize, Deserialize)]
+pub struct WideUintRv32Config {
+    #[system]
+    pub system: SystemConfig,
+    #[extension]
+    pub rv32i: Rv32I,
+    #[extension]
+    pub rv32m: Rv32M,
+    #[extension]
+    pub io: Rv32Io,
+    #[extension]
+    pub wide_uint: WideUint,
+}
+
+impl Default for WideUintRv32Config {
+    fn default() -> Self {
+        let wide_uint = WideUint::default();
+        let mut system = SystemConfig::default().with_continuations();
+        system.memory_config.max_access_adapter_n = wide_uint.max_num_limbs();
+        Self {
+            system,
+            rv32i: Rv32I,
+            rv32m: Rv32M::default(),
+            io: Rv32Io,
+            wide_uint,
+        }
+    }
+}
+
+/// Unsigned integers wider than 256 bits. Integers of `N` bytes are read from and written to
+/// memory in a single access, so the memory config must have `max_access_adapter_n >= N`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WideUint {
+    /// Bit widths of the supported integers. Each must be one of 512, 1024 or 2048.
+    pub supported_bits: Vec<usize>,
+}
+
+impl Default for WideUint {
+    fn default() -> Self {
+        Self {
+            supported_bits: vec![512, 1024, 2048],
+        }
+    }
+}
+
+impl WideUint {
+    /// Number of limbs of the widest supported integer.
+    pub fn max_num_limbs(&self) -> usize {
+        self.supported_bits
+            .iter()
+            .map(|bits| bits / RV32_CELL_BITS)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[derive(ChipUsageGetter, Chip, InstructionExecutor, From, AnyEnum)]
+pub enum WideUintExecutor<F: PrimeField32> {
+    BaseAlu512(Rv32BaseAluWideChip<F, INT512_NUM_LIMBS>),
+    LessThan512(Rv32LessThanWideChip<F, INT512_NUM_LIMBS>),
+    Multiplication512(Rv32MultiplicationWideChip<F, INT512_NUM_LIMBS>),
+    Shift512(Rv32ShiftWideChip<F, INT512_NUM_LIMBS>),
+    BaseAlu1024(Rv32BaseAluWideChip<F, INT1024_NUM_LIMBS>),
+    LessThan1024(Rv32LessThanWideChip<F, INT1024_NUM_LIMBS>),
+    Multiplication1024(Rv32MultiplicationWideChip<F, INT1024_NUM_LIMBS>),
+    Shift1024(Rv32ShiftWideChip<F, INT1024_NUM_LIMBS>),
+    BaseAlu2048(Rv32BaseAluWideChip<F, INT2048_NUM_LIMBS>),
+    LessThan2048(Rv32LessThanWideChip<F, INT2048_NUM_LIMBS>),
+    Multiplication2048(Rv32MultiplicationWideChip<F, INT2048_NUM_LIMBS>),
+    Shift2048(Rv32ShiftWideChip<F, INT2048_NUM_LIMBS>),
+}
+
+#[derive(From, ChipUsageGetter, Chip, AnyEnum)]
+pub enum WideUintPeriphery<F: PrimeField32> {
+    BitwiseOperationLookup(Arc<BitwiseOperationLookupChip<8>>),
+    Phantom(PhantomChip<F>),
+}
+
+impl<F: PrimeField32> VmExtension<F> for WideUint {
+    type Executor = WideUintExecutor<F>;
+    type Periphery = WideUintPeriphery<F>;
+
+    fn build(
+        &self,
+        builder: &mut VmInventoryBuilder<F>,
+    ) -> Result<VmInventory<Self::Executor, Self::Periphery>, VmInventoryError> {
+        let mut inventory = VmInventory::new();
+        if self.supported_bits.is_empty() {
+            return Ok(inventory);
+        }
+        let SystemPort {
+            execution_bus,
+            program_bus,
+            memory_controller,
+        } = builder.system_port();
+        let max_access_adapter_n = builder.system_config().memory_config.max_access_adapter_n;
+        let range_checker_chip = builder.system_base().range_checker_chip.clone();
+        let bitwise_lu_chip = if let Some(chip) = builder
+            .find_chip::<Arc<BitwiseOperationLookupChip<8>>>()
+            .first()
+        {
+            Arc::clone(chip)
+        } else {
+            let bitwise_lu_bus = BitwiseOperationLookupBus::new(builder.new_bus_idx());
+            let chip = Arc::new(BitwiseOperationLookupChip::new(bitwise_lu_bus));
+            inventory.add_periphery_chip(chip.clone());
+            chip
+        };
+
+        for &bits in &self.supported_bits {
+            let width_idx = INT_WIDTH_BITS
+                .iter()
+                .position(|&b| b == bits)
+                .filter(|&idx| idx > 0)
+                .ok_or_else(|| VmInventoryError::InvalidConfig {
+                    reason: format!("unsupported integer width: {bits} bits"),
+                })?;
+            let num_limbs = bits / RV32_CELL_BITS;
+            if num_limbs > max_access_adapter_n {
+                return Err(VmInventoryError::InvalidConfig {
+                    reason: format!(
+                        "{bits}-bit integers need max_access_adapter_n >= {num_limbs}, got {max_access_adapter_n}"
+                    ),
+                });
+            }
+            let width_shift = width_idx - 1;
+            let base_alu_offset =
+                Rv32BaseAluWideOpcode::default_offset() + width_shift * BaseAluOpcode::COUNT;
+            let less_than_offset =
+                Rv32LessThanWideOpcode::default_offset() + width_shift * LessThanOpcode::COUNT;
+            let mul_offset = Rv32MulWideOpcode::default_offset() + width_shift * MulOpcode::COUNT;
+            let shift_offset =
+                Rv32ShiftWideOpcode::default_offset() + width_shift * ShiftOpcode::COUNT;
+
+            macro_rules! add_wide_chips {
+                ($num_limbs:expr, $base_alu:ident, $less_than:ident, $mul:ident, $shift:ident) => {{
+                    let base_alu_chip = Rv32BaseAluWideChip::<F, $num_limbs>::new(
+                        Rv32HeapAdapterChip::new(
+                            execution_bus,
+                            program_bus,
+                            memory_controller.clone(),
+                            bitwise_lu_chip.clone(),
+                        ),
+                        BaseAluCoreChip::new(bitwise_lu_chip.clone(), base_alu_offset),
+                        memory_controller.clone(),
+                    );
+                    inventory.add_executor(
+                        WideUintExecutor::$base_alu(base_alu_chip),
+                        BaseAluOpcode::iter()
+                            .map(|x| VmOpcode::from_usize(x as usize + base_alu_offset)),
+                    )?;
+
+                    let less_than_chip = Rv32LessThanWideChip::<F, $num_limbs>::new(
+                        Rv32HeapAdapterChip::new(
+                            execution_bus,
+                            program_bus,
+                            memory_controller.clone(),
+                            bitwise_lu_chip.clone(),
+                        ),
+                        LessThanCoreChip::new(bitwise_lu_chip.clone(), less_than_offset),
+                        memory_controller.clone(),
+                    );
+                    inventory.add_executor(
+                        WideUintExecutor::$less_than(less_than_chip),
+                        LessThanOpcode::iter()
+                            .map(|x| VmOpcode::from_usize(x as usize + less_than_offset)),
+                    )?;
+
+                    let multiplication_chip = Rv32MultiplicationWideChip::<F, $num_limbs>::new(
+                        Rv32HeapAdapterChip::new(
+                            execution_bus,
+                            program_bus,
+                            memory_controller.clone(),
+                            bitwise_lu_chip.clone(),
+                        ),
+                        WideMultiplicationCoreChip::new(bitwise_lu_chip.clone(), mul_offset),
+                        memory_controller.clone(),
+                    );
+                    inventory.add_executor(
+                        WideUintExecutor::$mul(multiplication_chip),
+                        MulOpcode::iter().map(|x| VmOpcode::from_usize(x as usize + mul_offset)),
+                    )?;
+
+                    let shift_chip = Rv32ShiftWideChip::<F, $num_limbs>::new(
+                        Rv32HeapAdapterChip::new(
+                            execution_bus,
+                            program_bus,
+                            memory_controller.clone(),
+                            bitwise_lu_chip.clone(),
+                        ),
+                        ShiftCoreChip::new(
+                            bitwise_lu_chip.clone(),
+                            range_checker_chip.clone(),
+                            shift_offset,
+                        ),
+                        memory_controller.clone(),
+                    );
+                    inventory.add_executor(
+                        WideUintExecutor::$shift(shift_chip),
+                        ShiftOpcode::iter()
+                            .map(|x| VmOpcode::from_usize(x as usize + shift_offset)),
+                    )?;
+                }};
+            }
+
+            match num_limbs {
+                INT512_NUM_LIMBS => add_wide_chips!(
+                    INT512_NUM_LIMBS,
+                    BaseAlu512,
+                    LessThan512,
+                    Multiplication512,
+                    Shift512
+                ),
+                INT1024_NUM_LIMBS => add_wide_chips!(
+                    INT1024_NUM_LIMBS,
+                    BaseAlu1024,
+                    LessThan1024,
+                    Multiplication1024,
+                    Shift1024
+                ),
+                INT2048_NUM_LIMBS => add_wide_chips!(
+                    INT2048_NUM_LIMBS,
+                    BaseAlu2048,
+                    LessThan2048,
+                    Multiplication2048,
+                    Shift2048
+                ),
+                _ => unreachable!(),
+            }
+        }
+
+        builder.add_phantom_sub_executor(
+            phantom::DivRemHintSubEx,
+            PhantomDiscriminant(BigIntPhantom::HintDivRem as u16),
+        )?;
+
+        Ok(inventory)
+    }
+}
+
+pub(crate) mod phantom {
+    use std::iter::repeat;
+
+    use eyre::bail;
+    use num_bigint_dig::BigUint;
+    use openvm_bigint_guest::INT_WIDTH_BITS;
+    use openvm_circuit::{
+        arch::{PhantomSubExecutor, Streams},
+        system::memory::MemoryController,
+    };
+    use openvm_instructions::{
+        riscv::{RV32_CELL_BITS, RV32_MEMORY_AS},
+        PhantomDiscriminant,
+    };
+    use openvm_rv32im_circuit::adapters::unsafe_read_rv32_register;
+    use openvm_stark_backend::p3_field::PrimeField32;
+
+    pub struct DivRemHintSubEx;
+
+    impl<F: PrimeField32> PhantomSubExecutor<F> for DivRemHintSubEx {
+        fn phantom_execute(
+            &mut self,
+            memory: &MemoryController<F>,
+            streams: &mut Streams<F>,
+            _: PhantomDiscriminant,
+            a: F,
+            b: F,
+            c_upper: u16,
+        ) -> eyre::Result<()> {
+            let width_idx = c_upper as usize;
+            if width_idx == 0 || width_idx >= INT_WIDTH_BITS.len() {
+                bail!("Integer width index {width_idx} out of range");
+            }
+            let bits = INT_WIDTH_BITS[width_idx];
+            let num_limbs = bits / RV32_CELL_BITS;
+
+            let rs1 = unsafe_read_rv32_register(memory, a);
+            let rs2 = unsafe_read_rv32_register(memory, b);
+            let dividend = read_biguint(memory, rs1, 2 * num_limbs);
+            let divisor = read_biguint(memory, rs2, num_limbs);
+            if divisor == BigUint::default() {
+                bail!("Division by zero");
+            }
+            let quotient = &dividend / &divisor;
+            if quotient.bits() > bits {
+                bail!("Quotient does not fit in {bits} bits");
+            }
+            let remainder = dividend % divisor;
+
+            let to_limbs = |x: BigUint| {
+                x.to_bytes_le()
+                    .into_iter()
+                    .map(F::from_canonical_u8)
+                    .chain(repeat(F::ZERO))
+                    .take(num_limbs)
+            };
+            streams.hint_stream = to_limbs(quotient).chain(to_limbs(remainder)).collect();
+            Ok(())
+        }
+    }
+
+    fn read_biguint<F: PrimeField32>(
+        memory: &MemoryController<F>,
+        pointer: u32,
+        num_limbs: usize,
+    ) -> BigUint {
+        let limbs: Vec<u8> = (0..num_limbs)
+            .map(|i| {
+                memory
+                    .unsafe_read_cell(
+                        F::from_canonical_u32(RV32_MEMORY_AS),
+                        F::from_canonical_u32(pointer + i as u32),
+                    )
+                    .as_canonical_u32() as u8
+            })
+            .collect();
+        BigUint::from_bytes_le(&limbs)
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    array,
+    borrow::{Borrow, BorrowMut},
+    sync::Arc,
+};
+
+use openvm_circuit::arch::{
+    AdapterAirContext, AdapterRuntimeContext, MinimalInstruction, Result, VmAdapterInterface,
+    VmCoreAir, VmCoreChip,
+};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, BitwiseOperationLookupChip,
+};
+use openvm_circuit_primitives_derive::AlignedBorrow;
+use openvm_instructions::{instruction::Instruction, UsizeOpcode};
+use openvm_rv32im_transpiler::MulOpcode;
+use openvm_stark_backend::{
+    interaction::InteractionBuilder,
+    p3_air::BaseAir,
+    p3_field::{AbstractField, Field, PrimeField32},
+    rap::BaseAirWithPublicValues,
+};
+
+/// Multiplication of wide integers. Unlike [openvm_rv32im_circuit::MultiplicationCoreChip], which
+/// range checks each carry with a range tuple checker sized for `NUM_LIMBS`, the carries are
+/// decomposed into two limbs and range checked with the bitwise lookup, so wide integers do not
+/// need a large range tuple checker.
+#[repr(C)]
+#[derive(AlignedBorrow)]
+pub struct WideMultiplicationCoreCols<T, const NUM_LIMBS: usize, const LIMB_BITS: usize> {
+    pub a: [T; NUM_LIMBS],
+    pub b: [T; NUM_LIMBS],
+    pub c: [T; NUM_LIMBS],
+    /// `carry[i] = carry_lo[i] + carry_hi[i] * 2^LIMB_BITS`
+    pub carry_lo: [T; NUM_LIMBS],
+    pub carry_hi: [T; NUM_LIMBS],
+    pub is_valid: T,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct WideMultiplicationCoreAir<const NUM_LIMBS: usize, const LIMB_BITS: usize> {
+    pub bus: BitwiseOperationLookupBus,
+    offset: usize,
+}
+
+impl<F: Field, const NUM_LIMBS: usize, const LIMB_BITS: usize> BaseAir<F>
+    for WideMultiplicationCoreAir<NUM_LIMBS, LIMB_BITS>
+{
+    fn width(&self) -> usize {
+        WideMultiplicationCoreCols::<F, NUM_LIMBS, LIMB_BITS>::width()
+    }
+}
+impl<F: Field, const NUM_LIMBS: usize, const LIMB_BITS: usize> BaseAirWithPublicValues<F>
+    for WideMultiplicationCoreAir<NUM_LIMBS, LIMB_BITS>
+{
+}
+
+impl<AB, I, const NUM_LIMBS: usize, const LIMB_BITS: usize> VmCoreAir<AB, I>
+    for WideMultiplicationCoreAir<NUM_LIMBS, LIMB_BITS>
+where
+    AB: InteractionBuilder,
+    I: VmAdapterInterface<AB::Expr>,
+    I::Reads: From<[[AB::Expr; NUM_LIMBS]; 2]>,
+    I::Writes: From<[[AB::Expr; NUM_LIMBS]; 1]>,
+    I::ProcessedInstruction: From<MinimalInstruction<AB::Expr>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        _from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols: &WideMultiplicationCoreCols<_, NUM_LIMBS, LIMB_BITS> = local_core.borrow();
+        builder.assert_bool(cols.is_valid);
+
+        let a = &cols.a;
+        let b = &cols.b;
+        let c = &cols.c;
+        let limb_base = AB::Expr::from_canonical_u32(1 << LIMB_BITS);
+        let carry: [AB::Expr; NUM_LIMBS] =
+            array::from_fn(|i| cols.carry_lo[i] + cols.carry_hi[i] * limb_base.clone());
+
+        // All terms are range checked below, so both sides are less than the field modulus and
+        // the equation holds over the integers.
+        for i in 0..NUM_LIMBS {
+            let expected_limb = if i == 0 {
+                AB::Expr::ZERO
+            } else {
+                carry[i - 1].clone()
+            } + (0..=i).fold(AB::Expr::ZERO, |acc, k| acc + (b[k] * c[i - k]));
+            builder.assert_eq(expected_limb, a[i] + carry[i].clone() * limb_base.clone());
+        }
+
+        for pair in a.chunks_exact(2) {
+            self.bus
+                .send_range(pair[0], pair[1])
+                .eval(builder, cols.is_valid);
+        }
+        for (lo, hi) in cols.carry_lo.iter().zip(cols.carry_hi.iter()) {
+            self.bus.send_range(*lo, *hi).eval(builder, cols.is_valid);
+        }
+
+        let expected_opcode = AB::Expr::from_canonical_usize(MulOpcode::MUL as usize + self.offset);
+
+        AdapterAirContext {
+            to_pc: None,
+            reads: [cols.b.map(Into::into), cols.c.map(Into::into)].into(),
+            writes: [cols.a.map(Into::into)].into(),
+            instruction: MinimalInstruction {
+                is_valid: cols.is_valid.into(),
+                opcode: expected_opcode,
+            }
+            .into(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct WideMultiplicationCoreChip<const NUM_LIMBS: usize, const LIMB_BITS: usize> {
+    pub air: WideMultiplicationCoreAir<NUM_LIMBS, LIMB_BITS>,
+    pub bitwise_lookup_chip: Arc<BitwiseOperationLookupChip<LIMB_BITS>>,
+}
+
+impl<const NUM_LIMBS: usize, const LIMB_BITS: usize>
+    WideMultiplicationCoreChip<NUM_LIMBS, LIMB_BITS>
+{
+    pub fn new(
+        bitwise_lookup_chip: Arc<BitwiseOperationLookupChip<LIMB_BITS>>,
+        offset: usize,
+    ) -> Self {
+        // Every carry is less than NUM_LIMBS * 2^LIMB_BITS, so it fits in two limbs.
+        assert!(NUM_LIMBS % 2 == 0 && NUM_LIMBS <= 1 << LIMB_BITS);
+        Self {
+            air: WideMultiplicationCoreAir {
+                bus: bitwise_lookup_chip.bus(),
+                offset,
+            },
+            bitwise_lookup_chip,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct WideMultiplicationCoreRecord<T, const NUM_LIMBS: usize> {
+    pub a: [T; NUM_LIMBS],
+    pub b: [T; NUM_LIMBS],
+    pub c: [T; NUM_LIMBS],
+    pub carry: [T; NUM_LIMBS],
+}
+
+impl<F: PrimeField32, I: VmAdapterInterface<F>, const NUM_LIMBS: usize, const LIMB_BITS: usize>
+    VmCoreChip<F, I> for WideMultiplicationCoreChip<NUM_LIMBS, LIMB_BITS>
+where
+    I::Reads: Into<[[F; NUM_LIMBS]; 2]>,
+    I::Writes: From<[[F; NUM_LIMBS]; 1]>,
+{
+    type Record = WideMultiplicationCoreRecord<F, NUM_LIMBS>;
+    type Air = WideMultiplicationCoreAir<NUM_LIMBS, LIMB_BITS>;
+
+    #[allow(clippy::type_complexity)]
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction<F>,
+        _from_pc: u32,
+        reads: I::Reads,
+    ) -> Result<(AdapterRuntimeContext<F, I>, Self::Record)> {
+        let Instruction { opcode, .. } = instruction;
+        assert_eq!(
+            MulOpcode::from_usize(opcode.local_opcode_idx(self.air.offset)),
+            MulOpcode::MUL
+        );
+
+        let data: [[F; NUM_LIMBS]; 2] = reads.into();
+        let b = data[0].map(|x| x.as_canonical_u32());
+        let c = data[1].map(|y| y.as_canonical_u32());
+        let (a, carry) = run_wide_mul::<NUM_LIMBS, LIMB_BITS>(&b, &c);
+
+        for pair in a.chunks_exact(2) {
+            self.bitwise_lookup_chip.request_range(pair[0], pair[1]);
+        }
+        let limb_mask = (1 << LIMB_BITS) - 1;
+        for carry in carry {
+            self.bitwise_lookup_chip
+                .request_range(carry & limb_mask, carry >> LIMB_BITS);
+        }
+
+        let output = AdapterRuntimeContext::without_pc([a.map(F::from_canonical_u32)]);
+        let record = WideMultiplicationCoreRecord {
+            a: a.map(F::from_canonical_u32),
+            b: data[0],
+            c: data[1],
+            carry: carry.map(F::from_canonical_u32),
+        };
+
+        Ok((output, record))
+    }
+
+    fn get_opcode_name(&self, opcode: usize) -> String {
+        format!("{:?}", MulOpcode::from_usize(opcode - self.air.offset))
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        let row_slice: &mut WideMultiplicationCoreCols<_, NUM_LIMBS, LIMB_BITS> =
+            row_slice.borrow_mut();
+        let limb_mask = (1 << LIMB_BITS) - 1;
+        row_slice.a = record.a;
+        row_slice.b = record.b;
+        row_slice.c = record.c;
+        row_slice.carry_lo = record
+            .carry
+            .map(|carry| F::from_canonical_u32(carry.as_canonical_u32() & limb_mask));
+        row_slice.carry_hi = record
+            .carry
+            .map(|carry| F::from_canonical_u32(carry.as_canonical_u32() >> LIMB_BITS));
+        row_slice.is_valid = F::ONE;
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
+
+// returns mul, carry
+pub(crate) fn run_wide_mul<const NUM_LIMBS: usize, const LIMB_BITS: usize>(
+    x: &[u32; NUM_LIMBS],
+    y: &[u32; NUM_LIMBS],
+) -> ([u32; NUM_LIMBS], [u32; NUM_LIMBS]) {
+    let mut result = [0; NUM_LIMBS];
+    let mut carry = [0; NUM_LIMBS];
+    for i in 0..NUM_LIMBS {
+        if i > 0 {
+            result[i] = carry[i - 1];
+        }
+        for j in 0..=i {
+            result[i] += x[j] * y[i - j];
+        }
+        carry[i] = result[i] >> LIMB_BITS;
+        result[i] %= 1 << LIMB_BITS;
+    }
+    (result, carry)
+}
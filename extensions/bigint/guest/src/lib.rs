@@ -2,10 +2,12 @@
 
 mod i256;
 mod u256;
+mod wide;
 
 pub use i256::*;
 use strum_macros::FromRepr;
 pub use u256::*;
+pub use wide::*;
 
 mod utils;
 #[allow(unused)]
@@ -16,7 +18,9 @@ pub const OPCODE: u8 = 0x0b;
 pub const INT256_FUNCT3: u8 = 0b101;
 pub const BEQ256_FUNCT3: u8 = 0b110;
 
-/// funct7 options for 256-bit integer instructions.
+/// funct7 options for big integer instructions.
+/// The funct7 field equals `width_idx * INT_MAX_KINDS + base_funct7`, where the integer width in
+/// bits is `INT_WIDTH_BITS[width_idx]`. The 256-bit integers use `width_idx = 0`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, FromRepr)]
 #[repr(u8)]
 pub enum Int256Funct7 {
@@ -31,7 +35,17 @@ pub enum Int256Funct7 {
     Slt,
     Sltu,
     Mul,
+    /// Hint the quotient and remainder of a double-width dividend by a divisor.
+    /// Only supported for the wide unsigned integers.
+    HintDivRem,
 }
 
+impl Int256Funct7 {
+    pub const INT_MAX_KINDS: u8 = 16;
+}
+
+/// Bit widths of the integers, indexed by `width_idx`.
+pub const INT_WIDTH_BITS: [usize; 4] = [256, 512, 1024, 2048];
+
 #[cfg(all(feature = "export-intrinsics", target_os = "zkvm"))]
 pub mod externs;
@@ -0,0 +1,398 @@
+use core::{
+    cmp::{Eq, Ord, Ordering, PartialEq, PartialOrd},
+    ops::{
+        Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Mul,
+        MulAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+    },
+};
+
+#[cfg(target_os = "zkvm")]
+use {
+    super::{Int256Funct7, INT256_FUNCT3, OPCODE},
+    core::{arch::asm, mem::MaybeUninit},
+    openvm_platform::custom_insn_r,
+};
+#[cfg(not(target_os = "zkvm"))]
+use {num_bigint_dig::BigUint, num_traits::One, openvm::utils::biguint_to_limbs};
+
+use crate::impl_bin_op;
+
+/// Generates an unsigned integer type of `$num_bytes` bytes backed by the wide integer
+/// intrinsics of width index `$width_idx`.
+macro_rules! impl_wide_uint {
+    ($name:ident, $num_bytes:literal, $width_idx:literal, $doc:literal) => {
+        #[doc = $doc]
+        #[derive(Debug)]
+        #[repr(align($num_bytes), C)]
+        pub struct $name {
+            limbs: [u8; $num_bytes],
+        }
+
+        impl $name {
+            /// The number of bits of this integer type.
+            pub const BITS: usize = $num_bytes * 8;
+
+            /// The maximum value.
+            pub const MAX: Self = Self {
+                limbs: [u8::MAX; $num_bytes],
+            };
+
+            /// The minimum value.
+            pub const MIN: Self = Self {
+                limbs: [0u8; $num_bytes],
+            };
+
+            /// The zero constant.
+            pub const ZERO: Self = Self {
+                limbs: [0u8; $num_bytes],
+            };
+
+            #[cfg(target_os = "zkvm")]
+            const fn funct7(op: Int256Funct7) -> u8 {
+                $width_idx * Int256Funct7::INT_MAX_KINDS + op as u8
+            }
+
+            /// Value of this integer as a BigUint.
+            #[cfg(not(target_os = "zkvm"))]
+            pub fn as_biguint(&self) -> BigUint {
+                BigUint::from_bytes_le(&self.limbs)
+            }
+
+            /// Creates a new integer from a BigUint, truncated to the width of the type.
+            #[cfg(not(target_os = "zkvm"))]
+            pub fn from_biguint(value: &BigUint) -> Self {
+                Self {
+                    limbs: biguint_to_limbs(value),
+                }
+            }
+
+            /// Creates a new integer that equals to the given u8 value.
+            pub fn from_u8(value: u8) -> Self {
+                let mut limbs = [0u8; $num_bytes];
+                limbs[0] = value;
+                Self { limbs }
+            }
+
+            /// Creates a new integer that equals to the given u32 value.
+            pub fn from_u32(value: u32) -> Self {
+                let mut limbs = [0u8; $num_bytes];
+                limbs[..4].copy_from_slice(&value.to_le_bytes());
+                Self { limbs }
+            }
+
+            /// Creates a new integer that equals to the given u64 value.
+            pub fn from_u64(value: u64) -> Self {
+                let mut limbs = [0u8; $num_bytes];
+                limbs[..8].copy_from_slice(&value.to_le_bytes());
+                Self { limbs }
+            }
+
+            /// Creates a new integer from its little-endian byte representation.
+            pub fn from_le_bytes(bytes: [u8; $num_bytes]) -> Self {
+                Self { limbs: bytes }
+            }
+
+            /// The little-endian byte representation of this integer.
+            pub fn as_le_bytes(&self) -> &[u8; $num_bytes] {
+                &self.limbs
+            }
+
+            /// Returns `true` if the bit at index `i` is set.
+            pub fn bit(&self, i: usize) -> bool {
+                (self.limbs[i / 8] >> (i % 8)) & 1 == 1
+            }
+
+            /// Full product of `self` and `rhs` as `(low, high)` halves.
+            pub fn widening_mul(&self, rhs: &Self) -> (Self, Self) {
+                // Split both operands in halves of `BITS / 2` bits so that the partial products
+                // fit in a single integer.
+                let half = Self::from_u32((Self::BITS / 2) as u32);
+                let low_mask = &Self::MAX >> &half;
+                let (a0, a1) = (self & &low_mask, self >> &half);
+                let (b0, b1) = (rhs & &low_mask, rhs >> &half);
+
+                let p00 = &a0 * &b0;
+                let p01 = &a0 * &b1;
+                let p10 = &a1 * &b0;
+                let p11 = &a1 * &b1;
+
+                let mid = &p01 + &p10;
+                let mid_carry = mid < p01;
+                let lo = &p00 + &(&mid << &half);
+                let lo_carry = lo < p00;
+
+                let mut hi = p11 + (&mid >> &half);
+                if mid_carry {
+                    hi += &Self::from_u8(1) << &half;
+                }
+                if lo_carry {
+                    hi += Self::from_u8(1);
+                }
+                (lo, hi)
+            }
+
+            /// Quotient and remainder of the double-width integer `(lo, hi)` divided by `m`.
+            /// Panics if `m` is zero or if the quotient does not fit in a single integer.
+            ///
+            /// The result is hinted by the host and verified in the guest.
+            fn div_rem_wide(lo: &Self, hi: &Self, m: &Self) -> (Self, Self) {
+                let (q, r) = Self::hint_div_rem(lo, hi, m);
+                let (prod_lo, prod_hi) = q.widening_mul(m);
+                let sum_lo = &prod_lo + &r;
+                let mut sum_hi = prod_hi;
+                if sum_lo < prod_lo {
+                    sum_hi += Self::from_u8(1);
+                }
+                assert!(sum_lo == *lo && sum_hi == *hi, "invalid quotient hint");
+                assert!(r < *m, "invalid remainder hint");
+                (q, r)
+            }
+
+            #[cfg(target_os = "zkvm")]
+            fn hint_div_rem(lo: &Self, hi: &Self, m: &Self) -> (Self, Self) {
+                // The dividend must be contiguous in memory.
+                let dividend = [lo.clone(), hi.clone()];
+                let mut q = MaybeUninit::<Self>::uninit();
+                let mut r = MaybeUninit::<Self>::uninit();
+                unsafe {
+                    asm!(
+                        ".insn r {opcode}, {funct3}, {funct7}, x0, {rs1}, {rs2}",
+                        opcode = const OPCODE,
+                        funct3 = const INT256_FUNCT3,
+                        funct7 = const $name::funct7(Int256Funct7::HintDivRem),
+                        rs1 = in(reg) dividend.as_ptr(),
+                        rs2 = in(reg) m as *const Self,
+                    );
+                }
+                openvm_rv32im_guest::hint_buffer_u32!(q.as_mut_ptr(), $num_bytes / 4);
+                openvm_rv32im_guest::hint_buffer_u32!(r.as_mut_ptr(), $num_bytes / 4);
+                unsafe { (q.assume_init(), r.assume_init()) }
+            }
+
+            #[cfg(not(target_os = "zkvm"))]
+            fn hint_div_rem(lo: &Self, hi: &Self, m: &Self) -> (Self, Self) {
+                let dividend = (hi.as_biguint() << Self::BITS) + lo.as_biguint();
+                let m = m.as_biguint();
+                assert!(m != BigUint::default(), "division by zero");
+                let q = &dividend / &m;
+                assert!(q.bits() <= Self::BITS, "quotient overflow");
+                (Self::from_biguint(&q), Self::from_biguint(&(dividend % m)))
+            }
+
+            /// Returns `self mod m`. Panics if `m` is zero.
+            pub fn reduce(&self, m: &Self) -> Self {
+                Self::div_rem_wide(self, &Self::ZERO, m).1
+            }
+
+            /// Returns `self * rhs mod m`. Panics if `m` is zero.
+            pub fn mulmod(&self, rhs: &Self, m: &Self) -> Self {
+                Self::mul_reduced(&self.reduce(m), &rhs.reduce(m), m)
+            }
+
+            /// Returns `a * b mod m` for `a, b < m`, so that the quotient fits in one integer.
+            fn mul_reduced(a: &Self, b: &Self, m: &Self) -> Self {
+                let (lo, hi) = a.widening_mul(b);
+                Self::div_rem_wide(&lo, &hi, m).1
+            }
+
+            /// Returns `self ^ exp mod m`. Panics if `m` is zero.
+            pub fn powmod(&self, exp: &Self, m: &Self) -> Self {
+                let base = self.reduce(m);
+                let mut res = Self::from_u8(1).reduce(m);
+                let num_bits = (0..Self::BITS).rev().find(|&i| exp.bit(i)).map_or(0, |i| i + 1);
+                for i in (0..num_bits).rev() {
+                    res = Self::mul_reduced(&res, &res, m);
+                    if exp.bit(i) {
+                        res = Self::mul_reduced(&res, &base, m);
+                    }
+                }
+                res
+            }
+        }
+
+        impl_bin_op!(
+            $name,
+            Add,
+            AddAssign,
+            add,
+            add_assign,
+            OPCODE,
+            INT256_FUNCT3,
+            $name::funct7(Int256Funct7::Add),
+            +=,
+            |lhs: &$name, rhs: &$name| -> $name {$name::from_biguint(&(lhs.as_biguint() + rhs.as_biguint()))}
+        );
+
+        impl_bin_op!(
+            $name,
+            Sub,
+            SubAssign,
+            sub,
+            sub_assign,
+            OPCODE,
+            INT256_FUNCT3,
+            $name::funct7(Int256Funct7::Sub),
+            -=,
+            |lhs: &$name, rhs: &$name| -> $name {$name::from_biguint(&($name::MAX.as_biguint() + BigUint::one() + lhs.as_biguint() - rhs.as_biguint()))}
+        );
+
+        impl_bin_op!(
+            $name,
+            Mul,
+            MulAssign,
+            mul,
+            mul_assign,
+            OPCODE,
+            INT256_FUNCT3,
+            $name::funct7(Int256Funct7::Mul),
+            *=,
+            |lhs: &$name, rhs: &$name| -> $name {$name::from_biguint(&(lhs.as_biguint() * rhs.as_biguint()))}
+        );
+
+        impl_bin_op!(
+            $name,
+            BitXor,
+            BitXorAssign,
+            bitxor,
+            bitxor_assign,
+            OPCODE,
+            INT256_FUNCT3,
+            $name::funct7(Int256Funct7::Xor),
+            ^=,
+            |lhs: &$name, rhs: &$name| -> $name {$name::from_biguint(&(lhs.as_biguint() ^ rhs.as_biguint()))}
+        );
+
+        impl_bin_op!(
+            $name,
+            BitAnd,
+            BitAndAssign,
+            bitand,
+            bitand_assign,
+            OPCODE,
+            INT256_FUNCT3,
+            $name::funct7(Int256Funct7::And),
+            &=,
+            |lhs: &$name, rhs: &$name| -> $name {$name::from_biguint(&(lhs.as_biguint() & rhs.as_biguint()))}
+        );
+
+        impl_bin_op!(
+            $name,
+            BitOr,
+            BitOrAssign,
+            bitor,
+            bitor_assign,
+            OPCODE,
+            INT256_FUNCT3,
+            $name::funct7(Int256Funct7::Or),
+            |=,
+            |lhs: &$name, rhs: &$name| -> $name {$name::from_biguint(&(lhs.as_biguint() | rhs.as_biguint()))}
+        );
+
+        // The shift amount is taken modulo `BITS` from the two lowest limbs of `rhs`.
+        impl_bin_op!(
+            $name,
+            Shl,
+            ShlAssign,
+            shl,
+            shl_assign,
+            OPCODE,
+            INT256_FUNCT3,
+            $name::funct7(Int256Funct7::Sll),
+            <<=,
+            |lhs: &$name, rhs: &$name| -> $name {$name::from_biguint(&(lhs.as_biguint() << rhs.shift_amount()))}
+        );
+
+        impl_bin_op!(
+            $name,
+            Shr,
+            ShrAssign,
+            shr,
+            shr_assign,
+            OPCODE,
+            INT256_FUNCT3,
+            $name::funct7(Int256Funct7::Srl),
+            >>=,
+            |lhs: &$name, rhs: &$name| -> $name {$name::from_biguint(&(lhs.as_biguint() >> rhs.shift_amount()))}
+        );
+
+        #[cfg(not(target_os = "zkvm"))]
+        impl $name {
+            fn shift_amount(&self) -> usize {
+                u16::from_le_bytes([self.limbs[0], self.limbs[1]]) as usize % Self::BITS
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            fn cmp(&self, other: &Self) -> Ordering {
+                #[cfg(target_os = "zkvm")]
+                {
+                    let mut cmp_result = MaybeUninit::<$name>::uninit();
+                    custom_insn_r!(
+                        OPCODE,
+                        INT256_FUNCT3,
+                        $name::funct7(Int256Funct7::Sltu),
+                        cmp_result.as_mut_ptr(),
+                        self as *const Self,
+                        other as *const Self
+                    );
+                    let mut cmp_result = unsafe { cmp_result.assume_init() };
+                    if cmp_result.limbs[0] != 0 {
+                        return Ordering::Less;
+                    }
+                    custom_insn_r!(
+                        OPCODE,
+                        INT256_FUNCT3,
+                        $name::funct7(Int256Funct7::Sltu),
+                        &mut cmp_result as *mut $name,
+                        other as *const Self,
+                        self as *const Self
+                    );
+                    if cmp_result.limbs[0] != 0 {
+                        return Ordering::Greater;
+                    }
+                    return Ordering::Equal;
+                }
+                #[cfg(not(target_os = "zkvm"))]
+                return self.as_biguint().cmp(&other.as_biguint());
+            }
+        }
+
+        impl Clone for $name {
+            fn clone(&self) -> Self {
+                #[cfg(target_os = "zkvm")]
+                {
+                    let mut uninit: MaybeUninit<Self> = MaybeUninit::uninit();
+                    custom_insn_r!(
+                        OPCODE,
+                        INT256_FUNCT3,
+                        $name::funct7(Int256Funct7::Add),
+                        uninit.as_mut_ptr(),
+                        self as *const Self,
+                        &Self::ZERO as *const Self
+                    );
+                    unsafe { uninit.assume_init() }
+                }
+                #[cfg(not(target_os = "zkvm"))]
+                return Self { limbs: self.limbs };
+            }
+        }
+    };
+}
+
+impl_wide_uint!(U512, 64, 1, "A 512-bit unsigned integer type.");
+impl_wide_uint!(U1024, 128, 2, "A 1024-bit unsigned integer type.");
+impl_wide_uint!(U2048, 256, 3, "A 2048-bit unsigned integer type.");
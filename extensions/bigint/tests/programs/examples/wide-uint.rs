@@ -0,0 +1,68 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+openvm::entry!(main);
+use openvm_bigint_guest::{U1024, U2048, U512};
+
+macro_rules! check_ops {
+    ($ty:ident) => {{
+        let a = $ty::from_u64(0xdead_beef_cafe_babe);
+        let b = $ty::from_u32(0x1234_5678);
+        let one = $ty::from_u8(1);
+
+        assert_eq!(&(&a + &b) - &b, a);
+        assert_eq!($ty::ZERO - &one, $ty::MAX);
+        assert_eq!(&$ty::MAX + &one, $ty::ZERO);
+        assert_eq!(&a ^ &a, $ty::ZERO);
+        assert_eq!(&a & &$ty::MAX, a);
+        assert_eq!(&a | &$ty::ZERO, a);
+        assert!(b < a && a > b);
+
+        // Shifts by more than 255 bits.
+        let shift = $ty::from_u32(($ty::BITS - 1) as u32);
+        let top = &one << &shift;
+        assert!(top > a);
+        assert_eq!(&top >> &shift, one);
+
+        // (2^(BITS - 1))^2 = 2^(2 * BITS - 2)
+        let (lo, hi) = top.widening_mul(&top);
+        assert_eq!(lo, $ty::ZERO);
+        assert_eq!(hi, &one << &$ty::from_u32(($ty::BITS - 2) as u32));
+
+        // (2^BITS - 1)^2 = 2^BITS * (2^BITS - 2) + 1
+        let (lo, hi) = $ty::MAX.widening_mul(&$ty::MAX);
+        assert_eq!(lo, one);
+        assert_eq!(hi, &$ty::MAX - &one);
+
+        assert_eq!(
+            a.mulmod(&b, &$ty::from_u32(1_000_000_007)),
+            $ty::from_u32(874_595_138)
+        );
+        assert_eq!($ty::MAX.mulmod(&$ty::MAX, &one), $ty::ZERO);
+    }};
+}
+
+pub fn main() {
+    check_ops!(U512);
+    check_ops!(U1024);
+    check_ops!(U2048);
+
+    // Fermat's little theorem for the prime p = 2^255 - 19.
+    let one = U512::from_u8(1);
+    let p = &(&one << &U512::from_u32(255)) - &U512::from_u8(19);
+    let a = U512::from_u64(0x0123_4567_89ab_cdef);
+    assert_eq!(a.powmod(&(&p - &one), &p), one);
+
+    // Fermat's little theorem for the prime p = 2^521 - 1.
+    let one = U1024::from_u8(1);
+    let p = &(&one << &U1024::from_u32(521)) - &one;
+    let a = U1024::from_u32(3);
+    assert_eq!(a.powmod(&(&p - &one), &p), one);
+
+    // Textbook RSA with modulus 3233 = 61 * 53.
+    let m = U2048::from_u32(3233);
+    let msg = U2048::from_u32(65);
+    let cipher = msg.powmod(&U2048::from_u32(17), &m);
+    assert_eq!(cipher, U2048::from_u32(2790));
+    assert_eq!(cipher.powmod(&U2048::from_u32(2753), &m), msg);
+}
@@ -1,8 +1,8 @@
 #[cfg(test)]
 mod tests {
     use eyre::Result;
-    use openvm_bigint_circuit::Int256Rv32Config;
-    use openvm_bigint_transpiler::Int256TranspilerExtension;
+    use openvm_bigint_circuit::{Int256Rv32Config, WideUintRv32Config};
+    use openvm_bigint_transpiler::{Int256TranspilerExtension, WideUintTranspilerExtension};
     use openvm_circuit::utils::air_test;
     use openvm_instructions::exe::VmExe;
     use openvm_rv32im_transpiler::{
@@ -45,4 +45,20 @@ mod tests {
         air_test(config, openvm_exe);
         Ok(())
     }
+
+    #[test]
+    fn test_wide_uint() -> Result<()> {
+        let elf = build_example_program_at_path(get_programs_dir!(), "wide-uint")?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(WideUintTranspilerExtension),
+        )?;
+        let config = WideUintRv32Config::default();
+        air_test(config, openvm_exe);
+        Ok(())
+    }
 }
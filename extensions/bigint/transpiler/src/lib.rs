@@ -1,7 +1,7 @@
-use openvm_bigint_guest::{Int256Funct7, BEQ256_FUNCT3, INT256_FUNCT3, OPCODE};
+use openvm_bigint_guest::{Int256Funct7, BEQ256_FUNCT3, INT256_FUNCT3, INT_WIDTH_BITS, OPCODE};
 use openvm_instructions::{
-    instruction::Instruction, riscv::RV32_REGISTER_NUM_LIMBS, utils::isize_to_field, UsizeOpcode,
-    VmOpcode,
+    instruction::Instruction, riscv::RV32_REGISTER_NUM_LIMBS, utils::isize_to_field,
+    PhantomDiscriminant, UsizeOpcode, VmOpcode,
 };
 use openvm_instructions_derive::UsizeOpcode;
 use openvm_rv32im_transpiler::{
@@ -10,7 +10,7 @@ use openvm_rv32im_transpiler::{
 use openvm_stark_backend::p3_field::PrimeField32;
 use openvm_transpiler::{util::from_r_type, TranspilerExtension};
 use rrs_lib::instruction_formats::{BType, RType};
-use strum::IntoEnumIterator;
+use strum::{EnumCount, FromRepr, IntoEnumIterator};
 
 // =================================================================================================
 // Intrinsics: 256-bit Integers
@@ -76,6 +76,62 @@ impl Rv32Mul256Opcode {
     }
 }
 
+// =================================================================================================
+// Intrinsics: Wide Unsigned Integers
+// =================================================================================================
+// Each opcode class has one copy of its opcodes per wide integer width, where the opcodes of
+// `width_idx` are shifted by `(width_idx - 1) * COUNT` from the default offset.
+
+#[derive(Copy, Clone, Debug, UsizeOpcode)]
+#[opcode_offset = 0x460]
+pub struct Rv32BaseAluWideOpcode(pub BaseAluOpcode);
+
+impl Rv32BaseAluWideOpcode {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        BaseAluOpcode::iter().map(Self)
+    }
+}
+
+#[derive(Copy, Clone, Debug, UsizeOpcode)]
+#[opcode_offset = 0x470]
+pub struct Rv32ShiftWideOpcode(pub ShiftOpcode);
+
+impl Rv32ShiftWideOpcode {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        ShiftOpcode::iter().map(Self)
+    }
+}
+
+#[derive(Copy, Clone, Debug, UsizeOpcode)]
+#[opcode_offset = 0x480]
+pub struct Rv32LessThanWideOpcode(pub LessThanOpcode);
+
+impl Rv32LessThanWideOpcode {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        LessThanOpcode::iter().map(Self)
+    }
+}
+
+#[derive(Copy, Clone, Debug, UsizeOpcode)]
+#[opcode_offset = 0x490]
+pub struct Rv32MulWideOpcode(pub MulOpcode);
+
+impl Rv32MulWideOpcode {
+    pub fn iter() -> impl Iterator<Item = Self> {
+        MulOpcode::iter().map(Self)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, FromRepr)]
+#[repr(u16)]
+pub enum BigIntPhantom {
+    /// Uses `c_upper` as the `width_idx` of the integers. Peeks at `[r32{0}(a)..r32{0}(a) + 2 * N]_2`
+    /// to get a dividend of twice the width and at `[r32{0}(b)..r32{0}(b) + N]_2` to get the
+    /// divisor, where `N` is the number of bytes of the width. Then resets the hint stream to the
+    /// quotient followed by the remainder, as `N` bytes each.
+    HintDivRem = 0x50,
+}
+
 #[derive(Default)]
 pub struct Int256TranspilerExtension;
 
@@ -98,6 +154,10 @@ impl<F: PrimeField32> TranspilerExtension<F> for Int256TranspilerExtension {
         let dec_insn = RType::new(instruction_u32);
         let instruction = match funct3 {
             INT256_FUNCT3 => {
+                if dec_insn.funct7 as u8 >= Int256Funct7::INT_MAX_KINDS {
+                    // Wide integer instruction
+                    return None;
+                }
                 let global_opcode = match Int256Funct7::from_repr(dec_insn.funct7 as u8) {
                     Some(Int256Funct7::Add) => {
                         BaseAluOpcode::ADD as usize + Rv32BaseAlu256Opcode::default_offset()
@@ -157,3 +217,102 @@ impl<F: PrimeField32> TranspilerExtension<F> for Int256TranspilerExtension {
         instruction.map(|instruction| (instruction, 1))
     }
 }
+
+#[derive(Default)]
+pub struct WideUintTranspilerExtension;
+
+impl<F: PrimeField32> TranspilerExtension<F> for WideUintTranspilerExtension {
+    fn process_custom(&self, instruction_stream: &[u32]) -> Option<(Instruction<F>, usize)> {
+        if instruction_stream.is_empty() {
+            return None;
+        }
+        let instruction_u32 = instruction_stream[0];
+        let opcode = (instruction_u32 & 0x7f) as u8;
+        let funct3 = ((instruction_u32 >> 12) & 0b111) as u8;
+
+        if opcode != OPCODE || funct3 != INT256_FUNCT3 {
+            return None;
+        }
+
+        let dec_insn = RType::new(instruction_u32);
+        let base_funct7 = (dec_insn.funct7 as u8) % Int256Funct7::INT_MAX_KINDS;
+        let width_idx = ((dec_insn.funct7 as u8) / Int256Funct7::INT_MAX_KINDS) as usize;
+        if width_idx == 0 || width_idx >= INT_WIDTH_BITS.len() {
+            return None;
+        }
+        let width_shift = width_idx - 1;
+
+        if let Some(Int256Funct7::HintDivRem) = Int256Funct7::from_repr(base_funct7) {
+            assert_eq!(dec_insn.rd, 0);
+            return Some((
+                Instruction::phantom(
+                    PhantomDiscriminant(BigIntPhantom::HintDivRem as u16),
+                    F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rs1),
+                    F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rs2),
+                    width_idx as u16,
+                ),
+                1,
+            ));
+        }
+
+        let global_opcode = match Int256Funct7::from_repr(base_funct7) {
+            Some(Int256Funct7::Add) => {
+                BaseAluOpcode::ADD as usize
+                    + Rv32BaseAluWideOpcode::default_offset()
+                    + width_shift * BaseAluOpcode::COUNT
+            }
+            Some(Int256Funct7::Sub) => {
+                BaseAluOpcode::SUB as usize
+                    + Rv32BaseAluWideOpcode::default_offset()
+                    + width_shift * BaseAluOpcode::COUNT
+            }
+            Some(Int256Funct7::Xor) => {
+                BaseAluOpcode::XOR as usize
+                    + Rv32BaseAluWideOpcode::default_offset()
+                    + width_shift * BaseAluOpcode::COUNT
+            }
+            Some(Int256Funct7::Or) => {
+                BaseAluOpcode::OR as usize
+                    + Rv32BaseAluWideOpcode::default_offset()
+                    + width_shift * BaseAluOpcode::COUNT
+            }
+            Some(Int256Funct7::And) => {
+                BaseAluOpcode::AND as usize
+                    + Rv32BaseAluWideOpcode::default_offset()
+                    + width_shift * BaseAluOpcode::COUNT
+            }
+            Some(Int256Funct7::Sll) => {
+                ShiftOpcode::SLL as usize
+                    + Rv32ShiftWideOpcode::default_offset()
+                    + width_shift * ShiftOpcode::COUNT
+            }
+            Some(Int256Funct7::Srl) => {
+                ShiftOpcode::SRL as usize
+                    + Rv32ShiftWideOpcode::default_offset()
+                    + width_shift * ShiftOpcode::COUNT
+            }
+            Some(Int256Funct7::Sra) => {
+                ShiftOpcode::SRA as usize
+                    + Rv32ShiftWideOpcode::default_offset()
+                    + width_shift * ShiftOpcode::COUNT
+            }
+            Some(Int256Funct7::Slt) => {
+                LessThanOpcode::SLT as usize
+                    + Rv32LessThanWideOpcode::default_offset()
+                    + width_shift * LessThanOpcode::COUNT
+            }
+            Some(Int256Funct7::Sltu) => {
+                LessThanOpcode::SLTU as usize
+                    + Rv32LessThanWideOpcode::default_offset()
+                    + width_shift * LessThanOpcode::COUNT
+            }
+            Some(Int256Funct7::Mul) => {
+                MulOpcode::MUL as usize
+                    + Rv32MulWideOpcode::default_offset()
+                    + width_shift * MulOpcode::COUNT
+            }
+            _ => unimplemented!(),
+        };
+        Some((from_r_type(global_opcode, 2, &dec_insn), 1))
+    }
+}
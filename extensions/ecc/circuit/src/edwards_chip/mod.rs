@@ -0,0 +1,55 @@
+mod add;
+
+pub use add::*;
+
+#[cfg(test)]
+mod tests;
+
+use num_bigint_dig::BigUint;
+use openvm_circuit::{arch::VmChipWrapper, system::memory::MemoryControllerRef};
+use openvm_circuit_derive::InstructionExecutor;
+use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
+use openvm_ecc_transpiler::Rv32EdwardsOpcode;
+use openvm_mod_circuit_builder::{ExprBuilderConfig, FieldExpressionCoreChip};
+use openvm_rv32_adapters::Rv32VecHeapAdapterChip;
+use openvm_stark_backend::p3_field::PrimeField32;
+
+/// BLOCK_SIZE: how many cells do we read at a time, must be a power of 2.
+/// BLOCKS: how many blocks do we need to represent one input or output
+/// For ed25519, BLOCK_SIZE = 32, BLOCKS = 2.
+#[derive(Chip, ChipUsageGetter, InstructionExecutor)]
+pub struct TeAddChip<F: PrimeField32, const BLOCKS: usize, const BLOCK_SIZE: usize>(
+    VmChipWrapper<
+        F,
+        Rv32VecHeapAdapterChip<F, 2, BLOCKS, BLOCKS, BLOCK_SIZE, BLOCK_SIZE>,
+        FieldExpressionCoreChip,
+    >,
+);
+
+impl<F: PrimeField32, const BLOCKS: usize, const BLOCK_SIZE: usize>
+    TeAddChip<F, BLOCKS, BLOCK_SIZE>
+{
+    pub fn new(
+        adapter: Rv32VecHeapAdapterChip<F, 2, BLOCKS, BLOCKS, BLOCK_SIZE, BLOCK_SIZE>,
+        memory_controller: MemoryControllerRef<F>,
+        config: ExprBuilderConfig,
+        offset: usize,
+        a: BigUint,
+        d: BigUint,
+    ) -> Self {
+        let expr = te_add_expr(config, memory_controller.borrow().range_checker.bus(), a, d);
+        let core = FieldExpressionCoreChip::new(
+            expr,
+            offset,
+            vec![
+                Rv32EdwardsOpcode::EC_ADD as usize,
+                Rv32EdwardsOpcode::SETUP_EC_ADD as usize,
+            ],
+            vec![],
+            memory_controller.borrow().range_checker.clone(),
+            "TeAdd",
+            false,
+        );
+        Self(VmChipWrapper::new(adapter, core, memory_controller))
+    }
+}
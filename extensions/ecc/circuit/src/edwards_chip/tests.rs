@@ -0,0 +1,138 @@
+use std::{str::FromStr, sync::Arc};
+
+use num_bigint_dig::BigUint;
+use num_traits::{One, Zero};
+use openvm_circuit::arch::{testing::VmChipTestBuilder, BITWISE_OP_LOOKUP_BUS};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, BitwiseOperationLookupChip,
+};
+use openvm_ecc_transpiler::Rv32EdwardsOpcode;
+use openvm_instructions::{riscv::RV32_CELL_BITS, UsizeOpcode};
+use openvm_mod_circuit_builder::{test_utils::biguint_to_limbs, ExprBuilderConfig};
+use openvm_rv32_adapters::{rv32_write_heap_default, Rv32VecHeapAdapterChip};
+use openvm_stark_backend::p3_field::AbstractField;
+use openvm_stark_sdk::p3_baby_bear::BabyBear;
+
+use super::TeAddChip;
+use crate::ED25519_CONFIG;
+
+const NUM_LIMBS: usize = 32;
+const LIMB_BITS: usize = 8;
+const BLOCK_SIZE: usize = 32;
+type F = BabyBear;
+
+lazy_static::lazy_static! {
+    // Multiples of the Ed25519 base point.
+    pub static ref SampleEdwardsPoints: Vec<(BigUint, BigUint)> = {
+        let x1 = BigUint::from_str(
+            "15112221349535400772501151409588531511454012693041857206046113283949847762202",
+        )
+        .unwrap();
+        let y1 = BigUint::from_str(
+            "46316835694926478169428394003475163141307993866256225615783033603165251855960",
+        )
+        .unwrap();
+        let x2 = BigUint::from_str(
+            "24727413235106541002554574571675588834622768167397638456726423682521233608206",
+        )
+        .unwrap();
+        let y2 = BigUint::from_str(
+            "15549675580280190176352668710449542251549572066445060580507079593062643049417",
+        )
+        .unwrap();
+        let x3 = BigUint::from_str(
+            "46896733464454938657123544595386787789046198280132665686241321779790909858396",
+        )
+        .unwrap();
+        let y3 = BigUint::from_str(
+            "8324843778533443976490377120369201138301417226297555316741202210403726505172",
+        )
+        .unwrap();
+        vec![(x1, y1), (x2, y2), (x3, y3)]
+    };
+}
+
+fn point_limbs((x, y): &(BigUint, BigUint)) -> [[F; NUM_LIMBS]; 2] {
+    [x, y].map(|c| biguint_to_limbs::<NUM_LIMBS>(c.clone(), LIMB_BITS).map(F::from_canonical_u32))
+}
+
+#[test]
+fn test_te_add() {
+    let mut tester: VmChipTestBuilder<F> = VmChipTestBuilder::default();
+    let config = ExprBuilderConfig {
+        modulus: ED25519_CONFIG.modulus.clone(),
+        num_limbs: NUM_LIMBS,
+        limb_bits: LIMB_BITS,
+    };
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = Arc::new(BitwiseOperationLookupChip::<RV32_CELL_BITS>::new(
+        bitwise_bus,
+    ));
+    let adapter = Rv32VecHeapAdapterChip::<F, 2, 2, 2, BLOCK_SIZE, BLOCK_SIZE>::new(
+        tester.execution_bus(),
+        tester.program_bus(),
+        tester.memory_controller(),
+        bitwise_chip.clone(),
+    );
+    let mut chip = TeAddChip::new(
+        adapter,
+        tester.memory_controller(),
+        config,
+        Rv32EdwardsOpcode::default_offset(),
+        ED25519_CONFIG.a.clone(),
+        ED25519_CONFIG.d.clone(),
+    );
+
+    let [g, g2, g3] = [0, 1, 2].map(|i| SampleEdwardsPoints[i].clone());
+    let identity = (BigUint::zero(), BigUint::one());
+    // Addition, doubling and adding the identity all use the same formula.
+    let cases = [
+        (g.clone(), g2.clone(), g3),
+        (g.clone(), g, g2.clone()),
+        (g2.clone(), identity, g2),
+    ];
+    let output_indices = chip.0.core.expr().builder.output_indices.clone();
+    for (p1, p2, sum) in cases {
+        let r = chip
+            .0
+            .core
+            .expr()
+            .execute(vec![p1.0, p1.1, p2.0, p2.1], vec![]);
+        assert_eq!(r[output_indices[0]], sum.0);
+        assert_eq!(r[output_indices[1]], sum.1);
+    }
+
+    let prime_limbs: [F; NUM_LIMBS] = chip
+        .0
+        .core
+        .expr()
+        .prime_limbs
+        .iter()
+        .map(|n| F::from_canonical_usize(*n))
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    let mut one_limbs = [F::ZERO; NUM_LIMBS];
+    one_limbs[0] = F::ONE;
+    let setup_instruction = rv32_write_heap_default(
+        &mut tester,
+        vec![prime_limbs, one_limbs], // inputs[0] = prime, others doesn't matter
+        vec![one_limbs, one_limbs],
+        chip.0.core.air.offset + Rv32EdwardsOpcode::SETUP_EC_ADD as usize,
+    );
+    tester.execute(&mut chip, setup_instruction);
+
+    for (i, j) in [(0, 1), (1, 1)] {
+        let instruction = rv32_write_heap_default(
+            &mut tester,
+            point_limbs(&SampleEdwardsPoints[i]).to_vec(),
+            point_limbs(&SampleEdwardsPoints[j]).to_vec(),
+            chip.0.core.air.offset + Rv32EdwardsOpcode::EC_ADD as usize,
+        );
+        tester.execute(&mut chip, instruction);
+    }
+
+    let tester = tester.build().load(chip).load(bitwise_chip).finalize();
+
+    tester.simple_test().expect("Verification failed");
+}
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use derive_more::derive::From;
+use num_bigint_dig::BigUint;
+use once_cell::sync::Lazy;
+use openvm_circuit::{
+    arch::{SystemPort, VmExtension, VmInventory, VmInventoryBuilder, VmInventoryError},
+    system::phantom::PhantomChip,
+};
+use openvm_circuit_derive::{AnyEnum, InstructionExecutor};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, BitwiseOperationLookupChip,
+};
+use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
+use openvm_ecc_guest::ed25519::{ED25519_MODULUS, ED25519_ORDER};
+use openvm_ecc_transpiler::Rv32EdwardsOpcode;
+use openvm_instructions::{UsizeOpcode, VmOpcode};
+use openvm_mod_circuit_builder::ExprBuilderConfig;
+use openvm_rv32_adapters::Rv32VecHeapAdapterChip;
+use openvm_stark_backend::p3_field::PrimeField32;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use strum::EnumCount;
+
+use super::TeAddChip;
+
+#[serde_as]
+#[derive(Clone, Debug, derive_new::new, Serialize, Deserialize)]
+pub struct TeCurveConfig {
+    /// The coordinate modulus of the curve.
+    #[serde_as(as = "DisplayFromStr")]
+    pub modulus: BigUint,
+    /// The scalar field modulus of the curve.
+    #[serde_as(as = "DisplayFromStr")]
+    pub scalar: BigUint,
+    /// The coefficient a of a x^2 + y^2 = 1 + d x^2 y^2.
+    #[serde_as(as = "DisplayFromStr")]
+    pub a: BigUint,
+    /// The coefficient d of a x^2 + y^2 = 1 + d x^2 y^2.
+    #[serde_as(as = "DisplayFromStr")]
+    pub d: BigUint,
+}
+
+pub static ED25519_CONFIG: Lazy<TeCurveConfig> = Lazy::new(|| {
+    let modulus = ED25519_MODULUS.clone();
+    // d = -121665 / 121666
+    let d = (&modulus - BigUint::from(121665u32))
+        * BigUint::from(121666u32).modpow(&(&modulus - BigUint::from(2u32)), &modulus)
+        % &modulus;
+    TeCurveConfig {
+        a: &modulus - BigUint::from(1u32),
+        d,
+        modulus,
+        scalar: ED25519_ORDER.clone(),
+    }
+});
+
+#[derive(Clone, Debug, derive_new::new, Serialize, Deserialize)]
+pub struct EdwardsExtension {
+    pub supported_curves: Vec<TeCurveConfig>,
+}
+
+#[derive(Chip, ChipUsageGetter, InstructionExecutor, AnyEnum)]
+pub enum EdwardsExtensionExecutor<F: PrimeField32> {
+    // 32 limbs prime
+    TeAddRv32_32(TeAddChip<F, 2, 32>),
+    // 48 limbs prime
+    TeAddRv32_48(TeAddChip<F, 6, 16>),
+    // 64 limbs prime
+    TeAddRv32_64(TeAddChip<F, 4, 32>),
+    // 128 limbs prime
+    TeAddRv32_128(TeAddChip<F, 8, 32>),
+}
+
+#[derive(ChipUsageGetter, Chip, AnyEnum, From)]
+pub enum EdwardsExtensionPeriphery<F: PrimeField32> {
+    BitwiseOperationLookup(Arc<BitwiseOperationLookupChip<8>>),
+    Phantom(PhantomChip<F>),
+}
+
+impl<F: PrimeField32> VmExtension<F> for EdwardsExtension {
+    type Executor = EdwardsExtensionExecutor<F>;
+    type Periphery = EdwardsExtensionPeriphery<F>;
+
+    fn build(
+        &self,
+        builder: &mut VmInventoryBuilder<F>,
+    ) -> Result<VmInventory<Self::Executor, Self::Periphery>, VmInventoryError> {
+        let mut inventory = VmInventory::new();
+        let SystemPort {
+            execution_bus,
+            program_bus,
+            memory_controller,
+        } = builder.system_port();
+        let bitwise_lu_chip = if let Some(chip) = builder
+            .find_chip::<Arc<BitwiseOperationLookupChip<8>>>()
+            .first()
+        {
+            Arc::clone(chip)
+        } else {
+            let bitwise_lu_bus = BitwiseOperationLookupBus::new(builder.new_bus_idx());
+            let chip = Arc::new(BitwiseOperationLookupChip::new(bitwise_lu_bus));
+            inventory.add_periphery_chip(chip.clone());
+            chip
+        };
+        let te_add_opcodes =
+            (Rv32EdwardsOpcode::EC_ADD as usize)..=(Rv32EdwardsOpcode::SETUP_EC_ADD as usize);
+
+        for (i, curve) in self.supported_curves.iter().enumerate() {
+            let class_offset = Rv32EdwardsOpcode::default_offset() + i * Rv32EdwardsOpcode::COUNT;
+            let bytes = curve.modulus.bits().div_ceil(8);
+
+            macro_rules! add_edwards_chips {
+                ($num_limbs:literal, $add:ident) => {{
+                    let config = ExprBuilderConfig {
+                        modulus: curve.modulus.clone(),
+                        num_limbs: $num_limbs,
+                        limb_bits: 8,
+                    };
+                    let add_chip = TeAddChip::new(
+                        Rv32VecHeapAdapterChip::new(
+                            execution_bus,
+                            program_bus,
+                            memory_controller.clone(),
+                            bitwise_lu_chip.clone(),
+                        ),
+                        memory_controller.clone(),
+                        config,
+                        class_offset,
+                        curve.a.clone(),
+                        curve.d.clone(),
+                    );
+                    inventory.add_executor(
+                        EdwardsExtensionExecutor::$add(add_chip),
+                        te_add_opcodes
+                            .clone()
+                            .map(|x| VmOpcode::from_usize(x + class_offset)),
+                    )?;
+                }};
+            }
+
+            // The block layout must match the one chosen by `moduli_declare!` in the guest.
+            if bytes <= 32 {
+                add_edwards_chips!(32, TeAddRv32_32);
+            } else if bytes <= 48 {
+                add_edwards_chips!(48, TeAddRv32_48);
+            } else if bytes <= 64 {
+                add_edwards_chips!(64, TeAddRv32_64);
+            } else if bytes <= 128 {
+                add_edwards_chips!(128, TeAddRv32_128);
+            } else {
+                panic!("Modulus too large: at most 128 bytes are supported");
+            }
+        }
+
+        Ok(inventory)
+    }
+}
@@ -1,8 +1,10 @@
 mod add_ne;
 mod double;
+pub mod mul;
 
 pub use add_ne::*;
 pub use double::*;
+pub use mul::{EcMulAir, EcMulChip};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,407 @@
+use std::{array::from_fn, borrow::Borrow};
+
+use openvm_circuit::{
+    arch::{ExecutionBridge, ExecutionState},
+    system::memory::{offline_checker::MemoryBridge, MemoryAddress},
+};
+use openvm_circuit_primitives::{bitwise_op_lookup::BitwiseOperationLookupBus, utils::not, SubAir};
+use openvm_ecc_transpiler::Rv32WeierstrassOpcode;
+use openvm_instructions::riscv::{
+    RV32_CELL_BITS, RV32_MEMORY_AS, RV32_REGISTER_AS, RV32_REGISTER_NUM_LIMBS,
+};
+use openvm_mod_circuit_builder::{FieldExpr, FieldExprCols};
+use openvm_rv32im_circuit::adapters::abstract_compose;
+use openvm_stark_backend::{
+    interaction::InteractionBuilder,
+    p3_air::{Air, AirBuilder, BaseAir},
+    p3_field::AbstractField,
+    p3_matrix::Matrix,
+    rap::{BaseAirWithPublicValues, PartitionedBaseAir},
+};
+
+use super::columns::{EcMulCols, EC_MUL_ROWS_PER_BYTE};
+
+/// AIR for the EC_MUL instruction, which computes `[rd:2*NUM_LIMBS]_2 <- s * P` for the point
+/// `P = [rs1:2*NUM_LIMBS]_2` and the scalar `s = [rs2:NUM_LIMBS]_2` in little-endian bytes.
+///
+/// Scalar multiplication is MSB-first double-and-add, with one row per bit of the scalar. Each row
+/// doubles the accumulator and, if the bit is set, adds `P` to it, using the field expressions of
+/// [super::super::ec_double_expr] and [super::super::ec_add_ne_expr]. The accumulator is identity
+/// until the first set bit, which is handled by the `is_started` column instead of the field
+/// expressions.
+///
+/// The AIR assumes that `P` is not identity, that `P` has prime order `n`, and that `0 < s < n`.
+/// Under these assumptions none of the doublings or additions hit an exceptional case. A zero
+/// scalar cannot be proven.
+#[derive(Clone)]
+pub struct EcMulAir<const NUM_LIMBS: usize, const BLOCKS: usize, const BLOCK_SIZE: usize> {
+    pub execution_bridge: ExecutionBridge,
+    pub memory_bridge: MemoryBridge,
+    /// Bus to send 8-bit range check requests to.
+    pub bitwise_lookup_bus: BitwiseOperationLookupBus,
+    /// Maximum number of bits allowed for an address pointer
+    pub ptr_max_bits: usize,
+    pub double_expr: FieldExpr,
+    pub add_ne_expr: FieldExpr,
+    pub(super) offset: usize,
+}
+
+impl<F, const NUM_LIMBS: usize, const BLOCKS: usize, const BLOCK_SIZE: usize>
+    BaseAirWithPublicValues<F> for EcMulAir<NUM_LIMBS, BLOCKS, BLOCK_SIZE>
+{
+}
+impl<F, const NUM_LIMBS: usize, const BLOCKS: usize, const BLOCK_SIZE: usize> PartitionedBaseAir<F>
+    for EcMulAir<NUM_LIMBS, BLOCKS, BLOCK_SIZE>
+{
+}
+impl<F, const NUM_LIMBS: usize, const BLOCKS: usize, const BLOCK_SIZE: usize> BaseAir<F>
+    for EcMulAir<NUM_LIMBS, BLOCKS, BLOCK_SIZE>
+{
+    fn width(&self) -> usize {
+        EcMulCols::<F, NUM_LIMBS, BLOCKS, BLOCK_SIZE>::width()
+            + BaseAir::<F>::width(&self.double_expr)
+            + BaseAir::<F>::width(&self.add_ne_expr)
+    }
+}
+
+impl<
+        AB: InteractionBuilder,
+        const NUM_LIMBS: usize,
+        const BLOCKS: usize,
+        const BLOCK_SIZE: usize,
+    > Air<AB> for EcMulAir<NUM_LIMBS, BLOCKS, BLOCK_SIZE>
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let (local, local_double, local_add) = self.split_row(&*local);
+        let (next, next_double, _) = self.split_row(&*next);
+
+        SubAir::eval(&self.double_expr, builder, local_double);
+        SubAir::eval(&self.add_ne_expr, builder, local_add);
+        let double = self.double_expr.load_vars(local_double);
+        let add = self.add_ne_expr.load_vars(local_add);
+        let next_double = self.double_expr.load_vars(next_double);
+
+        self.eval_rows(builder, local, next);
+        self.eval_scalar_bits(builder, local, next);
+        self.eval_accumulator(builder, local, &double, &add, &next_double);
+        self.eval_memory(builder, local);
+    }
+}
+
+impl<const NUM_LIMBS: usize, const BLOCKS: usize, const BLOCK_SIZE: usize>
+    EcMulAir<NUM_LIMBS, BLOCKS, BLOCK_SIZE>
+{
+    /// Splits a row into the columns of the AIR and of the doubling and addition expressions.
+    pub fn split_row<'a, T>(
+        &self,
+        row: &'a [T],
+    ) -> (
+        &'a EcMulCols<T, NUM_LIMBS, BLOCKS, BLOCK_SIZE>,
+        &'a [T],
+        &'a [T],
+    ) {
+        let (cols, exprs) = row.split_at(EcMulCols::<T, NUM_LIMBS, BLOCKS, BLOCK_SIZE>::width());
+        let (double, add) = exprs.split_at(self.double_width());
+        (cols.borrow(), double, add)
+    }
+
+    pub(super) fn double_width(&self) -> usize {
+        BaseAir::<u8>::width(&self.double_expr)
+    }
+
+    /// Constrains the row layout: every instruction takes `EC_MUL_ROWS_PER_BYTE * NUM_LIMBS`
+    /// consecutive rows, processing the scalar bits from the most significant, and the padding
+    /// rows come last.
+    fn eval_rows<AB: InteractionBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &EcMulCols<AB::Var, NUM_LIMBS, BLOCKS, BLOCK_SIZE>,
+        next: &EcMulCols<AB::Var, NUM_LIMBS, BLOCKS, BLOCK_SIZE>,
+    ) {
+        builder.assert_bool(local.is_valid);
+        builder.assert_bool(local.is_first);
+        for flag in local.byte_flags.iter().chain(&local.bit_flags) {
+            builder.assert_bool(*flag);
+        }
+        builder.assert_eq(
+            local
+                .byte_flags
+                .iter()
+                .fold(AB::Expr::ZERO, |acc, &x| acc + x),
+            local.is_valid,
+        );
+        builder.assert_eq(
+            local
+                .bit_flags
+                .iter()
+                .fold(AB::Expr::ZERO, |acc, &x| acc + x),
+            local.is_valid,
+        );
+        builder.assert_eq(local.is_last, local.byte_flags[0] * local.bit_flags[0]);
+
+        let mut first_builder = builder.when(local.is_first);
+        first_builder.assert_one(local.byte_flags[NUM_LIMBS - 1]);
+        first_builder.assert_one(local.bit_flags[EC_MUL_ROWS_PER_BYTE - 1]);
+
+        builder
+            .when_first_row()
+            .assert_eq(local.is_first, local.is_valid);
+        builder
+            .when_last_row()
+            .assert_eq(local.is_valid, local.is_last);
+        builder
+            .when_transition()
+            .assert_eq(next.is_first, next.is_valid * local.is_last);
+        builder
+            .when_transition()
+            .when(not(local.is_valid))
+            .assert_zero(next.is_valid);
+
+        // The next row belongs to the same instruction
+        let mut transition_builder = builder.when_transition();
+        let mut instruction_builder = transition_builder.when(local.is_valid - local.is_last);
+        instruction_builder.assert_one(next.is_valid);
+        local
+            .instruction
+            .assert_eq(&mut instruction_builder, next.instruction);
+        for j in 0..EC_MUL_ROWS_PER_BYTE - 1 {
+            instruction_builder.assert_eq(next.bit_flags[j], local.bit_flags[j + 1]);
+        }
+        instruction_builder.assert_eq(next.bit_flags[EC_MUL_ROWS_PER_BYTE - 1], local.bit_flags[0]);
+        // Move to the next less significant byte after the last bit of the current one
+        for k in 0..NUM_LIMBS {
+            let higher: AB::Expr = if k + 1 < NUM_LIMBS {
+                local.byte_flags[k + 1].into()
+            } else {
+                AB::Expr::ZERO
+            };
+            instruction_builder.assert_eq(
+                next.byte_flags[k],
+                local.byte_flags[k] + local.bit_flags[0] * (higher - local.byte_flags[k]),
+            );
+        }
+    }
+
+    /// Decomposes each scalar byte into bits, from the most significant bit of the byte.
+    fn eval_scalar_bits<AB: InteractionBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &EcMulCols<AB::Var, NUM_LIMBS, BLOCKS, BLOCK_SIZE>,
+        next: &EcMulCols<AB::Var, NUM_LIMBS, BLOCKS, BLOCK_SIZE>,
+    ) {
+        builder.assert_bool(local.bit);
+        let current_byte = local
+            .byte_flags
+            .iter()
+            .zip(local.instruction.scalar)
+            .fold(AB::Expr::ZERO, |acc, (&flag, byte)| acc + flag * byte);
+        let shifted_bit = local.bit * AB::F::from_canonical_u32(1 << (EC_MUL_ROWS_PER_BYTE - 1));
+
+        builder
+            .when(local.bit_flags[EC_MUL_ROWS_PER_BYTE - 1])
+            .assert_eq(local.byte_rem, current_byte);
+        // After the last bit of the byte, no bits may remain
+        builder
+            .when(local.bit_flags[0])
+            .assert_eq(local.byte_rem, shifted_bit.clone());
+        builder
+            .when_transition()
+            .when(local.is_valid - local.bit_flags[0])
+            .assert_eq(next.byte_rem, (local.byte_rem - shifted_bit) * AB::F::TWO);
+    }
+
+    /// Constrains the double-and-add step of each row and the result on the last row.
+    fn eval_accumulator<AB: InteractionBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &EcMulCols<AB::Var, NUM_LIMBS, BLOCKS, BLOCK_SIZE>,
+        double: &FieldExprCols<AB::Var>,
+        add: &FieldExprCols<AB::Var>,
+        next_double: &FieldExprCols<AB::Var>,
+    ) {
+        builder.when(local.is_first).assert_zero(local.is_started);
+        builder
+            .when(not(local.is_valid))
+            .assert_zero(local.is_started);
+        // `is_started` after this row
+        let is_started_next = local.is_started + local.bit - local.is_started * local.bit;
+        builder
+            .when_transition()
+            .when(local.is_valid - local.is_last)
+            .assert_eq(next_double.is_valid, is_started_next.clone());
+        // A zero scalar is not supported
+        builder.when(local.is_last).assert_one(is_started_next);
+
+        // Doubling is applied once the accumulator is not identity, and addition if moreover
+        // the bit is set. The setup mode of the expressions is never used.
+        builder.assert_eq(double.is_valid, local.is_started);
+        builder.assert_eq(add.is_valid, local.is_started * local.bit);
+        builder.assert_eq(double.flags[0], double.is_valid);
+        builder.assert_eq(add.flags[0], add.is_valid);
+
+        let double_out = self.outputs(&self.double_expr, double);
+        let add_out = self.outputs(&self.add_ne_expr, add);
+        let point = local.instruction.point;
+        for c in 0..2 {
+            for i in 0..NUM_LIMBS {
+                let mut add_builder = builder.when(add.is_valid);
+                add_builder.assert_eq(add.inputs[c][i], double_out[c][i]);
+                add_builder.assert_eq(add.inputs[2 + c][i], point[c][i]);
+            }
+        }
+
+        // The accumulator after this row: the sum if the bit is set, else the double, or `P`
+        // if this row processes the first set bit.
+        let acc: [[AB::Expr; NUM_LIMBS]; 2] = from_fn(|c| {
+            from_fn(|i| {
+                add_out[c][i] * add.is_valid
+                    + double_out[c][i] * (double.is_valid - add.is_valid)
+                    + point[c][i] * not::<AB::Expr>(double.is_valid)
+            })
+        });
+        for c in 0..2 {
+            for i in 0..NUM_LIMBS {
+                builder
+                    .when_transition()
+                    .when(next_double.is_valid)
+                    .assert_eq(next_double.inputs[c][i], acc[c][i].clone());
+                builder
+                    .when(local.is_last)
+                    .assert_eq(local.result[c][i], acc[c][i].clone());
+            }
+        }
+    }
+
+    /// Receives the instruction and reads the registers, point and scalar on the first row,
+    /// and writes the result on the last row.
+    fn eval_memory<AB: InteractionBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &EcMulCols<AB::Var, NUM_LIMBS, BLOCKS, BLOCK_SIZE>,
+    ) {
+        let instruction = local.instruction;
+        let timestamp = instruction.start_timestamp;
+        let mut timestamp_delta: usize = 0;
+        let mut timestamp_pp = || {
+            timestamp_delta += 1;
+            timestamp + AB::F::from_canonical_usize(timestamp_delta - 1)
+        };
+
+        for (ptr, val, aux) in [
+            (instruction.rs1_ptr, instruction.rs1_val, 0),
+            (instruction.rs2_ptr, instruction.rs2_val, 1),
+            (instruction.rd_ptr, instruction.rd_val, 2),
+        ] {
+            self.memory_bridge
+                .read(
+                    MemoryAddress::new(AB::F::from_canonical_u32(RV32_REGISTER_AS), ptr),
+                    val,
+                    timestamp_pp(),
+                    &local.reads_aux.register_aux[aux],
+                )
+                .eval(builder, local.is_first);
+        }
+
+        // Range check the most significant limbs of the pointers, see `Rv32VecHeapAdapterAir`
+        let limb_shift = AB::F::from_canonical_usize(
+            1 << (RV32_CELL_BITS * RV32_REGISTER_NUM_LIMBS - self.ptr_max_bits),
+        );
+        let top_limb = |val: [AB::Var; RV32_REGISTER_NUM_LIMBS]| val[RV32_REGISTER_NUM_LIMBS - 1];
+        self.bitwise_lookup_bus
+            .send_range(
+                top_limb(instruction.rs1_val) * limb_shift,
+                top_limb(instruction.rs2_val) * limb_shift,
+            )
+            .eval(builder, local.is_first);
+        self.bitwise_lookup_bus
+            .send_range(
+                top_limb(instruction.rd_val) * limb_shift,
+                top_limb(instruction.rd_val) * limb_shift,
+            )
+            .eval(builder, local.is_first);
+
+        let memory_as = AB::F::from_canonical_u32(RV32_MEMORY_AS);
+        let point_ptr: AB::Expr = abstract_compose(instruction.rs1_val);
+        let scalar_ptr: AB::Expr = abstract_compose(instruction.rs2_val);
+        let result_ptr: AB::Expr = abstract_compose(instruction.rd_val);
+        for c in 0..2 {
+            for i in 0..BLOCKS {
+                self.memory_bridge
+                    .read(
+                        MemoryAddress::new(
+                            memory_as,
+                            point_ptr.clone()
+                                + AB::F::from_canonical_usize(c * NUM_LIMBS + i * BLOCK_SIZE),
+                        ),
+                        from_fn::<_, BLOCK_SIZE, _>(|j| instruction.point[c][i * BLOCK_SIZE + j]),
+                        timestamp_pp(),
+                        &local.reads_aux.point_aux[c][i],
+                    )
+                    .eval(builder, local.is_first);
+            }
+        }
+        for i in 0..BLOCKS {
+            self.memory_bridge
+                .read(
+                    MemoryAddress::new(
+                        memory_as,
+                        scalar_ptr.clone() + AB::F::from_canonical_usize(i * BLOCK_SIZE),
+                    ),
+                    from_fn::<_, BLOCK_SIZE, _>(|j| instruction.scalar[i * BLOCK_SIZE + j]),
+                    timestamp_pp(),
+                    &local.reads_aux.scalar_aux[i],
+                )
+                .eval(builder, local.is_first);
+        }
+        for c in 0..2 {
+            for i in 0..BLOCKS {
+                self.memory_bridge
+                    .write(
+                        MemoryAddress::new(
+                            memory_as,
+                            result_ptr.clone()
+                                + AB::F::from_canonical_usize(c * NUM_LIMBS + i * BLOCK_SIZE),
+                        ),
+                        from_fn::<_, BLOCK_SIZE, _>(|j| local.result[c][i * BLOCK_SIZE + j]),
+                        timestamp_pp(),
+                        &local.writes_aux[c][i],
+                    )
+                    .eval(builder, local.is_last);
+            }
+        }
+        debug_assert_eq!(timestamp_delta, Self::timestamp_change());
+
+        self.execution_bridge
+            .execute_and_increment_pc(
+                AB::Expr::from_canonical_usize(
+                    Rv32WeierstrassOpcode::EC_MUL as usize + self.offset,
+                ),
+                [
+                    instruction.rd_ptr.into(),
+                    instruction.rs1_ptr.into(),
+                    instruction.rs2_ptr.into(),
+                    AB::Expr::from_canonical_u32(RV32_REGISTER_AS),
+                    AB::Expr::from_canonical_u32(RV32_MEMORY_AS),
+                ],
+                ExecutionState::new(instruction.pc, instruction.start_timestamp),
+                AB::Expr::from_canonical_usize(Self::timestamp_change()),
+            )
+            .eval(builder, local.is_first);
+    }
+
+    /// The `x, y` output variables of a point expression.
+    fn outputs<T: Copy>(&self, expr: &FieldExpr, cols: &FieldExprCols<T>) -> [[T; NUM_LIMBS]; 2] {
+        from_fn(|c| {
+            let var = &cols.vars[expr.output_indices[c]];
+            from_fn(|i| var[i])
+        })
+    }
+
+    /// Amount to advance timestamp by after execution of one opcode instruction:
+    /// three register reads, then reads of the point and the scalar and writes of the result.
+    pub const fn timestamp_change() -> usize {
+        3 + 5 * BLOCKS
+    }
+}
@@ -0,0 +1,93 @@
+use openvm_circuit::system::memory::offline_checker::{MemoryReadAuxCols, MemoryWriteAuxCols};
+use openvm_circuit_primitives::utils::assert_array_eq;
+use openvm_circuit_primitives_derive::AlignedBorrow;
+use openvm_instructions::riscv::RV32_REGISTER_NUM_LIMBS;
+use openvm_stark_backend::p3_air::AirBuilder;
+
+/// Number of rows used to process one byte of the scalar.
+pub const EC_MUL_ROWS_PER_BYTE: usize = 8;
+
+/// Columns owned by [super::EcMulAir] itself. They are followed in each row by the columns of
+/// the doubling and of the addition field expressions, in that order.
+///
+/// One instruction takes `EC_MUL_ROWS_PER_BYTE * NUM_LIMBS` rows, one per bit of the scalar,
+/// starting from the most significant bit.
+#[repr(C)]
+#[derive(AlignedBorrow)]
+pub struct EcMulCols<T, const NUM_LIMBS: usize, const BLOCKS: usize, const BLOCK_SIZE: usize> {
+    /// True for all rows that are part of an instruction.
+    /// False on dummy rows only used to pad the height.
+    pub is_valid: T,
+    /// First row of an instruction.
+    pub is_first: T,
+    /// Last row of an instruction. Equals `byte_flags[0] * bit_flags[0]`.
+    pub is_last: T,
+    /// Constant across the rows of one instruction.
+    pub instruction: EcMulInstructionCols<T, NUM_LIMBS>,
+    /// One-hot encoding of the index of the scalar byte processed on this row.
+    pub byte_flags: [T; NUM_LIMBS],
+    /// One-hot encoding of the index of the bit, within its byte, processed on this row.
+    pub bit_flags: [T; EC_MUL_ROWS_PER_BYTE],
+    /// The bits of the current byte not processed before this row, shifted so that
+    /// the current bit is the most significant one: `byte_rem = 128 * bit + lower bits`.
+    pub byte_rem: T,
+    /// The scalar bit processed on this row.
+    pub bit: T,
+    /// Whether a bit equal to one was processed before this row, i.e. whether the accumulator
+    /// is not identity. When set, the accumulator is the input of the doubling.
+    pub is_started: T,
+    /// The result `scalar * point`. Only used on the last row.
+    pub result: [[T; NUM_LIMBS]; 2],
+    /// Only used on the first row.
+    pub reads_aux: EcMulReadsAuxCols<T, BLOCKS, BLOCK_SIZE>,
+    /// Only used on the last row.
+    pub writes_aux: [[MemoryWriteAuxCols<T, BLOCK_SIZE>; BLOCKS]; 2],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, AlignedBorrow)]
+pub struct EcMulInstructionCols<T, const NUM_LIMBS: usize> {
+    pub pc: T,
+    pub start_timestamp: T,
+    /// Pointers to the `rd, rs1, rs2` registers
+    pub rd_ptr: T,
+    pub rs1_ptr: T,
+    pub rs2_ptr: T,
+    /// Register values: pointers to the result, the point and the scalar in memory
+    pub rd_val: [T; RV32_REGISTER_NUM_LIMBS],
+    pub rs1_val: [T; RV32_REGISTER_NUM_LIMBS],
+    pub rs2_val: [T; RV32_REGISTER_NUM_LIMBS],
+    /// The `x, y` coordinates of the point, as little-endian bytes
+    pub point: [[T; NUM_LIMBS]; 2],
+    /// The scalar, as little-endian bytes
+    pub scalar: [T; NUM_LIMBS],
+}
+
+#[repr(C)]
+#[derive(Clone, Debug, AlignedBorrow)]
+pub struct EcMulReadsAuxCols<T, const BLOCKS: usize, const BLOCK_SIZE: usize> {
+    /// Reads of `rs1, rs2, rd`, in that order
+    pub register_aux: [MemoryReadAuxCols<T, RV32_REGISTER_NUM_LIMBS>; 3],
+    pub point_aux: [[MemoryReadAuxCols<T, BLOCK_SIZE>; BLOCKS]; 2],
+    pub scalar_aux: [MemoryReadAuxCols<T, BLOCK_SIZE>; BLOCKS],
+}
+
+impl<T: Copy, const NUM_LIMBS: usize> EcMulInstructionCols<T, NUM_LIMBS> {
+    pub fn assert_eq<AB: AirBuilder>(&self, builder: &mut AB, other: Self)
+    where
+        T: Into<AB::Expr>,
+    {
+        builder.assert_eq(self.pc, other.pc);
+        builder.assert_eq(self.start_timestamp, other.start_timestamp);
+        builder.assert_eq(self.rd_ptr, other.rd_ptr);
+        builder.assert_eq(self.rs1_ptr, other.rs1_ptr);
+        builder.assert_eq(self.rs2_ptr, other.rs2_ptr);
+        assert_array_eq(builder, self.rd_val, other.rd_val);
+        assert_array_eq(builder, self.rs1_val, other.rs1_val);
+        assert_array_eq(builder, self.rs2_val, other.rs2_val);
+        for (a, b) in self.point.into_iter().zip(other.point) {
+            assert_array_eq(builder, a, b);
+        }
+        assert_array_eq(builder, self.scalar, other.scalar);
+    }
+}
@@ -0,0 +1,251 @@
+//! Chip for scalar multiplication of a point on a short Weierstrass curve, in a single instruction.
+use std::{array::from_fn, sync::Arc};
+
+use num_bigint_dig::BigUint;
+use openvm_circuit::{
+    arch::{ExecutionBridge, ExecutionBus, ExecutionError, ExecutionState, InstructionExecutor},
+    system::{
+        memory::{MemoryControllerRef, MemoryReadRecord, MemoryWriteRecord},
+        program::ProgramBus,
+    },
+};
+use openvm_circuit_primitives::{
+    bitwise_op_lookup::BitwiseOperationLookupChip, var_range::VariableRangeCheckerChip,
+};
+use openvm_ecc_transpiler::Rv32WeierstrassOpcode;
+use openvm_instructions::{
+    instruction::Instruction, program::DEFAULT_PC_STEP, riscv::RV32_REGISTER_NUM_LIMBS, UsizeOpcode,
+};
+use openvm_mod_circuit_builder::{ExprBuilderConfig, FieldExpr};
+use openvm_rv32im_circuit::adapters::read_rv32_register;
+use openvm_stark_backend::p3_field::PrimeField32;
+
+use super::{ec_add_ne_expr, ec_double_expr};
+
+pub mod air;
+pub mod columns;
+pub mod trace;
+
+pub use air::EcMulAir;
+
+/// NUM_LIMBS: number of bytes of a coordinate, and of the scalar.
+/// BLOCKS: how many blocks of BLOCK_SIZE bytes represent one coordinate.
+/// For example, for secp256k1, NUM_LIMBS = 32, BLOCKS = 1, BLOCK_SIZE = 32.
+pub struct EcMulChip<
+    F: PrimeField32,
+    const NUM_LIMBS: usize,
+    const BLOCKS: usize,
+    const BLOCK_SIZE: usize,
+> {
+    pub air: EcMulAir<NUM_LIMBS, BLOCKS, BLOCK_SIZE>,
+    pub records: Vec<EcMulRecord<F, BLOCKS, BLOCK_SIZE>>,
+    pub memory_controller: MemoryControllerRef<F>,
+    pub bitwise_lookup_chip: Arc<BitwiseOperationLookupChip<8>>,
+    pub range_checker: Arc<VariableRangeCheckerChip>,
+
+    offset: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct EcMulRecord<F, const BLOCKS: usize, const BLOCK_SIZE: usize> {
+    pub pc: F,
+    /// Reads of the `rs1, rs2, rd` registers, in that order
+    pub register_reads: [MemoryReadRecord<F, RV32_REGISTER_NUM_LIMBS>; 3],
+    pub point_reads: [[MemoryReadRecord<F, BLOCK_SIZE>; BLOCKS]; 2],
+    pub scalar_reads: [MemoryReadRecord<F, BLOCK_SIZE>; BLOCKS],
+    pub result_writes: [[MemoryWriteRecord<F, BLOCK_SIZE>; BLOCKS]; 2],
+}
+
+impl<F: PrimeField32, const NUM_LIMBS: usize, const BLOCKS: usize, const BLOCK_SIZE: usize>
+    EcMulChip<F, NUM_LIMBS, BLOCKS, BLOCK_SIZE>
+{
+    pub fn new(
+        execution_bus: ExecutionBus,
+        program_bus: ProgramBus,
+        memory_controller: MemoryControllerRef<F>,
+        bitwise_lookup_chip: Arc<BitwiseOperationLookupChip<8>>,
+        config: ExprBuilderConfig,
+        offset: usize,
+        a: BigUint,
+    ) -> Self {
+        assert_eq!(config.num_limbs, NUM_LIMBS);
+        assert_eq!(BLOCKS * BLOCK_SIZE, NUM_LIMBS);
+        let ptr_max_bits = memory_controller.borrow().mem_config().pointer_max_bits;
+        let memory_bridge = memory_controller.borrow().memory_bridge();
+        let range_checker = memory_controller.borrow().range_checker.clone();
+        let air = EcMulAir {
+            execution_bridge: ExecutionBridge::new(execution_bus, program_bus),
+            memory_bridge,
+            bitwise_lookup_bus: bitwise_lookup_chip.bus(),
+            ptr_max_bits,
+            double_expr: ec_double_expr(config.clone(), range_checker.bus(), a),
+            add_ne_expr: ec_add_ne_expr(config, range_checker.bus()),
+            offset,
+        };
+        Self {
+            air,
+            records: Vec::new(),
+            memory_controller,
+            bitwise_lookup_chip,
+            range_checker,
+            offset,
+        }
+    }
+}
+
+impl<F: PrimeField32, const NUM_LIMBS: usize, const BLOCKS: usize, const BLOCK_SIZE: usize>
+    InstructionExecutor<F> for EcMulChip<F, NUM_LIMBS, BLOCKS, BLOCK_SIZE>
+{
+    fn execute(
+        &mut self,
+        instruction: Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> Result<ExecutionState<u32>, ExecutionError> {
+        let Instruction {
+            opcode,
+            a,
+            b,
+            c,
+            d,
+            e,
+            ..
+        } = instruction;
+        let local_opcode = Rv32WeierstrassOpcode::from_usize(opcode.local_opcode_idx(self.offset));
+        debug_assert_eq!(local_opcode, Rv32WeierstrassOpcode::EC_MUL);
+
+        let mut memory = self.memory_controller.borrow_mut();
+        debug_assert_eq!(from_state.timestamp, memory.timestamp());
+
+        let (rs1_read, point_ptr) = read_rv32_register(&mut memory, d, b);
+        let (rs2_read, scalar_ptr) = read_rv32_register(&mut memory, d, c);
+        let (rd_read, result_ptr) = read_rv32_register(&mut memory, d, a);
+        for (ptr, len) in [
+            (point_ptr, 2 * NUM_LIMBS),
+            (scalar_ptr, NUM_LIMBS),
+            (result_ptr, 2 * NUM_LIMBS),
+        ] {
+            assert!(ptr as usize + len - 1 < (1 << self.air.ptr_max_bits));
+        }
+
+        let point_reads: [[_; BLOCKS]; 2] = from_fn(|c| {
+            from_fn(|i| {
+                memory.read::<BLOCK_SIZE>(
+                    e,
+                    F::from_canonical_usize(point_ptr as usize + c * NUM_LIMBS + i * BLOCK_SIZE),
+                )
+            })
+        });
+        let scalar_reads: [_; BLOCKS] = from_fn(|i| {
+            memory.read::<BLOCK_SIZE>(
+                e,
+                F::from_canonical_usize(scalar_ptr as usize + i * BLOCK_SIZE),
+            )
+        });
+        let point = point_reads.each_ref().map(|reads| {
+            BigUint::from_bytes_le(&blocks_to_bytes(reads.each_ref().map(|read| read.data)))
+        });
+        let scalar = blocks_to_bytes(scalar_reads.each_ref().map(|read| read.data));
+
+        let Some(result) = ec_mul(
+            &self.air.double_expr,
+            &self.air.add_ne_expr,
+            &point,
+            &scalar,
+        ) else {
+            return Err(ExecutionError::Fail { pc: from_state.pc });
+        };
+        let result_writes: [[_; BLOCKS]; 2] = from_fn(|c| {
+            let mut bytes = result[c].to_bytes_le();
+            bytes.resize(NUM_LIMBS, 0);
+            from_fn(|i| {
+                memory.write::<BLOCK_SIZE>(
+                    e,
+                    F::from_canonical_usize(result_ptr as usize + c * NUM_LIMBS + i * BLOCK_SIZE),
+                    from_fn(|j| F::from_canonical_u8(bytes[i * BLOCK_SIZE + j])),
+                )
+            })
+        });
+
+        let record = EcMulRecord {
+            pc: F::from_canonical_u32(from_state.pc),
+            register_reads: [rs1_read, rs2_read, rd_read],
+            point_reads,
+            scalar_reads,
+            result_writes,
+        };
+        if !memory.is_pure_execution() {
+            self.records.push(record);
+        }
+
+        Ok(ExecutionState {
+            pc: from_state.pc + DEFAULT_PC_STEP,
+            timestamp: memory.timestamp(),
+        })
+    }
+
+    fn get_opcode_name(&self, _: usize) -> String {
+        "EcMul".to_string()
+    }
+}
+
+impl<F: Copy, const BLOCKS: usize, const BLOCK_SIZE: usize> EcMulRecord<F, BLOCKS, BLOCK_SIZE> {
+    pub fn start_timestamp(&self) -> u32 {
+        self.register_reads[0].timestamp
+    }
+}
+
+/// Computes `scalar * point` by MSB-first double-and-add, where `scalar` is in little-endian
+/// bytes. Returns `None` if the scalar is zero.
+pub(crate) fn ec_mul(
+    double_expr: &FieldExpr,
+    add_ne_expr: &FieldExpr,
+    point: &[BigUint; 2],
+    scalar: &[u8],
+) -> Option<[BigUint; 2]> {
+    let mut acc: Option<[BigUint; 2]> = None;
+    for byte in scalar.iter().rev() {
+        for i in (0..8).rev() {
+            acc = ec_mul_step(double_expr, add_ne_expr, point, acc, (byte >> i) & 1 == 1);
+        }
+    }
+    acc
+}
+
+/// One double-and-add step on the accumulator, where `None` is identity.
+pub(crate) fn ec_mul_step(
+    double_expr: &FieldExpr,
+    add_ne_expr: &FieldExpr,
+    point: &[BigUint; 2],
+    acc: Option<[BigUint; 2]>,
+    bit: bool,
+) -> Option<[BigUint; 2]> {
+    let to_point = |coords: Vec<BigUint>| -> [BigUint; 2] { coords.try_into().unwrap() };
+    match acc {
+        Some(acc) => {
+            let double = to_point(double_expr.execute_with_output(acc.to_vec(), vec![true]));
+            if bit {
+                let inputs = double.into_iter().chain(point.iter().cloned()).collect();
+                Some(to_point(
+                    add_ne_expr.execute_with_output(inputs, vec![true]),
+                ))
+            } else {
+                Some(double)
+            }
+        }
+        None => bit.then(|| point.clone()),
+    }
+}
+
+fn blocks_to_bytes<F: PrimeField32, const BLOCKS: usize, const BLOCK_SIZE: usize>(
+    blocks: [[F; BLOCK_SIZE]; BLOCKS],
+) -> Vec<u8> {
+    blocks
+        .iter()
+        .flatten()
+        .map(|x| {
+            x.as_canonical_u32()
+                .try_into()
+                .expect("Memory cell not a byte")
+        })
+        .collect()
+}
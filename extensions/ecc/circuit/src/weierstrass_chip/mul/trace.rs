@@ -0,0 +1,181 @@
+use std::{array::from_fn, borrow::BorrowMut, sync::Arc};
+
+use num_bigint_dig::BigUint;
+use openvm_circuit_primitives::TraceSubRowGenerator;
+use openvm_instructions::riscv::{RV32_CELL_BITS, RV32_REGISTER_NUM_LIMBS};
+use openvm_stark_backend::{
+    config::{StarkGenericConfig, Val},
+    p3_air::BaseAir,
+    p3_field::{AbstractField, PrimeField32},
+    p3_matrix::dense::RowMajorMatrix,
+    p3_maybe_rayon::prelude::*,
+    prover::types::AirProofInput,
+    rap::{get_air_name, AnyRap},
+    Chip, ChipUsageGetter,
+};
+
+use super::{
+    columns::{EcMulCols, EcMulInstructionCols, EC_MUL_ROWS_PER_BYTE},
+    ec_mul_step, EcMulChip,
+};
+
+impl<
+        SC: StarkGenericConfig,
+        const NUM_LIMBS: usize,
+        const BLOCKS: usize,
+        const BLOCK_SIZE: usize,
+    > Chip<SC> for EcMulChip<Val<SC>, NUM_LIMBS, BLOCKS, BLOCK_SIZE>
+where
+    Val<SC>: PrimeField32,
+{
+    fn air(&self) -> Arc<dyn AnyRap<SC>> {
+        Arc::new(self.air.clone())
+    }
+
+    fn generate_air_proof_input(self) -> AirProofInput<SC> {
+        let air = self.air();
+        let trace_width = self.trace_width();
+        let rows_per_instruction = EC_MUL_ROWS_PER_BYTE * NUM_LIMBS;
+        let height = (self.records.len() * rows_per_instruction).next_power_of_two();
+        let records = self.records;
+
+        let aux_cols_factory = self.memory_controller.borrow().aux_cols_factory();
+        let double_width = self.air.double_width();
+        let limb_shift_bits = RV32_CELL_BITS * RV32_REGISTER_NUM_LIMBS - self.air.ptr_max_bits;
+
+        let mut trace = RowMajorMatrix::new(Val::<SC>::zero_vec(height * trace_width), trace_width);
+        // Rows not covered by a record are dummy rows with `is_valid = 0`
+        trace
+            .values
+            .par_chunks_mut(trace_width * rows_per_instruction)
+            .zip(records.into_par_iter())
+            .for_each(|(rows, record)| {
+                let [rs1_read, rs2_read, rd_read] = record.register_reads;
+                let blocks_to_limbs = |blocks: [[Val<SC>; BLOCK_SIZE]; BLOCKS]| {
+                    from_fn::<_, NUM_LIMBS, _>(|i| blocks[i / BLOCK_SIZE][i % BLOCK_SIZE])
+                };
+                let instruction = EcMulInstructionCols {
+                    pc: record.pc,
+                    start_timestamp: Val::<SC>::from_canonical_u32(record.start_timestamp()),
+                    rd_ptr: rd_read.pointer,
+                    rs1_ptr: rs1_read.pointer,
+                    rs2_ptr: rs2_read.pointer,
+                    rd_val: rd_read.data,
+                    rs1_val: rs1_read.data,
+                    rs2_val: rs2_read.data,
+                    point: record
+                        .point_reads
+                        .map(|reads| blocks_to_limbs(reads.map(|read| read.data))),
+                    scalar: blocks_to_limbs(record.scalar_reads.map(|read| read.data)),
+                };
+                let to_bytes = |limbs: [Val<SC>; NUM_LIMBS]| -> Vec<u8> {
+                    limbs.map(|x| x.as_canonical_u32() as u8).to_vec()
+                };
+                let point = instruction
+                    .point
+                    .map(|coord| BigUint::from_bytes_le(&to_bytes(coord)));
+                let scalar = to_bytes(instruction.scalar);
+
+                let mut acc: Option<[BigUint; 2]> = None;
+                for (r, row) in rows.chunks_exact_mut(trace_width).enumerate() {
+                    let (cols, exprs) =
+                        row.split_at_mut(
+                            EcMulCols::<Val<SC>, NUM_LIMBS, BLOCKS, BLOCK_SIZE>::width(),
+                        );
+                    let (double_row, add_row) = exprs.split_at_mut(double_width);
+                    let cols: &mut EcMulCols<Val<SC>, NUM_LIMBS, BLOCKS, BLOCK_SIZE> =
+                        cols.borrow_mut();
+
+                    // Bits are processed from the most significant
+                    let byte_idx = NUM_LIMBS - 1 - r / EC_MUL_ROWS_PER_BYTE;
+                    let bit_idx = EC_MUL_ROWS_PER_BYTE - 1 - r % EC_MUL_ROWS_PER_BYTE;
+                    let byte = scalar[byte_idx];
+                    let bit = (byte >> bit_idx) & 1 == 1;
+
+                    cols.is_valid = Val::<SC>::ONE;
+                    cols.is_first = Val::<SC>::from_bool(r == 0);
+                    cols.is_last = Val::<SC>::from_bool(r == rows_per_instruction - 1);
+                    cols.instruction = instruction;
+                    cols.byte_flags[byte_idx] = Val::<SC>::ONE;
+                    cols.bit_flags[bit_idx] = Val::<SC>::ONE;
+                    cols.byte_rem =
+                        Val::<SC>::from_canonical_u8(byte << (EC_MUL_ROWS_PER_BYTE - 1 - bit_idx));
+                    cols.bit = Val::<SC>::from_bool(bit);
+                    cols.is_started = Val::<SC>::from_bool(acc.is_some());
+
+                    if let Some(acc) = &acc {
+                        self.air.double_expr.generate_subrow(
+                            (&self.range_checker, acc.to_vec(), vec![true]),
+                            double_row,
+                        );
+                        if bit {
+                            let double = self
+                                .air
+                                .double_expr
+                                .execute_with_output(acc.to_vec(), vec![true]);
+                            let inputs = double.into_iter().chain(point.iter().cloned()).collect();
+                            self.air.add_ne_expr.generate_subrow(
+                                (&self.range_checker, inputs, vec![true]),
+                                add_row,
+                            );
+                        }
+                    }
+                    acc = ec_mul_step(
+                        &self.air.double_expr,
+                        &self.air.add_ne_expr,
+                        &point,
+                        acc,
+                        bit,
+                    );
+
+                    if r == 0 {
+                        let top_limb = |data: [Val<SC>; RV32_REGISTER_NUM_LIMBS]| {
+                            data[RV32_REGISTER_NUM_LIMBS - 1].as_canonical_u32() << limb_shift_bits
+                        };
+                        self.bitwise_lookup_chip
+                            .request_range(top_limb(rs1_read.data), top_limb(rs2_read.data));
+                        self.bitwise_lookup_chip
+                            .request_range(top_limb(rd_read.data), top_limb(rd_read.data));
+                        cols.reads_aux.register_aux = record
+                            .register_reads
+                            .map(|read| aux_cols_factory.make_read_aux_cols(read));
+                        cols.reads_aux.point_aux = record.point_reads.map(|reads| {
+                            reads.map(|read| aux_cols_factory.make_read_aux_cols(read))
+                        });
+                        cols.reads_aux.scalar_aux = record
+                            .scalar_reads
+                            .map(|read| aux_cols_factory.make_read_aux_cols(read));
+                    }
+                    if r == rows_per_instruction - 1 {
+                        let result = acc.as_ref().expect("scalar must be nonzero");
+                        cols.result = from_fn(|c| {
+                            let mut bytes = result[c].to_bytes_le();
+                            bytes.resize(NUM_LIMBS, 0);
+                            from_fn(|i| Val::<SC>::from_canonical_u8(bytes[i]))
+                        });
+                        cols.writes_aux = record.result_writes.map(|writes| {
+                            writes.map(|write| aux_cols_factory.make_write_aux_cols(write))
+                        });
+                    }
+                }
+            });
+
+        AirProofInput::simple_no_pis(air, trace)
+    }
+}
+
+impl<F: PrimeField32, const NUM_LIMBS: usize, const BLOCKS: usize, const BLOCK_SIZE: usize>
+    ChipUsageGetter for EcMulChip<F, NUM_LIMBS, BLOCKS, BLOCK_SIZE>
+{
+    fn air_name(&self) -> String {
+        get_air_name(&self.air)
+    }
+
+    fn current_trace_height(&self) -> usize {
+        self.records.len() * EC_MUL_ROWS_PER_BYTE * NUM_LIMBS
+    }
+
+    fn trace_width(&self) -> usize {
+        BaseAir::<F>::width(&self.air)
+    }
+}
@@ -1,22 +1,31 @@
-use std::{str::FromStr, sync::Arc};
+use std::{borrow::BorrowMut, str::FromStr, sync::Arc};
 
 use num_bigint_dig::BigUint;
 use num_traits::{FromPrimitive, Num, Zero};
-use openvm_circuit::arch::{testing::VmChipTestBuilder, BITWISE_OP_LOOKUP_BUS};
+use openvm_circuit::arch::{
+    testing::{VmChipTestBuilder, VmChipTester},
+    BITWISE_OP_LOOKUP_BUS,
+};
 use openvm_circuit_primitives::{
     bigint::utils::{secp256k1_coord_prime, secp256r1_coord_prime},
     bitwise_op_lookup::{BitwiseOperationLookupBus, BitwiseOperationLookupChip},
 };
+use openvm_ecc_guest::halo2curves::{
+    group::Curve,
+    secp256k1::{Fq as Secp256k1Scalar, Secp256k1Affine},
+};
 use openvm_ecc_transpiler::Rv32WeierstrassOpcode;
 use openvm_instructions::{riscv::RV32_CELL_BITS, UsizeOpcode};
 use openvm_mod_circuit_builder::{
     test_utils::biguint_to_limbs, ExprBuilderConfig, FieldExpressionCoreChip,
 };
 use openvm_rv32_adapters::{rv32_write_heap_default, Rv32VecHeapAdapterChip};
-use openvm_stark_backend::p3_field::AbstractField;
-use openvm_stark_sdk::p3_baby_bear::BabyBear;
+use openvm_stark_backend::{
+    p3_field::AbstractField, utils::disable_debug_builder, verifier::VerificationError,
+};
+use openvm_stark_sdk::{config::baby_bear_blake3::BabyBearBlake3Config, p3_baby_bear::BabyBear};
 
-use super::{EcAddNeChip, EcDoubleChip};
+use super::{mul::columns::EcMulCols, EcAddNeChip, EcDoubleChip, EcMulChip};
 
 const NUM_LIMBS: usize = 32;
 const LIMB_BITS: usize = 8;
@@ -302,3 +311,88 @@ fn test_p256_double() {
 
     tester.simple_test().expect("Verification failed");
 }
+
+/// Multiplies the secp256k1 generator by each scalar, checking the results against halo2curves.
+/// If `prank`, the first coordinate byte of the last result is replaced in the trace.
+fn build_ec_mul_test(
+    scalars: &[Secp256k1Scalar],
+    prank: bool,
+) -> VmChipTester<BabyBearBlake3Config> {
+    let mut tester: VmChipTestBuilder<F> = VmChipTestBuilder::default();
+    let config = ExprBuilderConfig {
+        modulus: secp256k1_coord_prime(),
+        num_limbs: NUM_LIMBS,
+        limb_bits: LIMB_BITS,
+    };
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = Arc::new(BitwiseOperationLookupChip::<RV32_CELL_BITS>::new(
+        bitwise_bus,
+    ));
+    let mut chip = EcMulChip::<F, NUM_LIMBS, 1, BLOCK_SIZE>::new(
+        tester.execution_bus(),
+        tester.program_bus(),
+        tester.memory_controller(),
+        bitwise_chip.clone(),
+        config,
+        Rv32WeierstrassOpcode::default_offset(),
+        BigUint::zero(),
+    );
+
+    let to_limbs = |bytes: [u8; NUM_LIMBS]| bytes.map(BabyBear::from_canonical_u8);
+    let base = Secp256k1Affine::generator();
+    for scalar in scalars {
+        let instruction = rv32_write_heap_default(
+            &mut tester,
+            vec![to_limbs(base.x.to_bytes()), to_limbs(base.y.to_bytes())],
+            vec![to_limbs(scalar.to_bytes())],
+            Rv32WeierstrassOpcode::default_offset() + Rv32WeierstrassOpcode::EC_MUL as usize,
+        );
+        tester.execute(&mut chip, instruction);
+
+        let expected = (base * scalar).to_affine();
+        let writes = &chip.records.last().unwrap().result_writes;
+        assert_eq!(writes[0][0].data, to_limbs(expected.x.to_bytes()));
+        assert_eq!(writes[1][0].data, to_limbs(expected.y.to_bytes()));
+    }
+    if prank {
+        chip.records.last_mut().unwrap().result_writes[0][0].data[0] += BabyBear::ONE;
+    }
+    let mut tester = tester.build().load(chip).load(bitwise_chip).finalize();
+
+    if prank {
+        let trace = tester.air_proof_inputs[2].raw.common_main.as_mut().unwrap();
+        let last_row = trace.row_mut(scalars.len() * 8 * NUM_LIMBS - 1);
+        let cols: &mut EcMulCols<_, NUM_LIMBS, 1, BLOCK_SIZE> =
+            last_row[..EcMulCols::<F, NUM_LIMBS, 1, BLOCK_SIZE>::width()].borrow_mut();
+        cols.result[0][0] += BabyBear::ONE;
+    }
+    tester
+}
+
+#[test]
+fn test_ec_mul() {
+    let scalars = [
+        Secp256k1Scalar::from(1),
+        Secp256k1Scalar::from(2),
+        Secp256k1Scalar::from(0x80),
+        Secp256k1Scalar::from_raw([
+            0x0123_4567_89ab_cdef,
+            0xfedc_ba98_7654_3210,
+            0xdead_beef_cafe_babe,
+            0x7fff_ffff_0000_0001,
+        ]),
+        -Secp256k1Scalar::from(1),
+    ];
+    let tester = build_ec_mul_test(&scalars, false);
+    tester.simple_test().expect("Verification failed");
+}
+
+#[test]
+fn test_ec_mul_negative() {
+    let tester = build_ec_mul_test(&[Secp256k1Scalar::from(12345)], true);
+    disable_debug_builder();
+    assert_eq!(
+        tester.simple_test().err(),
+        Some(VerificationError::OodEvaluationMismatch)
+    );
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use derive_more::derive::From;
+use hex_literal::hex;
 use num_bigint_dig::BigUint;
 use num_traits::{FromPrimitive, Zero};
 use once_cell::sync::Lazy;
@@ -13,7 +14,10 @@ use openvm_circuit_primitives::bitwise_op_lookup::{
     BitwiseOperationLookupBus, BitwiseOperationLookupChip,
 };
 use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
-use openvm_ecc_guest::k256::{SECP256K1_MODULUS, SECP256K1_ORDER};
+use openvm_ecc_guest::{
+    k256::{SECP256K1_MODULUS, SECP256K1_ORDER},
+    p256::{P256_MODULUS, P256_ORDER},
+};
 use openvm_ecc_transpiler::{EccPhantom, Rv32WeierstrassOpcode};
 use openvm_instructions::{PhantomDiscriminant, UsizeOpcode, VmOpcode};
 use openvm_mod_circuit_builder::ExprBuilderConfig;
@@ -23,7 +27,7 @@ use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
 use strum::EnumCount;
 
-use super::{EcAddNeChip, EcDoubleChip};
+use super::{EcAddNeChip, EcDoubleChip, EcMulChip};
 
 #[serde_as]
 #[derive(Clone, Debug, derive_new::new, Serialize, Deserialize)]
@@ -40,6 +44,12 @@ pub struct CurveConfig {
     /// The coefficient b of y^2 = x^3 + ax + b.
     #[serde_as(as = "DisplayFromStr")]
     pub b: BigUint,
+    /// Whether to add the `EC_MUL` scalar multiplication chip for this curve, which is used by
+    /// `msm_by_scalar_mul` in the guest. The guest `msm` of secp256k1 and P-256 uses it, so it
+    /// must be enabled for these curves.
+    #[serde(default)]
+    #[new(default)]
+    pub scalar_mul: bool,
 }
 
 pub static SECP256K1_CONFIG: Lazy<CurveConfig> = Lazy::new(|| CurveConfig {
@@ -47,6 +57,17 @@ pub static SECP256K1_CONFIG: Lazy<CurveConfig> = Lazy::new(|| CurveConfig {
     scalar: SECP256K1_ORDER.clone(),
     a: BigUint::zero(),
     b: BigUint::from_u8(7u8).unwrap(),
+    scalar_mul: true,
+});
+
+pub static SECP256R1_CONFIG: Lazy<CurveConfig> = Lazy::new(|| CurveConfig {
+    modulus: P256_MODULUS.clone(),
+    scalar: P256_ORDER.clone(),
+    a: P256_MODULUS.clone() - BigUint::from_u8(3u8).unwrap(),
+    b: BigUint::from_bytes_be(&hex!(
+        "5AC635D8 AA3A93E7 B3EBBD55 769886BC 651D06B0 CC53B0F6 3BCE3C3E 27D2604B"
+    )),
+    scalar_mul: true,
 });
 
 #[derive(Clone, Debug, derive_new::new, Serialize, Deserialize)]
@@ -59,9 +80,19 @@ pub enum WeierstrassExtensionExecutor<F: PrimeField32> {
     // 32 limbs prime
     EcAddNeRv32_32(EcAddNeChip<F, 2, 32>),
     EcDoubleRv32_32(EcDoubleChip<F, 2, 32>),
+    EcMulRv32_32(EcMulChip<F, 32, 1, 32>),
     // 48 limbs prime
     EcAddNeRv32_48(EcAddNeChip<F, 6, 16>),
     EcDoubleRv32_48(EcDoubleChip<F, 6, 16>),
+    EcMulRv32_48(EcMulChip<F, 48, 3, 16>),
+    // 64 limbs prime
+    EcAddNeRv32_64(EcAddNeChip<F, 4, 32>),
+    EcDoubleRv32_64(EcDoubleChip<F, 4, 32>),
+    EcMulRv32_64(EcMulChip<F, 64, 2, 32>),
+    // 128 limbs prime
+    EcAddNeRv32_128(EcAddNeChip<F, 8, 32>),
+    EcDoubleRv32_128(EcDoubleChip<F, 8, 32>),
+    EcMulRv32_128(EcMulChip<F, 128, 4, 32>),
 }
 
 #[derive(ChipUsageGetter, Chip, AnyEnum, From)]
@@ -104,91 +135,80 @@ impl<F: PrimeField32> VmExtension<F> for WeierstrassExtension {
             let class_offset =
                 Rv32WeierstrassOpcode::default_offset() + i * Rv32WeierstrassOpcode::COUNT;
             let bytes = curve.modulus.bits().div_ceil(8);
-            let config32 = ExprBuilderConfig {
-                modulus: curve.modulus.clone(),
-                num_limbs: 32,
-                limb_bits: 8,
-            };
-            let config48 = ExprBuilderConfig {
-                modulus: curve.modulus.clone(),
-                num_limbs: 48,
-                limb_bits: 8,
-            };
-            // TODO: Better support for different limb sizes. Currently only 32 or 48 limbs are supported.
-            if bytes <= 32 {
-                let add_ne_chip = EcAddNeChip::new(
-                    Rv32VecHeapAdapterChip::<F, 2, 2, 2, 32, 32>::new(
-                        execution_bus,
-                        program_bus,
+
+            macro_rules! add_weierstrass_chips {
+                ($num_limbs:literal, $add_ne:ident, $double:ident, $mul:ident) => {{
+                    let config = ExprBuilderConfig {
+                        modulus: curve.modulus.clone(),
+                        num_limbs: $num_limbs,
+                        limb_bits: 8,
+                    };
+                    let add_ne_chip = EcAddNeChip::new(
+                        Rv32VecHeapAdapterChip::new(
+                            execution_bus,
+                            program_bus,
+                            memory_controller.clone(),
+                            bitwise_lu_chip.clone(),
+                        ),
                         memory_controller.clone(),
-                        bitwise_lu_chip.clone(),
-                    ),
-                    memory_controller.clone(),
-                    config32.clone(),
-                    class_offset,
-                );
-                inventory.add_executor(
-                    WeierstrassExtensionExecutor::EcAddNeRv32_32(add_ne_chip),
-                    ec_add_ne_opcodes
-                        .clone()
-                        .map(|x| VmOpcode::from_usize(x + class_offset)),
-                )?;
-                let double_chip = EcDoubleChip::new(
-                    Rv32VecHeapAdapterChip::<F, 1, 2, 2, 32, 32>::new(
-                        execution_bus,
-                        program_bus,
+                        config.clone(),
+                        class_offset,
+                    );
+                    inventory.add_executor(
+                        WeierstrassExtensionExecutor::$add_ne(add_ne_chip),
+                        ec_add_ne_opcodes
+                            .clone()
+                            .map(|x| VmOpcode::from_usize(x + class_offset)),
+                    )?;
+                    let double_chip = EcDoubleChip::new(
+                        Rv32VecHeapAdapterChip::new(
+                            execution_bus,
+                            program_bus,
+                            memory_controller.clone(),
+                            bitwise_lu_chip.clone(),
+                        ),
                         memory_controller.clone(),
-                        bitwise_lu_chip.clone(),
-                    ),
-                    memory_controller.clone(),
-                    config32.clone(),
-                    class_offset,
-                    curve.a.clone(),
-                );
-                inventory.add_executor(
-                    WeierstrassExtensionExecutor::EcDoubleRv32_32(double_chip),
-                    ec_double_opcodes
-                        .clone()
-                        .map(|x| VmOpcode::from_usize(x + class_offset)),
-                )?;
+                        config.clone(),
+                        class_offset,
+                        curve.a.clone(),
+                    );
+                    inventory.add_executor(
+                        WeierstrassExtensionExecutor::$double(double_chip),
+                        ec_double_opcodes
+                            .clone()
+                            .map(|x| VmOpcode::from_usize(x + class_offset)),
+                    )?;
+                    if curve.scalar_mul {
+                        let mul_chip = EcMulChip::new(
+                            execution_bus,
+                            program_bus,
+                            memory_controller.clone(),
+                            bitwise_lu_chip.clone(),
+                            config,
+                            class_offset,
+                            curve.a.clone(),
+                        );
+                        inventory.add_executor(
+                            WeierstrassExtensionExecutor::$mul(mul_chip),
+                            [VmOpcode::from_usize(
+                                Rv32WeierstrassOpcode::EC_MUL as usize + class_offset,
+                            )],
+                        )?;
+                    }
+                }};
+            }
+
+            // The block layout must match the one chosen by `moduli_declare!` in the guest.
+            if bytes <= 32 {
+                add_weierstrass_chips!(32, EcAddNeRv32_32, EcDoubleRv32_32, EcMulRv32_32);
             } else if bytes <= 48 {
-                let add_ne_chip = EcAddNeChip::new(
-                    Rv32VecHeapAdapterChip::<F, 2, 6, 6, 16, 16>::new(
-                        execution_bus,
-                        program_bus,
-                        memory_controller.clone(),
-                        bitwise_lu_chip.clone(),
-                    ),
-                    memory_controller.clone(),
-                    config48.clone(),
-                    class_offset,
-                );
-                inventory.add_executor(
-                    WeierstrassExtensionExecutor::EcAddNeRv32_48(add_ne_chip),
-                    ec_add_ne_opcodes
-                        .clone()
-                        .map(|x| VmOpcode::from_usize(x + class_offset)),
-                )?;
-                let double_chip = EcDoubleChip::new(
-                    Rv32VecHeapAdapterChip::<F, 1, 6, 6, 16, 16>::new(
-                        execution_bus,
-                        program_bus,
-                        memory_controller.clone(),
-                        bitwise_lu_chip.clone(),
-                    ),
-                    memory_controller.clone(),
-                    config48.clone(),
-                    class_offset,
-                    curve.a.clone(),
-                );
-                inventory.add_executor(
-                    WeierstrassExtensionExecutor::EcDoubleRv32_48(double_chip),
-                    ec_double_opcodes
-                        .clone()
-                        .map(|x| VmOpcode::from_usize(x + class_offset)),
-                )?;
+                add_weierstrass_chips!(48, EcAddNeRv32_48, EcDoubleRv32_48, EcMulRv32_48);
+            } else if bytes <= 64 {
+                add_weierstrass_chips!(64, EcAddNeRv32_64, EcDoubleRv32_64, EcMulRv32_64);
+            } else if bytes <= 128 {
+                add_weierstrass_chips!(128, EcAddNeRv32_128, EcDoubleRv32_128, EcMulRv32_128);
             } else {
-                panic!("Modulus too large");
+                panic!("Modulus too large: at most 128 bytes are supported");
             }
         }
         builder.add_phantom_sub_executor(
@@ -246,13 +266,12 @@ pub(crate) mod phantom {
                 // TODO: Tonelli-Shanks algorithm
             }
             let rs1 = unsafe_read_rv32_register(memory, a);
-            // TODO: Better support for different limb sizes
-            let num_limbs: usize = if curve.modulus.bits().div_ceil(8) <= 32 {
-                32
-            } else if curve.modulus.bits().div_ceil(8) <= 48 {
-                48
-            } else {
-                bail!("Modulus too large")
+            let num_limbs: usize = match curve.modulus.bits().div_ceil(8) {
+                0..=32 => 32,
+                33..=48 => 48,
+                49..=64 => 64,
+                65..=128 => 128,
+                _ => bail!("Modulus too large"),
             };
             let mut x_limbs: Vec<u8> = Vec::with_capacity(num_limbs);
             for i in 0..num_limbs {
@@ -37,6 +37,62 @@ impl<C: IntrinsicCurve> VerifyingKey<C> {
     }
 }
 
+impl<C> VerifyingKey<C>
+where
+    C: IntrinsicCurve,
+    C::Point: WeierstrassPoint + Group + FromCompressed<Coordinate<C>>,
+    Coordinate<C>: IntMod,
+{
+    /// Creates a verifying key from an affine point. Returns an error if the point is the
+    /// identity or not on the curve.
+    pub fn from_affine(point: <C as IntrinsicCurve>::Point) -> Result<Self>
+    where
+        for<'a> &'a Coordinate<C>: Mul<&'a Coordinate<C>, Output = Coordinate<C>>,
+    {
+        if point.is_identity() {
+            return Err(Error::new());
+        }
+        let (x, y) = point.into_coords();
+        let point = C::Point::from_xy_nonidentity(x, y).ok_or_else(Error::new)?;
+        Ok(Self {
+            inner: PublicKey { point },
+        })
+    }
+
+    /// Parses a public key in SEC1 encoding, either compressed or uncompressed. Coordinates are
+    /// big endian.
+    ///
+    /// ## Panics
+    /// If a coordinate is not less than the modulus, or a compressed key cannot be
+    /// decompressed.
+    pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self>
+    where
+        for<'a> &'a Coordinate<C>: Mul<&'a Coordinate<C>, Output = Coordinate<C>>,
+    {
+        let num_limbs = Coordinate::<C>::NUM_LIMBS;
+        let (&tag, coords) = bytes.split_first().ok_or_else(Error::new)?;
+        let point = match tag {
+            // Compressed, the tag encodes the parity of y
+            0x02 | 0x03 if coords.len() == num_limbs => {
+                let x = Coordinate::<C>::from_be_bytes(coords);
+                x.assert_unique();
+                C::Point::decompress(x, &(tag & 1))
+            }
+            // Uncompressed
+            0x04 if coords.len() == 2 * num_limbs => {
+                let (x_be, y_be) = coords.split_at(num_limbs);
+                let x = Coordinate::<C>::from_be_bytes(x_be);
+                let y = Coordinate::<C>::from_be_bytes(y_be);
+                x.assert_unique();
+                y.assert_unique();
+                C::Point::from_xy_unchecked(x, y)
+            }
+            _ => return Err(Error::new()),
+        };
+        Self::from_affine(point)
+    }
+}
+
 impl<C> VerifyingKey<C>
 where
     C: PrimeCurve + IntrinsicCurve,
@@ -0,0 +1,69 @@
+use ecdsa::{Error, Result};
+use openvm_algebra_guest::{IntMod, Reduce};
+use sha2::{Digest, Sha512};
+
+use super::{is_less_le, Ed25519, Ed25519Point, Ed25519Scalar};
+use crate::{weierstrass::IntrinsicCurve, CyclicGroup};
+
+/// An Ed25519 public key.
+#[derive(Clone, Debug)]
+pub struct Ed25519VerifyingKey {
+    point: Ed25519Point,
+    /// The encoding of `point`, which is hashed during verification.
+    bytes: [u8; 32],
+}
+
+impl Ed25519VerifyingKey {
+    /// Decodes a public key from its 32 byte encoding.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self> {
+        let point = Ed25519Point::from_compressed_bytes(bytes).ok_or_else(Error::new)?;
+        Ok(Self {
+            point,
+            bytes: *bytes,
+        })
+    }
+
+    pub fn as_point(&self) -> &Ed25519Point {
+        &self.point
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+
+    /// Verifies an Ed25519 signature `R || S` of `msg` as in
+    /// [RFC 8032](https://datatracker.ietf.org/doc/html/rfc8032#section-5.1.7).
+    ///
+    /// Checks the cofactorless equation `[S]B = R + [k]A` with `k = SHA-512(R || A || msg)`,
+    /// and rejects `S` that are not less than the group order.
+    #[allow(non_snake_case)]
+    pub fn verify(&self, msg: &[u8], signature: &[u8; 64]) -> Result<()> {
+        let (R_bytes, s_bytes) = signature.split_at(32);
+        let R = Ed25519Point::from_compressed_bytes(R_bytes.try_into().unwrap())
+            .ok_or_else(Error::new)?;
+        if !is_less_le(s_bytes, Ed25519Scalar::MODULUS.as_ref()) {
+            return Err(Error::new());
+        }
+        let s = Ed25519Scalar::from_le_bytes(s_bytes);
+
+        let hash = Sha512::new()
+            .chain_update(R_bytes)
+            .chain_update(self.bytes)
+            .chain_update(msg)
+            .finalize();
+        let neg_k = -Ed25519Scalar::reduce_le_bytes(&hash);
+        // The scalar bytes are used directly by the MSM and `A` may have a small order
+        // component, so the scalar must be in its canonical form.
+        neg_k.assert_unique();
+
+        // [S]B - [k]A
+        let G = Ed25519Point::GENERATOR;
+        let A = self.point.clone();
+        let sum = <Ed25519 as IntrinsicCurve>::msm(&[s, neg_k], &[G, A]);
+        if sum == R {
+            Ok(())
+        } else {
+            Err(Error::new())
+        }
+    }
+}
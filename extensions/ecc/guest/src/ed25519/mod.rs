@@ -0,0 +1,162 @@
+use hex_literal::hex;
+#[cfg(not(target_os = "zkvm"))]
+use lazy_static::lazy_static;
+#[cfg(not(target_os = "zkvm"))]
+use num_bigint_dig::BigUint;
+use openvm_algebra_guest::{ExpBytes, Field, IntMod};
+use openvm_algebra_moduli_setup::moduli_declare;
+use openvm_ecc_te_setup::te_declare;
+
+use super::group::CyclicGroup;
+use crate::{edwards::TwistedEdwardsPoint, weierstrass::IntrinsicCurve};
+
+mod eddsa;
+pub use eddsa::*;
+
+#[cfg(not(target_os = "zkvm"))]
+lazy_static! {
+    pub static ref ED25519_MODULUS: BigUint = BigUint::from_bytes_be(&hex!(
+        "7FFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFED"
+    ));
+    pub static ref ED25519_ORDER: BigUint = BigUint::from_bytes_be(&hex!(
+        "10000000 00000000 00000000 00000000 14DEF9DE A2F79CD6 5812631A 5CF5D3ED"
+    ));
+}
+
+pub const ED25519_NUM_LIMBS: usize = 32;
+pub const ED25519_LIMB_BITS: usize = 8;
+pub const ED25519_BLOCK_SIZE: usize = 32;
+// a = -1
+const CURVE_A: Ed25519Coord = Ed25519Coord::from_const_bytes(hex!(
+    "ECFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF7F"
+));
+// d = -121665 / 121666
+const CURVE_D: Ed25519Coord = Ed25519Coord::from_const_bytes(hex!(
+    "A3785913CA4DEB75ABD841414D0A700098E879777940C78C73FE6F2BEE6C0352"
+));
+/// A square root of -1, i.e. 2^((p - 1) / 4).
+const SQRT_M1: Ed25519Coord = Ed25519Coord::from_const_bytes(hex!(
+    "B0A00E4A271BEEC478E42FAD0618432FA7D7FB3D99004D2B0BDFC14F8024832B"
+));
+/// (p - 5) / 8 in big endian.
+const SQRT_EXP_BE: [u8; 32] =
+    hex!("0FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFD");
+
+moduli_declare! {
+    Ed25519Coord { modulus = "0x7FFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFED" },
+    Ed25519Scalar { modulus = "0x10000000 00000000 00000000 00000000 14DEF9DE A2F79CD6 5812631A 5CF5D3ED" },
+}
+
+te_declare! {
+    Ed25519Point { mod_type = Ed25519Coord, a = CURVE_A, d = CURVE_D },
+}
+
+impl Field for Ed25519Coord {
+    const ZERO: Self = <Self as IntMod>::ZERO;
+    const ONE: Self = <Self as IntMod>::ONE;
+
+    type SelfRef<'a> = &'a Self;
+
+    fn double_assign(&mut self) {
+        IntMod::double_assign(self);
+    }
+
+    fn square_assign(&mut self) {
+        IntMod::square_assign(self);
+    }
+}
+
+impl CyclicGroup for Ed25519Point {
+    const GENERATOR: Self = Ed25519Point {
+        x: Ed25519Coord::from_const_bytes(hex!(
+            "1AD5258F602D56C9B2A7259560C72C695CDCD6FD31E2A4C0FE536ECDD3366921"
+        )),
+        y: Ed25519Coord::from_const_bytes(hex!(
+            "5866666666666666666666666666666666666666666666666666666666666666"
+        )),
+    };
+    const NEG_GENERATOR: Self = Ed25519Point {
+        x: Ed25519Coord::from_const_bytes(hex!(
+            "D32ADA709FD2A9364D58DA6A9F38D396A3232902CE1D5B3F01AC91322CC9965E"
+        )),
+        y: Ed25519Coord::from_const_bytes(hex!(
+            "5866666666666666666666666666666666666666666666666666666666666666"
+        )),
+    };
+}
+
+impl Ed25519Point {
+    /// Decodes a point from its 32 byte encoding in [RFC 8032](https://datatracker.ietf.org/doc/html/rfc8032#section-5.1.3):
+    /// `y` in little endian, with the parity of `x` in the most significant bit.
+    /// Returns `None` if `y` is not less than the modulus or the encoding is not a point on the
+    /// curve.
+    pub fn from_compressed_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let x_is_odd = bytes[31] >> 7 == 1;
+        let mut y_le = *bytes;
+        y_le[31] &= 0x7f;
+        if !is_less_le(&y_le, Ed25519Coord::MODULUS.as_ref()) {
+            return None;
+        }
+        let y = Ed25519Coord::from_le_bytes(&y_le);
+
+        // x^2 = u / v where u = y^2 - 1 and v = d y^2 + 1.
+        // Since p = 5 mod 8, a candidate root is x = u v^3 (u v^7)^((p - 5) / 8).
+        let one = <Ed25519Coord as IntMod>::ONE;
+        let y2 = y.square();
+        let u = &y2 - &one;
+        let v = &CURVE_D * &y2 + &one;
+        let v3 = &v.square() * &v;
+        let uv7 = &u * &(&v3.square() * &v);
+        let mut x = &u * &v3 * uv7.exp_bytes(true, &SQRT_EXP_BE);
+        let vx2 = &v * &x.square();
+        if vx2 == u {
+            // x is a square root
+        } else if vx2 == -&u {
+            x *= &SQRT_M1;
+        } else {
+            return None;
+        }
+
+        // Must assert unique so we can check the parity
+        x.assert_unique();
+        if x == <Ed25519Coord as IntMod>::ZERO && x_is_odd {
+            return None;
+        }
+        if (x.as_le_bytes()[0] & 1 == 1) != x_is_odd {
+            x = -x;
+        }
+        Some(Self::from_xy_unchecked(x, y))
+    }
+
+    /// Encodes the point as in [Ed25519Point::from_compressed_bytes].
+    pub fn to_compressed_bytes(&self) -> [u8; 32] {
+        self.x.assert_unique();
+        self.y.assert_unique();
+        let mut bytes: [u8; 32] = self.y.as_le_bytes().try_into().unwrap();
+        bytes[31] |= (self.x.as_le_bytes()[0] & 1) << 7;
+        bytes
+    }
+}
+
+/// Whether `a < b` for little endian byte strings of equal length.
+fn is_less_le(a: &[u8], b: &[u8]) -> bool {
+    for (a, b) in a.iter().rev().zip(b.iter().rev()) {
+        if a != b {
+            return a < b;
+        }
+    }
+    false
+}
+
+/// Marker type for Ed25519 to implement [IntrinsicCurve] on.
+#[derive(Clone, Copy, Debug)]
+pub struct Ed25519;
+
+impl IntrinsicCurve for Ed25519 {
+    type Scalar = Ed25519Scalar;
+    type Point = Ed25519Point;
+
+    fn msm(coeffs: &[Self::Scalar], bases: &[Self::Point]) -> Self::Point {
+        crate::msm(coeffs, bases)
+    }
+}
@@ -0,0 +1,145 @@
+use core::ops::Mul;
+
+use openvm_algebra_guest::Field;
+
+/// Twisted Edwards curve affine point.
+pub trait TwistedEdwardsPoint: Sized {
+    /// The `a` coefficient in the twisted Edwards curve equation `a x^2 + y^2 = 1 + d x^2 y^2`.
+    const CURVE_A: Self::Coordinate;
+    /// The `d` coefficient in the twisted Edwards curve equation `a x^2 + y^2 = 1 + d x^2 y^2`.
+    const CURVE_D: Self::Coordinate;
+    /// The identity point `(0, 1)`.
+    const IDENTITY: Self;
+
+    type Coordinate: Field;
+
+    /// The concatenated `x, y` coordinates of the affine point, where
+    /// coordinates are in little endian.
+    ///
+    /// **Warning**: The memory layout of `Self` is expected to pack
+    /// `x` and `y` contigously with no unallocated space in between.
+    fn as_le_bytes(&self) -> &[u8];
+
+    /// Raw constructor without asserting point is on the curve.
+    fn from_xy_unchecked(x: Self::Coordinate, y: Self::Coordinate) -> Self;
+    fn into_coords(self) -> (Self::Coordinate, Self::Coordinate);
+    fn x(&self) -> &Self::Coordinate;
+    fn y(&self) -> &Self::Coordinate;
+
+    /// Adds two points with the unified addition formula. The formula is complete, i.e. it
+    /// also handles doubling and the identity, when `a` is a square and `d` is not a square
+    /// in the coordinate field, as for Ed25519.
+    fn add_impl(&self, p2: &Self) -> Self;
+    /// See [TwistedEdwardsPoint::add_impl].
+    fn add_assign_impl(&mut self, p2: &Self);
+
+    fn from_xy(x: Self::Coordinate, y: Self::Coordinate) -> Option<Self>
+    where
+        for<'a> &'a Self::Coordinate: Mul<&'a Self::Coordinate, Output = Self::Coordinate>,
+    {
+        let x2 = &x * &x;
+        let y2 = &y * &y;
+        let lhs = &Self::CURVE_A * &x2 + &y2;
+        let rhs = Self::Coordinate::ONE + &Self::CURVE_D * &(&x2 * &y2);
+        if lhs != rhs {
+            return None;
+        }
+        Some(Self::from_xy_unchecked(x, y))
+    }
+}
+
+/// Implements `Group` on `$struct_name` assuming that `$struct_name` implements
+/// `TwistedEdwardsPoint` with a complete addition formula.
+/// Assumes that `Neg` is implemented for `&$struct_name`.
+#[macro_export]
+macro_rules! impl_te_group_ops {
+    ($struct_name:ident, $field:ty) => {
+        impl Group for $struct_name {
+            type SelfRef<'a> = &'a Self;
+
+            const IDENTITY: Self = <Self as TwistedEdwardsPoint>::IDENTITY;
+
+            fn double(&self) -> Self {
+                self.add_impl(self)
+            }
+
+            fn double_assign(&mut self) {
+                let p = self.clone();
+                self.add_assign_impl(&p);
+            }
+        }
+
+        impl core::ops::Add<&$struct_name> for $struct_name {
+            type Output = Self;
+
+            fn add(mut self, p2: &$struct_name) -> Self::Output {
+                self.add_assign_impl(p2);
+                self
+            }
+        }
+
+        impl core::ops::Add for $struct_name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                self.add(&rhs)
+            }
+        }
+
+        impl core::ops::Add<&$struct_name> for &$struct_name {
+            type Output = $struct_name;
+
+            fn add(self, p2: &$struct_name) -> Self::Output {
+                self.add_impl(p2)
+            }
+        }
+
+        impl core::ops::AddAssign<&$struct_name> for $struct_name {
+            fn add_assign(&mut self, p2: &$struct_name) {
+                self.add_assign_impl(p2);
+            }
+        }
+
+        impl core::ops::AddAssign for $struct_name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.add_assign_impl(&rhs);
+            }
+        }
+
+        impl core::ops::Sub<&$struct_name> for $struct_name {
+            type Output = Self;
+
+            fn sub(self, rhs: &$struct_name) -> Self::Output {
+                core::ops::Sub::sub(&self, rhs)
+            }
+        }
+
+        impl core::ops::Sub for $struct_name {
+            type Output = $struct_name;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                self.sub(&rhs)
+            }
+        }
+
+        impl core::ops::Sub<&$struct_name> for &$struct_name {
+            type Output = $struct_name;
+
+            fn sub(self, p2: &$struct_name) -> Self::Output {
+                self.add_impl(&core::ops::Neg::neg(p2))
+            }
+        }
+
+        impl core::ops::SubAssign<&$struct_name> for $struct_name {
+            fn sub_assign(&mut self, p2: &$struct_name) {
+                self.add_assign_impl(&core::ops::Neg::neg(p2));
+            }
+        }
+
+        impl core::ops::SubAssign for $struct_name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.sub_assign(&rhs);
+            }
+        }
+    };
+}
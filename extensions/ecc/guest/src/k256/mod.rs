@@ -10,7 +10,7 @@ use openvm_algebra_moduli_setup::moduli_declare;
 use openvm_ecc_sw_setup::sw_declare;
 
 use super::group::{CyclicGroup, Group};
-use crate::weierstrass::{CachedMulTable, IntrinsicCurve};
+use crate::weierstrass::{msm_by_scalar_mul, IntrinsicCurve};
 
 #[cfg(not(target_os = "zkvm"))]
 lazy_static! {
@@ -85,8 +85,9 @@ impl IntrinsicCurve for k256::Secp256k1 {
     {
         // heuristic
         if coeffs.len() < 25 {
-            let table = CachedMulTable::<Self>::new_with_prime_order(bases, 4);
-            table.windowed_mul(coeffs)
+            // SAFETY: secp256k1 has prime order, so every point on the curve has prime order
+            // equal to the scalar modulus.
+            unsafe { msm_by_scalar_mul::<Self>(coeffs, bases) }
         } else {
             crate::msm(coeffs, bases)
         }
@@ -7,6 +7,7 @@ extern crate alloc;
 pub use halo2curves_axiom as halo2curves;
 pub use openvm_algebra_guest as algebra;
 pub use openvm_ecc_sw_setup as sw_setup;
+pub use openvm_ecc_te_setup as te_setup;
 use strum_macros::FromRepr;
 
 mod affine_point;
@@ -18,6 +19,8 @@ pub use msm::*;
 
 /// ECDSA
 pub mod ecdsa;
+/// Twisted Edwards curve traits
+pub mod edwards;
 /// Weierstrass curve traits
 pub mod weierstrass;
 
@@ -25,9 +28,18 @@ pub mod weierstrass;
 #[cfg(feature = "k256")]
 pub mod k256;
 
+/// Types for Secp256r1 (NIST P-256) curve with intrinsic functions. Implements traits necessary for ECDSA.
+#[cfg(feature = "p256")]
+pub mod p256;
+
+/// Types for the Ed25519 curve with intrinsic functions, and EdDSA signature verification.
+#[cfg(feature = "ed25519")]
+pub mod ed25519;
+
 /// This is custom-1 defined in RISC-V spec document
 pub const OPCODE: u8 = 0x2b;
 pub const SW_FUNCT3: u8 = 0b001;
+pub const TE_FUNCT3: u8 = 0b100;
 
 /// Short Weierstrass curves are configurable.
 /// The funct7 field equals `curve_idx * SHORT_WEIERSTRASS_MAX_KINDS + base_funct7`.
@@ -38,8 +50,22 @@ pub enum SwBaseFunct7 {
     SwDouble,
     SwSetup,
     HintDecompress,
+    SwMul,
 }
 
 impl SwBaseFunct7 {
     pub const SHORT_WEIERSTRASS_MAX_KINDS: u8 = 8;
 }
+
+/// Twisted Edwards curves are configurable.
+/// The funct7 field equals `curve_idx * TWISTED_EDWARDS_MAX_KINDS + base_funct7`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, FromRepr)]
+#[repr(u8)]
+pub enum TeBaseFunct7 {
+    TeAdd = 0,
+    TeSetup,
+}
+
+impl TeBaseFunct7 {
+    pub const TWISTED_EDWARDS_MAX_KINDS: u8 = 8;
+}
@@ -0,0 +1,96 @@
+use core::ops::{Add, Neg};
+
+use hex_literal::hex;
+#[cfg(not(target_os = "zkvm"))]
+use lazy_static::lazy_static;
+#[cfg(not(target_os = "zkvm"))]
+use num_bigint_dig::BigUint;
+use openvm_algebra_guest::{Field, IntMod};
+use openvm_algebra_moduli_setup::moduli_declare;
+use openvm_ecc_sw_setup::sw_declare;
+
+use super::group::{CyclicGroup, Group};
+use crate::weierstrass::{msm_by_scalar_mul, IntrinsicCurve};
+
+#[cfg(not(target_os = "zkvm"))]
+lazy_static! {
+    pub static ref P256_MODULUS: BigUint = BigUint::from_bytes_be(&hex!(
+        "FFFFFFFF 00000001 00000000 00000000 00000000 FFFFFFFF FFFFFFFF FFFFFFFF"
+    ));
+    pub static ref P256_ORDER: BigUint = BigUint::from_bytes_be(&hex!(
+        "FFFFFFFF 00000000 FFFFFFFF FFFFFFFF BCE6FAAD A7179E84 F3B9CAC2 FC632551"
+    ));
+}
+
+pub const P256_NUM_LIMBS: usize = 32;
+pub const P256_LIMB_BITS: usize = 8;
+pub const P256_BLOCK_SIZE: usize = 32;
+// a = -3
+const CURVE_A: P256Coord = P256Coord::from_const_bytes(hex!(
+    "FCFFFFFFFFFFFFFFFFFFFFFF00000000000000000000000001000000FFFFFFFF"
+));
+const CURVE_B: P256Coord = P256Coord::from_const_bytes(hex!(
+    "4B60D2273E3CCE3BF6B053CCB0061D65BC86987655BDEBB3E7933AAAD835C65A"
+));
+
+moduli_declare! {
+    P256Coord { modulus = "0xFFFFFFFF 00000001 00000000 00000000 00000000 FFFFFFFF FFFFFFFF FFFFFFFF" },
+    P256Scalar { modulus = "0xFFFFFFFF 00000000 FFFFFFFF FFFFFFFF BCE6FAAD A7179E84 F3B9CAC2 FC632551" },
+}
+
+sw_declare! {
+    P256Point { mod_type = P256Coord, a = CURVE_A, b = CURVE_B },
+}
+
+impl Field for P256Coord {
+    const ZERO: Self = <Self as IntMod>::ZERO;
+    const ONE: Self = <Self as IntMod>::ONE;
+
+    type SelfRef<'a> = &'a Self;
+
+    fn double_assign(&mut self) {
+        IntMod::double_assign(self);
+    }
+
+    fn square_assign(&mut self) {
+        IntMod::square_assign(self);
+    }
+}
+
+impl CyclicGroup for P256Point {
+    const GENERATOR: Self = P256Point {
+        x: P256Coord::from_const_bytes(hex!(
+            "96C298D84539A1F4A033EB2D817D0377F240A463E5E6BCF847422CE1F2D1176B"
+        )),
+        y: P256Coord::from_const_bytes(hex!(
+            "F551BF376840B6CBCE5E316B5733CE2B169E0F7C4AEBE78E9B7F1AFEE242E34F"
+        )),
+    };
+    const NEG_GENERATOR: Self = P256Point {
+        x: P256Coord::from_const_bytes(hex!(
+            "96C298D84539A1F4A033EB2D817D0377F240A463E5E6BCF847422CE1F2D1176B"
+        )),
+        y: P256Coord::from_const_bytes(hex!(
+            "0AAE40C897BF493431A1CE94A9CC31D4E961F083B51418716580E5011CBD1CB0"
+        )),
+    };
+}
+
+impl IntrinsicCurve for p256::NistP256 {
+    type Scalar = P256Scalar;
+    type Point = P256Point;
+
+    fn msm(coeffs: &[Self::Scalar], bases: &[Self::Point]) -> Self::Point
+    where
+        for<'a> &'a Self::Point: Add<&'a Self::Point, Output = Self::Point>,
+    {
+        // heuristic
+        if coeffs.len() < 25 {
+            // SAFETY: P-256 has prime order, so every point on the curve has prime order
+            // equal to the scalar modulus.
+            unsafe { msm_by_scalar_mul::<Self>(coeffs, bases) }
+        } else {
+            crate::msm(coeffs, bases)
+        }
+    }
+}
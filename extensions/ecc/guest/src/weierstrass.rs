@@ -7,6 +7,8 @@ use super::group::Group;
 
 /// Short Weierstrass curve affine point.
 pub trait WeierstrassPoint: Sized {
+    /// The `a` coefficient in the Weierstrass curve equation `y^2 = x^3 + a x + b`.
+    const CURVE_A: Self::Coordinate;
     /// The `b` coefficient in the Weierstrass curve equation `y^2 = x^3 + a x + b`.
     const CURVE_B: Self::Coordinate;
     const IDENTITY: Self;
@@ -41,6 +43,34 @@ pub trait WeierstrassPoint: Sized {
     /// Hazmat: Assumes self != identity and 2 * self != identity.
     fn double_assign_nonidentity(&mut self);
 
+    /// Hazmat: Returns `scalar * self`, where `scalar` is given in little-endian bytes.
+    /// Assumes self != identity, self has prime order `n`, and `0 < scalar < n`.
+    ///
+    /// Only callable through [msm_by_scalar_mul], which checks the assumptions on the scalar.
+    ///
+    /// The default implementation is MSB-first double-and-add. Under the assumptions above,
+    /// none of the intermediate additions or doublings hit an exceptional case.
+    #[doc(hidden)]
+    fn mul_nonidentity(&self, scalar: &[u8], _: ScalarMulToken) -> Self {
+        let mut res: Option<Self> = None;
+        for byte in scalar.iter().rev() {
+            for i in (0..8).rev() {
+                if let Some(res) = res.as_mut() {
+                    res.double_assign_nonidentity();
+                }
+                if (byte >> i) & 1 == 1 {
+                    match res.as_mut() {
+                        Some(res) => res.add_ne_assign_nonidentity(self),
+                        None => {
+                            res = Some(Self::from_xy_unchecked(self.x().clone(), self.y().clone()))
+                        }
+                    }
+                }
+            }
+        }
+        res.expect("scalar must be nonzero")
+    }
+
     fn from_xy(x: Self::Coordinate, y: Self::Coordinate) -> Option<Self>
     where
         for<'a> &'a Self::Coordinate: Mul<&'a Self::Coordinate, Output = Self::Coordinate>,
@@ -57,7 +87,7 @@ pub trait WeierstrassPoint: Sized {
         for<'a> &'a Self::Coordinate: Mul<&'a Self::Coordinate, Output = Self::Coordinate>,
     {
         let lhs = &y * &y;
-        let rhs = &x * &x * &x + &Self::CURVE_B;
+        let rhs = &x * &x * &x + &Self::CURVE_A * &x + &Self::CURVE_B;
         if lhs != rhs {
             return None;
         }
@@ -101,6 +131,41 @@ pub trait IntrinsicCurve {
     fn msm(coeffs: &[Self::Scalar], bases: &[Self::Point]) -> Self::Point;
 }
 
+/// Restricts calls of [WeierstrassPoint::mul_nonidentity] to this crate. It can be named by the
+/// implementations generated by `sw_declare!`, but not constructed outside this crate.
+pub struct ScalarMulToken(());
+
+/// Computes `sum coeffs[i] * bases[i]` using one scalar multiplication per term, which is a
+/// single `EC_MUL` instruction in the zkVM. The curve must be configured with `scalar_mul`
+/// enabled in the VM.
+///
+/// # Safety
+/// Each element of `bases` must be identity or have prime order equal to the modulus of
+/// `C::Scalar`. This is not checked, and for any other base the scalar multiplication may hit an
+/// exceptional case of the addition formulas and return a wrong result. On curves of prime
+/// order, such as secp256k1 and P-256, this holds for every point on the curve.
+pub unsafe fn msm_by_scalar_mul<C: IntrinsicCurve>(
+    coeffs: &[C::Scalar],
+    bases: &[C::Point],
+) -> C::Point
+where
+    C::Point: WeierstrassPoint + Group,
+    C::Scalar: IntMod,
+{
+    assert_eq!(coeffs.len(), bases.len());
+    let mut res = <C::Point as Group>::IDENTITY;
+    for (coeff, base) in coeffs.iter().zip(bases) {
+        // The comparison also constrains `coeff` to be less than the modulus, as required by
+        // `mul_nonidentity`.
+        if *coeff == <C::Scalar as IntMod>::ZERO || base.is_identity() {
+            continue;
+        }
+        // handles identity and equal points
+        res.add_assign(&base.mul_nonidentity(coeff.as_le_bytes(), ScalarMulToken(())));
+    }
+    res
+}
+
 // MSM using preprocessed table (windowed method)
 // Reference: modified from https://github.com/arkworks-rs/algebra/blob/master/ec/src/scalar_mul/mod.rs
 //
@@ -242,6 +307,7 @@ macro_rules! impl_sw_affine {
         pub struct $struct_name(AffinePoint<$field>);
 
         impl WeierstrassPoint for $struct_name {
+            const CURVE_A: $field = <$field>::ZERO;
             const CURVE_B: $field = $b;
             const IDENTITY: Self = Self(AffinePoint::new(<$field>::ZERO, <$field>::ZERO));
 
@@ -14,9 +14,12 @@ use syn::{
 /// Usage:
 /// ```
 /// sw_declare! {
-///     [TODO]
+///     Secp256k1Point { mod_type = Secp256k1Coord, b = CURVE_B },
+///     P256Point { mod_type = P256Coord, a = CURVE_A, b = CURVE_B },
 /// }
 /// ```
+/// `a` and `b` are the coefficients of `y^2 = x^3 + ax + b` and must be constants of type
+/// `mod_type`. `a` defaults to zero.
 ///
 /// For this macro to work, you must import the `elliptic_curve` crate and the `openvm_ecc_guest` crate..
 #[proc_macro]
@@ -31,6 +34,7 @@ pub fn sw_declare(input: TokenStream) -> TokenStream {
         let struct_name = item.name.to_string();
         let struct_name = syn::Ident::new(&struct_name, span.into());
         let mut intmod_type: Option<syn::Path> = None;
+        let mut const_a: Option<syn::Expr> = None;
         let mut const_b: Option<syn::Expr> = None;
         for param in item.params {
             match param.name.to_string().as_str() {
@@ -43,6 +47,10 @@ pub fn sw_declare(input: TokenStream) -> TokenStream {
                             .into();
                     }
                 }
+                "a" => {
+                    // We currently leave it to the compiler to check if the expression is actually a constant
+                    const_a = Some(param.value);
+                }
                 "b" => {
                     // We currently leave it to the compiler to check if the expression is actually a constant
                     const_b = Some(param.value);
@@ -54,6 +62,9 @@ pub fn sw_declare(input: TokenStream) -> TokenStream {
         }
 
         let intmod_type = intmod_type.expect("mod_type parameter is required");
+        let const_a = const_a.unwrap_or_else(
+            || syn::parse_quote!(<#intmod_type as openvm_algebra_guest::IntMod>::ZERO),
+        );
         let const_b = const_b.expect("constant b coefficient is required");
 
         macro_rules! create_extern_func {
@@ -76,6 +87,7 @@ pub fn sw_declare(input: TokenStream) -> TokenStream {
         create_extern_func!(sw_add_ne_extern_func);
         create_extern_func!(sw_double_extern_func);
         create_extern_func!(hint_decompress_extern_func);
+        create_extern_func!(sw_mul_extern_func);
 
         let group_ops_mod_name = format_ident!("{}_ops", struct_name.to_string().to_lowercase());
 
@@ -84,6 +96,7 @@ pub fn sw_declare(input: TokenStream) -> TokenStream {
                 fn #sw_add_ne_extern_func(rd: usize, rs1: usize, rs2: usize);
                 fn #sw_double_extern_func(rd: usize, rs1: usize);
                 fn #hint_decompress_extern_func(rs1: usize, rs2: usize);
+                fn #sw_mul_extern_func(rd: usize, rs1: usize, rs2: usize);
             }
 
             #[derive(Eq, PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -156,7 +169,8 @@ pub fn sw_declare(input: TokenStream) -> TokenStream {
                     {
                         use openvm_algebra_guest::DivUnsafe;
                         let two = #intmod_type::from_u8(2);
-                        let lambda = &p.x * &p.x * #intmod_type::from_u8(3).div_unsafe(&p.y * &two);
+                        let lambda = (&p.x * &p.x * #intmod_type::from_u8(3) + &#const_a)
+                            .div_unsafe(&p.y * &two);
                         let x3 = &lambda * &lambda - &p.x * &two;
                         let y3 = &lambda * &(&p.x - &x3) - &p.y;
                         #struct_name { x: x3, y: y3 }
@@ -193,6 +207,7 @@ pub fn sw_declare(input: TokenStream) -> TokenStream {
             }
 
             impl ::openvm_ecc_guest::weierstrass::WeierstrassPoint for #struct_name {
+                const CURVE_A: #intmod_type = #const_a;
                 const CURVE_B: #intmod_type = #const_b;
                 const IDENTITY: Self = Self::identity();
                 type Coordinate = #intmod_type;
@@ -250,6 +265,33 @@ pub fn sw_declare(input: TokenStream) -> TokenStream {
                 fn double_assign_nonidentity(&mut self) {
                     Self::double_assign_impl(self);
                 }
+
+                // Outside the zkVM, the default double-and-add implementation is used.
+                #[cfg(target_os = "zkvm")]
+                fn mul_nonidentity(
+                    &self,
+                    scalar: &[u8],
+                    _: ::openvm_ecc_guest::weierstrass::ScalarMulToken,
+                ) -> Self {
+                    // The intrinsic reads the scalar as `NUM_LIMBS` little-endian bytes,
+                    // so it is copied into a coordinate-sized buffer. A longer scalar is
+                    // rejected unless its extra bytes are zero.
+                    const NUM_LIMBS: usize = <#intmod_type as openvm_algebra_guest::IntMod>::NUM_LIMBS;
+                    let (low, high) = scalar.split_at(scalar.len().min(NUM_LIMBS));
+                    assert!(high.iter().all(|&byte| byte == 0), "scalar is larger than the coordinate field");
+                    let mut scalar_bytes = [0u8; NUM_LIMBS];
+                    scalar_bytes[..low.len()].copy_from_slice(low);
+                    let scalar = <#intmod_type as openvm_algebra_guest::IntMod>::from_le_bytes(&scalar_bytes);
+                    let mut uninit: core::mem::MaybeUninit<#struct_name> = core::mem::MaybeUninit::uninit();
+                    unsafe {
+                        #sw_mul_extern_func(
+                            uninit.as_mut_ptr() as usize,
+                            self as *const #struct_name as usize,
+                            &scalar as *const #intmod_type as usize
+                        )
+                    };
+                    unsafe { uninit.assume_init() }
+                }
             }
 
             impl core::ops::Neg for #struct_name {
@@ -296,17 +338,13 @@ pub fn sw_declare(input: TokenStream) -> TokenStream {
                         }
                         #[cfg(target_os = "zkvm")]
                         {
-                            use openvm::platform as openvm_platform; // needed for hint_store_u32!
+                            use openvm::platform as openvm_platform; // needed for hint_buffer_u32!
 
                             let y = core::mem::MaybeUninit::<#intmod_type>::uninit();
                             unsafe {
                                 #hint_decompress_extern_func(x as *const _ as usize, rec_id as *const u8 as usize);
-                                let mut ptr = y.as_ptr() as *const u8;
-                                // NOTE[jpw]: this loop could be unrolled using seq_macro and hint_store_u32(ptr, $imm)
-                                for _ in (0..<#intmod_type as openvm_algebra_guest::IntMod>::NUM_LIMBS).step_by(4) {
-                                    openvm_rv32im_guest::hint_store_u32!(ptr, 0);
-                                    ptr = ptr.add(4);
-                                }
+                                let ptr = y.as_ptr() as *const u8;
+                                openvm_rv32im_guest::hint_buffer_u32!(ptr, <#intmod_type as openvm_algebra_guest::IntMod>::NUM_LIMBS / 4);
                                 y.assume_init()
                             }
                         }
@@ -367,6 +405,8 @@ pub fn sw_init(input: TokenStream) -> TokenStream {
             &format!("hint_decompress_extern_func_{}", str_path),
             span.into(),
         );
+        let mul_extern_func =
+            syn::Ident::new(&format!("sw_mul_extern_func_{}", str_path), span.into());
         externs.push(quote::quote_spanned! { span.into() =>
             #[no_mangle]
             extern "C" fn #add_ne_extern_func(rd: usize, rs1: usize, rs2: usize) {
@@ -408,6 +448,19 @@ pub fn sw_init(input: TokenStream) -> TokenStream {
                     );
                 }
             }
+
+            #[no_mangle]
+            extern "C" fn #mul_extern_func(rd: usize, rs1: usize, rs2: usize) {
+                openvm_platform::custom_insn_r!(
+                    OPCODE,
+                    SW_FUNCT3 as usize,
+                    SwBaseFunct7::SwMul as usize + #ec_idx
+                        * (SwBaseFunct7::SHORT_WEIERSTRASS_MAX_KINDS as usize),
+                    rd,
+                    rs1,
+                    rs2
+                );
+            }
         });
 
         let setup_function = syn::Ident::new(&format!("setup_sw_{}", str_path), span.into());
@@ -0,0 +1,69 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::hint::black_box;
+
+use hex_literal::hex;
+use openvm_algebra_guest::IntMod;
+use openvm_ecc_guest::{
+    k256::{Secp256k1Coord, Secp256k1Point, Secp256k1Scalar},
+    weierstrass::IntrinsicCurve,
+};
+
+openvm::entry!(main);
+
+openvm_algebra_moduli_setup::moduli_init! {
+    "0xFFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFE FFFFFC2F",
+    "0xFFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFE BAAEDCE6 AF48A03B BFD25E8C D0364141"
+}
+
+openvm_ecc_sw_setup::sw_init! {
+    Secp256k1Coord,
+}
+
+pub fn main() {
+    setup_all_moduli();
+    setup_all_curves();
+
+    let x1 = Secp256k1Coord::from_u32(1);
+    let y1 = Secp256k1Coord::from_le_bytes(&hex!(
+        "EEA7767E580D75BC6FDD7F58D2A84C2614FB22586068DB63B346C6E60AF21842"
+    ));
+    let p1 = black_box(Secp256k1Point {
+        x: x1.clone(),
+        y: y1.clone(),
+    });
+
+    // Multiplication by one is the identity map
+    let one = Secp256k1Scalar::from_u32(1);
+    let result = k256::Secp256k1::msm(&[one.clone()], &[p1.clone()]);
+    if result.x != x1 || result.y != y1 {
+        panic!();
+    }
+
+    // 12345678 * (x1, y1), same as in the `ec` example
+    let x5 = Secp256k1Coord::from_le_bytes(&hex!(
+        "194A93387F790803D972AF9C4A40CB89D106A36F58EE2F31DC48A41768216D6D"
+    ));
+    let y5 = Secp256k1Coord::from_le_bytes(&hex!(
+        "9E272F746DA7BED171E522610212B6AEEAAFDB2AD9F4B530B8E1B27293B19B2C"
+    ));
+    let scalar = black_box(Secp256k1Scalar::from_u32(12345678));
+    let result = k256::Secp256k1::msm(&[scalar], &[p1.clone()]);
+    if result.x != x5 || result.y != y5 {
+        panic!();
+    }
+
+    // Terms are multiplied separately with `EC_MUL` and summed, and zero coefficients are skipped
+    let result = k256::Secp256k1::msm(
+        &[
+            Secp256k1Scalar::from_u32(12345677),
+            Secp256k1Scalar::from_u32(0),
+            one,
+        ],
+        &[p1.clone(), p1.clone(), p1],
+    );
+    if result.x != x5 || result.y != y5 {
+        panic!();
+    }
+}
@@ -0,0 +1,52 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::hint::black_box;
+
+use hex_literal::hex;
+use openvm_ecc_guest::{ecdsa::VerifyingKey, p256::P256Coord};
+use openvm_sha256_guest::sha256;
+use p256::NistP256;
+openvm::entry!(main);
+
+openvm_algebra_moduli_setup::moduli_init! {
+    "0xFFFFFFFF 00000001 00000000 00000000 00000000 FFFFFFFF FFFFFFFF FFFFFFFF",
+    "0xFFFFFFFF 00000000 FFFFFFFF FFFFFFFF BCE6FAAD A7179E84 F3B9CAC2 FC632551"
+}
+openvm_ecc_sw_setup::sw_init! {
+    P256Coord,
+}
+
+pub fn main() {
+    setup_all_moduli();
+    setup_all_curves();
+
+    let msg = b"example message";
+
+    // r || s, big endian
+    let signature = hex!(
+        "ca88229bf979b1e0bff8d517d186635e328de8673e67c40d297abdef115a5fdcd723dff2f10d0595300523e3d0460d6d05884a13bad5e1e22a2a191bfa0388b3"
+    );
+
+    let prehash = sha256(black_box(msg));
+
+    let uncompressed_key = VerifyingKey::<NistP256>::from_sec1_bytes(&hex!(
+        "04f1d94b8ce37d3e8f855319d2b0938d22c69c1aba5afdbe036ebd64a73fc37d1bbb609dc3ffbaf0c6f396edbc42a731661d6ef72618a4366b7144c7f29301a996"
+    ))
+    .unwrap();
+    let compressed_key = VerifyingKey::<NistP256>::from_sec1_bytes(&hex!(
+        "02f1d94b8ce37d3e8f855319d2b0938d22c69c1aba5afdbe036ebd64a73fc37d1b"
+    ))
+    .unwrap();
+    assert_eq!(uncompressed_key.as_affine(), compressed_key.as_affine());
+
+    uncompressed_key
+        .verify_prehashed(&prehash, &signature)
+        .unwrap();
+
+    // A different message must not verify.
+    let other_prehash = sha256(black_box(b"other message"));
+    assert!(compressed_key
+        .verify_prehashed(&other_prehash, &signature)
+        .is_err());
+}
@@ -0,0 +1,49 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::hint::black_box;
+
+use hex_literal::hex;
+use openvm_ecc_guest::{
+    ed25519::{Ed25519Coord, Ed25519Point, Ed25519VerifyingKey},
+    CyclicGroup, Group,
+};
+openvm::entry!(main);
+
+openvm_algebra_moduli_setup::moduli_init! {
+    "0x7FFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFED",
+    "0x10000000 00000000 00000000 00000000 14DEF9DE A2F79CD6 5812631A 5CF5D3ED"
+}
+openvm_ecc_te_setup::te_init! {
+    Ed25519Coord,
+}
+
+pub fn main() {
+    setup_all_moduli();
+    setup_all_te_curves();
+
+    // The unified addition formula also doubles and adds the identity.
+    let g = Ed25519Point::GENERATOR;
+    assert_eq!(&g + &Ed25519Point::IDENTITY, g);
+    assert_eq!(&g + &Ed25519Point::NEG_GENERATOR, Ed25519Point::IDENTITY);
+    assert_eq!(g.double(), &g + &g);
+    assert_eq!(
+        Ed25519Point::from_compressed_bytes(&g.to_compressed_bytes()),
+        Some(g)
+    );
+
+    let msg = b"example message";
+    let public_key = Ed25519VerifyingKey::from_bytes(&hex!(
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+    ))
+    .unwrap();
+    let signature = hex!(
+        "72d9799d89c3cfc615fb160c04520aa4a2aef11569dfa87184328c3dda33f26c1c7d2dbbb32b9b5ae4f163933e44e01fc031e42ffcd13792bdcfcef22c407106"
+    );
+    public_key.verify(black_box(msg), &signature).unwrap();
+
+    // A different message must not verify.
+    assert!(public_key
+        .verify(black_box(b"other message"), &signature)
+        .is_err());
+}
@@ -7,13 +7,17 @@ mod tests {
         arch::{instructions::exe::VmExe, SystemConfig},
         utils::{air_test, air_test_with_min_segments},
     };
-    use openvm_ecc_circuit::{Rv32WeierstrassConfig, WeierstrassExtension, SECP256K1_CONFIG};
-    use openvm_ecc_transpiler::EccTranspilerExtension;
+    use openvm_ecc_circuit::{
+        Rv32EdwardsConfig, Rv32WeierstrassConfig, WeierstrassExtension, ED25519_CONFIG,
+        SECP256K1_CONFIG, SECP256R1_CONFIG,
+    };
+    use openvm_ecc_transpiler::{EccTranspilerExtension, EdwardsTranspilerExtension};
     use openvm_keccak256_transpiler::Keccak256TranspilerExtension;
     use openvm_rv32im_transpiler::{
         Rv32ITranspilerExtension, Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
     };
     use openvm_sdk::config::SdkVmConfig;
+    use openvm_sha256_transpiler::Sha256TranspilerExtension;
     use openvm_stark_backend::p3_field::AbstractField;
     use openvm_stark_sdk::{openvm_stark_backend, p3_baby_bear::BabyBear};
     use openvm_toolchain_tests::{build_example_program_at_path_with_features, get_programs_dir};
@@ -37,6 +41,24 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_ec_mul() -> Result<()> {
+        let elf =
+            build_example_program_at_path_with_features(get_programs_dir!(), "ec_mul", ["k256"])?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(EccTranspilerExtension)
+                .with_extension(ModularTranspilerExtension),
+        )?;
+        let config = Rv32WeierstrassConfig::new(vec![SECP256K1_CONFIG.clone()]);
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+
     #[test]
     fn test_decompress() -> Result<()> {
         use openvm_ecc_guest::halo2curves::{group::Curve, secp256k1::Secp256k1Affine};
@@ -98,4 +120,55 @@ mod tests {
         air_test(config, openvm_exe);
         Ok(())
     }
+
+    #[test]
+    fn test_ecdsa_p256() -> Result<()> {
+        let elf = build_example_program_at_path_with_features(
+            get_programs_dir!(),
+            "ecdsa_p256",
+            ["p256"],
+        )?;
+        let config = SdkVmConfig::builder()
+            .system(SystemConfig::default().with_continuations().into())
+            .rv32i(Default::default())
+            .rv32m(Default::default())
+            .io(Default::default())
+            .modular(ModularExtension::new(vec![
+                SECP256R1_CONFIG.modulus.clone(),
+                SECP256R1_CONFIG.scalar.clone(),
+            ]))
+            .sha256(Default::default())
+            .ecc(WeierstrassExtension::new(vec![SECP256R1_CONFIG.clone()]))
+            .build();
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(Sha256TranspilerExtension)
+                .with_extension(EccTranspilerExtension)
+                .with_extension(ModularTranspilerExtension),
+        )?;
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+
+    #[test]
+    fn test_eddsa() -> Result<()> {
+        let elf =
+            build_example_program_at_path_with_features(get_programs_dir!(), "eddsa", ["ed25519"])?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(EdwardsTranspilerExtension)
+                .with_extension(ModularTranspilerExtension),
+        )?;
+        let config = Rv32EdwardsConfig::new(vec![ED25519_CONFIG.clone()]);
+        air_test(config, openvm_exe);
+        Ok(())
+    }
 }
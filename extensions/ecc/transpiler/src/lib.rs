@@ -1,4 +1,4 @@
-use openvm_ecc_guest::{SwBaseFunct7, OPCODE, SW_FUNCT3};
+use openvm_ecc_guest::{SwBaseFunct7, TeBaseFunct7, OPCODE, SW_FUNCT3, TE_FUNCT3};
 use openvm_instructions::{
     instruction::Instruction, riscv::RV32_REGISTER_NUM_LIMBS, PhantomDiscriminant, UsizeOpcode,
     VmOpcode,
@@ -20,6 +20,18 @@ pub enum Rv32WeierstrassOpcode {
     SETUP_EC_ADD_NE,
     EC_DOUBLE,
     SETUP_EC_DOUBLE,
+    EC_MUL,
+}
+
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, EnumCount, EnumIter, FromRepr, UsizeOpcode,
+)]
+#[opcode_offset = 0x680]
+#[allow(non_camel_case_types)]
+#[repr(usize)]
+pub enum Rv32EdwardsOpcode {
+    EC_ADD,
+    SETUP_EC_ADD,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, FromRepr)]
@@ -95,6 +107,10 @@ impl<F: PrimeField32> TranspilerExtension<F> for EccTranspilerExtension {
                         Rv32WeierstrassOpcode::EC_DOUBLE as usize
                             + Rv32WeierstrassOpcode::default_offset()
                     }
+                    Some(SwBaseFunct7::SwMul) => {
+                        Rv32WeierstrassOpcode::EC_MUL as usize
+                            + Rv32WeierstrassOpcode::default_offset()
+                    }
                     _ => unimplemented!(),
                 };
                 let global_opcode = global_opcode + curve_idx_shift;
@@ -104,3 +120,53 @@ impl<F: PrimeField32> TranspilerExtension<F> for EccTranspilerExtension {
         instruction.map(|instruction| (instruction, 1))
     }
 }
+
+#[derive(Default)]
+pub struct EdwardsTranspilerExtension;
+
+impl<F: PrimeField32> TranspilerExtension<F> for EdwardsTranspilerExtension {
+    fn process_custom(&self, instruction_stream: &[u32]) -> Option<(Instruction<F>, usize)> {
+        if instruction_stream.is_empty() {
+            return None;
+        }
+        let instruction_u32 = instruction_stream[0];
+        let opcode = (instruction_u32 & 0x7f) as u8;
+        let funct3 = ((instruction_u32 >> 12) & 0b111) as u8;
+
+        if opcode != OPCODE {
+            return None;
+        }
+        if funct3 != TE_FUNCT3 {
+            return None;
+        }
+
+        // twisted edwards ec
+        assert!(Rv32EdwardsOpcode::COUNT <= TeBaseFunct7::TWISTED_EDWARDS_MAX_KINDS as usize);
+        let dec_insn = RType::new(instruction_u32);
+        let base_funct7 = (dec_insn.funct7 as u8) % TeBaseFunct7::TWISTED_EDWARDS_MAX_KINDS;
+        let curve_idx =
+            ((dec_insn.funct7 as u8) / TeBaseFunct7::TWISTED_EDWARDS_MAX_KINDS) as usize;
+        let curve_idx_shift = curve_idx * Rv32EdwardsOpcode::COUNT;
+        let instruction = match TeBaseFunct7::from_repr(base_funct7) {
+            Some(TeBaseFunct7::TeSetup) => Instruction::new(
+                VmOpcode::from_usize(
+                    Rv32EdwardsOpcode::SETUP_EC_ADD.with_default_offset() + curve_idx_shift,
+                ),
+                F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rd),
+                F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rs1),
+                F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rs2),
+                F::ONE, // d_as = 1
+                F::TWO, // e_as = 2
+                F::ZERO,
+                F::ZERO,
+            ),
+            Some(TeBaseFunct7::TeAdd) => from_r_type(
+                Rv32EdwardsOpcode::EC_ADD.with_default_offset() + curve_idx_shift,
+                2,
+                &dec_insn,
+            ),
+            None => unimplemented!(),
+        };
+        Some((instruction, 1))
+    }
+}
@@ -16,7 +16,6 @@ use openvm_rv32im_circuit::{
 };
 use openvm_stark_backend::p3_field::PrimeField32;
 use serde::{Deserialize, Serialize};
-use strum::IntoEnumIterator;
 
 use crate::*;
 
@@ -52,6 +51,7 @@ pub struct Keccak256;
 #[derive(ChipUsageGetter, Chip, InstructionExecutor, From, AnyEnum)]
 pub enum Keccak256Executor<F: PrimeField32> {
     Keccak256(KeccakVmChip<F>),
+    Keccakf(KeccakfVmChip<F>),
 }
 
 #[derive(From, ChipUsageGetter, Chip, AnyEnum)]
@@ -87,6 +87,18 @@ impl<F: PrimeField32> VmExtension<F> for Keccak256 {
         };
 
         let keccak_chip = KeccakVmChip::new(
+            execution_bus,
+            program_bus,
+            memory_controller.clone(),
+            bitwise_lu_chip.clone(),
+            Rv32KeccakOpcode::default_offset(),
+        );
+        inventory.add_executor(
+            keccak_chip,
+            [Rv32KeccakOpcode::KECCAK256.with_default_offset()],
+        )?;
+
+        let keccakf_chip = KeccakfVmChip::new(
             execution_bus,
             program_bus,
             memory_controller,
@@ -94,8 +106,8 @@ impl<F: PrimeField32> VmExtension<F> for Keccak256 {
             Rv32KeccakOpcode::default_offset(),
         );
         inventory.add_executor(
-            keccak_chip,
-            Rv32KeccakOpcode::iter().map(VmOpcode::with_default_offset),
+            keccakf_chip,
+            [Rv32KeccakOpcode::KECCAKF.with_default_offset()],
         )?;
 
         Ok(inventory)
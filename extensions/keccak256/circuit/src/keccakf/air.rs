@@ -0,0 +1,228 @@
+use std::borrow::Borrow;
+
+use itertools::Itertools;
+use openvm_circuit::{
+    arch::{ExecutionBridge, ExecutionState},
+    system::memory::{offline_checker::MemoryBridge, MemoryAddress},
+};
+use openvm_circuit_primitives::{bitwise_op_lookup::BitwiseOperationLookupBus, utils::not};
+use openvm_instructions::riscv::{
+    RV32_CELL_BITS, RV32_MEMORY_AS, RV32_REGISTER_AS, RV32_REGISTER_NUM_LIMBS,
+};
+use openvm_keccak256_transpiler::Rv32KeccakOpcode;
+use openvm_rv32im_circuit::adapters::abstract_compose;
+use openvm_stark_backend::{
+    air_builders::sub::SubAirBuilder,
+    interaction::InteractionBuilder,
+    p3_air::{Air, AirBuilder, BaseAir},
+    p3_field::AbstractField,
+    p3_matrix::Matrix,
+    rap::{BaseAirWithPublicValues, PartitionedBaseAir},
+};
+use p3_keccak_air::{KeccakAir, NUM_KECCAK_COLS as NUM_KECCAK_PERM_COLS, U64_LIMBS};
+
+use super::{
+    columns::{KeccakfVmCols, NUM_KECCAKF_VM_COLS},
+    KECCAKF_STATE_WORDS,
+};
+use crate::{KECCAK_WIDTH_U16S, KECCAK_WORD_SIZE};
+
+/// AIR for the KECCAKF_RV32 instruction, which applies keccak-f\[1600\] in place to the
+/// 200-byte state at `[rd:200]_2`. Each instruction takes [p3_keccak_air::NUM_ROUNDS] rows.
+#[derive(Clone, Copy, Debug, derive_new::new)]
+pub struct KeccakfVmAir {
+    pub execution_bridge: ExecutionBridge,
+    pub memory_bridge: MemoryBridge,
+    /// Bus to send 8-bit range check requests to.
+    pub bitwise_lookup_bus: BitwiseOperationLookupBus,
+    /// Maximum number of bits allowed for an address pointer
+    pub ptr_max_bits: usize,
+    pub(super) offset: usize,
+}
+
+impl<F> BaseAirWithPublicValues<F> for KeccakfVmAir {}
+impl<F> PartitionedBaseAir<F> for KeccakfVmAir {}
+impl<F> BaseAir<F> for KeccakfVmAir {
+    fn width(&self) -> usize {
+        NUM_KECCAKF_VM_COLS
+    }
+}
+
+impl<AB: InteractionBuilder> Air<AB> for KeccakfVmAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &KeccakfVmCols<AB::Var> = (*local).borrow();
+        let next: &KeccakfVmCols<AB::Var> = (*next).borrow();
+
+        // WARNING: The keccak-f AIR columns **must** be the first columns in the main AIR.
+        let keccak_f_air = KeccakAir {};
+        let mut sub_builder =
+            SubAirBuilder::<AB, KeccakAir, AB::Var>::new(builder, 0..NUM_KECCAK_PERM_COLS);
+        keccak_f_air.eval(&mut sub_builder);
+
+        let instruction = local.instruction;
+        builder.assert_bool(instruction.is_enabled);
+        builder.assert_eq(
+            instruction.is_enabled_first_round,
+            instruction.is_enabled * local.is_first_round(),
+        );
+        builder.assert_eq(
+            local.inner.export,
+            instruction.is_enabled * local.is_last_round(),
+        );
+        // Instruction columns are constant across the rounds of one permutation
+        let mut transition_builder = builder.when_transition();
+        let mut round_builder = transition_builder.when(not(local.is_last_round()));
+        instruction.assert_eq(&mut round_builder, next.instruction);
+
+        self.eval_instruction(builder, local);
+        self.constrain_state_bytes(builder, local);
+    }
+}
+
+impl KeccakfVmAir {
+    /// Receive the instruction on the program bus, send+receive on the execution bus and read
+    /// the `rd` register. Range checks the most significant limb of the register value.
+    pub fn eval_instruction<AB: InteractionBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &KeccakfVmCols<AB::Var>,
+    ) {
+        let instruction = local.instruction;
+        let should_receive = instruction.is_enabled_first_round;
+
+        self.execution_bridge
+            .execute_and_increment_pc(
+                AB::Expr::from_canonical_usize(Rv32KeccakOpcode::KECCAKF as usize + self.offset),
+                [
+                    instruction.rd_ptr.into(),
+                    AB::Expr::ZERO,
+                    AB::Expr::ZERO,
+                    AB::Expr::from_canonical_u32(RV32_REGISTER_AS),
+                    AB::Expr::from_canonical_u32(RV32_MEMORY_AS),
+                ],
+                ExecutionState::new(instruction.pc, instruction.start_timestamp),
+                AB::Expr::from_canonical_usize(Self::timestamp_change()),
+            )
+            .eval(builder, should_receive);
+
+        self.memory_bridge
+            .read(
+                MemoryAddress::new(
+                    AB::Expr::from_canonical_u32(RV32_REGISTER_AS),
+                    instruction.rd_ptr,
+                ),
+                instruction.rd,
+                instruction.start_timestamp,
+                &local.mem_oc.register_aux,
+            )
+            .eval(builder, should_receive);
+
+        let limb_shift = AB::F::from_canonical_usize(
+            1 << (RV32_CELL_BITS * RV32_REGISTER_NUM_LIMBS - self.ptr_max_bits),
+        );
+        self.bitwise_lookup_bus
+            .send_range(*instruction.rd.last().unwrap() * limb_shift, AB::Expr::ZERO)
+            .eval(builder, should_receive);
+    }
+
+    /// Reads the preimage from memory on the first round and writes the postimage to memory on
+    /// the last round.
+    ///
+    /// Bytes are derived from the `u16` limbs of the keccak-f state as in
+    /// [crate::KeccakVmAir::constrain_absorb]: for each limb `x` we are given `hi` and use
+    /// `lo = x - hi * 256`. Both `lo` and `hi` are range checked to 8 bits on the rows where the
+    /// bytes are used.
+    pub fn constrain_state_bytes<AB: InteractionBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &KeccakfVmCols<AB::Var>,
+    ) {
+        let instruction = local.instruction;
+        let state_bytes = |limbs: [AB::Var; KECCAK_WIDTH_U16S]| -> Vec<AB::Expr> {
+            limbs
+                .into_iter()
+                .zip(local.state_hi)
+                .flat_map(|(limb, hi)| {
+                    let lo = limb - hi * AB::F::from_canonical_u64(1 << 8);
+                    // Conversion from bytes to u64 is little-endian
+                    [lo, hi.into()]
+                })
+                .collect()
+        };
+        // `u16` limb `i` of the state belongs to lane `i / U64_LIMBS`, and lane `k` is at
+        // `(y, x) = (k / 5, k % 5)`
+        let preimage_bytes = state_bytes(std::array::from_fn(|i| {
+            let k = i / U64_LIMBS;
+            local.inner.preimage[k / 5][k % 5][i % U64_LIMBS]
+        }));
+        let postimage_bytes = state_bytes(std::array::from_fn(|i| {
+            let k = i / U64_LIMBS;
+            local.postimage(k / 5, k % 5, i % U64_LIMBS)
+        }));
+
+        for (bytes, count) in [
+            (&preimage_bytes, instruction.is_enabled_first_round),
+            (&postimage_bytes, local.inner.export),
+        ] {
+            for pair in bytes.chunks_exact(2) {
+                self.bitwise_lookup_bus
+                    .send_range(pair[0].clone(), pair[1].clone())
+                    .eval(builder, count);
+            }
+        }
+
+        let state_ptr = abstract_compose::<AB::Expr, _>(instruction.rd);
+        let memory_as = AB::Expr::from_canonical_u32(RV32_MEMORY_AS);
+        let start_read_timestamp = instruction.start_timestamp + AB::Expr::ONE;
+        let start_write_timestamp =
+            start_read_timestamp.clone() + AB::Expr::from_canonical_usize(KECCAKF_STATE_WORDS);
+        for (i, (word, aux)) in preimage_bytes
+            .into_iter()
+            .chunks(KECCAK_WORD_SIZE)
+            .into_iter()
+            .zip(&local.mem_oc.state_reads)
+            .enumerate()
+        {
+            let word: [_; KECCAK_WORD_SIZE] = word.collect_vec().try_into().unwrap();
+            self.memory_bridge
+                .read(
+                    MemoryAddress::new(
+                        memory_as.clone(),
+                        state_ptr.clone() + AB::F::from_canonical_usize(i * KECCAK_WORD_SIZE),
+                    ),
+                    word,
+                    start_read_timestamp.clone() + AB::F::from_canonical_usize(i),
+                    aux,
+                )
+                .eval(builder, instruction.is_enabled_first_round);
+        }
+        for (i, (word, aux)) in postimage_bytes
+            .into_iter()
+            .chunks(KECCAK_WORD_SIZE)
+            .into_iter()
+            .zip(&local.mem_oc.state_writes)
+            .enumerate()
+        {
+            let word: [_; KECCAK_WORD_SIZE] = word.collect_vec().try_into().unwrap();
+            self.memory_bridge
+                .write(
+                    MemoryAddress::new(
+                        memory_as.clone(),
+                        state_ptr.clone() + AB::F::from_canonical_usize(i * KECCAK_WORD_SIZE),
+                    ),
+                    word,
+                    start_write_timestamp.clone() + AB::F::from_canonical_usize(i),
+                    aux,
+                )
+                .eval(builder, local.inner.export);
+        }
+    }
+
+    /// Amount to advance timestamp by after execution of one opcode instruction:
+    /// one register read, then one read and one write per word of state.
+    pub const fn timestamp_change() -> usize {
+        1 + 2 * KECCAKF_STATE_WORDS
+    }
+}
@@ -0,0 +1,155 @@
+//! Chip for the keccak-f\[1600\] permutation applied in place to a 200-byte state in VM memory.
+//! Reuses the keccak-f AIR of [crate::KeccakVmAir], without any of the sponge logic.
+use std::{array::from_fn, sync::Arc};
+
+use openvm_circuit::{
+    arch::{ExecutionBridge, ExecutionBus, ExecutionError, ExecutionState, InstructionExecutor},
+    system::{
+        memory::{MemoryControllerRef, MemoryReadRecord, MemoryWriteRecord},
+        program::ProgramBus,
+    },
+};
+use openvm_circuit_primitives::bitwise_op_lookup::BitwiseOperationLookupChip;
+use openvm_instructions::{
+    instruction::Instruction, program::DEFAULT_PC_STEP, riscv::RV32_REGISTER_NUM_LIMBS, UsizeOpcode,
+};
+use openvm_keccak256_transpiler::Rv32KeccakOpcode;
+use openvm_rv32im_circuit::adapters::read_rv32_register;
+use openvm_stark_backend::p3_field::PrimeField32;
+use tiny_keccak::keccakf;
+
+use crate::{KECCAK_WIDTH_BYTES, KECCAK_WORD_SIZE};
+
+pub mod air;
+pub mod columns;
+pub mod trace;
+
+pub use air::KeccakfVmAir;
+
+/// Memory reads (and writes) to access the full state
+pub const KECCAKF_STATE_WORDS: usize = KECCAK_WIDTH_BYTES / KECCAK_WORD_SIZE;
+
+#[derive(Debug)]
+pub struct KeccakfVmChip<F: PrimeField32> {
+    pub air: KeccakfVmAir,
+    pub records: Vec<KeccakfRecord<F>>,
+    pub memory_controller: MemoryControllerRef<F>,
+    pub bitwise_lookup_chip: Arc<BitwiseOperationLookupChip<8>>,
+
+    offset: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct KeccakfRecord<F> {
+    pub pc: F,
+    pub rd_read: MemoryReadRecord<F, RV32_REGISTER_NUM_LIMBS>,
+    /// Reads of the preimage state
+    pub state_reads: [MemoryReadRecord<F, KECCAK_WORD_SIZE>; KECCAKF_STATE_WORDS],
+    /// Writes of the postimage state
+    pub state_writes: [MemoryWriteRecord<F, KECCAK_WORD_SIZE>; KECCAKF_STATE_WORDS],
+}
+
+impl<F: PrimeField32> KeccakfVmChip<F> {
+    pub fn new(
+        execution_bus: ExecutionBus,
+        program_bus: ProgramBus,
+        memory_controller: MemoryControllerRef<F>,
+        bitwise_lookup_chip: Arc<BitwiseOperationLookupChip<8>>,
+        offset: usize,
+    ) -> Self {
+        let ptr_max_bits = memory_controller.borrow().mem_config().pointer_max_bits;
+        let memory_bridge = memory_controller.borrow().memory_bridge();
+        Self {
+            air: KeccakfVmAir::new(
+                ExecutionBridge::new(execution_bus, program_bus),
+                memory_bridge,
+                bitwise_lookup_chip.bus(),
+                ptr_max_bits,
+                offset,
+            ),
+            memory_controller,
+            bitwise_lookup_chip,
+            records: Vec::new(),
+            offset,
+        }
+    }
+}
+
+impl<F: PrimeField32> InstructionExecutor<F> for KeccakfVmChip<F> {
+    fn execute(
+        &mut self,
+        instruction: Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> Result<ExecutionState<u32>, ExecutionError> {
+        let Instruction {
+            opcode, a, d, e, ..
+        } = instruction;
+        let local_opcode = Rv32KeccakOpcode::from_usize(opcode.local_opcode_idx(self.offset));
+        debug_assert_eq!(local_opcode, Rv32KeccakOpcode::KECCAKF);
+
+        let mut memory = self.memory_controller.borrow_mut();
+        debug_assert_eq!(from_state.timestamp, memory.timestamp());
+
+        let (rd_read, state_ptr) = read_rv32_register(&mut memory, d, a);
+        debug_assert!(state_ptr < (1 << self.air.ptr_max_bits));
+        let state_ptr = state_ptr as usize;
+
+        let state_reads: [_; KECCAKF_STATE_WORDS] = from_fn(|i| {
+            memory.read::<KECCAK_WORD_SIZE>(
+                e,
+                F::from_canonical_usize(state_ptr + i * KECCAK_WORD_SIZE),
+            )
+        });
+        let mut state = state_from_words(&state_reads.map(|read| read.data));
+        keccakf(&mut state);
+        let output: Vec<u8> = state.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let state_writes: [_; KECCAKF_STATE_WORDS] = from_fn(|i| {
+            memory.write::<KECCAK_WORD_SIZE>(
+                e,
+                F::from_canonical_usize(state_ptr + i * KECCAK_WORD_SIZE),
+                from_fn(|j| F::from_canonical_u8(output[i * KECCAK_WORD_SIZE + j])),
+            )
+        });
+
+        let record = KeccakfRecord {
+            pc: F::from_canonical_u32(from_state.pc),
+            rd_read,
+            state_reads,
+            state_writes,
+        };
+        if !memory.is_pure_execution() {
+            self.records.push(record);
+        }
+
+        Ok(ExecutionState {
+            pc: from_state.pc + DEFAULT_PC_STEP,
+            timestamp: memory.timestamp(),
+        })
+    }
+
+    fn get_opcode_name(&self, _: usize) -> String {
+        "KECCAKF".to_string()
+    }
+}
+
+impl<F: Copy> KeccakfRecord<F> {
+    pub fn start_timestamp(&self) -> u32 {
+        self.rd_read.timestamp
+    }
+}
+
+/// Converts the state from words of bytes in memory to little-endian `u64` lanes.
+pub(crate) fn state_from_words<F: PrimeField32>(
+    words: &[[F; KECCAK_WORD_SIZE]; KECCAKF_STATE_WORDS],
+) -> [u64; 25] {
+    let bytes: Vec<u8> = words
+        .iter()
+        .flatten()
+        .map(|x| {
+            x.as_canonical_u32()
+                .try_into()
+                .expect("Memory cell not a byte")
+        })
+        .collect();
+    from_fn(|i| u64::from_le_bytes(bytes[i * 8..(i + 1) * 8].try_into().unwrap()))
+}
@@ -0,0 +1,155 @@
+use std::{borrow::BorrowMut, sync::Arc};
+
+use openvm_instructions::riscv::{RV32_CELL_BITS, RV32_REGISTER_NUM_LIMBS};
+use openvm_stark_backend::{
+    config::{StarkGenericConfig, Val},
+    p3_air::BaseAir,
+    p3_field::{AbstractField, PrimeField32},
+    p3_matrix::{dense::RowMajorMatrix, Matrix},
+    p3_maybe_rayon::prelude::*,
+    prover::types::AirProofInput,
+    rap::{get_air_name, AnyRap},
+    Chip, ChipUsageGetter,
+};
+use p3_keccak_air::{generate_trace_rows, NUM_KECCAK_COLS as NUM_KECCAK_PERM_COLS, NUM_ROUNDS};
+
+use super::{
+    columns::{KeccakfInstructionCols, KeccakfVmCols},
+    state_from_words, KeccakfRecord, KeccakfVmChip,
+};
+use crate::KECCAK_WIDTH_U16S;
+
+impl<SC: StarkGenericConfig> Chip<SC> for KeccakfVmChip<Val<SC>>
+where
+    Val<SC>: PrimeField32,
+{
+    fn air(&self) -> Arc<dyn AnyRap<SC>> {
+        Arc::new(self.air)
+    }
+
+    fn generate_air_proof_input(self) -> AirProofInput<SC> {
+        let air = self.air();
+        let trace_width = self.trace_width();
+        let records = self.records;
+
+        let states: Vec<[u64; 25]> = records
+            .iter()
+            .map(|record| state_from_words(&record.state_reads.map(|read| read.data)))
+            .collect();
+        let p3_keccak_trace: RowMajorMatrix<Val<SC>> = generate_trace_rows(states);
+        let num_rows = p3_keccak_trace.height();
+        // Every `NUM_ROUNDS` rows corresponds to one permutation; the rest are dummy rows
+        // with `is_enabled = 0`
+        let mut records: Vec<Option<KeccakfRecord<Val<SC>>>> =
+            records.into_iter().map(Some).collect();
+        records.resize(num_rows.div_ceil(NUM_ROUNDS), None);
+
+        let aux_cols_factory = self.memory_controller.borrow().aux_cols_factory();
+
+        let mut trace =
+            RowMajorMatrix::new(Val::<SC>::zero_vec(num_rows * trace_width), trace_width);
+        let limb_shift_bits = RV32_CELL_BITS * RV32_REGISTER_NUM_LIMBS - self.air.ptr_max_bits;
+        trace
+            .values
+            .par_chunks_mut(trace_width * NUM_ROUNDS)
+            .zip(
+                p3_keccak_trace
+                    .values
+                    .par_chunks(NUM_KECCAK_PERM_COLS * NUM_ROUNDS),
+            )
+            .zip(records.into_par_iter())
+            .for_each(|((rows, p3_keccak_mat), record)| {
+                let height = rows.len() / trace_width;
+                let instruction = record
+                    .as_ref()
+                    .map(|record| KeccakfInstructionCols {
+                        pc: record.pc,
+                        is_enabled: Val::<SC>::ONE,
+                        is_enabled_first_round: Val::<SC>::ZERO,
+                        start_timestamp: Val::<SC>::from_canonical_u32(record.start_timestamp()),
+                        rd_ptr: record.rd_read.pointer,
+                        rd: record.rd_read.data,
+                    })
+                    .unwrap_or_default();
+                for (row, p3_keccak_row) in rows
+                    .chunks_exact_mut(trace_width)
+                    .zip(p3_keccak_mat.chunks_exact(NUM_KECCAK_PERM_COLS))
+                {
+                    // Safety: `KeccakPermCols` **must** be the first field in `KeccakfVmCols`
+                    row[..NUM_KECCAK_PERM_COLS].copy_from_slice(p3_keccak_row);
+                    let row_mut: &mut KeccakfVmCols<Val<SC>> = row.borrow_mut();
+                    row_mut.instruction = instruction;
+                }
+                let Some(record) = record else {
+                    return;
+                };
+
+                // Bytes of the state in memory order, as `(lo, hi)` pairs of each `u16` limb
+                let request_range = |bytes: Vec<u32>| -> [Val<SC>; KECCAK_WIDTH_U16S] {
+                    let mut state_hi = [Val::<SC>::ZERO; KECCAK_WIDTH_U16S];
+                    for (pair, hi) in bytes.chunks_exact(2).zip(state_hi.iter_mut()) {
+                        self.bitwise_lookup_chip.request_range(pair[0], pair[1]);
+                        *hi = Val::<SC>::from_canonical_u32(pair[1]);
+                    }
+                    state_hi
+                };
+
+                let first_row: &mut KeccakfVmCols<Val<SC>> = rows[..trace_width].borrow_mut();
+                first_row.instruction.is_enabled_first_round = Val::<SC>::ONE;
+                self.bitwise_lookup_chip.request_range(
+                    record.rd_read.data.last().unwrap().as_canonical_u32() << limb_shift_bits,
+                    0,
+                );
+                first_row.mem_oc.register_aux = aux_cols_factory.make_read_aux_cols(record.rd_read);
+                first_row.state_hi = request_range(
+                    record
+                        .state_reads
+                        .iter()
+                        .flat_map(|read| read.data.map(|x| x.as_canonical_u32()))
+                        .collect(),
+                );
+                for (aux, read) in first_row
+                    .mem_oc
+                    .state_reads
+                    .iter_mut()
+                    .zip(record.state_reads)
+                {
+                    *aux = aux_cols_factory.make_read_aux_cols(read);
+                }
+
+                let last_row: &mut KeccakfVmCols<Val<SC>> =
+                    rows[(height - 1) * trace_width..].borrow_mut();
+                last_row.inner.export = Val::<SC>::ONE;
+                last_row.state_hi = request_range(
+                    record
+                        .state_writes
+                        .iter()
+                        .flat_map(|write| write.data.map(|x| x.as_canonical_u32()))
+                        .collect(),
+                );
+                for (aux, write) in last_row
+                    .mem_oc
+                    .state_writes
+                    .iter_mut()
+                    .zip(record.state_writes)
+                {
+                    *aux = aux_cols_factory.make_write_aux_cols(write);
+                }
+            });
+
+        AirProofInput::simple_no_pis(air, trace)
+    }
+}
+
+impl<F: PrimeField32> ChipUsageGetter for KeccakfVmChip<F> {
+    fn air_name(&self) -> String {
+        get_air_name(&self.air)
+    }
+    fn current_trace_height(&self) -> usize {
+        self.records.len() * NUM_ROUNDS
+    }
+
+    fn trace_width(&self) -> usize {
+        BaseAir::<F>::width(&self.air)
+    }
+}
@@ -1,5 +1,6 @@
 //! Stateful keccak256 hasher. Handles full keccak sponge (padding, absorb, keccak-f) on
 //! variable length inputs read from VM memory.
+//! Also provides a standalone keccak-f permutation chip in [keccakf] for sponges driven by the guest.
 use std::{array::from_fn, cmp::min, sync::Arc};
 
 use openvm_circuit_primitives::bitwise_op_lookup::BitwiseOperationLookupChip;
@@ -9,6 +10,7 @@ use utils::num_keccak_f;
 
 pub mod air;
 pub mod columns;
+pub mod keccakf;
 pub mod trace;
 pub mod utils;
 
@@ -19,6 +21,7 @@ pub use extension::*;
 mod tests;
 
 pub use air::KeccakVmAir;
+pub use keccakf::KeccakfVmChip;
 use openvm_circuit::{
     arch::{ExecutionBridge, ExecutionBus, ExecutionError, ExecutionState, InstructionExecutor},
     system::{
@@ -235,7 +238,9 @@ impl<F: PrimeField32> InstructionExecutor<F> for KeccakVmChip<F> {
         };
 
         // Add the events to chip state for later trace generation usage
-        self.records.push(record);
+        if !memory.is_pure_execution() {
+            self.records.push(record);
+        }
 
         // NOTE: Check this is consistent with KeccakVmAir::timestamp_change (we don't use it to avoid
         // unnecessary conversions here)
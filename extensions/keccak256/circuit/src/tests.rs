@@ -1,4 +1,4 @@
-use std::{borrow::BorrowMut, sync::Arc};
+use std::{array::from_fn, borrow::BorrowMut, sync::Arc};
 
 use hex::FromHex;
 use openvm_circuit::arch::{
@@ -21,7 +21,12 @@ use p3_keccak_air::NUM_ROUNDS;
 use rand::Rng;
 use tiny_keccak::Hasher;
 
-use super::{columns::KeccakVmCols, utils::num_keccak_f, KeccakVmChip, KECCAK_WORD_SIZE};
+use super::{
+    columns::KeccakVmCols,
+    keccakf::{columns::KeccakfVmCols, KeccakfVmChip},
+    utils::num_keccak_f,
+    KeccakVmChip, KECCAK_WIDTH_BYTES, KECCAK_WORD_SIZE,
+};
 
 type F = BabyBear;
 // io is vector of (input, expected_output, prank_output) where prank_output is Some if the trace
@@ -158,3 +163,97 @@ fn test_keccak256_positive_kat_vectors() {
     let tester = build_keccak256_test(io);
     tester.simple_test().expect("Verification failed");
 }
+
+// states is vector of (state, prank_output) where prank_output is Some if the first byte of the
+// postimage will be replaced in both the memory write and the trace
+fn build_keccakf_test(states: Vec<([u64; 25], Option<u8>)>) -> VmChipTester<BabyBearBlake3Config> {
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = Arc::new(BitwiseOperationLookupChip::<8>::new(bitwise_bus));
+
+    let mut tester = VmChipTestBuilder::default();
+    let mut chip = KeccakfVmChip::new(
+        tester.execution_bus(),
+        tester.program_bus(),
+        tester.memory_controller(),
+        bitwise_chip.clone(),
+        0,
+    );
+
+    let [a, d, e] = [0, 1, 2];
+    for (i, (state, prank_output)) in states.iter().enumerate() {
+        // separate states so pranked writes are never read back
+        let ptr = i * KECCAK_WIDTH_BYTES;
+        tester.write(d, a, (ptr as u32).to_le_bytes().map(F::from_canonical_u8));
+        let bytes: Vec<u8> = state.iter().flat_map(|s| s.to_le_bytes()).collect();
+        for (j, word) in bytes.chunks_exact(KECCAK_WORD_SIZE).enumerate() {
+            tester.write(
+                e,
+                ptr + j * KECCAK_WORD_SIZE,
+                from_fn::<_, KECCAK_WORD_SIZE, _>(|k| F::from_canonical_u8(word[k])),
+            );
+        }
+
+        tester.execute(
+            &mut chip,
+            Instruction::from_isize(
+                VmOpcode::from_usize(Rv32KeccakOpcode::KECCAKF as usize),
+                a as isize,
+                0,
+                0,
+                d as isize,
+                e as isize,
+            ),
+        );
+
+        let mut expected = *state;
+        tiny_keccak::keccakf(&mut expected);
+        let expected: Vec<u8> = expected.iter().flat_map(|s| s.to_le_bytes()).collect();
+        for (j, byte) in expected.iter().enumerate() {
+            assert_eq!(tester.read_cell(e, ptr + j), F::from_canonical_u8(*byte));
+        }
+        if let Some(byte) = prank_output {
+            chip.records.last_mut().unwrap().state_writes[0].data[0] = F::from_canonical_u8(*byte);
+        }
+    }
+    let mut tester = tester.build().load(chip).load(bitwise_chip).finalize();
+
+    let keccakf_trace = tester.air_proof_inputs[2].raw.common_main.as_mut().unwrap();
+    for (i, (state, prank_output)) in states.into_iter().enumerate() {
+        let Some(byte) = prank_output else {
+            continue;
+        };
+        let mut output = state;
+        tiny_keccak::keccakf(&mut output);
+        let out_limb = F::from_canonical_u64((output[0] & 0xff00) | byte as u64);
+        let last_row: &mut KeccakfVmCols<_> =
+            keccakf_trace.row_mut((i + 1) * NUM_ROUNDS - 1).borrow_mut();
+        last_row.inner.a_prime_prime_prime_0_0_limbs[0] = out_limb;
+    }
+
+    tester
+}
+
+#[test]
+fn test_keccakf_positive() {
+    let mut rng = create_seeded_rng();
+    let mut states = vec![([0u64; 25], None)];
+    for _ in 0..3 {
+        states.push((from_fn(|_| rng.gen()), None));
+    }
+    let tester = build_keccakf_test(states);
+    tester.simple_test().expect("Verification failed");
+}
+
+#[test]
+fn test_keccakf_negative() {
+    let mut rng = create_seeded_rng();
+    let state: [u64; 25] = from_fn(|_| rng.gen());
+    let mut output = state;
+    tiny_keccak::keccakf(&mut output);
+    let tester = build_keccakf_test(vec![(state, Some((output[0] as u8).wrapping_add(1)))]);
+    disable_debug_builder();
+    assert_eq!(
+        tester.simple_test().err(),
+        Some(VerificationError::OodEvaluationMismatch)
+    );
+}
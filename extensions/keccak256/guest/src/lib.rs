@@ -3,10 +3,23 @@
 #[cfg(target_os = "zkvm")]
 use core::mem::MaybeUninit;
 
+use strum_macros::FromRepr;
+
+mod sponge;
+pub use sponge::*;
+
 /// This is custom-0 defined in RISC-V spec document
 pub const OPCODE: u8 = 0x0b;
 pub const FUNCT3: u8 = 0b100;
 
+/// funct7 options for keccak instructions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, FromRepr)]
+#[repr(u8)]
+pub enum KeccakFunct7 {
+    Keccak256 = 0,
+    Keccakf,
+}
+
 /// The keccak256 cryptographic hash function.
 #[inline(always)]
 pub fn keccak256(input: &[u8]) -> [u8; 32] {
@@ -40,7 +53,14 @@ pub fn keccak256(input: &[u8]) -> [u8; 32] {
 #[inline(always)]
 #[no_mangle]
 extern "C" fn native_keccak256(bytes: *const u8, len: usize, output: *mut u8) {
-    openvm_platform::custom_insn_r!(OPCODE, FUNCT3, 0x0, output, bytes, len);
+    openvm_platform::custom_insn_r!(
+        OPCODE,
+        FUNCT3,
+        KeccakFunct7::Keccak256 as u8,
+        output,
+        bytes,
+        len
+    );
 }
 
 /// Sets `output` to the keccak256 hash of `input`.
@@ -55,3 +75,33 @@ pub fn set_keccak256(input: &[u8], output: &mut [u8; 32]) {
     #[cfg(target_os = "zkvm")]
     native_keccak256(input.as_ptr(), input.len(), output.as_mut_ptr() as *mut u8);
 }
+
+/// The keccak-f\[1600\] permutation, applied in place to the 25 lanes of the state.
+#[inline(always)]
+pub fn keccakf(state: &mut [u64; 25]) {
+    #[cfg(not(target_os = "zkvm"))]
+    tiny_keccak::keccakf(state);
+    #[cfg(target_os = "zkvm")]
+    native_keccakf(state.as_mut_ptr() as *mut u8);
+}
+
+/// Native hook for the keccak-f\[1600\] permutation.
+///
+/// # Safety
+///
+/// `state` must point to a 4-byte aligned buffer of 200 bytes, which is overwritten with the
+/// permuted state.
+#[cfg(target_os = "zkvm")]
+#[inline(always)]
+#[no_mangle]
+extern "C" fn native_keccakf(state: *mut u8) {
+    unsafe {
+        core::arch::asm!(
+            ".insn r {opcode}, {funct3}, {funct7}, {rd}, x0, x0",
+            opcode = const OPCODE,
+            funct3 = const FUNCT3,
+            funct7 = const KeccakFunct7::Keccakf as u8,
+            rd = in(reg) state,
+        );
+    }
+}
@@ -0,0 +1,226 @@
+//! Incremental hashers built on the [keccakf] permutation.
+
+use crate::keccakf;
+
+/// Keccak sponge with a byte rate of `rate` and domain separation suffix `delim`.
+#[derive(Clone, Debug)]
+struct KeccakSponge {
+    state: [u64; 25],
+    rate: usize,
+    delim: u8,
+    /// Byte offset into the rate portion of the state for the next absorbed or squeezed byte.
+    offset: usize,
+}
+
+impl KeccakSponge {
+    const fn new(rate: usize, delim: u8) -> Self {
+        Self {
+            state: [0; 25],
+            rate,
+            delim,
+            offset: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn xor_byte(&mut self, idx: usize, byte: u8) {
+        // The lanes are little-endian
+        self.state[idx / 8] ^= (byte as u64) << (8 * (idx % 8));
+    }
+
+    fn absorb(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            let len = (self.rate - self.offset).min(input.len());
+            for (i, &byte) in input[..len].iter().enumerate() {
+                self.xor_byte(self.offset + i, byte);
+            }
+            self.offset += len;
+            input = &input[len..];
+            if self.offset == self.rate {
+                keccakf(&mut self.state);
+                self.offset = 0;
+            }
+        }
+    }
+
+    /// Applies the padding and switches the sponge to squeezing.
+    fn pad(&mut self) {
+        self.xor_byte(self.offset, self.delim);
+        self.xor_byte(self.rate - 1, 0x80);
+        keccakf(&mut self.state);
+        self.offset = 0;
+    }
+
+    fn squeeze(&mut self, output: &mut [u8]) {
+        for byte in output {
+            if self.offset == self.rate {
+                keccakf(&mut self.state);
+                self.offset = 0;
+            }
+            *byte = (self.state[self.offset / 8] >> (8 * (self.offset % 8))) as u8;
+            self.offset += 1;
+        }
+    }
+}
+
+macro_rules! impl_fixed_output_hasher {
+    ($name:ident, $rate:expr, $delim:expr, $output_bytes:expr, $doc:literal) => {
+        #[doc = $doc]
+        #[derive(Clone, Debug)]
+        pub struct $name {
+            sponge: KeccakSponge,
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl $name {
+            pub const fn new() -> Self {
+                Self {
+                    sponge: KeccakSponge::new($rate, $delim),
+                }
+            }
+
+            /// Absorbs `input` into the hasher.
+            pub fn update(&mut self, input: &[u8]) {
+                self.sponge.absorb(input);
+            }
+
+            /// Consumes the hasher and returns the digest.
+            pub fn finalize(mut self) -> [u8; $output_bytes] {
+                let mut output = [0u8; $output_bytes];
+                self.sponge.pad();
+                self.sponge.squeeze(&mut output);
+                output
+            }
+        }
+    };
+}
+
+macro_rules! impl_xof_hasher {
+    ($name:ident, $rate:expr, $doc:literal) => {
+        #[doc = $doc]
+        #[derive(Clone, Debug)]
+        pub struct $name {
+            sponge: KeccakSponge,
+            is_squeezing: bool,
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl $name {
+            pub const fn new() -> Self {
+                Self {
+                    sponge: KeccakSponge::new($rate, 0x1f),
+                    is_squeezing: false,
+                }
+            }
+
+            /// Absorbs `input` into the hasher.
+            ///
+            /// # Panics
+            ///
+            /// Panics if called after [Self::squeeze].
+            pub fn update(&mut self, input: &[u8]) {
+                assert!(!self.is_squeezing, "cannot absorb after squeezing");
+                self.sponge.absorb(input);
+            }
+
+            /// Fills `output` with the next bytes of the output stream. Can be called repeatedly.
+            pub fn squeeze(&mut self, output: &mut [u8]) {
+                if !self.is_squeezing {
+                    self.sponge.pad();
+                    self.is_squeezing = true;
+                }
+                self.sponge.squeeze(output);
+            }
+
+            /// Consumes the hasher and fills `output` with the start of the output stream.
+            pub fn finalize(mut self, output: &mut [u8]) {
+                self.squeeze(output);
+            }
+        }
+    };
+}
+
+impl_fixed_output_hasher!(
+    Keccak256,
+    136,
+    0x01,
+    32,
+    "Incremental keccak256 hasher. Produces the same digest as [crate::keccak256]."
+);
+impl_fixed_output_hasher!(Sha3_256, 136, 0x06, 32, "Incremental SHA3-256 hasher.");
+impl_xof_hasher!(
+    Shake128,
+    168,
+    "Incremental SHAKE128 extendable-output function."
+);
+impl_xof_hasher!(
+    Shake256,
+    136,
+    "Incremental SHAKE256 extendable-output function."
+);
+
+#[cfg(test)]
+mod tests {
+    use tiny_keccak::{Hasher, Keccak, Sha3, Shake, Xof};
+
+    use super::*;
+
+    fn input() -> [u8; 500] {
+        core::array::from_fn(|i| (i * 7 + 3) as u8)
+    }
+
+    #[test]
+    fn test_chunked_hashers() {
+        let input = input();
+        for chunk_size in [1, 17, 135, 136, 137, 500] {
+            let mut hasher = Keccak256::new();
+            let mut sha3 = Sha3_256::new();
+            let mut shake128 = Shake128::new();
+            let mut shake256 = Shake256::new();
+            for chunk in input.chunks(chunk_size) {
+                hasher.update(chunk);
+                sha3.update(chunk);
+                shake128.update(chunk);
+                shake256.update(chunk);
+            }
+
+            let mut expected = [0u8; 32];
+            let mut reference = Keccak::v256();
+            reference.update(&input);
+            reference.finalize(&mut expected);
+            assert_eq!(hasher.finalize(), expected);
+
+            let mut reference = Sha3::v256();
+            reference.update(&input);
+            reference.finalize(&mut expected);
+            assert_eq!(sha3.finalize(), expected);
+
+            // Squeeze across the rate boundary in pieces
+            let mut expected = [0u8; 400];
+            let mut output = [0u8; 400];
+            let mut reference = Shake::v128();
+            reference.update(&input);
+            reference.squeeze(&mut expected);
+            let (first, second) = output.split_at_mut(100);
+            shake128.squeeze(first);
+            shake128.squeeze(second);
+            assert_eq!(output, expected);
+
+            let mut reference = Shake::v256();
+            reference.update(&input);
+            reference.squeeze(&mut expected);
+            shake256.finalize(&mut output);
+            assert_eq!(output, expected);
+        }
+    }
+}
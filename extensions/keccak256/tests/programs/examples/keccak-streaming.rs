@@ -0,0 +1,78 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::hint::black_box;
+
+use hex::FromHex;
+use openvm_keccak256_guest::{keccak256, Keccak256, Sha3_256, Shake128, Shake256};
+
+openvm::entry!(main);
+
+pub fn main() {
+    // Chunked keccak256 must agree with the one-shot intrinsic
+    let input: Vec<u8> = (0..500u32).map(|i| (i * 7 + 3) as u8).collect();
+    for len in [0, 1, 135, 136, 137, 272, 500] {
+        let input = &input[..len];
+        let expected = keccak256(black_box(input));
+        for chunk_size in [1, 17, 136, 200] {
+            let mut hasher = Keccak256::new();
+            for chunk in input.chunks(chunk_size) {
+                hasher.update(black_box(chunk));
+            }
+            if hasher.finalize() != expected {
+                panic!();
+            }
+        }
+    }
+
+    let sha3_vectors = [
+        (
+            "",
+            "A7FFC6F8BF1ED76651C14756A061D662F580FF4DE43B49FA82D80A4B80F8434A",
+        ),
+        (
+            "616263",
+            "3A985DA74FE225B2045C172D6BD390BD855F086E3E9D525B46BFE24511431532",
+        ), // "abc"
+    ];
+    for (input, expected_output) in sha3_vectors.iter() {
+        let input = Vec::from_hex(input).unwrap();
+        let expected_output = Vec::from_hex(expected_output).unwrap();
+        let mut hasher = Sha3_256::new();
+        hasher.update(&black_box(input));
+        if hasher.finalize() != *expected_output {
+            panic!();
+        }
+    }
+
+    let expected_output =
+        Vec::from_hex("7F9C2BA4E88F827D616045507605853ED73B8093F6EFBC88EB1A6EACFA66EF26").unwrap();
+    let mut output = [0u8; 32];
+    Shake128::new().finalize(&mut output);
+    if output != *expected_output {
+        panic!();
+    }
+
+    let expected_output = Vec::from_hex("46B9DD2B0BA88D13233B3FEB743EEB243FCD52EA62B81B82B50C27646ED5762FD75DC4DDD8C0F200CB05019D67B592F6FC821C49479AB48640292EACB3B7C4BE").unwrap();
+    let mut output = [0u8; 64];
+    Shake256::new().finalize(&mut output);
+    if output != *expected_output {
+        panic!();
+    }
+
+    // Squeezing in pieces across the rate boundary must agree with a single squeeze
+    let mut expected_output = [0u8; 400];
+    let mut shake = Shake128::new();
+    shake.update(&input);
+    shake.clone().finalize(&mut expected_output);
+    let mut output = [0u8; 400];
+    for chunk in output.chunks_mut(100) {
+        shake.squeeze(chunk);
+    }
+    if output != expected_output {
+        panic!();
+    }
+}
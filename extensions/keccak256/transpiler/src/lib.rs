@@ -1,6 +1,6 @@
-use openvm_instructions::{instruction::Instruction, UsizeOpcode};
+use openvm_instructions::{instruction::Instruction, riscv::RV32_REGISTER_NUM_LIMBS, UsizeOpcode};
 use openvm_instructions_derive::UsizeOpcode;
-use openvm_keccak256_guest::{FUNCT3, OPCODE};
+use openvm_keccak256_guest::{KeccakFunct7, FUNCT3, OPCODE};
 use openvm_stark_backend::p3_field::PrimeField32;
 use openvm_transpiler::{util::from_r_type, TranspilerExtension};
 use rrs_lib::instruction_formats::RType;
@@ -13,6 +13,7 @@ use strum::{EnumCount, EnumIter, FromRepr};
 #[repr(usize)]
 pub enum Rv32KeccakOpcode {
     KECCAK256,
+    KECCAKF,
 }
 
 #[derive(Default)]
@@ -31,11 +32,27 @@ impl<F: PrimeField32> TranspilerExtension<F> for Keccak256TranspilerExtension {
             return None;
         }
         let dec_insn = RType::new(instruction_u32);
-        let instruction = from_r_type(
-            Rv32KeccakOpcode::KECCAK256.with_default_offset(),
-            2,
-            &dec_insn,
-        );
+        let instruction = match KeccakFunct7::from_repr(dec_insn.funct7 as u8)? {
+            KeccakFunct7::Keccak256 => from_r_type(
+                Rv32KeccakOpcode::KECCAK256.with_default_offset(),
+                2,
+                &dec_insn,
+            ),
+            KeccakFunct7::Keccakf => {
+                // The permutation only has the state pointer operand.
+                if dec_insn.rs1 != 0 || dec_insn.rs2 != 0 {
+                    return None;
+                }
+                Instruction::from_isize(
+                    Rv32KeccakOpcode::KECCAKF.with_default_offset(),
+                    (RV32_REGISTER_NUM_LIMBS * dec_insn.rd) as isize,
+                    0,
+                    0,
+                    1,
+                    2,
+                )
+            }
+        };
         Some((instruction, 1))
     }
 }
@@ -399,21 +399,22 @@ impl<F: PrimeField32> InstructionExecutor<F> for FriReducedOpeningChip<F> {
         debug_assert_eq!(alpha_pow_write.prev_data, alpha_pow_original);
         let result_write = memory.write(addr_space, result_ptr, result);
 
-        self.records.push(FriReducedOpeningRecord {
-            pc: F::from_canonical_u32(from_state.pc),
-            start_timestamp: F::from_canonical_u32(from_state.timestamp),
-            instruction,
-            alpha_read,
-            length_read,
-            a_ptr_read,
-            b_ptr_read,
-            a_reads,
-            b_reads,
-            alpha_pow_write,
-            result_write,
-        });
-
-        self.height += length;
+        if !memory.is_pure_execution() {
+            self.records.push(FriReducedOpeningRecord {
+                pc: F::from_canonical_u32(from_state.pc),
+                start_timestamp: F::from_canonical_u32(from_state.timestamp),
+                instruction,
+                alpha_read,
+                length_read,
+                a_ptr_read,
+                b_ptr_read,
+                a_reads,
+                b_reads,
+                alpha_pow_write,
+                result_write,
+            });
+            self.height += length;
+        }
 
         Ok(ExecutionState {
             pc: from_state.pc + DEFAULT_PC_STEP,
@@ -132,19 +132,21 @@ impl<F: PrimeField32, const SBOX_REGISTERS: usize> InstructionExecutor<F>
             }
         };
 
-        self.records.push(Some(NativePoseidon2ChipRecord {
-            from_state,
-            opcode: local_opcode,
-            input: input_state,
-            c,
-            rd,
-            rs1,
-            rs2,
-            read1,
-            read2,
-            write1,
-            write2,
-        }));
+        if !memory.is_pure_execution() {
+            self.records.push(Some(NativePoseidon2ChipRecord {
+                from_state,
+                opcode: local_opcode,
+                input: input_state,
+                c,
+                rd,
+                rs1,
+                rs2,
+                read1,
+                read2,
+                write1,
+                write2,
+            }));
+        }
 
         Ok(ExecutionState {
             pc: from_state.pc + DEFAULT_PC_STEP,
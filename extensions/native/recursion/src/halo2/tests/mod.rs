@@ -32,6 +32,7 @@ use crate::{
 mod multi_field32;
 mod outer_poseidon2;
 mod stark;
+mod wrapper;
 
 const DUMMY_K: usize = 10;
 const DUMMY_N: usize = 2 * (1 << DUMMY_K);
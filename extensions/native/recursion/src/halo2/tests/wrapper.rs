@@ -0,0 +1,36 @@
+use crate::halo2::wrapper::decode_revert_reason;
+
+/// ABI encoding of `Error(string)` with `reason`.
+fn encode_error(reason: &str) -> Vec<u8> {
+    let mut output = vec![0x08, 0xc3, 0x79, 0xa0];
+    output.extend([0u8; 31].into_iter().chain([0x20]));
+    output.extend(
+        [0u8; 24]
+            .into_iter()
+            .chain((reason.len() as u64).to_be_bytes()),
+    );
+    output.extend(reason.as_bytes());
+    output.resize((output.len() - 4).next_multiple_of(32) + 4, 0);
+    output
+}
+
+#[test]
+fn test_decode_revert_reason() {
+    assert_eq!(
+        decode_revert_reason(&encode_error("invalid proof")).as_deref(),
+        Some("invalid proof")
+    );
+
+    let mut panic = vec![0x4e, 0x48, 0x7b, 0x71];
+    panic.extend([0u8; 31].into_iter().chain([0x11]));
+    assert_eq!(
+        decode_revert_reason(&panic).as_deref(),
+        Some("panic with code 0x11")
+    );
+
+    // Bare reverts, custom errors and truncated outputs have no reason.
+    assert_eq!(decode_revert_reason(&[]), None);
+    assert_eq!(decode_revert_reason(&[0xde, 0xad, 0xbe, 0xef]), None);
+    let error = encode_error("invalid proof");
+    assert_eq!(decode_revert_reason(&error[..4 + 64 + 4]), None);
+}
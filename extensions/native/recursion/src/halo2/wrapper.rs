@@ -1,18 +1,27 @@
 use itertools::Itertools;
 use openvm_stark_backend::p3_util::log2_ceil_usize;
+use revm::{
+    db::InMemoryDB,
+    primitives::{ExecutionResult, Output, TxKind},
+    Evm,
+};
 use serde::{Deserialize, Serialize};
 use snark_verifier_sdk::{
-    evm::{evm_verify, gen_evm_proof_shplonk, gen_evm_verifier_shplonk},
+    evm::{gen_evm_proof_shplonk, gen_evm_verifier_shplonk},
     halo2::aggregation::{AggregationCircuit, AggregationConfigParams, VerifierUniversality},
-    snark_verifier::halo2_base::{
-        gates::circuit::{
-            CircuitBuilderStage,
-            CircuitBuilderStage::{Keygen, Prover},
+    snark_verifier::{
+        halo2_base::{
+            gates::circuit::{
+                CircuitBuilderStage,
+                CircuitBuilderStage::{Keygen, Prover},
+            },
+            halo2_proofs::{plonk::keygen_pk2, poly::commitment::Params},
         },
-        halo2_proofs::{plonk::keygen_pk2, poly::commitment::Params},
+        loader::evm::encode_calldata,
     },
     CircuitExt, Snark, SHPLONK,
 };
+use thiserror::Error;
 
 use crate::halo2::{
     utils::{Halo2ParamsReader, KZG_PARAMS_FOR_SVK},
@@ -34,6 +43,35 @@ impl From<EvmVerifier> for Vec<u8> {
     }
 }
 
+/// Number of public values of the wrapper circuit which are used by the KZG accumulator. They
+/// come before the public values of the wrapped snark.
+pub const NUM_ACCUMULATOR_PUBLIC_VALUES: usize = 12;
+
+#[derive(Debug, Error)]
+pub enum EvmVerificationError {
+    #[error("malformed calldata: {0}")]
+    MalformedCalldata(String),
+    #[error("public input {index} does not match the expected value")]
+    PublicInputMismatch { index: usize },
+    #[error("EVM verifier deployment failed: {0}")]
+    DeploymentFailed(String),
+    #[error(
+        "EVM verifier reverted{} (gas used: {gas_used})",
+        .reason.as_ref().map(|reason| format!(": {reason}")).unwrap_or_default()
+    )]
+    Reverted {
+        gas_used: u64,
+        /// Reason decoded from a Solidity `Error(string)` or `Panic(uint256)` revert. `None` if
+        /// the output is neither, e.g. for a bare `revert(0, 0)`.
+        reason: Option<String>,
+        output: Vec<u8>,
+    },
+    #[error("EVM verifier halted: {reason} (gas used: {gas_used})")]
+    Halted { reason: String, gas_used: u64 },
+    #[error("EVM error: {0}")]
+    Evm(String),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Halo2WrapperProvingKey {
     pub pinning: Halo2ProvingPinning,
@@ -77,14 +115,33 @@ impl Halo2WrapperProvingKey {
             },
         }
     }
-    /// A helper function for testing to verify the proof of this circuit with evm verifier.
-    // FIXME: the signature is not ideal. It should return an Error instead of panicking when the verification fails.
-    pub fn evm_verify(evm_verifier: &EvmVerifier, evm_proof: &EvmProof) {
-        evm_verify(
-            evm_verifier.0.clone(),
-            evm_proof.instances.clone(),
-            evm_proof.proof.clone(),
-        );
+    /// Verify the proof of this circuit by deploying the EVM verifier in a local EVM and calling
+    /// it. Returns the gas used by the verification call.
+    pub fn evm_verify(
+        evm_verifier: &EvmVerifier,
+        evm_proof: &EvmProof,
+    ) -> Result<u64, EvmVerificationError> {
+        if evm_proof.instances.len() != 1 {
+            return Err(EvmVerificationError::MalformedCalldata(format!(
+                "expected 1 instance column, got {}",
+                evm_proof.instances.len()
+            )));
+        }
+        if evm_proof.instances[0].len() < NUM_ACCUMULATOR_PUBLIC_VALUES {
+            return Err(EvmVerificationError::MalformedCalldata(format!(
+                "expected at least {} public values, got {}",
+                NUM_ACCUMULATOR_PUBLIC_VALUES,
+                evm_proof.instances[0].len()
+            )));
+        }
+        if evm_proof.proof.is_empty() || evm_proof.proof.len() % 32 != 0 {
+            return Err(EvmVerificationError::MalformedCalldata(format!(
+                "proof length {} is not a positive multiple of 32",
+                evm_proof.proof.len()
+            )));
+        }
+        let calldata = encode_calldata(&evm_proof.instances, &evm_proof.proof);
+        deploy_and_call(evm_verifier.0.clone(), calldata)
     }
     /// Return deployment code for EVM verifier which can verify the snark of this circuit.
     pub fn generate_evm_verifier(&self, params: &Halo2Params) -> EvmVerifier {
@@ -127,8 +184,7 @@ impl Halo2WrapperProvingKey {
         );
         assert_eq!(
             self.pinning.metadata.num_pvs[0],
-            // 12 is the number of public values for the accumulator
-            snark_to_verify.instances[0].len() + 12
+            snark_to_verify.instances[0].len() + NUM_ACCUMULATOR_PUBLIC_VALUES
         );
         generate_wrapper_circuit_object(Prover, k, snark_to_verify)
             .use_params(
@@ -169,6 +225,89 @@ impl Halo2WrapperProvingKey {
     }
 }
 
+/// Deploys `deployment_code` in an empty in-memory EVM and calls the deployed contract with
+/// `calldata`. Returns the gas used by the call.
+fn deploy_and_call(
+    deployment_code: Vec<u8>,
+    calldata: Vec<u8>,
+) -> Result<u64, EvmVerificationError> {
+    let mut evm = Evm::builder()
+        .with_db(InMemoryDB::default())
+        .modify_cfg_env(|cfg| {
+            // Verifier contracts are usually larger than the EIP-170 limit.
+            cfg.limit_contract_code_size = Some(usize::MAX);
+        })
+        .modify_tx_env(|tx| {
+            tx.gas_limit = u64::MAX;
+            tx.transact_to = TxKind::Create;
+            tx.data = deployment_code.into();
+        })
+        .build();
+
+    let contract = match evm.transact_commit() {
+        Ok(ExecutionResult::Success {
+            output: Output::Create(_, Some(contract)),
+            ..
+        }) => contract,
+        Ok(result) => {
+            return Err(EvmVerificationError::DeploymentFailed(format!(
+                "{:?}",
+                result
+            )))
+        }
+        Err(e) => return Err(EvmVerificationError::Evm(format!("{:?}", e))),
+    };
+
+    evm.tx_mut().transact_to = TxKind::Call(contract);
+    evm.tx_mut().data = calldata.into();
+    match evm.transact_commit() {
+        Ok(ExecutionResult::Success { gas_used, .. }) => Ok(gas_used),
+        Ok(ExecutionResult::Revert { gas_used, output }) => Err(EvmVerificationError::Reverted {
+            gas_used,
+            reason: decode_revert_reason(&output),
+            output: output.to_vec(),
+        }),
+        Ok(ExecutionResult::Halt { reason, gas_used }) => Err(EvmVerificationError::Halted {
+            reason: format!("{:?}", reason),
+            gas_used,
+        }),
+        Err(e) => Err(EvmVerificationError::Evm(format!("{:?}", e))),
+    }
+}
+
+/// Selector of the Solidity `Error(string)` revert.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// Selector of the Solidity `Panic(uint256)` revert.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Decodes the ABI-encoded output of a Solidity `Error(string)` or `Panic(uint256)` revert.
+pub(crate) fn decode_revert_reason(output: &[u8]) -> Option<String> {
+    if output.len() < 4 {
+        return None;
+    }
+    let (selector, data) = output.split_at(4);
+    // Reads the 32-byte big-endian word at `offset` as a `usize`.
+    let word = |offset: usize| -> Option<usize> {
+        let word = data.get(offset..offset.checked_add(32)?)?;
+        let (high, low) = word.split_at(24);
+        if high.iter().any(|&byte| byte != 0) {
+            return None;
+        }
+        usize::try_from(u64::from_be_bytes(low.try_into().ok()?)).ok()
+    };
+    if selector == ERROR_SELECTOR {
+        let offset = word(0)?;
+        let len = word(offset)?;
+        let start = offset.checked_add(32)?;
+        let bytes = data.get(start..start.checked_add(len)?)?;
+        String::from_utf8(bytes.to_vec()).ok()
+    } else if selector == PANIC_SELECTOR {
+        Some(format!("panic with code {:#x}", word(0)?))
+    } else {
+        None
+    }
+}
+
 fn generate_wrapper_circuit_object(
     stage: CircuitBuilderStage,
     k: usize,
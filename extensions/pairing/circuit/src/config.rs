@@ -45,7 +45,8 @@ impl Rv32PairingConfig {
             mul: Default::default(),
             io: Default::default(),
             modular: ModularExtension::new(primes.to_vec()),
-            fp2: Fp2Extension::new(primes.to_vec()),
+            fp2: Fp2Extension::new(primes.to_vec())
+                .with_non_residues(curves.iter().map(|c| c.config().fp2_non_residue).collect()),
             weierstrass: WeierstrassExtension::new(
                 curves.iter().map(|c| c.curve_config()).collect(),
             ),
@@ -12,7 +12,12 @@ pub struct Fp12 {
 
 impl Fp12 {
     pub fn new(builder: Rc<RefCell<ExprBuilder>>) -> Self {
-        let c = from_fn(|_| Fp2::new(builder.clone()));
+        Self::new_with_non_residue(builder, -1)
+    }
+
+    /// Creates an input whose `Fp2` coefficients are defined by `u^2 = fp2_non_residue`.
+    pub fn new_with_non_residue(builder: Rc<RefCell<ExprBuilder>>, fp2_non_residue: isize) -> Self {
+        let c = from_fn(|_| Fp2::new_with_non_residue(builder.clone(), fp2_non_residue));
         Fp12 { c }
     }
 
@@ -163,6 +163,7 @@ fn test_fp12_mul_bn254() {
     let expr = fp12_mul_expr(
         config,
         tester.memory_controller().borrow().range_checker.bus(),
+        -1,
         xi,
     );
 
@@ -251,6 +252,7 @@ fn test_fp12_mul_bls12381() {
     let expr = fp12_mul_expr(
         config,
         tester.memory_controller().borrow().range_checker.bus(),
+        -1,
         xi,
     );
 
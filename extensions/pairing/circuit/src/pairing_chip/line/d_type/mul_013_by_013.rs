@@ -39,6 +39,7 @@ impl<
         adapter: Rv32VecHeapAdapterChip<F, 2, INPUT_BLOCKS, OUTPUT_BLOCKS, BLOCK_SIZE, BLOCK_SIZE>,
         memory_controller: MemoryControllerRef<F>,
         config: ExprBuilderConfig,
+        fp2_non_residue: isize,
         xi: [isize; 2],
         offset: usize,
     ) -> Self {
@@ -50,7 +51,12 @@ impl<
             xi[1].unsigned_abs() < 1 << config.limb_bits,
             "expect xi to be small"
         );
-        let expr = mul_013_by_013_expr(config, memory_controller.borrow().range_checker.bus(), xi);
+        let expr = mul_013_by_013_expr(
+            config,
+            memory_controller.borrow().range_checker.bus(),
+            fp2_non_residue,
+            xi,
+        );
         let core = FieldExpressionCoreChip::new(
             expr,
             offset,
@@ -67,16 +73,17 @@ impl<
 pub fn mul_013_by_013_expr(
     config: ExprBuilderConfig,
     range_bus: VariableRangeCheckerBus,
+    fp2_non_residue: isize,
     xi: [isize; 2],
 ) -> FieldExpr {
     config.check_valid();
     let builder = ExprBuilder::new(config.clone(), range_bus.range_max_bits);
     let builder = Rc::new(RefCell::new(builder));
 
-    let mut b0 = Fp2::new(builder.clone());
-    let mut c0 = Fp2::new(builder.clone());
-    let mut b1 = Fp2::new(builder.clone());
-    let mut c1 = Fp2::new(builder.clone());
+    let mut b0 = Fp2::new_with_non_residue(builder.clone(), fp2_non_residue);
+    let mut c0 = Fp2::new_with_non_residue(builder.clone(), fp2_non_residue);
+    let mut b1 = Fp2::new_with_non_residue(builder.clone(), fp2_non_residue);
+    let mut c1 = Fp2::new_with_non_residue(builder.clone(), fp2_non_residue);
 
     // where w⁶ = xi
     // l0 * l1 = 1 + (b0 + b1)w + (b0b1)w² + (c0 + c1)w³ + (b0c1 + b1c0)w⁴ + (c0c1)w⁶
@@ -57,6 +57,7 @@ impl<
         >,
         memory_controller: MemoryControllerRef<F>,
         config: ExprBuilderConfig,
+        fp2_non_residue: isize,
         xi: [isize; 2],
         offset: usize,
     ) -> Self {
@@ -68,7 +69,12 @@ impl<
             xi[1].unsigned_abs() < 1 << config.limb_bits,
             "expect xi to be small"
         );
-        let expr = mul_by_01234_expr(config, memory_controller.borrow().range_checker.bus(), xi);
+        let expr = mul_by_01234_expr(
+            config,
+            memory_controller.borrow().range_checker.bus(),
+            fp2_non_residue,
+            xi,
+        );
         let core = FieldExpressionCoreChip::new(
             expr,
             offset,
@@ -85,18 +91,19 @@ impl<
 pub fn mul_by_01234_expr(
     config: ExprBuilderConfig,
     range_bus: VariableRangeCheckerBus,
+    fp2_non_residue: isize,
     xi: [isize; 2],
 ) -> FieldExpr {
     config.check_valid();
     let builder = ExprBuilder::new(config.clone(), range_bus.range_max_bits);
     let builder = Rc::new(RefCell::new(builder));
 
-    let mut f = Fp12::new(builder.clone());
-    let mut x0 = Fp2::new(builder.clone());
-    let mut x1 = Fp2::new(builder.clone());
-    let mut x2 = Fp2::new(builder.clone());
-    let mut x3 = Fp2::new(builder.clone());
-    let mut x4 = Fp2::new(builder.clone());
+    let mut f = Fp12::new_with_non_residue(builder.clone(), fp2_non_residue);
+    let mut x0 = Fp2::new_with_non_residue(builder.clone(), fp2_non_residue);
+    let mut x1 = Fp2::new_with_non_residue(builder.clone(), fp2_non_residue);
+    let mut x2 = Fp2::new_with_non_residue(builder.clone(), fp2_non_residue);
+    let mut x3 = Fp2::new_with_non_residue(builder.clone(), fp2_non_residue);
+    let mut x4 = Fp2::new_with_non_residue(builder.clone(), fp2_non_residue);
 
     let mut r = f.mul_by_01234(&mut x0, &mut x1, &mut x2, &mut x3, &mut x4, xi);
     r.save_output();
@@ -58,6 +58,7 @@ fn test_mul_013_by_013() {
             num_limbs: NUM_LIMBS,
             limb_bits: LIMB_BITS,
         },
+        -1,
         BN254_XI_ISIZE,
         PairingOpcode::default_offset(),
     );
@@ -154,6 +155,7 @@ fn test_mul_by_01234() {
             num_limbs: NUM_LIMBS,
             limb_bits: LIMB_BITS,
         },
+        -1,
         BN254_XI_ISIZE,
         PairingOpcode::default_offset(),
     );
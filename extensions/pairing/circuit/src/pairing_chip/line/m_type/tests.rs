@@ -52,6 +52,7 @@ fn test_mul_023_by_023() {
             num_limbs: BLS12_381_NUM_LIMBS,
             limb_bits: BLS12_381_LIMB_BITS,
         },
+        -1,
         BLS12_381_XI_ISIZE,
         PairingOpcode::default_offset(),
     );
@@ -151,6 +152,7 @@ fn test_mul_by_02345() {
             num_limbs: BLS12_381_NUM_LIMBS,
             limb_bits: BLS12_381_LIMB_BITS,
         },
+        -1,
         BLS12_381_XI_ISIZE,
         PairingOpcode::default_offset(),
     );
@@ -39,10 +39,14 @@ impl<
         adapter: Rv32VecHeapAdapterChip<F, 2, INPUT_BLOCKS, OUTPUT_BLOCKS, BLOCK_SIZE, BLOCK_SIZE>,
         memory_controller: MemoryControllerRef<F>,
         config: ExprBuilderConfig,
+        fp2_non_residue: isize,
         offset: usize,
     ) -> Self {
-        let expr =
-            miller_double_and_add_step_expr(config, memory_controller.borrow().range_checker.bus());
+        let expr = miller_double_and_add_step_expr(
+            config,
+            memory_controller.borrow().range_checker.bus(),
+            fp2_non_residue,
+        );
         let core = FieldExpressionCoreChip::new(
             expr,
             offset,
@@ -60,15 +64,16 @@ impl<
 pub fn miller_double_and_add_step_expr(
     config: ExprBuilderConfig,
     range_bus: VariableRangeCheckerBus,
+    fp2_non_residue: isize,
 ) -> FieldExpr {
     config.check_valid();
     let builder = ExprBuilder::new(config, range_bus.range_max_bits);
     let builder = Rc::new(RefCell::new(builder));
 
-    let mut x_s = Fp2::new(builder.clone());
-    let mut y_s = Fp2::new(builder.clone());
-    let mut x_q = Fp2::new(builder.clone());
-    let mut y_q = Fp2::new(builder.clone());
+    let mut x_s = Fp2::new_with_non_residue(builder.clone(), fp2_non_residue);
+    let mut y_s = Fp2::new_with_non_residue(builder.clone(), fp2_non_residue);
+    let mut x_q = Fp2::new_with_non_residue(builder.clone(), fp2_non_residue);
+    let mut y_q = Fp2::new_with_non_residue(builder.clone(), fp2_non_residue);
 
     // λ1 = (y_s - y_q) / (x_s - x_q)
     let mut lambda1 = y_s.sub(&mut y_q).div(&mut x_s.sub(&mut x_q));
@@ -147,6 +152,7 @@ mod tests {
                 limb_bits: LIMB_BITS,
                 num_limbs: NUM_LIMBS,
             },
+            -1,
             PairingOpcode::default_offset(),
         );
 
@@ -39,9 +39,14 @@ impl<
         adapter: Rv32VecHeapAdapterChip<F, 1, INPUT_BLOCKS, OUTPUT_BLOCKS, BLOCK_SIZE, BLOCK_SIZE>,
         memory_controller: MemoryControllerRef<F>,
         config: ExprBuilderConfig,
+        fp2_non_residue: isize,
         offset: usize,
     ) -> Self {
-        let expr = miller_double_step_expr(config, memory_controller.borrow().range_checker.bus());
+        let expr = miller_double_step_expr(
+            config,
+            memory_controller.borrow().range_checker.bus(),
+            fp2_non_residue,
+        );
         let core = FieldExpressionCoreChip::new(
             expr,
             offset,
@@ -59,13 +64,14 @@ impl<
 pub fn miller_double_step_expr(
     config: ExprBuilderConfig,
     range_bus: VariableRangeCheckerBus,
+    fp2_non_residue: isize,
 ) -> FieldExpr {
     config.check_valid();
     let builder = ExprBuilder::new(config, range_bus.range_max_bits);
     let builder = Rc::new(RefCell::new(builder));
 
-    let mut x_s = Fp2::new(builder.clone());
-    let mut y_s = Fp2::new(builder.clone());
+    let mut x_s = Fp2::new_with_non_residue(builder.clone(), fp2_non_residue);
+    let mut y_s = Fp2::new_with_non_residue(builder.clone(), fp2_non_residue);
 
     let mut three_x_square = x_s.square().int_mul([3, 0]);
     let mut lambda = three_x_square.div(&mut y_s.int_mul([2, 0]));
@@ -140,6 +146,7 @@ mod tests {
             adapter,
             tester.memory_controller(),
             config,
+            -1,
             PairingOpcode::default_offset(),
         );
 
@@ -207,6 +214,7 @@ mod tests {
             adapter,
             tester.memory_controller(),
             config,
+            -1,
             PairingOpcode::default_offset(),
         );
 
@@ -3,6 +3,8 @@ use std::sync::Arc;
 use derive_more::derive::From;
 use num_bigint_dig::BigUint;
 use num_traits::{FromPrimitive, Zero};
+use once_cell::sync::Lazy;
+use openvm_algebra_circuit::is_quadratic_non_residue;
 use openvm_circuit::{
     arch::{SystemPort, VmExtension, VmInventory, VmInventoryBuilder, VmInventoryError},
     system::phantom::PhantomChip,
@@ -16,49 +18,133 @@ use openvm_ecc_circuit::CurveConfig;
 use openvm_instructions::{PhantomDiscriminant, UsizeOpcode, VmOpcode};
 use openvm_mod_circuit_builder::ExprBuilderConfig;
 use openvm_pairing_guest::{
-    bls12_381::{BLS12_381_MODULUS, BLS12_381_ORDER, BLS12_381_XI_ISIZE},
-    bn254::{BN254_MODULUS, BN254_ORDER, BN254_XI_ISIZE},
+    bls12_381::{
+        BLS12_381_MODULUS, BLS12_381_ORDER, BLS12_381_PSEUDO_BINARY_ENCODING, BLS12_381_SEED_ABS,
+        BLS12_381_XI_ISIZE,
+    },
+    bn254::{BN254_MODULUS, BN254_ORDER, BN254_PSEUDO_BINARY_ENCODING, BN254_SEED, BN254_XI_ISIZE},
+    PairingBaseFunct7,
 };
 use openvm_pairing_transpiler::{Fp12Opcode, PairingOpcode, PairingPhantom};
 use openvm_rv32_adapters::{Rv32VecHeapAdapterChip, Rv32VecHeapTwoReadsAdapterChip};
 use openvm_stark_backend::p3_field::PrimeField32;
 use serde::{Deserialize, Serialize};
-use strum::{EnumCount, FromRepr};
+use serde_with::{serde_as, DisplayFromStr};
+use strum::EnumCount;
 
 use super::*;
 
+/// Where the sextic twist `E'` of the G2 curve places its line functions in `Fp12`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TwistType {
+    /// Divisive twist `y^2 = x^3 + b / xi`: lines are sparse in the 0, 1, 3 coefficients.
+    D,
+    /// Multiplicative twist `y^2 = x^3 + b * xi`: lines are sparse in the 0, 2, 3 coefficients.
+    M,
+}
+
+/// Description of a pairing-friendly curve `y^2 = x^3 + b` with embedding degree 12, whose
+/// `Fp12` is the tower `Fp2[w] / (w^6 - xi)` over `Fp2 = Fp[u] / (u^2 - fp2_non_residue)`.
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PairingCurveConfig {
+    /// The coordinate modulus of the curve.
+    #[serde_as(as = "DisplayFromStr")]
+    pub modulus: BigUint,
+    /// The scalar field modulus of the curve.
+    #[serde_as(as = "DisplayFromStr")]
+    pub scalar: BigUint,
+    /// The coefficient b of y^2 = x^3 + b.
+    #[serde_as(as = "DisplayFromStr")]
+    pub b: BigUint,
+    /// The quadratic non-residue in `Fp` defining `Fp2`. It must be small, and is `-1` for the
+    /// built-in curves, whose moduli are 3 mod 4. BLS12-377, for example, uses `-5`.
+    #[serde(default = "default_fp2_non_residue")]
+    pub fp2_non_residue: isize,
+    /// The non-residue `xi = xi[0] + xi[1] * u` in `Fp2` defining `Fp12`. Both parts must be small.
+    pub xi: [isize; 2],
+    /// Absolute value of the curve seed, which is the Miller loop scalar.
+    pub seed_abs: u64,
+    /// Signed-digit encoding of the Miller loop scalar, least significant digit first.
+    pub pseudo_binary_encoding: Vec<i8>,
+    pub twist: TwistType,
+}
+
+fn default_fp2_non_residue() -> isize {
+    -1
+}
+
+impl PairingCurveConfig {
+    pub fn curve_config(&self) -> CurveConfig {
+        CurveConfig::new(
+            self.modulus.clone(),
+            self.scalar.clone(),
+            BigUint::zero(),
+            self.b.clone(),
+        )
+    }
+}
+
+pub static BN254_CONFIG: Lazy<PairingCurveConfig> = Lazy::new(|| PairingCurveConfig {
+    modulus: BN254_MODULUS.clone(),
+    scalar: BN254_ORDER.clone(),
+    b: BigUint::from_u8(3).unwrap(),
+    fp2_non_residue: -1,
+    xi: BN254_XI_ISIZE,
+    seed_abs: BN254_SEED,
+    pseudo_binary_encoding: BN254_PSEUDO_BINARY_ENCODING.to_vec(),
+    twist: TwistType::D,
+});
+
+pub static BLS12_381_CONFIG: Lazy<PairingCurveConfig> = Lazy::new(|| PairingCurveConfig {
+    modulus: BLS12_381_MODULUS.clone(),
+    scalar: BLS12_381_ORDER.clone(),
+    b: BigUint::from_u8(4).unwrap(),
+    fp2_non_residue: -1,
+    xi: BLS12_381_XI_ISIZE,
+    seed_abs: BLS12_381_SEED_ABS,
+    pseudo_binary_encoding: BLS12_381_PSEUDO_BINARY_ENCODING.to_vec(),
+    twist: TwistType::M,
+});
+
 // All the supported pairing curves.
-#[derive(Clone, Copy, Debug, FromRepr, Serialize, Deserialize)]
-#[repr(usize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum PairingCurve {
     Bn254,
     Bls12_381,
+    /// A user-defined curve. `pairing_idx` must match the `PAIRING_IDX` of the guest declaration
+    /// and must not collide with the built-in curves.
+    Custom {
+        pairing_idx: usize,
+        config: PairingCurveConfig,
+    },
 }
 
 impl PairingCurve {
-    pub fn curve_config(&self) -> CurveConfig {
+    /// The index used to select this curve's opcodes and hint in the guest.
+    pub fn pairing_idx(&self) -> usize {
         match self {
-            PairingCurve::Bn254 => CurveConfig::new(
-                BN254_MODULUS.clone(),
-                BN254_ORDER.clone(),
-                BigUint::zero(),
-                BigUint::from_u8(3).unwrap(),
-            ),
-            PairingCurve::Bls12_381 => CurveConfig::new(
-                BLS12_381_MODULUS.clone(),
-                BLS12_381_ORDER.clone(),
-                BigUint::zero(),
-                BigUint::from_u8(4).unwrap(),
-            ),
+            PairingCurve::Bn254 => 0,
+            PairingCurve::Bls12_381 => 1,
+            PairingCurve::Custom { pairing_idx, .. } => *pairing_idx,
         }
     }
 
-    pub fn xi(&self) -> [isize; 2] {
+    pub fn config(&self) -> &PairingCurveConfig {
         match self {
-            PairingCurve::Bn254 => BN254_XI_ISIZE,
-            PairingCurve::Bls12_381 => BLS12_381_XI_ISIZE,
+            PairingCurve::Bn254 => &BN254_CONFIG,
+            PairingCurve::Bls12_381 => &BLS12_381_CONFIG,
+            PairingCurve::Custom { config, .. } => config,
         }
     }
+
+    pub fn curve_config(&self) -> CurveConfig {
+        self.config().curve_config()
+    }
+
+    pub fn xi(&self) -> [isize; 2] {
+        self.config().xi
+    }
 }
 
 #[derive(Clone, Debug, derive_new::new, Serialize, Deserialize)]
@@ -68,20 +154,24 @@ pub struct PairingExtension {
 
 #[derive(Chip, ChipUsageGetter, InstructionExecutor, AnyEnum)]
 pub enum PairingExtensionExecutor<F: PrimeField32> {
-    // bn254 (32 limbs)
+    // 32 limbs prime
     MillerDoubleStepRv32_32(MillerDoubleStepChip<F, 4, 8, 32>),
     MillerDoubleAndAddStepRv32_32(MillerDoubleAndAddStepChip<F, 4, 12, 32>),
     EvaluateLineRv32_32(EvaluateLineChip<F, 4, 2, 4, 32>),
     Fp12MulRv32_32(Fp12MulChip<F, 12, 32>),
-    EcLineMul013By013(EcLineMul013By013Chip<F, 4, 10, 32>),
-    EcLineMulBy01234(EcLineMulBy01234Chip<F, 12, 10, 12, 32>),
-    // bls12-381 (48 limbs)
+    EcLineMul013By013Rv32_32(EcLineMul013By013Chip<F, 4, 10, 32>),
+    EcLineMulBy01234Rv32_32(EcLineMulBy01234Chip<F, 12, 10, 12, 32>),
+    EcLineMul023By023Rv32_32(EcLineMul023By023Chip<F, 4, 10, 32>),
+    EcLineMulBy02345Rv32_32(EcLineMulBy02345Chip<F, 12, 10, 12, 32>),
+    // 48 limbs prime
     MillerDoubleStepRv32_48(MillerDoubleStepChip<F, 12, 24, 16>),
     MillerDoubleAndAddStepRv32_48(MillerDoubleAndAddStepChip<F, 12, 36, 16>),
     EvaluateLineRv32_48(EvaluateLineChip<F, 12, 6, 12, 16>),
     Fp12MulRv32_48(Fp12MulChip<F, 36, 16>),
-    EcLineMul023By023(EcLineMul023By023Chip<F, 12, 30, 16>),
-    EcLineMulBy02345(EcLineMulBy02345Chip<F, 36, 30, 36, 16>),
+    EcLineMul013By013Rv32_48(EcLineMul013By013Chip<F, 12, 30, 16>),
+    EcLineMulBy01234Rv32_48(EcLineMulBy01234Chip<F, 36, 30, 36, 16>),
+    EcLineMul023By023Rv32_48(EcLineMul023By023Chip<F, 12, 30, 16>),
+    EcLineMulBy02345Rv32_48(EcLineMulBy02345Chip<F, 36, 30, 36, 16>),
 }
 
 #[derive(ChipUsageGetter, Chip, AnyEnum, From)]
@@ -115,247 +205,268 @@ impl<F: PrimeField32> VmExtension<F> for PairingExtension {
             inventory.add_periphery_chip(chip.clone());
             chip
         };
-        for curve in self.supported_curves.iter() {
-            let pairing_idx = *curve as usize;
+        for (i, curve) in self.supported_curves.iter().enumerate() {
+            let pairing_idx = curve.pairing_idx();
+            // funct7 = pairing_idx * PAIRING_MAX_KINDS + base_funct7 must fit in 7 bits.
+            let max_pairing_idx = (1 << 7) / PairingBaseFunct7::PAIRING_MAX_KINDS as usize;
+            if pairing_idx >= max_pairing_idx {
+                return Err(VmInventoryError::InvalidConfig {
+                    reason: format!(
+                        "pairing_idx {pairing_idx} must be less than {max_pairing_idx}"
+                    ),
+                });
+            }
+            if self.supported_curves[..i]
+                .iter()
+                .any(|other| other.pairing_idx() == pairing_idx)
+            {
+                return Err(VmInventoryError::InvalidConfig {
+                    reason: format!("duplicate pairing_idx {pairing_idx}"),
+                });
+            }
+            let config = curve.config();
+            if config.fp2_non_residue.unsigned_abs() >= 1 << 8
+                || !is_quadratic_non_residue(config.fp2_non_residue, &config.modulus)
+            {
+                return Err(VmInventoryError::InvalidConfig {
+                    reason: format!(
+                        "fp2_non_residue {} must be a small quadratic non-residue",
+                        config.fp2_non_residue
+                    ),
+                });
+            }
+            let encoded_seed =
+                config
+                    .pseudo_binary_encoding
+                    .iter()
+                    .rev()
+                    .try_fold(0i128, |acc, &digit| {
+                        if !(-1..=1).contains(&digit) {
+                            return None;
+                        }
+                        acc.checked_mul(2)?.checked_add(digit as i128)
+                    });
+            if encoded_seed != Some(config.seed_abs as i128) {
+                return Err(VmInventoryError::InvalidConfig {
+                    reason: "pseudo_binary_encoding does not encode seed_abs".to_string(),
+                });
+            }
+
             let pairing_class_offset =
                 PairingOpcode::default_offset() + pairing_idx * PairingOpcode::COUNT;
             let fp12_class_offset = Fp12Opcode::default_offset() + pairing_idx * Fp12Opcode::COUNT;
-            match curve {
-                PairingCurve::Bn254 => {
-                    let bn_config = ExprBuilderConfig {
-                        modulus: curve.curve_config().modulus.clone(),
-                        num_limbs: 32,
-                        limb_bits: 8,
-                    };
-                    let miller_double = MillerDoubleStepChip::new(
-                        Rv32VecHeapAdapterChip::<F, 1, 4, 8, 32, 32>::new(
-                            execution_bus,
-                            program_bus,
-                            memory_controller.clone(),
-                            bitwise_lu_chip.clone(),
-                        ),
-                        memory_controller.clone(),
-                        bn_config.clone(),
-                        pairing_class_offset,
-                    );
-                    inventory.add_executor(
-                        PairingExtensionExecutor::MillerDoubleStepRv32_32(miller_double),
-                        [VmOpcode::from_usize(
-                            pairing_class_offset + PairingOpcode::MILLER_DOUBLE_STEP as usize,
-                        )],
-                    )?;
-                    let miller_double_and_add = MillerDoubleAndAddStepChip::new(
-                        Rv32VecHeapAdapterChip::<F, 2, 4, 12, 32, 32>::new(
-                            execution_bus,
-                            program_bus,
-                            memory_controller.clone(),
-                            bitwise_lu_chip.clone(),
-                        ),
-                        memory_controller.clone(),
-                        bn_config.clone(),
-                        pairing_class_offset,
-                    );
-                    inventory.add_executor(
-                        PairingExtensionExecutor::MillerDoubleAndAddStepRv32_32(
-                            miller_double_and_add,
-                        ),
-                        [VmOpcode::from_usize(
-                            pairing_class_offset
-                                + PairingOpcode::MILLER_DOUBLE_AND_ADD_STEP as usize,
-                        )],
-                    )?;
-                    let eval_line = EvaluateLineChip::new(
-                        Rv32VecHeapTwoReadsAdapterChip::<F, 4, 2, 4, 32, 32>::new(
-                            execution_bus,
-                            program_bus,
-                            memory_controller.clone(),
-                            bitwise_lu_chip.clone(),
-                        ),
-                        memory_controller.clone(),
-                        bn_config.clone(),
-                        pairing_class_offset,
-                    );
-                    inventory.add_executor(
-                        PairingExtensionExecutor::EvaluateLineRv32_32(eval_line),
-                        [VmOpcode::from_usize(
-                            pairing_class_offset + PairingOpcode::EVALUATE_LINE as usize,
-                        )],
-                    )?;
-                    let mul013 = EcLineMul013By013Chip::new(
-                        Rv32VecHeapAdapterChip::<F, 2, 4, 10, 32, 32>::new(
-                            execution_bus,
-                            program_bus,
-                            memory_controller.clone(),
-                            bitwise_lu_chip.clone(),
-                        ),
-                        memory_controller.clone(),
-                        bn_config.clone(),
-                        curve.xi(),
-                        pairing_class_offset,
-                    );
-                    inventory.add_executor(
-                        PairingExtensionExecutor::EcLineMul013By013(mul013),
-                        [VmOpcode::from_usize(
-                            pairing_class_offset + PairingOpcode::MUL_013_BY_013 as usize,
-                        )],
-                    )?;
-                    let mul01234 = EcLineMulBy01234Chip::new(
-                        Rv32VecHeapTwoReadsAdapterChip::<F, 12, 10, 12, 32, 32>::new(
-                            execution_bus,
-                            program_bus,
-                            memory_controller.clone(),
-                            bitwise_lu_chip.clone(),
-                        ),
-                        memory_controller.clone(),
-                        bn_config.clone(),
-                        curve.xi(),
-                        pairing_class_offset,
-                    );
-                    inventory.add_executor(
-                        PairingExtensionExecutor::EcLineMulBy01234(mul01234),
-                        [VmOpcode::from_usize(
-                            pairing_class_offset + PairingOpcode::MUL_BY_01234 as usize,
-                        )],
-                    )?;
-                    let fp12_mul = Fp12MulChip::new(
-                        Rv32VecHeapAdapterChip::<F, 2, 12, 12, 32, 32>::new(
-                            execution_bus,
-                            program_bus,
-                            memory_controller.clone(),
-                            bitwise_lu_chip.clone(),
-                        ),
-                        memory_controller.clone(),
-                        bn_config.clone(),
-                        curve.xi(),
-                        fp12_class_offset,
-                    );
-                    inventory.add_executor(
-                        PairingExtensionExecutor::Fp12MulRv32_32(fp12_mul),
-                        [VmOpcode::from_usize(
-                            fp12_class_offset + Fp12Opcode::MUL as usize,
-                        )],
-                    )?;
-                }
-                PairingCurve::Bls12_381 => {
-                    let bls_config = ExprBuilderConfig {
-                        modulus: curve.curve_config().modulus.clone(),
-                        num_limbs: 48,
+            let bytes = config.modulus.bits().div_ceil(8);
+
+            macro_rules! add_pairing_chips {
+                (
+                    $num_limbs:literal,
+                    $miller_double:ident,
+                    $miller_double_and_add:ident,
+                    $evaluate_line:ident,
+                    $fp12_mul:ident,
+                    $mul_013_by_013:ident,
+                    $mul_by_01234:ident,
+                    $mul_023_by_023:ident,
+                    $mul_by_02345:ident
+                ) => {{
+                    let expr_config = ExprBuilderConfig {
+                        modulus: config.modulus.clone(),
+                        num_limbs: $num_limbs,
                         limb_bits: 8,
                     };
                     let miller_double = MillerDoubleStepChip::new(
-                        Rv32VecHeapAdapterChip::<F, 1, 12, 24, 16, 16>::new(
+                        Rv32VecHeapAdapterChip::new(
                             execution_bus,
                             program_bus,
                             memory_controller.clone(),
                             bitwise_lu_chip.clone(),
                         ),
                         memory_controller.clone(),
-                        bls_config.clone(),
+                        expr_config.clone(),
+                        config.fp2_non_residue,
                         pairing_class_offset,
                     );
                     inventory.add_executor(
-                        PairingExtensionExecutor::MillerDoubleStepRv32_48(miller_double),
+                        PairingExtensionExecutor::$miller_double(miller_double),
                         [VmOpcode::from_usize(
                             pairing_class_offset + PairingOpcode::MILLER_DOUBLE_STEP as usize,
                         )],
                     )?;
                     let miller_double_and_add = MillerDoubleAndAddStepChip::new(
-                        Rv32VecHeapAdapterChip::<F, 2, 12, 36, 16, 16>::new(
+                        Rv32VecHeapAdapterChip::new(
                             execution_bus,
                             program_bus,
                             memory_controller.clone(),
                             bitwise_lu_chip.clone(),
                         ),
                         memory_controller.clone(),
-                        bls_config.clone(),
+                        expr_config.clone(),
+                        config.fp2_non_residue,
                         pairing_class_offset,
                     );
                     inventory.add_executor(
-                        PairingExtensionExecutor::MillerDoubleAndAddStepRv32_48(
-                            miller_double_and_add,
-                        ),
+                        PairingExtensionExecutor::$miller_double_and_add(miller_double_and_add),
                         [VmOpcode::from_usize(
                             pairing_class_offset
                                 + PairingOpcode::MILLER_DOUBLE_AND_ADD_STEP as usize,
                         )],
                     )?;
                     let eval_line = EvaluateLineChip::new(
-                        Rv32VecHeapTwoReadsAdapterChip::<F, 12, 6, 12, 16, 16>::new(
+                        Rv32VecHeapTwoReadsAdapterChip::new(
                             execution_bus,
                             program_bus,
                             memory_controller.clone(),
                             bitwise_lu_chip.clone(),
                         ),
                         memory_controller.clone(),
-                        bls_config.clone(),
+                        expr_config.clone(),
                         pairing_class_offset,
                     );
                     inventory.add_executor(
-                        PairingExtensionExecutor::EvaluateLineRv32_48(eval_line),
+                        PairingExtensionExecutor::$evaluate_line(eval_line),
                         [VmOpcode::from_usize(
                             pairing_class_offset + PairingOpcode::EVALUATE_LINE as usize,
                         )],
                     )?;
-                    let mul023 = EcLineMul023By023Chip::new(
-                        Rv32VecHeapAdapterChip::<F, 2, 12, 30, 16, 16>::new(
-                            execution_bus,
-                            program_bus,
-                            memory_controller.clone(),
-                            bitwise_lu_chip.clone(),
-                        ),
-                        memory_controller.clone(),
-                        bls_config.clone(),
-                        curve.xi(),
-                        pairing_class_offset,
-                    );
-                    inventory.add_executor(
-                        PairingExtensionExecutor::EcLineMul023By023(mul023),
-                        [VmOpcode::from_usize(
-                            pairing_class_offset + PairingOpcode::MUL_023_BY_023 as usize,
-                        )],
-                    )?;
-                    let mul02345 = EcLineMulBy02345Chip::new(
-                        Rv32VecHeapTwoReadsAdapterChip::<F, 36, 30, 36, 16, 16>::new(
-                            execution_bus,
-                            program_bus,
-                            memory_controller.clone(),
-                            bitwise_lu_chip.clone(),
-                        ),
-                        memory_controller.clone(),
-                        bls_config.clone(),
-                        curve.xi(),
-                        pairing_class_offset,
-                    );
-                    inventory.add_executor(
-                        PairingExtensionExecutor::EcLineMulBy02345(mul02345),
-                        [VmOpcode::from_usize(
-                            pairing_class_offset + PairingOpcode::MUL_BY_02345 as usize,
-                        )],
-                    )?;
+                    match config.twist {
+                        TwistType::D => {
+                            let mul013 = EcLineMul013By013Chip::new(
+                                Rv32VecHeapAdapterChip::new(
+                                    execution_bus,
+                                    program_bus,
+                                    memory_controller.clone(),
+                                    bitwise_lu_chip.clone(),
+                                ),
+                                memory_controller.clone(),
+                                expr_config.clone(),
+                                config.fp2_non_residue,
+                                config.xi,
+                                pairing_class_offset,
+                            );
+                            inventory.add_executor(
+                                PairingExtensionExecutor::$mul_013_by_013(mul013),
+                                [VmOpcode::from_usize(
+                                    pairing_class_offset + PairingOpcode::MUL_013_BY_013 as usize,
+                                )],
+                            )?;
+                            let mul01234 = EcLineMulBy01234Chip::new(
+                                Rv32VecHeapTwoReadsAdapterChip::new(
+                                    execution_bus,
+                                    program_bus,
+                                    memory_controller.clone(),
+                                    bitwise_lu_chip.clone(),
+                                ),
+                                memory_controller.clone(),
+                                expr_config.clone(),
+                                config.fp2_non_residue,
+                                config.xi,
+                                pairing_class_offset,
+                            );
+                            inventory.add_executor(
+                                PairingExtensionExecutor::$mul_by_01234(mul01234),
+                                [VmOpcode::from_usize(
+                                    pairing_class_offset + PairingOpcode::MUL_BY_01234 as usize,
+                                )],
+                            )?;
+                        }
+                        TwistType::M => {
+                            let mul023 = EcLineMul023By023Chip::new(
+                                Rv32VecHeapAdapterChip::new(
+                                    execution_bus,
+                                    program_bus,
+                                    memory_controller.clone(),
+                                    bitwise_lu_chip.clone(),
+                                ),
+                                memory_controller.clone(),
+                                expr_config.clone(),
+                                config.fp2_non_residue,
+                                config.xi,
+                                pairing_class_offset,
+                            );
+                            inventory.add_executor(
+                                PairingExtensionExecutor::$mul_023_by_023(mul023),
+                                [VmOpcode::from_usize(
+                                    pairing_class_offset + PairingOpcode::MUL_023_BY_023 as usize,
+                                )],
+                            )?;
+                            let mul02345 = EcLineMulBy02345Chip::new(
+                                Rv32VecHeapTwoReadsAdapterChip::new(
+                                    execution_bus,
+                                    program_bus,
+                                    memory_controller.clone(),
+                                    bitwise_lu_chip.clone(),
+                                ),
+                                memory_controller.clone(),
+                                expr_config.clone(),
+                                config.fp2_non_residue,
+                                config.xi,
+                                pairing_class_offset,
+                            );
+                            inventory.add_executor(
+                                PairingExtensionExecutor::$mul_by_02345(mul02345),
+                                [VmOpcode::from_usize(
+                                    pairing_class_offset + PairingOpcode::MUL_BY_02345 as usize,
+                                )],
+                            )?;
+                        }
+                    }
                     let fp12_mul = Fp12MulChip::new(
-                        Rv32VecHeapAdapterChip::<F, 2, 36, 36, 16, 16>::new(
+                        Rv32VecHeapAdapterChip::new(
                             execution_bus,
                             program_bus,
                             memory_controller.clone(),
                             bitwise_lu_chip.clone(),
                         ),
                         memory_controller.clone(),
-                        bls_config.clone(),
-                        curve.xi(),
+                        expr_config.clone(),
+                        config.fp2_non_residue,
+                        config.xi,
                         fp12_class_offset,
                     );
                     inventory.add_executor(
-                        PairingExtensionExecutor::Fp12MulRv32_48(fp12_mul),
+                        PairingExtensionExecutor::$fp12_mul(fp12_mul),
                         [VmOpcode::from_usize(
                             fp12_class_offset + Fp12Opcode::MUL as usize,
                         )],
                     )?;
-                }
+                }};
+            }
+
+            if bytes <= 32 {
+                add_pairing_chips!(
+                    32,
+                    MillerDoubleStepRv32_32,
+                    MillerDoubleAndAddStepRv32_32,
+                    EvaluateLineRv32_32,
+                    Fp12MulRv32_32,
+                    EcLineMul013By013Rv32_32,
+                    EcLineMulBy01234Rv32_32,
+                    EcLineMul023By023Rv32_32,
+                    EcLineMulBy02345Rv32_32
+                );
+            } else if bytes <= 48 {
+                add_pairing_chips!(
+                    48,
+                    MillerDoubleStepRv32_48,
+                    MillerDoubleAndAddStepRv32_48,
+                    EvaluateLineRv32_48,
+                    Fp12MulRv32_48,
+                    EcLineMul013By013Rv32_48,
+                    EcLineMulBy01234Rv32_48,
+                    EcLineMul023By023Rv32_48,
+                    EcLineMulBy02345Rv32_48
+                );
+            } else {
+                return Err(VmInventoryError::InvalidConfig {
+                    reason: format!(
+                        "pairing modulus of {bytes} bytes is too large: at most 48 bytes are supported"
+                    ),
+                });
             }
         }
 
         builder.add_phantom_sub_executor(
-            phantom::PairingHintSubEx,
+            phantom::PairingHintSubEx::new(self.supported_curves.clone()),
             PhantomDiscriminant(PairingPhantom::HintFinalExp as u16),
         )?;
 
@@ -386,7 +497,10 @@ pub(crate) mod phantom {
 
     use super::PairingCurve;
 
-    pub struct PairingHintSubEx;
+    #[derive(derive_new::new)]
+    pub struct PairingHintSubEx {
+        supported_curves: Vec<PairingCurve>,
+    }
 
     impl<F: PrimeField32> PhantomSubExecutor<F> for PairingHintSubEx {
         fn phantom_execute(
@@ -400,7 +514,11 @@ pub(crate) mod phantom {
         ) -> eyre::Result<()> {
             let rs1 = unsafe_read_rv32_register(memory, a);
             let rs2 = unsafe_read_rv32_register(memory, b);
-            hint_pairing(memory, &mut streams.hint_stream, rs1, rs2, c_upper)
+            let curve = self
+                .supported_curves
+                .iter()
+                .find(|curve| curve.pairing_idx() == c_upper as usize);
+            hint_pairing(memory, &mut streams.hint_stream, rs1, rs2, curve, c_upper)
         }
     }
 
@@ -409,6 +527,7 @@ pub(crate) mod phantom {
         hint_stream: &mut VecDeque<F>,
         rs1: u32,
         rs2: u32,
+        curve: Option<&PairingCurve>,
         c_upper: u16,
     ) -> eyre::Result<()> {
         let p_ptr = compose(memory.unsafe_read(
@@ -430,7 +549,7 @@ pub(crate) mod phantom {
             F::from_canonical_u32(rs2 + RV32_REGISTER_NUM_LIMBS as u32),
         ));
 
-        match PairingCurve::from_repr(c_upper as usize) {
+        match curve {
             Some(PairingCurve::Bn254) => {
                 use openvm_ecc_guest::halo2curves::bn256::{Fq, Fq12, Fq2};
                 use openvm_pairing_guest::halo2curves_shims::bn254::Bn254;
@@ -517,7 +636,10 @@ pub(crate) mod phantom {
                         .map(F::from_canonical_u8),
                 );
             }
-            _ => {
+            Some(PairingCurve::Custom { .. }) => {
+                bail!("hint_pairing: no final exponentiation hint for custom curve {c_upper}");
+            }
+            None => {
                 bail!("hint_pairing: invalid PairingCurve={c_upper}");
             }
         }
@@ -14,7 +14,7 @@ use {
 use super::{Bn254, Fp, Fp12, Fp2};
 use crate::pairing::{
     Evaluatable, EvaluatedLine, FromLineDType, LineMulDType, MillerStep, MultiMillerLoop,
-    PairingCheck, PairingCheckError, PairingIntrinsics, UnevaluatedLine,
+    PairingCheck, PairingCheckError, PairingCheckHint, PairingIntrinsics, UnevaluatedLine,
 };
 
 // TODO[jpw]: make macro
@@ -295,11 +295,7 @@ impl MultiMillerLoop for Bn254 {
 }
 
 #[allow(non_snake_case)]
-impl PairingCheck for Bn254 {
-    type Fp = Fp;
-    type Fp2 = Fp2;
-    type Fp12 = Fp12;
-
+impl PairingCheckHint for Bn254 {
     #[allow(unused_variables)]
     fn pairing_check_hint(
         P: &[AffinePoint<Self::Fp>],
@@ -324,16 +320,19 @@ impl PairingCheck for Bn254 {
                     rs1 = in(reg) &p_fat_ptr,
                     rs2 = in(reg) &q_fat_ptr
                 );
-                let mut ptr = hint.as_ptr() as *const u8;
-                // NOTE[jpw]: this loop could be unrolled using seq_macro and hint_store_u32(ptr, $imm)
-                for _ in (0..32 * 12 * 2).step_by(4) {
-                    openvm_rv32im_guest::hint_store_u32!(ptr, 0);
-                    ptr = ptr.add(4);
-                }
+                let ptr = hint.as_ptr() as *const u8;
+                openvm_rv32im_guest::hint_buffer_u32!(ptr, (32 * 12 * 2) / 4);
                 hint.assume_init()
             }
         }
     }
+}
+
+#[allow(non_snake_case)]
+impl PairingCheck for Bn254 {
+    type Fp = Fp;
+    type Fp2 = Fp2;
+    type Fp12 = Fp12;
 
     fn pairing_check(
         P: &[AffinePoint<Self::Fp>],
@@ -30,6 +30,7 @@ extern crate self as openvm_ecc;
 #[cfg(feature = "halo2curves")]
 pub use halo2curves_axiom as halo2curves;
 pub use openvm_algebra_guest as algebra;
+pub use openvm_ecc_guest as ecc;
 
 pub mod affine_point;
 
@@ -0,0 +1,969 @@
+//! Support for user-defined pairing curves declared with
+//! [`pairing_declare!`](crate::pairing_declare).
+//!
+//! The functions in this module are used by the macro expansion and are not meant to be called
+//! directly.
+
+pub use alloc::vec::Vec;
+
+#[cfg(target_os = "zkvm")]
+use {
+    super::{shifted_funct7, PairingIntrinsics},
+    crate::{PairingBaseFunct7, OPCODE, PAIRING_FUNCT3},
+    openvm_platform::custom_insn_r,
+};
+
+/// SAFETY: `dst` must be a raw pointer to an uninitialized `EvaluatedLine<P::Fp2>`, `line` to an
+/// `UnevaluatedLine<P::Fp2>` and `xy_frac` to a `(P::Fp, P::Fp)`.
+#[cfg(target_os = "zkvm")]
+#[inline(always)]
+pub fn evaluate_line_intrinsic<P: PairingIntrinsics>(
+    dst: *mut u8,
+    line: *const u8,
+    xy_frac: *const u8,
+) {
+    custom_insn_r!(
+        OPCODE,
+        PAIRING_FUNCT3,
+        shifted_funct7::<P>(PairingBaseFunct7::EvaluateLine),
+        dst,
+        line,
+        xy_frac
+    );
+}
+
+/// SAFETY: `dst` must be a raw pointer to an uninitialized `[P::Fp2; 5]` and `l0`, `l1` to
+/// `EvaluatedLine<P::Fp2>`s.
+#[cfg(target_os = "zkvm")]
+#[inline(always)]
+pub fn mul_013_by_013_intrinsic<P: PairingIntrinsics>(dst: *mut u8, l0: *const u8, l1: *const u8) {
+    custom_insn_r!(
+        OPCODE,
+        PAIRING_FUNCT3,
+        shifted_funct7::<P>(PairingBaseFunct7::Mul013By013),
+        dst,
+        l0,
+        l1
+    );
+}
+
+/// SAFETY: `dst` must be a raw pointer to an uninitialized `P::Fp12`, `f` to a `P::Fp12` and `x`
+/// to a `[P::Fp2; 5]`.
+#[cfg(target_os = "zkvm")]
+#[inline(always)]
+pub fn mul_by_01234_intrinsic<P: PairingIntrinsics>(dst: *mut u8, f: *const u8, x: *const u8) {
+    custom_insn_r!(
+        OPCODE,
+        PAIRING_FUNCT3,
+        shifted_funct7::<P>(PairingBaseFunct7::MulBy01234),
+        dst,
+        f,
+        x
+    );
+}
+
+/// SAFETY: `dst` must be a raw pointer to an uninitialized `[P::Fp2; 5]` and `l0`, `l1` to
+/// `EvaluatedLine<P::Fp2>`s.
+#[cfg(target_os = "zkvm")]
+#[inline(always)]
+pub fn mul_023_by_023_intrinsic<P: PairingIntrinsics>(dst: *mut u8, l0: *const u8, l1: *const u8) {
+    custom_insn_r!(
+        OPCODE,
+        PAIRING_FUNCT3,
+        shifted_funct7::<P>(PairingBaseFunct7::Mul023By023),
+        dst,
+        l0,
+        l1
+    );
+}
+
+/// SAFETY: `dst` must be a raw pointer to an uninitialized `P::Fp12`, `f` to a `P::Fp12` and `x`
+/// to a `[P::Fp2; 5]`.
+#[cfg(target_os = "zkvm")]
+#[inline(always)]
+pub fn mul_by_02345_intrinsic<P: PairingIntrinsics>(dst: *mut u8, f: *const u8, x: *const u8) {
+    custom_insn_r!(
+        OPCODE,
+        PAIRING_FUNCT3,
+        shifted_funct7::<P>(PairingBaseFunct7::MulBy02345),
+        dst,
+        f,
+        x
+    );
+}
+
+/// Declares a pairing-friendly curve with embedding degree 12 on top of moduli and complex
+/// extension fields already declared with `moduli_declare!` and `complex_declare!`.
+/// `fp` must implement `Field`, and `fp2` must implement `Field` via `complex_impl_field!`.
+///
+/// The macro defines the curve type `$curve`, the tower field `$fp12 = Fp2[w] / (w^6 - xi)`, and
+/// implements [`PairingIntrinsics`](crate::pairing::PairingIntrinsics),
+/// [`MultiMillerLoop`](crate::pairing::MultiMillerLoop) and
+/// [`PairingCheck`](crate::pairing::PairingCheck) for it using the pairing opcodes at
+/// `pairing_idx`. The host must enable the same curve in `PairingExtension` under the same
+/// `pairing_idx`.
+///
+/// - `twist` is `D` or `M` and selects the sparse line multiplication opcodes.
+/// - `family` is `Bn` or `Bls12` and selects the end of the Miller loop: BN curves add the
+///   Frobenius twists of `Q`, BLS12 curves conjugate for a negative seed.
+/// - `final_exp_hard_part` is the big-endian encoding of `(p^4 - p^2 + 1) / r`.
+///
+/// Unlike the built-in curves, no final exponentiation hint is available, so the curve does not
+/// implement [`PairingCheckHint`](crate::pairing::PairingCheckHint) and `pairing_check` computes
+/// the full final exponentiation in the guest.
+///
+/// ```ignore
+/// openvm_pairing_guest::pairing_declare! {
+///     MyCurve {
+///         fp = Fp,
+///         fp2 = Fp2,
+///         fp12 = Fp12,
+///         pairing_idx = 2,
+///         xi = Fp2::new(Fp::from_const_u8(9), Fp::from_const_u8(1)),
+///         frobenius_coeffs = FROBENIUS_COEFFS,
+///         twist = D,
+///         family = Bn,
+///         seed_abs = 0x44e992b44a6909f1,
+///         seed_is_negative = false,
+///         pseudo_binary_encoding = &PSEUDO_BINARY_ENCODING,
+///         final_exp_hard_part = &FINAL_EXP_HARD_PART,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! pairing_declare {
+    (
+        $curve:ident {
+            fp = $fp:ident,
+            fp2 = $fp2:ident,
+            fp12 = $fp12:ident,
+            pairing_idx = $pairing_idx:expr,
+            xi = $xi:expr,
+            frobenius_coeffs = $frobenius_coeffs:expr,
+            twist = $twist:ident,
+            family = $family:ident,
+            seed_abs = $seed_abs:expr,
+            seed_is_negative = $seed_is_negative:expr,
+            pseudo_binary_encoding = $pseudo_binary_encoding:expr,
+            final_exp_hard_part = $final_exp_hard_part:expr $(,)?
+        }
+    ) => {
+        pub struct $curve;
+
+        /// Sextic extension `Fp2[w] / (w^6 - xi)`.
+        /// Elements are represented as `c0 + c1 * w + ... + c5 * w^5`.
+        #[derive(Clone, PartialEq, Eq)]
+        #[repr(C)]
+        pub struct $fp12 {
+            pub c: [$fp2; 6],
+        }
+
+        impl $fp12 {
+            pub const fn new(c: [$fp2; 6]) -> Self {
+                Self { c }
+            }
+
+            pub fn invert(&self) -> Self {
+                let mut s = self.clone();
+                $crate::pairing::fp12_invert_assign::<$fp, $fp2>(
+                    &mut s.c,
+                    &<$curve as $crate::pairing::PairingIntrinsics>::XI,
+                );
+                s
+            }
+        }
+
+        const _: () = {
+            use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+            use $crate::{
+                algebra::{
+                    field::{ComplexConjugate, FieldExtension},
+                    DivAssignUnsafe, DivUnsafe, ExpBytes, Field, IntMod,
+                },
+                ecc::AffinePoint,
+                pairing::{
+                    declare::Vec, Evaluatable, EvaluatedLine, MillerStep, MultiMillerLoop,
+                    PairingCheck, PairingCheckError, PairingIntrinsics, UnevaluatedLine,
+                },
+            };
+
+            impl FieldExtension<$fp> for $fp2 {
+                const D: usize = 2;
+                type Coeffs = [$fp; 2];
+
+                fn from_coeffs([c0, c1]: Self::Coeffs) -> Self {
+                    Self { c0, c1 }
+                }
+
+                fn from_bytes(bytes: &[u8]) -> Self {
+                    let n = <$fp as IntMod>::NUM_LIMBS;
+                    assert_eq!(bytes.len(), 2 * n);
+                    Self::new(
+                        <$fp as IntMod>::from_le_bytes(&bytes[..n]),
+                        <$fp as IntMod>::from_le_bytes(&bytes[n..]),
+                    )
+                }
+
+                fn to_coeffs(self) -> Self::Coeffs {
+                    [self.c0, self.c1]
+                }
+
+                fn to_bytes(&self) -> Vec<u8> {
+                    let mut bytes = Vec::with_capacity(2 * <$fp as IntMod>::NUM_LIMBS);
+                    bytes.extend_from_slice(self.c0.as_le_bytes());
+                    bytes.extend_from_slice(self.c1.as_le_bytes());
+                    bytes
+                }
+
+                fn embed(c0: $fp) -> Self {
+                    Self::new(c0, <$fp as IntMod>::ZERO)
+                }
+
+                fn frobenius_map(&self, power: usize) -> Self {
+                    if power % 2 == 0 {
+                        self.clone()
+                    } else {
+                        self.clone().conjugate()
+                    }
+                }
+
+                fn mul_base(&self, rhs: &$fp) -> Self {
+                    Self::new(&self.c0 * rhs, &self.c1 * rhs)
+                }
+            }
+
+            impl Field for $fp12 {
+                type SelfRef<'a> = &'a Self;
+                const ZERO: Self = Self::new([$fp2::ZERO; 6]);
+                const ONE: Self = Self::new([
+                    $fp2::ONE,
+                    $fp2::ZERO,
+                    $fp2::ZERO,
+                    $fp2::ZERO,
+                    $fp2::ZERO,
+                    $fp2::ZERO,
+                ]);
+
+                fn double_assign(&mut self) {
+                    *self += self.clone();
+                }
+
+                fn square_assign(&mut self) {
+                    *self *= self.clone();
+                }
+            }
+
+            impl FieldExtension<$fp2> for $fp12 {
+                const D: usize = 6;
+                type Coeffs = [$fp2; 6];
+
+                fn from_coeffs(coeffs: Self::Coeffs) -> Self {
+                    Self::new(coeffs)
+                }
+
+                fn from_bytes(bytes: &[u8]) -> Self {
+                    let n = 2 * <$fp as IntMod>::NUM_LIMBS;
+                    assert_eq!(bytes.len(), 6 * n);
+                    Self::new(core::array::from_fn(|i| {
+                        <$fp2 as FieldExtension<$fp>>::from_bytes(&bytes[i * n..(i + 1) * n])
+                    }))
+                }
+
+                fn to_coeffs(self) -> Self::Coeffs {
+                    self.c
+                }
+
+                fn to_bytes(&self) -> Vec<u8> {
+                    let mut bytes = Vec::with_capacity(12 * <$fp as IntMod>::NUM_LIMBS);
+                    for coeff in self.c.iter() {
+                        bytes.extend_from_slice(&FieldExtension::<$fp>::to_bytes(coeff));
+                    }
+                    bytes
+                }
+
+                fn embed(c0: $fp2) -> Self {
+                    Self::new([
+                        c0,
+                        $fp2::ZERO,
+                        $fp2::ZERO,
+                        $fp2::ZERO,
+                        $fp2::ZERO,
+                        $fp2::ZERO,
+                    ])
+                }
+
+                /// We assume that the frobenius map power is < 12
+                fn frobenius_map(&self, power: usize) -> Self {
+                    let coeffs = &<$curve as PairingIntrinsics>::FROBENIUS_COEFFS[power];
+                    let mut c = self.c.clone();
+                    if power & 1 != 0 {
+                        c = c.map(ComplexConjugate::conjugate);
+                    }
+                    for (ci, coeff) in c[1..].iter_mut().zip(coeffs.iter()) {
+                        *ci *= coeff;
+                    }
+                    Self::new(c)
+                }
+
+                fn mul_base(&self, rhs: &$fp2) -> Self {
+                    Self::new(self.c.clone().map(|ci| ci * rhs))
+                }
+            }
+
+            // This is conjugation for Fp12 over Fp6.
+            impl ComplexConjugate for $fp12 {
+                #[inline(always)]
+                fn conjugate(self) -> Self {
+                    let [c0, c1, c2, c3, c4, c5] = self.c;
+                    Self::new([c0, -c1, c2, -c3, c4, -c5])
+                }
+
+                fn conjugate_assign(&mut self) {
+                    self.c[1].neg_assign();
+                    self.c[3].neg_assign();
+                    self.c[5].neg_assign();
+                }
+            }
+
+            impl core::fmt::Debug for $fp12 {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    write!(
+                        f,
+                        "{:?}, {:?}, {:?}, {:?}, {:?}, {:?}",
+                        self.c[0], self.c[1], self.c[2], self.c[3], self.c[4], self.c[5]
+                    )
+                }
+            }
+
+            impl<'a> AddAssign<&'a $fp12> for $fp12 {
+                #[inline(always)]
+                fn add_assign(&mut self, other: &'a $fp12) {
+                    for i in 0..6 {
+                        self.c[i] += &other.c[i];
+                    }
+                }
+            }
+
+            impl AddAssign for $fp12 {
+                #[inline(always)]
+                fn add_assign(&mut self, other: Self) {
+                    *self += &other;
+                }
+            }
+
+            impl Add for $fp12 {
+                type Output = Self;
+                #[inline(always)]
+                fn add(mut self, other: Self) -> Self::Output {
+                    self += &other;
+                    self
+                }
+            }
+
+            impl<'a> Add<&'a $fp12> for $fp12 {
+                type Output = Self;
+                #[inline(always)]
+                fn add(mut self, other: &'a $fp12) -> Self::Output {
+                    self += other;
+                    self
+                }
+            }
+
+            impl<'a> Add<&'a $fp12> for &$fp12 {
+                type Output = $fp12;
+                #[inline(always)]
+                fn add(self, other: &'a $fp12) -> Self::Output {
+                    let mut res = self.clone();
+                    res += other;
+                    res
+                }
+            }
+
+            impl<'a> SubAssign<&'a $fp12> for $fp12 {
+                #[inline(always)]
+                fn sub_assign(&mut self, other: &'a $fp12) {
+                    for i in 0..6 {
+                        self.c[i] -= &other.c[i];
+                    }
+                }
+            }
+
+            impl SubAssign for $fp12 {
+                #[inline(always)]
+                fn sub_assign(&mut self, other: Self) {
+                    *self -= &other;
+                }
+            }
+
+            impl Sub for $fp12 {
+                type Output = Self;
+                #[inline(always)]
+                fn sub(mut self, other: Self) -> Self::Output {
+                    self -= &other;
+                    self
+                }
+            }
+
+            impl<'a> Sub<&'a $fp12> for $fp12 {
+                type Output = Self;
+                #[inline(always)]
+                fn sub(mut self, other: &'a $fp12) -> Self::Output {
+                    self -= other;
+                    self
+                }
+            }
+
+            impl<'a> Sub<&'a $fp12> for &$fp12 {
+                type Output = $fp12;
+                #[inline(always)]
+                fn sub(self, other: &'a $fp12) -> Self::Output {
+                    let mut res = self.clone();
+                    res -= other;
+                    res
+                }
+            }
+
+            impl<'a> MulAssign<&'a $fp12> for $fp12 {
+                #[inline(always)]
+                fn mul_assign(&mut self, other: &'a $fp12) {
+                    #[cfg(not(target_os = "zkvm"))]
+                    {
+                        *self = &*self * other;
+                    }
+                    #[cfg(target_os = "zkvm")]
+                    {
+                        $crate::pairing::sextic_tower_mul_intrinsic::<$curve>(
+                            self as *mut $fp12 as *mut u8,
+                            self as *const $fp12 as *const u8,
+                            other as *const $fp12 as *const u8,
+                        );
+                    }
+                }
+            }
+
+            impl<'a> Mul<&'a $fp12> for &'a $fp12 {
+                type Output = $fp12;
+                #[inline(always)]
+                fn mul(self, other: &'a $fp12) -> Self::Output {
+                    #[cfg(not(target_os = "zkvm"))]
+                    {
+                        let prod = $crate::pairing::sextic_tower_mul_host(
+                            &$crate::pairing::SexticExtField::new(self.c.clone()),
+                            &$crate::pairing::SexticExtField::new(other.c.clone()),
+                            &<$curve as PairingIntrinsics>::XI,
+                        );
+                        $fp12::new(prod.c)
+                    }
+                    #[cfg(target_os = "zkvm")]
+                    unsafe {
+                        let mut uninit: core::mem::MaybeUninit<$fp12> =
+                            core::mem::MaybeUninit::uninit();
+                        $crate::pairing::sextic_tower_mul_intrinsic::<$curve>(
+                            uninit.as_mut_ptr() as *mut u8,
+                            self as *const $fp12 as *const u8,
+                            other as *const $fp12 as *const u8,
+                        );
+                        uninit.assume_init()
+                    }
+                }
+            }
+
+            impl MulAssign for $fp12 {
+                #[inline(always)]
+                fn mul_assign(&mut self, other: Self) {
+                    *self *= &other;
+                }
+            }
+
+            impl Mul for $fp12 {
+                type Output = Self;
+                #[inline(always)]
+                fn mul(mut self, other: Self) -> Self::Output {
+                    self *= &other;
+                    self
+                }
+            }
+
+            impl<'a> Mul<&'a $fp12> for $fp12 {
+                type Output = Self;
+                #[inline(always)]
+                fn mul(mut self, other: &'a $fp12) -> Self::Output {
+                    self *= other;
+                    self
+                }
+            }
+
+            impl<'a> DivAssignUnsafe<&'a $fp12> for $fp12 {
+                #[inline(always)]
+                fn div_assign_unsafe(&mut self, other: &'a $fp12) {
+                    *self *= other.invert();
+                }
+            }
+
+            impl DivAssignUnsafe for $fp12 {
+                #[inline(always)]
+                fn div_assign_unsafe(&mut self, other: Self) {
+                    *self *= other.invert();
+                }
+            }
+
+            impl<'a> DivUnsafe<&'a $fp12> for &'a $fp12 {
+                type Output = $fp12;
+                #[inline(always)]
+                fn div_unsafe(self, other: &'a $fp12) -> Self::Output {
+                    let mut res = self.clone();
+                    res.div_assign_unsafe(other);
+                    res
+                }
+            }
+
+            impl DivUnsafe for $fp12 {
+                type Output = Self;
+                #[inline(always)]
+                fn div_unsafe(mut self, other: Self) -> Self::Output {
+                    self.div_assign_unsafe(other);
+                    self
+                }
+            }
+
+            impl<'a> DivUnsafe<&'a $fp12> for $fp12 {
+                type Output = Self;
+                #[inline(always)]
+                fn div_unsafe(mut self, other: &'a $fp12) -> Self::Output {
+                    self.div_assign_unsafe(other);
+                    self
+                }
+            }
+
+            impl Neg for $fp12 {
+                type Output = Self;
+                #[inline(always)]
+                fn neg(self) -> Self::Output {
+                    Self::new(self.c.map(Neg::neg))
+                }
+            }
+
+            impl PairingIntrinsics for $curve {
+                type Fp = $fp;
+                type Fp2 = $fp2;
+                type Fp12 = $fp12;
+
+                const PAIRING_IDX: usize = $pairing_idx;
+                const XI: $fp2 = $xi;
+                const FROBENIUS_COEFFS: [[$fp2; 5]; 12] = $frobenius_coeffs;
+            }
+
+            impl Evaluatable<$fp, $fp2> for UnevaluatedLine<$fp2> {
+                fn evaluate(&self, xy_frac: &($fp, $fp)) -> EvaluatedLine<$fp2> {
+                    #[cfg(not(target_os = "zkvm"))]
+                    {
+                        let (x_over_y, y_inv) = xy_frac;
+                        EvaluatedLine {
+                            b: self.b.mul_base(x_over_y),
+                            c: self.c.mul_base(y_inv),
+                        }
+                    }
+                    #[cfg(target_os = "zkvm")]
+                    unsafe {
+                        let mut uninit: core::mem::MaybeUninit<EvaluatedLine<$fp2>> =
+                            core::mem::MaybeUninit::uninit();
+                        let line_ptr = (self as *const UnevaluatedLine<$fp2>).cast::<u8>();
+                        let xy_frac_ptr = (xy_frac as *const ($fp, $fp)).cast::<u8>();
+                        $crate::pairing::declare::evaluate_line_intrinsic::<$curve>(
+                            uninit.as_mut_ptr().cast::<u8>(),
+                            line_ptr,
+                            xy_frac_ptr,
+                        );
+                        uninit.assume_init()
+                    }
+                }
+            }
+
+            $crate::pairing_declare! { @line_mul $twist, $curve, $fp2, $fp12 }
+
+            #[allow(non_snake_case)]
+            impl MultiMillerLoop for $curve {
+                type Fp = $fp;
+                type Fp12 = $fp12;
+
+                const SEED_ABS: u64 = $seed_abs;
+                const PSEUDO_BINARY_ENCODING: &[i8] = $pseudo_binary_encoding;
+
+                fn evaluate_lines_vec(f: $fp12, lines: Vec<EvaluatedLine<$fp2>>) -> $fp12 {
+                    $crate::pairing_declare!(@evaluate_lines $twist, f, lines)
+                }
+
+                /// Handles the two most significant digits of the pseudo-binary encoding: the
+                /// leading 1 is `Q_acc = Q`, and the next digit is a double followed by an add of
+                /// `±Q` when it is nonzero.
+                fn pre_loop(
+                    Q_acc: Vec<AffinePoint<$fp2>>,
+                    Q: &[AffinePoint<$fp2>],
+                    c: Option<$fp12>,
+                    xy_fracs: &[($fp, $fp)],
+                ) -> ($fp12, Vec<AffinePoint<$fp2>>) {
+                    let pbe = Self::PSEUDO_BINARY_ENCODING;
+                    let digit = pbe[pbe.len() - 2];
+                    let mut f = if let Some(c) = c {
+                        let mut f = c.clone();
+                        f.square_assign();
+                        match digit {
+                            0 => f,
+                            1 => f * &c,
+                            -1 => f.div_unsafe(&c),
+                            _ => panic!("Invalid sigma_i"),
+                        }
+                    } else {
+                        $fp12::ONE
+                    };
+
+                    let (mut Q_acc, lines_2S) = Q_acc
+                        .iter()
+                        .map(Self::miller_double_step)
+                        .unzip::<_, _, Vec<_>, Vec<_>>();
+                    let mut initial_lines = lines_2S
+                        .iter()
+                        .zip(xy_fracs)
+                        .map(|(line, xy_frac)| line.evaluate(xy_frac))
+                        .collect::<Vec<_>>();
+
+                    if digit != 0 {
+                        let (Q_out_add, lines_S_plus_Q) = Q_acc
+                            .iter()
+                            .zip(Q)
+                            .map(|(Q_acc, q)| match digit {
+                                1 => Self::miller_add_step(Q_acc, q),
+                                -1 => Self::miller_add_step(Q_acc, &q.neg_borrow()),
+                                _ => panic!("Invalid sigma_i"),
+                            })
+                            .unzip::<_, _, Vec<_>, Vec<_>>();
+                        Q_acc = Q_out_add;
+                        initial_lines.extend(
+                            lines_S_plus_Q
+                                .iter()
+                                .zip(xy_fracs)
+                                .map(|(line, xy_frac)| line.evaluate(xy_frac)),
+                        );
+                    }
+
+                    f = Self::evaluate_lines_vec(f, initial_lines);
+                    (f, Q_acc)
+                }
+
+                $crate::pairing_declare!(
+                    @post_loop $family, $curve, $fp, $fp2, $fp12, $seed_is_negative
+                );
+            }
+
+            #[allow(non_snake_case)]
+            impl PairingCheck for $curve {
+                type Fp = $fp;
+                type Fp2 = $fp2;
+                type Fp12 = $fp12;
+
+                fn pairing_check(
+                    P: &[AffinePoint<$fp>],
+                    Q: &[AffinePoint<$fp2>],
+                ) -> Result<(), PairingCheckError> {
+                    let f = Self::multi_miller_loop(P, Q);
+                    // Easy part: f^{(p^6 - 1)(p^2 + 1)}
+                    let g = f.clone().conjugate().div_unsafe(&f);
+                    let g = g.frobenius_map(2) * &g;
+                    // Hard part: g^{(p^4 - p^2 + 1) / r}
+                    if g.exp_bytes(true, $final_exp_hard_part) == $fp12::ONE {
+                        Ok(())
+                    } else {
+                        Err(PairingCheckError)
+                    }
+                }
+            }
+        };
+    };
+
+    (@line_mul D, $curve:ident, $fp2:ident, $fp12:ident) => {
+        impl $crate::pairing::FromLineDType<$fp2> for $fp12 {
+            fn from_evaluated_line_d_type(line: EvaluatedLine<$fp2>) -> $fp12 {
+                $fp12::new([
+                    $fp2::ONE,
+                    line.b,
+                    $fp2::ZERO,
+                    line.c,
+                    $fp2::ZERO,
+                    $fp2::ZERO,
+                ])
+            }
+        }
+
+        impl $crate::pairing::LineMulDType<$fp2, $fp12> for $curve {
+            /// Multiplies two lines in 013-form to get an element in 01234-form
+            fn mul_013_by_013(l0: &EvaluatedLine<$fp2>, l1: &EvaluatedLine<$fp2>) -> [$fp2; 5] {
+                #[cfg(not(target_os = "zkvm"))]
+                {
+                    let (b0, c0, b1, c1) = (&l0.b, &l0.c, &l1.b, &l1.c);
+                    // l0 * l1 = (1 + c0c1 * xi) + (b0 + b1)w + (b0b1)w² + (c0 + c1)w³
+                    //           + (b0c1 + b1c0)w⁴
+                    [
+                        $fp2::ONE + c0 * c1 * &Self::XI,
+                        b0 + b1,
+                        b0 * b1,
+                        c0 + c1,
+                        b0 * c1 + b1 * c0,
+                    ]
+                }
+                #[cfg(target_os = "zkvm")]
+                unsafe {
+                    let mut uninit: core::mem::MaybeUninit<[$fp2; 5]> =
+                        core::mem::MaybeUninit::uninit();
+                    $crate::pairing::declare::mul_013_by_013_intrinsic::<$curve>(
+                        uninit.as_mut_ptr() as *mut u8,
+                        l0 as *const EvaluatedLine<$fp2> as *const u8,
+                        l1 as *const EvaluatedLine<$fp2> as *const u8,
+                    );
+                    uninit.assume_init()
+                }
+            }
+
+            /// Multiplies a line in 013-form with a Fp12 element to get an Fp12 element
+            fn mul_by_013(f: &$fp12, l: &EvaluatedLine<$fp2>) -> $fp12 {
+                <$fp12 as $crate::pairing::FromLineDType<$fp2>>::from_evaluated_line_d_type(
+                    l.clone(),
+                ) * f
+            }
+
+            /// Multiplies a line in 01234-form with a Fp12 element to get an Fp12 element
+            fn mul_by_01234(f: &$fp12, x: &[$fp2; 5]) -> $fp12 {
+                #[cfg(not(target_os = "zkvm"))]
+                {
+                    let (o0, o1, o2, o3, o4) = (&x[0], &x[2], &x[4], &x[1], &x[3]);
+                    let xi = &Self::XI;
+                    let (s0, s1, s2, s3, s4, s5) =
+                        (&f.c[0], &f.c[2], &f.c[4], &f.c[1], &f.c[3], &f.c[5]);
+
+                    let c00 = s0 * o0 + xi * &(s1 * o2 + s2 * o1 + s4 * o4 + s5 * o3);
+                    let c01 = s0 * o1 + s1 * o0 + s3 * o3 + xi * &(s2 * o2 + s5 * o4);
+                    let c02 = s0 * o2 + s1 * o1 + s2 * o0 + s3 * o4 + s4 * o3;
+                    let c10 = s0 * o3 + s3 * o0 + xi * &(s2 * o4 + s4 * o2 + s5 * o1);
+                    let c11 = s0 * o4 + s1 * o3 + s3 * o1 + s4 * o0 + xi * &(s5 * o2);
+                    let c12 = s1 * o4 + s2 * o3 + s3 * o2 + s4 * o1 + s5 * o0;
+
+                    $fp12::new([c00, c10, c01, c11, c02, c12])
+                }
+                #[cfg(target_os = "zkvm")]
+                unsafe {
+                    let mut uninit: core::mem::MaybeUninit<$fp12> =
+                        core::mem::MaybeUninit::uninit();
+                    $crate::pairing::declare::mul_by_01234_intrinsic::<$curve>(
+                        uninit.as_mut_ptr() as *mut u8,
+                        f as *const $fp12 as *const u8,
+                        x as *const [$fp2; 5] as *const u8,
+                    );
+                    uninit.assume_init()
+                }
+            }
+        }
+    };
+
+    (@line_mul M, $curve:ident, $fp2:ident, $fp12:ident) => {
+        impl $crate::pairing::FromLineMType<$fp2> for $fp12 {
+            fn from_evaluated_line_m_type(line: EvaluatedLine<$fp2>) -> $fp12 {
+                $fp12::new([
+                    line.c,
+                    $fp2::ZERO,
+                    line.b,
+                    $fp2::ONE,
+                    $fp2::ZERO,
+                    $fp2::ZERO,
+                ])
+            }
+        }
+
+        impl $crate::pairing::LineMulMType<$fp2, $fp12> for $curve {
+            /// Multiplies two lines in 023-form to get an element in 02345-form
+            fn mul_023_by_023(l0: &EvaluatedLine<$fp2>, l1: &EvaluatedLine<$fp2>) -> [$fp2; 5] {
+                #[cfg(not(target_os = "zkvm"))]
+                {
+                    let (b0, c0, b1, c1) = (&l0.b, &l0.c, &l1.b, &l1.c);
+                    // l0 * l1 = (c0c1 + xi) + (c0b1 + c1b0)w² + (c0 + c1)w³ + (b0b1)w⁴
+                    //           + (b0 + b1)w⁵
+                    [
+                        c0 * c1 + Self::XI,
+                        c0 * b1 + c1 * b0,
+                        c0 + c1,
+                        b0 * b1,
+                        b0 + b1,
+                    ]
+                }
+                #[cfg(target_os = "zkvm")]
+                unsafe {
+                    let mut uninit: core::mem::MaybeUninit<[$fp2; 5]> =
+                        core::mem::MaybeUninit::uninit();
+                    $crate::pairing::declare::mul_023_by_023_intrinsic::<$curve>(
+                        uninit.as_mut_ptr() as *mut u8,
+                        l0 as *const EvaluatedLine<$fp2> as *const u8,
+                        l1 as *const EvaluatedLine<$fp2> as *const u8,
+                    );
+                    uninit.assume_init()
+                }
+            }
+
+            /// Multiplies a line in 023-form with a Fp12 element to get an Fp12 element
+            fn mul_by_023(f: &$fp12, l: &EvaluatedLine<$fp2>) -> $fp12 {
+                <$fp12 as $crate::pairing::FromLineMType<$fp2>>::from_evaluated_line_m_type(
+                    l.clone(),
+                ) * f
+            }
+
+            /// Multiplies a line in 02345-form with a Fp12 element to get an Fp12 element
+            fn mul_by_02345(f: &$fp12, x: &[$fp2; 5]) -> $fp12 {
+                #[cfg(not(target_os = "zkvm"))]
+                {
+                    let (o0, o1, o2, o4, o5) = (&x[0], &x[1], &x[3], &x[2], &x[4]);
+                    let xi = &Self::XI;
+                    let (s0, s1, s2, s3, s4, s5) =
+                        (&f.c[0], &f.c[2], &f.c[4], &f.c[1], &f.c[3], &f.c[5]);
+
+                    let c00 = s0 * o0 + xi * &(s1 * o2 + s2 * o1 + s3 * o5 + s4 * o4);
+                    let c01 = s0 * o1 + s1 * o0 + xi * &(s2 * o2 + s4 * o5 + s5 * o4);
+                    let c02 = s0 * o2 + s1 * o1 + s2 * o0 + s3 * o4 + xi * &(s5 * o5);
+                    let c10 = s3 * o0 + xi * &(s1 * o5 + s2 * o4 + s4 * o2 + s5 * o1);
+                    let c11 = s0 * o4 + s3 * o1 + s4 * o0 + xi * &(s2 * o5 + s5 * o2);
+                    let c12 = s0 * o5 + s1 * o4 + s3 * o2 + s4 * o1 + s5 * o0;
+
+                    $fp12::new([c00, c10, c01, c11, c02, c12])
+                }
+                #[cfg(target_os = "zkvm")]
+                unsafe {
+                    let mut uninit: core::mem::MaybeUninit<$fp12> =
+                        core::mem::MaybeUninit::uninit();
+                    $crate::pairing::declare::mul_by_02345_intrinsic::<$curve>(
+                        uninit.as_mut_ptr() as *mut u8,
+                        f as *const $fp12 as *const u8,
+                        x as *const [$fp2; 5] as *const u8,
+                    );
+                    uninit.assume_init()
+                }
+            }
+        }
+    };
+
+    (@evaluate_lines D, $f:ident, $lines:ident) => {{
+        use $crate::pairing::LineMulDType;
+
+        let mut f = $f;
+        let mut lines = $lines;
+        if lines.len() % 2 == 1 {
+            f = Self::mul_by_013(&f, &lines.pop().unwrap());
+        }
+        for chunk in lines.chunks(2) {
+            if let [line0, line1] = chunk {
+                let prod = Self::mul_013_by_013(line0, line1);
+                f = Self::mul_by_01234(&f, &prod);
+            } else {
+                panic!("lines.len() % 2 should be 0 at this point");
+            }
+        }
+        f
+    }};
+
+    (@evaluate_lines M, $f:ident, $lines:ident) => {{
+        use $crate::pairing::LineMulMType;
+
+        let mut f = $f;
+        let mut lines = $lines;
+        if lines.len() % 2 == 1 {
+            f = Self::mul_by_023(&f, &lines.pop().unwrap());
+        }
+        for chunk in lines.chunks(2) {
+            if let [line0, line1] = chunk {
+                let prod = Self::mul_023_by_023(line0, line1);
+                f = Self::mul_by_02345(&f, &prod);
+            } else {
+                panic!("lines.len() % 2 should be 0 at this point");
+            }
+        }
+        f
+    }};
+
+    (
+        @post_loop Bn, $curve:ident, $fp:ident, $fp2:ident, $fp12:ident,
+        $seed_is_negative:expr
+    ) => {
+        /// Adds the lines through `π(Q)` and `-π²(Q)`, computed with the twisted Frobenius.
+        fn post_loop(
+            f: &$fp12,
+            Q_acc: Vec<AffinePoint<$fp2>>,
+            Q: &[AffinePoint<$fp2>],
+            _c: Option<$fp12>,
+            xy_fracs: &[($fp, $fp)],
+        ) -> ($fp12, Vec<AffinePoint<$fp2>>) {
+            const _: () = assert!(!$seed_is_negative, "negative BN seeds are unsupported");
+
+            let coeffs = &Self::FROBENIUS_COEFFS;
+            // xi^((p - 1) / 3), xi^((p - 1) / 2) and xi^((p^2 - 1) / 3)
+            let (x_to_q_minus_1_over_3, xi_to_q_minus_1_over_2, x_to_q_sq_minus_1_over_3) =
+                (&coeffs[1][1], &coeffs[1][2], &coeffs[2][1]);
+
+            // twisted frobenius calculation: `frob_p(twist(q)) = twist(q1)`
+            let q1_vec = Q
+                .iter()
+                .map(|Q| AffinePoint {
+                    x: Q.x.frobenius_map(1) * x_to_q_minus_1_over_3,
+                    y: Q.y.frobenius_map(1) * xi_to_q_minus_1_over_2,
+                })
+                .collect::<Vec<_>>();
+            let (Q_acc, lines_S_plus_Q) = Q_acc
+                .iter()
+                .zip(q1_vec.iter())
+                .map(|(Q_acc, q1)| Self::miller_add_step(Q_acc, q1))
+                .unzip::<_, _, Vec<_>, Vec<_>>();
+            let mut lines = lines_S_plus_Q
+                .iter()
+                .zip(xy_fracs)
+                .map(|(line, xy_frac)| line.evaluate(xy_frac))
+                .collect::<Vec<_>>();
+
+            // twisted frobenius calculation: `-frob_p^2(twist(q)) = twist(q2)`
+            let q2_vec = Q
+                .iter()
+                .map(|Q| AffinePoint {
+                    x: &Q.x * x_to_q_sq_minus_1_over_3,
+                    y: Q.y.clone(),
+                })
+                .collect::<Vec<_>>();
+            let (Q_acc, lines_S_plus_Q) = Q_acc
+                .iter()
+                .zip(q2_vec.iter())
+                .map(|(Q_acc, q2)| Self::miller_add_step(Q_acc, q2))
+                .unzip::<_, _, Vec<_>, Vec<_>>();
+            lines.extend(
+                lines_S_plus_Q
+                    .iter()
+                    .zip(xy_fracs)
+                    .map(|(line, xy_frac)| line.evaluate(xy_frac)),
+            );
+
+            (Self::evaluate_lines_vec(f.clone(), lines), Q_acc)
+        }
+    };
+
+    (
+        @post_loop Bls12, $curve:ident, $fp:ident, $fp2:ident, $fp12:ident,
+        $seed_is_negative:expr
+    ) => {
+        /// Conjugates `f` if the curve seed is negative.
+        fn post_loop(
+            f: &$fp12,
+            Q_acc: Vec<AffinePoint<$fp2>>,
+            _Q: &[AffinePoint<$fp2>],
+            _c: Option<$fp12>,
+            _xy_fracs: &[($fp, $fp)],
+        ) -> ($fp12, Vec<AffinePoint<$fp2>>) {
+            let mut f = f.clone();
+            if $seed_is_negative {
+                f.conjugate_assign();
+            }
+            (f, Q_acc)
+        }
+    };
+}
@@ -1,3 +1,4 @@
+pub mod declare;
 mod final_exp;
 mod line;
 mod miller_loop;
@@ -14,6 +15,7 @@ use openvm_algebra_guest::{
     Field, IntMod,
 };
 use openvm_ecc_guest::AffinePoint;
+pub use operations::fp12_invert_assign;
 #[allow(unused_imports)]
 pub(crate) use operations::*;
 pub use sextic_ext_field::*;
@@ -40,6 +42,17 @@ pub trait PairingCheck {
     type Fp2: Field + FieldExtension<Self::Fp> + ComplexConjugate;
     type Fp12: FieldExtension<Self::Fp2> + ComplexConjugate;
 
+    fn pairing_check(
+        P: &[AffinePoint<Self::Fp>],
+        Q: &[AffinePoint<Self::Fp2>],
+    ) -> Result<(), PairingCheckError>;
+}
+
+/// Curves whose host provides the final exponentiation hint, which [PairingCheck::pairing_check]
+/// uses to avoid computing the final exponentiation in the guest. Only the built-in curves
+/// implement it, curves declared with [pairing_declare](crate::pairing_declare) do not.
+#[allow(non_snake_case)]
+pub trait PairingCheckHint: PairingCheck {
     /// Given points P[], Q[], computes the multi-Miller loop and then returns
     /// the final exponentiation hint from Novakovic-Eagon <https://eprint.iacr.org/2024/640.pdf>.
     ///
@@ -48,11 +61,6 @@ pub trait PairingCheck {
         P: &[AffinePoint<Self::Fp>],
         Q: &[AffinePoint<Self::Fp2>],
     ) -> (Self::Fp12, Self::Fp12);
-
-    fn pairing_check(
-        P: &[AffinePoint<Self::Fp>],
-        Q: &[AffinePoint<Self::Fp2>],
-    ) -> Result<(), PairingCheckError>;
 }
 
 pub const fn shifted_funct7<P: PairingIntrinsics>(funct7: PairingBaseFunct7) -> usize {
@@ -5,10 +5,8 @@ use super::{
     fp6_sub_assign,
 };
 
-pub(crate) fn fp12_invert_assign<
-    Fp: IntMod + Field,
-    Fp2: Field + FieldExtension<Fp, Coeffs = [Fp; 2]>,
->(
+/// Inverts `c0 + c1 * w + ... + c5 * w^5` in `Fp2[w] / (w^6 - xi)` in place.
+pub fn fp12_invert_assign<Fp: IntMod + Field, Fp2: Field + FieldExtension<Fp, Coeffs = [Fp; 2]>>(
     c: &mut [Fp2; 6],
     xi: &Fp2,
 ) {
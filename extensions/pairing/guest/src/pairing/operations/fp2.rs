@@ -1,5 +1,7 @@
 use openvm_algebra_guest::{Field, IntMod};
 
+/// Inverts `c[0] + c[1] * u` in `Fp2 = Fp[u]/(u^2 + 1)`. Only valid for curves whose `Fp2`
+/// non-residue is `-1`; otherwise use the `Fp2` division.
 pub(crate) fn fp2_invert_assign<F: Field + IntMod>(c: &mut [F; 2]) {
     let mut t1 = c[1].clone();
     <F as Field>::square_assign(&mut t1);
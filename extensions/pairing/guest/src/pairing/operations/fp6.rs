@@ -1,7 +1,5 @@
 use openvm_algebra_guest::{field::FieldExtension, Field, IntMod};
 
-use super::fp2_invert_assign;
-
 pub(crate) fn fp6_invert_assign<
     Fp: IntMod + Field,
     Fp2: Field + FieldExtension<Fp, Coeffs = [Fp; 2]>,
@@ -44,9 +42,8 @@ pub(crate) fn fp6_invert_assign<
     tmp2 *= &c0;
     tmp1 += &tmp2;
 
-    let mut coeffs = tmp1.clone().to_coeffs();
-    fp2_invert_assign::<Fp>(&mut coeffs);
-    let tmp = Fp2::from_coeffs(coeffs);
+    // Invert through the `Fp2` division, which is correct for any `Fp2` non-residue.
+    let tmp = tmp1.invert();
     let mut tmp = [tmp.clone(), tmp.clone(), tmp.clone()];
     tmp[0] *= &c0;
     tmp[1] *= &c1;
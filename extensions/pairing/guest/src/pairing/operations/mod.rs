@@ -3,7 +3,6 @@ mod fp12;
 mod fp2;
 mod fp6;
 
-#[allow(unused_imports)]
-pub(crate) use fp12::*;
+pub use fp12::*;
 pub(crate) use fp2::*;
 pub(crate) use fp6::*;
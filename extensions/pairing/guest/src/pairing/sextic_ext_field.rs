@@ -69,10 +69,9 @@ impl<'a, F: Field> Sub<&'a SexticExtField<F>> for &SexticExtField<F> {
 ///
 /// When `target_os = "zkvm"`, this function calls an intrinsic instruction,
 /// which is assumed to be supported by the VM.
-#[allow(dead_code)]
 #[cfg(target_os = "zkvm")]
 #[inline(always)]
-pub(crate) fn sextic_tower_mul_intrinsic<P: super::PairingIntrinsics>(
+pub fn sextic_tower_mul_intrinsic<P: super::PairingIntrinsics>(
     dst: *mut u8,
     lhs: *const u8,
     rhs: *const u8,
@@ -87,9 +86,8 @@ pub(crate) fn sextic_tower_mul_intrinsic<P: super::PairingIntrinsics>(
     );
 }
 
-#[allow(dead_code)]
 #[cfg(not(target_os = "zkvm"))]
-pub(crate) fn sextic_tower_mul_host<F: Field>(
+pub fn sextic_tower_mul_host<F: Field>(
     lhs: &SexticExtField<F>,
     rhs: &SexticExtField<F>,
     xi: &F,
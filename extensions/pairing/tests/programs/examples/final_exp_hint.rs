@@ -9,7 +9,7 @@ use openvm::io::read;
 use openvm_ecc_guest::AffinePoint;
 use openvm_pairing_guest::{
     bls12_381::{Bls12_381, Fp, Fp12, Fp2},
-    pairing::PairingCheck,
+    pairing::PairingCheckHint,
 };
 
 openvm::entry!(main);
@@ -0,0 +1,616 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use hex_literal::hex;
+use openvm::io::read_vec;
+use openvm_algebra_complex_macros::{complex_declare, complex_impl_field};
+use openvm_algebra_guest::{field::FieldExtension, Field, IntMod};
+use openvm_ecc_guest::AffinePoint;
+use openvm_pairing_guest::{pairing::PairingCheck, pairing_declare};
+
+openvm::entry!(main);
+
+// BLS12-377 declared as a user-defined pairing curve. Its base field is 1 mod 4, so Fp2 is
+// defined by u^2 = -5 instead of u^2 = -1, and Fp12 = Fp2[w] / (w^6 - u).
+openvm_algebra_moduli_setup::moduli_declare! {
+    Bls12_377Fp { modulus = "0x01ae3a4617c510eac63b05c06ca1493b1a22d9f300f5138f1ef3622fba094800170b5d44300000008508c00000000001" },
+}
+
+openvm_algebra_moduli_setup::moduli_init! {
+    "0x01ae3a4617c510eac63b05c06ca1493b1a22d9f300f5138f1ef3622fba094800170b5d44300000008508c00000000001",
+}
+
+pub type Fp = Bls12_377Fp;
+
+impl Field for Fp {
+    type SelfRef<'a> = &'a Self;
+    const ZERO: Self = <Self as IntMod>::ZERO;
+    const ONE: Self = <Self as IntMod>::ONE;
+
+    fn double_assign(&mut self) {
+        IntMod::double_assign(self);
+    }
+
+    fn square_assign(&mut self) {
+        IntMod::square_assign(self);
+    }
+}
+
+complex_declare! {
+    Bls12_377Fp2 { mod_type = Fp, non_residue = -5 }
+}
+
+complex_impl_field! {
+    Bls12_377Fp2,
+}
+
+openvm_algebra_complex_macros::complex_init! {
+    Bls12_377Fp2 { mod_idx = 0 },
+}
+
+pub type Fp2 = Bls12_377Fp2;
+
+/// `FROBENIUS_COEFFS[i][j] = u^((j + 1) * (p^i - 1) / 6)`
+const FROBENIUS_COEFFS: [[Fp2; 5]; 12] = [
+    [
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+    ],
+    [
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "31204f10d1a938e98801eb58e56876b59d55aaa334bf81c68ebc4ef9e0458a5c917f568206e3c13396019c3975999a00"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0200000000c0088500000090cc17224500ec0d974713edc5ab4a59347daf9a61ecf614dd05afb3090000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "631c39f1ecd5766e8e7df2bf59845899620f6b43cf49e6bcc1ded10af5980340b1be791adb34c5c0ca376579400a6801"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0100000000c0088500000090cc17224500ec0d974713edc5ab4a59347daf9a61ecf614dd05afb3090000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "32fce9e01b2c3e85057c0767741be2e3c4b9c09f9a8a64f632228311145379e31f3f2398d451038d3436c93fcb70cd00"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+    ],
+    [
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0200000000c0088500000090cc17224500ec0d974713edc5ab4a59347daf9a61ecf614dd05afb3090000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0100000000c0088500000090cc17224500ec0d974713edc5ab4a59347daf9a61ecf614dd05afb3090000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0000000000c0088500000030445d0b17004809ba2f62f31e8f13f500f3d9221a3b49a16cc0053bc6ea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "ffffffffffffffffffffff9f7745e9d1ff5bfb22e84e0659e3c89bcc752a88b84e528c8fba5687bcea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0000000000000000000000a07745e9d1ff5bfb22e84e0659e3c89bcc752a88b84e528c8fba5687bcea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+    ],
+    [
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "631c39f1ecd5766e8e7df2bf59845899620f6b43cf49e6bcc1ded10af5980340b1be791adb34c5c0ca376579400a6801"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0000000000c0088500000030445d0b17004809ba2f62f31e8f13f500f3d9221a3b49a16cc0053bc6ea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "9ee3c60e13ea911672820d70ead8b27d9d389e7660180d62cd3423f6fd401fda898a2752e5d0750520d95f9e05304600"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "631c39f1ecd5766e8e7df2bf59845899620f6b43cf49e6bcc1ded10af5980340b1be791adb34c5c0ca376579400a6801"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+    ],
+    [
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0100000000c0088500000090cc17224500ec0d974713edc5ab4a59347daf9a61ecf614dd05afb3090000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "ffffffffffffffffffffff9f7745e9d1ff5bfb22e84e0659e3c89bcc752a88b84e528c8fba5687bcea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0100000000c0088500000090cc17224500ec0d974713edc5ab4a59347daf9a61ecf614dd05afb3090000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "ffffffffffffffffffffff9f7745e9d1ff5bfb22e84e0659e3c89bcc752a88b84e528c8fba5687bcea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+    ],
+    [
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "32fce9e01b2c3e85057c0767741be2e3c4b9c09f9a8a64f632228311145379e31f3f2398d451038d3436c93fcb70cd00"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0000000000000000000000a07745e9d1ff5bfb22e84e0659e3c89bcc752a88b84e528c8fba5687bcea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "631c39f1ecd5766e8e7df2bf59845899620f6b43cf49e6bcc1ded10af5980340b1be791adb34c5c0ca376579400a6801"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "ffffffffffffffffffffff9f7745e9d1ff5bfb22e84e0659e3c89bcc752a88b84e528c8fba5687bcea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "31204f10d1a938e98801eb58e56876b59d55aaa334bf81c68ebc4ef9e0458a5c917f568206e3c13396019c3975999a00"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+    ],
+    [
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0000000000c0088500000030445d0b17004809ba2f62f31e8f13f500f3d9221a3b49a16cc0053bc6ea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0000000000c0088500000030445d0b17004809ba2f62f31e8f13f500f3d9221a3b49a16cc0053bc6ea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0000000000c0088500000030445d0b17004809ba2f62f31e8f13f500f3d9221a3b49a16cc0053bc6ea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+    ],
+    [
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "d0dfb0ef2e16d09b77fe14d75ef4946162f25e16fba271580057a607129498bda9c94aeab9227992540f29ded0a01301"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0200000000c0088500000090cc17224500ec0d974713edc5ab4a59347daf9a61ecf614dd05afb3090000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "9ee3c60e13ea911672820d70ead8b27d9d389e7660180d62cd3423f6fd401fda898a2752e5d0750520d95f9e05304600"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0100000000c0088500000090cc17224500ec0d974713edc5ab4a59347daf9a61ecf614dd05afb3090000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "cf03161fe493cafffa83f8c8cf4129333b8e481a95d78e285cf171efde86a9361b0a7ed4ebb33739b6dafbd77ac9e000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+    ],
+    [
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "ffffffffffffffffffffff9f7745e9d1ff5bfb22e84e0659e3c89bcc752a88b84e528c8fba5687bcea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0100000000c0088500000090cc17224500ec0d974713edc5ab4a59347daf9a61ecf614dd05afb3090000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "ffffffffffffffffffffff9f7745e9d1ff5bfb22e84e0659e3c89bcc752a88b84e528c8fba5687bcea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0100000000c0088500000090cc17224500ec0d974713edc5ab4a59347daf9a61ecf614dd05afb3090000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+    ],
+    [
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "9ee3c60e13ea911672820d70ead8b27d9d389e7660180d62cd3423f6fd401fda898a2752e5d0750520d95f9e05304600"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0000000000c0088500000030445d0b17004809ba2f62f31e8f13f500f3d9221a3b49a16cc0053bc6ea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "631c39f1ecd5766e8e7df2bf59845899620f6b43cf49e6bcc1ded10af5980340b1be791adb34c5c0ca376579400a6801"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "9ee3c60e13ea911672820d70ead8b27d9d389e7660180d62cd3423f6fd401fda898a2752e5d0750520d95f9e05304600"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+    ],
+    [
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0000000000000000000000a07745e9d1ff5bfb22e84e0659e3c89bcc752a88b84e528c8fba5687bcea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "ffffffffffffffffffffff9f7745e9d1ff5bfb22e84e0659e3c89bcc752a88b84e528c8fba5687bcea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0000000000c0088500000030445d0b17004809ba2f62f31e8f13f500f3d9221a3b49a16cc0053bc6ea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0100000000c0088500000090cc17224500ec0d974713edc5ab4a59347daf9a61ecf614dd05afb3090000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0200000000c0088500000090cc17224500ec0d974713edc5ab4a59347daf9a61ecf614dd05afb3090000000000000000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+    ],
+    [
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "cf03161fe493cafffa83f8c8cf4129333b8e481a95d78e285cf171efde86a9361b0a7ed4ebb33739b6dafbd77ac9e000"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "0000000000000000000000a07745e9d1ff5bfb22e84e0659e3c89bcc752a88b84e528c8fba5687bcea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "9ee3c60e13ea911672820d70ead8b27d9d389e7660180d62cd3423f6fd401fda898a2752e5d0750520d95f9e05304600"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "ffffffffffffffffffffff9f7745e9d1ff5bfb22e84e0659e3c89bcc752a88b84e528c8fba5687bcea10c517463aae01"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+        Fp2 {
+            c0: Fp::from_const_bytes(hex!(
+                "d0dfb0ef2e16d09b77fe14d75ef4946162f25e16fba271580057a607129498bda9c94aeab9227992540f29ded0a01301"
+            )),
+            c1: Fp::from_const_bytes(hex!(
+                "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+            )),
+        },
+    ],
+];
+
+/// (p^4 - p^2 + 1) / r in big endian
+const FINAL_EXP_HARD_PART: [u8; 157] = hex!(
+    "6d616e43720774d7d810d5cbdf0576728e56efc3bf3b4074a5448da5cfbef98d"
+    "9c2cce3b25c548afd84225b34ccc65eca9c9678a845497a9781d8129911a8d88"
+    "9828282015fcd1c3fa1470f8b2d1eefd89535f9b5aaae0551dffcf72fb0bd948"
+    "d5f4548283abcaf63f0a34fcb827dc8f4db069bf65f4f6974b4ff0fa27719b83"
+    "4b6904468768c0eaeea22e68002e16ba88600000000000000000000001"
+);
+
+pairing_declare! {
+    Bls12_377 {
+        fp = Fp,
+        fp2 = Fp2,
+        fp12 = Bls12_377Fp12,
+        pairing_idx = 2,
+        xi = Fp2::new(Fp::from_const_u8(0), Fp::from_const_u8(1)),
+        frobenius_coeffs = FROBENIUS_COEFFS,
+        twist = D,
+        family = Bls12,
+        seed_abs = 0x8508c00000000001,
+        seed_is_negative = false,
+        pseudo_binary_encoding = &[
+            1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0, 1, 0, 0, 0, 0, 1, 0,
+            1, 0, 0, 0, 0, 1,
+        ],
+        final_exp_hard_part = &FINAL_EXP_HARD_PART,
+    }
+}
+
+pub fn main() {
+    setup_0();
+    setup_all_complex_extensions();
+
+    // io = [should_pass, P0, P1, Q0, Q1]
+    let io = read_vec();
+    let should_pass = io[0] != 0;
+    let io = &io[1..];
+    let p = io[..48 * 4]
+        .chunks_exact(48 * 2)
+        .map(|pt| AffinePoint::new(Fp::from_le_bytes(&pt[..48]), Fp::from_le_bytes(&pt[48..])))
+        .collect::<alloc::vec::Vec<_>>();
+    let q = io[48 * 4..48 * 12]
+        .chunks_exact(48 * 4)
+        .map(|pt| {
+            AffinePoint::new(
+                <Fp2 as FieldExtension<Fp>>::from_bytes(&pt[..96]),
+                <Fp2 as FieldExtension<Fp>>::from_bytes(&pt[96..]),
+            )
+        })
+        .collect::<alloc::vec::Vec<_>>();
+
+    assert_eq!(Bls12_377::pairing_check(&p, &q).is_ok(), should_pass);
+}
@@ -18,7 +18,7 @@ mod bn254 {
         AffinePoint,
     };
     use openvm_instructions::exe::VmExe;
-    use openvm_pairing_circuit::{PairingCurve, PairingExtension, Rv32PairingConfig};
+    use openvm_pairing_circuit::{PairingCurve, PairingExtension, Rv32PairingConfig, BN254_CONFIG};
     use openvm_pairing_guest::{
         affine_point::AffineCoords,
         bn254::BN254_MODULUS,
@@ -30,7 +30,10 @@ mod bn254 {
         Rv32ITranspilerExtension, Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
     };
     use openvm_stark_sdk::{openvm_stark_backend::p3_field::AbstractField, p3_baby_bear::BabyBear};
-    use openvm_toolchain_tests::{build_example_program_at_path_with_features, get_programs_dir};
+    use openvm_toolchain_tests::{
+        build_example_program_at_path, build_example_program_at_path_with_features,
+        get_programs_dir,
+    };
     use openvm_transpiler::{transpiler::Transpiler, FromElf};
     use rand::SeedableRng;
 
@@ -292,6 +295,61 @@ mod bn254 {
         air_test_with_min_segments(get_testing_config(), openvm_exe, vec![io_all], 1);
         Ok(())
     }
+
+    #[test]
+    fn test_bn254_pairing_declare() -> Result<()> {
+        let elf = build_example_program_at_path(get_programs_dir!(), "pairing_declare")?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(PairingTranspilerExtension)
+                .with_extension(ModularTranspilerExtension)
+                .with_extension(Fp2TranspilerExtension),
+        )?;
+        let mut config = get_testing_config();
+        config.pairing = PairingExtension::new(vec![PairingCurve::Custom {
+            pairing_idx: 2,
+            config: BN254_CONFIG.clone(),
+        }]);
+
+        let S = G1Affine::generator();
+        let Q = G2Affine::generator();
+        let S_mul = [
+            G1Affine::from(S * Fr::from(1)),
+            G1Affine::from(-S * Fr::from(2)),
+        ];
+        let Q_mul = [
+            G2Affine::from(Q * Fr::from(2)),
+            G2Affine::from(Q * Fr::from(1)),
+        ];
+
+        // e(S, 2Q) * e(-2S, Q) == 1, while e(S, 2Q) * e(-2S, 2Q) != 1
+        let inputs = [(true, Q_mul), (false, [Q_mul[0], Q_mul[0]])]
+            .into_iter()
+            .map(|(should_pass, q)| {
+                let s = S_mul
+                    .iter()
+                    .flat_map(|s| [s.x, s.y])
+                    .flat_map(|fp| fp.to_bytes());
+                let q = q
+                    .iter()
+                    .flat_map(|q| [q.x, q.y])
+                    .flat_map(|fp2| fp2.to_coeffs())
+                    .flat_map(|fp| fp.to_bytes());
+                iter::once(should_pass as u8)
+                    .chain(s)
+                    .chain(q)
+                    .map(AbstractField::from_canonical_u8)
+                    .collect::<Vec<_>>()
+            });
+        for io in inputs {
+            air_test_with_min_segments(config.clone(), openvm_exe.clone(), vec![io], 1);
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -645,3 +703,117 @@ mod bls12_381 {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod bls12_377 {
+    use eyre::Result;
+    use num_bigint_dig::BigUint;
+    use openvm_algebra_circuit::{Fp2Extension, ModularExtension};
+    use openvm_algebra_transpiler::{Fp2TranspilerExtension, ModularTranspilerExtension};
+    use openvm_circuit::{arch::SystemConfig, utils::air_test_with_min_segments};
+    use openvm_ecc_circuit::WeierstrassExtension;
+    use openvm_instructions::exe::VmExe;
+    use openvm_pairing_circuit::{
+        PairingCurve, PairingCurveConfig, PairingExtension, Rv32PairingConfig, TwistType,
+    };
+    use openvm_pairing_transpiler::PairingTranspilerExtension;
+    use openvm_rv32im_transpiler::{
+        Rv32ITranspilerExtension, Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
+    };
+    use openvm_stark_sdk::{openvm_stark_backend::p3_field::AbstractField, p3_baby_bear::BabyBear};
+    use openvm_toolchain_tests::{build_example_program_at_path, get_programs_dir};
+    use openvm_transpiler::{transpiler::Transpiler, FromElf};
+
+    type F = BabyBear;
+
+    const SEED_ABS: u64 = 0x8508c00000000001;
+    const MODULUS: &[u8] = b"01ae3a4617c510eac63b05c06ca1493b1a22d9f300f5138f1ef3622fba094800170b5d44300000008508c00000000001";
+    const ORDER: &[u8] = b"12ab655e9a2ca55660b44d1e5c37b00159aa76fed00000010a11800000000001";
+
+    // Points of the prime-order subgroups, as big-endian hex coordinates.
+    const P: [&[u8]; 2] = [
+        b"0088786fa870347117bcc7940800c8ab02d0fcdcc6eca07451ab07a5d68abf8d3a412745e6a003d4d013084b3bbf85de",
+        b"005dc34e59a3d4d5370885fac1ba1a6a0fb2ede762c729055c069208ffc76556618580443a40a5f086de7735cf228537",
+    ];
+    const NEG_2P: [&[u8]; 2] = [
+        b"005abadc9ac3674aa9d90c1df228976dd5aff11752cb56156f95761f008ec79a122317f6735bd800f04f2d26df825f7f",
+        b"000ff92b65ec14d305634d8dda2ab04282bbc453ec9d9da4adff35792c23f48c6c65d0b627da5df2fc697e386c390c3e",
+    ];
+    // [x.c0, x.c1, y.c0, y.c1] on the twist y^2 = x^3 + 1 / u
+    const Q: [&[u8]; 4] = [
+        b"006f72205595a839df693176b247c2fa251f7e02a29061e50540dc9e1c2bf1957bf1bab2288c257c2cb36b58f2418bc9",
+        b"0138c24b2b4e17888beed0a9802aac837cdea39890effe00072f754ecb0152dd6cb524f281298966dbaeca23d3e462b8",
+        b"016235fdea6c3faf2a83d3730f6ab2c033ef6c2739002946f7dc48e4688bca1af1c9b417d58220817e0dc644b5e7d916",
+        b"00707ac6cc7d192827fc54eb83267f3bed8511bd3c74f63a1ea75eabb66476769c8786f2af2a75166f33142379b4963c",
+    ];
+    const Q2: [&[u8]; 4] = [
+        b"0093660a719c197fb32dde0327ead3a456bfafde84471ed4cd9d719666b6b48b6fa52990b62f905cb7d8d5aa28b1d746",
+        b"017c2e424cbdbf1ee9b46e77501f7a42a33065ec23b70eaff560a3764ac1305d1de691498793026bb60e88cfe266bf9f",
+        b"0022dabf60554230d9cc68e25fa15db94d0393f460d1b7d8ba471ea106acf39cf970d933905769feaa770e238c851b40",
+        b"00f31c9ae49fddb964b0629cee5458a0ea70cc108558a553d017507d615c4b13dbe46e5909627431fae1bba486cd6750",
+    ];
+
+    fn from_hex(hex: &[u8]) -> BigUint {
+        BigUint::parse_bytes(hex, 16).unwrap()
+    }
+
+    /// Little-endian bytes of a coordinate, padded to the 48 byte field element size.
+    fn to_le_bytes(hex: &[u8]) -> Vec<u8> {
+        let mut bytes = from_hex(hex).to_bytes_le();
+        bytes.resize(48, 0);
+        bytes
+    }
+
+    pub fn get_testing_config() -> Rv32PairingConfig {
+        let modulus = from_hex(MODULUS);
+        let curve = PairingCurve::Custom {
+            pairing_idx: 2,
+            config: PairingCurveConfig {
+                modulus: modulus.clone(),
+                scalar: from_hex(ORDER),
+                b: BigUint::from(1u32),
+                fp2_non_residue: -5,
+                xi: [0, 1],
+                seed_abs: SEED_ABS,
+                pseudo_binary_encoding: (0..64).map(|i| ((SEED_ABS >> i) & 1) as i8).collect(),
+                twist: TwistType::D,
+            },
+        };
+        Rv32PairingConfig {
+            system: SystemConfig::default().with_continuations(),
+            base: Default::default(),
+            mul: Default::default(),
+            io: Default::default(),
+            modular: ModularExtension::new(vec![modulus.clone()]),
+            fp2: Fp2Extension::new(vec![modulus]).with_non_residues(vec![-5]),
+            weierstrass: WeierstrassExtension::new(vec![]),
+            pairing: PairingExtension::new(vec![curve]),
+        }
+    }
+
+    #[test]
+    fn test_bls12_377_pairing_declare() -> Result<()> {
+        let elf = build_example_program_at_path(get_programs_dir!(), "pairing_declare_bls12_377")?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(PairingTranspilerExtension)
+                .with_extension(ModularTranspilerExtension)
+                .with_extension(Fp2TranspilerExtension),
+        )?;
+
+        // e(P, 2Q) * e(-2P, Q) == 1, while e(P, 2Q) * e(-2P, 2Q) != 1
+        for (should_pass, q) in [(true, [Q2, Q]), (false, [Q2, Q2])] {
+            let io = std::iter::once(should_pass as u8)
+                .chain(P.into_iter().chain(NEG_2P).flat_map(to_le_bytes))
+                .chain(q.into_iter().flatten().flat_map(to_le_bytes))
+                .map(AbstractField::from_canonical_u8)
+                .collect::<Vec<_>>();
+            air_test_with_min_segments(get_testing_config(), openvm_exe.clone(), vec![io], 1);
+        }
+        Ok(())
+    }
+}
@@ -72,7 +72,7 @@ impl UsizeOpcode for Bls12381Fp12Opcode {
 #[derive(Copy, Clone, Debug, PartialEq, Eq, FromRepr)]
 #[repr(u16)]
 pub enum PairingPhantom {
-    /// Uses `b` to determine the curve: `b` is the discriminant of `PairingCurve` kind.
+    /// Uses `b` to determine the curve: `b` is the `pairing_idx` of the `PairingCurve`.
     /// Peeks at `[r32{0}(a)..r32{0}(a) + Fp::NUM_LIMBS * 12]_2` to get `f: Fp12` and then resets the hint stream to equal `final_exp_hint(f) = (residue_witness, scaling_factor): (Fp12, Fp12)` as `Fp::NUM_LIMBS * 12 * 2` bytes.
     HintFinalExp = 0x30,
 }
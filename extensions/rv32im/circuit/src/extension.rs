@@ -179,6 +179,7 @@ pub enum Rv32MExecutor<F: PrimeField32> {
 #[derive(ChipUsageGetter, Chip, InstructionExecutor, From, AnyEnum)]
 pub enum Rv32IoExecutor<F: PrimeField32> {
     HintStore(Rv32HintStoreChip<F>),
+    HintBuffer(Rv32HintBufferChip<F>),
 }
 
 #[derive(From, ChipUsageGetter, Chip, AnyEnum)]
@@ -371,6 +372,10 @@ impl<F: PrimeField32> VmExtension<F> for Rv32I {
             phantom::Rv32PrintStrSubEx,
             PhantomDiscriminant(Rv32Phantom::PrintStr as u16),
         )?;
+        builder.add_phantom_sub_executor(
+            phantom::Rv32HintRandomSubEx,
+            PhantomDiscriminant(Rv32Phantom::HintRandom as u16),
+        )?;
 
         Ok(inventory)
     }
@@ -505,7 +510,25 @@ impl<F: PrimeField32> VmExtension<F> for Rv32Io {
 
         inventory.add_executor(
             hintstore_chip,
-            Rv32HintStoreOpcode::iter().map(VmOpcode::with_default_offset),
+            [VmOpcode::with_default_offset(
+                Rv32HintStoreOpcode::HINT_STOREW,
+            )],
+        )?;
+
+        let mut hint_buffer_chip = Rv32HintBufferChip::new(
+            execution_bus,
+            program_bus,
+            memory_controller.clone(),
+            range_checker.clone(),
+            bitwise_lu_chip.clone(),
+            Rv32HintStoreOpcode::default_offset(),
+        );
+        hint_buffer_chip.set_streams(builder.streams().clone());
+        inventory.add_executor(
+            hint_buffer_chip,
+            [VmOpcode::with_default_offset(
+                Rv32HintStoreOpcode::HINT_BUFFER,
+            )],
         )?;
 
         Ok(inventory)
@@ -521,11 +544,13 @@ mod phantom {
     };
     use openvm_instructions::PhantomDiscriminant;
     use openvm_stark_backend::p3_field::{Field, PrimeField32};
+    use rand::RngCore;
 
     use crate::adapters::unsafe_read_rv32_register;
 
     pub struct Rv32HintInputSubEx;
     pub struct Rv32PrintStrSubEx;
+    pub struct Rv32HintRandomSubEx;
 
     impl<F: Field> PhantomSubExecutor<F> for Rv32HintInputSubEx {
         fn phantom_execute(
@@ -582,4 +607,25 @@ mod phantom {
             Ok(())
         }
     }
+
+    impl<F: PrimeField32> PhantomSubExecutor<F> for Rv32HintRandomSubEx {
+        fn phantom_execute(
+            &mut self,
+            memory: &MemoryController<F>,
+            streams: &mut Streams<F>,
+            _: PhantomDiscriminant,
+            a: F,
+            _: F,
+            _: u16,
+        ) -> eyre::Result<()> {
+            let num_words = unsafe_read_rv32_register(memory, a) as usize;
+            let mut bytes = vec![0u8; num_words * 4];
+            streams.rng.fill_bytes(&mut bytes);
+            streams.hint_stream.clear();
+            streams
+                .hint_stream
+                .extend(bytes.into_iter().map(F::from_canonical_u8));
+            Ok(())
+        }
+    }
 }
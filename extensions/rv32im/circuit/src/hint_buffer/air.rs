@@ -0,0 +1,193 @@
+use std::borrow::Borrow;
+
+use openvm_circuit::{
+    arch::ExecutionBridge,
+    system::memory::{offline_checker::MemoryBridge, MemoryAddress},
+};
+use openvm_circuit_primitives::{
+    bitwise_op_lookup::BitwiseOperationLookupBus, utils::not, var_range::VariableRangeCheckerBus,
+};
+use openvm_instructions::riscv::{RV32_MEMORY_AS, RV32_REGISTER_AS};
+use openvm_rv32im_transpiler::Rv32HintStoreOpcode;
+use openvm_stark_backend::{
+    interaction::InteractionBuilder,
+    p3_air::{Air, AirBuilder, BaseAir},
+    p3_field::{AbstractField, Field},
+    p3_matrix::Matrix,
+    rap::{BaseAirWithPublicValues, PartitionedBaseAir},
+};
+
+use super::{Rv32HintBufferCols, HINT_BUFFER_REGISTER_READS};
+use crate::adapters::{abstract_compose, RV32_CELL_BITS, RV32_REGISTER_NUM_LIMBS};
+
+#[derive(Clone, Copy, Debug, derive_new::new)]
+pub struct Rv32HintBufferAir {
+    pub execution_bridge: ExecutionBridge,
+    pub memory_bridge: MemoryBridge,
+    pub range_bus: VariableRangeCheckerBus,
+    pub bitwise_lookup_bus: BitwiseOperationLookupBus,
+    /// Maximum number of bits allowed for an address pointer
+    pub pointer_max_bits: usize,
+    pub(super) offset: usize,
+}
+
+impl<F: Field> BaseAir<F> for Rv32HintBufferAir {
+    fn width(&self) -> usize {
+        Rv32HintBufferCols::<F>::width()
+    }
+}
+
+impl<F: Field> BaseAirWithPublicValues<F> for Rv32HintBufferAir {}
+impl<F: Field> PartitionedBaseAir<F> for Rv32HintBufferAir {}
+
+impl<AB: InteractionBuilder> Air<AB> for Rv32HintBufferAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Rv32HintBufferCols<AB::Var> = (*local).borrow();
+        let next: &Rv32HintBufferCols<AB::Var> = (*next).borrow();
+
+        builder.assert_bool(local.is_valid);
+        builder.assert_bool(local.is_start);
+        builder.when(local.is_start).assert_one(local.is_valid);
+        // Valid rows come first, and an instruction cannot continue from a previous trace.
+        builder
+            .when_first_row()
+            .assert_eq(local.is_valid, local.is_start);
+        builder
+            .when_transition()
+            .when(not(local.is_valid))
+            .assert_zero(next.is_valid);
+
+        // Rows after the first row of an instruction write consecutive words.
+        let next_is_continuation = next.is_valid - next.is_start;
+        builder
+            .when_transition()
+            .when(next_is_continuation.clone())
+            .assert_eq(next.rem_words, local.rem_words - AB::F::ONE);
+        builder
+            .when_transition()
+            .when(next_is_continuation.clone())
+            .assert_eq(next.timestamp, local.timestamp + AB::F::ONE);
+        let limb_base = AB::F::from_canonical_u32(1 << (RV32_CELL_BITS * 2));
+        let local_mem_ptr = local.mem_ptr_limbs[0] + local.mem_ptr_limbs[1] * limb_base;
+        let next_mem_ptr = next.mem_ptr_limbs[0] + next.mem_ptr_limbs[1] * limb_base;
+        builder
+            .when_transition()
+            .when(next_is_continuation.clone())
+            .assert_eq(
+                next_mem_ptr,
+                local_mem_ptr.clone() + AB::F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS),
+            );
+        // The last row of an instruction writes the last word.
+        builder
+            .when_transition()
+            .when(local.is_valid - next_is_continuation)
+            .assert_one(local.rem_words);
+        builder
+            .when_last_row()
+            .when(local.is_valid)
+            .assert_one(local.rem_words);
+
+        self.eval_instruction(builder, local);
+
+        // Every valid row writes one word of range checked bytes.
+        for pair in local.data.chunks_exact(2) {
+            self.bitwise_lookup_bus
+                .send_range(pair[0], pair[1])
+                .eval(builder, local.is_valid);
+        }
+        self.range_bus
+            .range_check(local.mem_ptr_limbs[0], RV32_CELL_BITS * 2)
+            .eval(builder, local.is_valid);
+        self.range_bus
+            .range_check(
+                local.mem_ptr_limbs[1],
+                self.pointer_max_bits - RV32_CELL_BITS * 2,
+            )
+            .eval(builder, local.is_valid);
+        self.memory_bridge
+            .write(
+                MemoryAddress::new(AB::F::from_canonical_u32(RV32_MEMORY_AS), local_mem_ptr),
+                local.data,
+                local.timestamp,
+                &local.write_aux,
+            )
+            .eval(builder, local.is_valid);
+    }
+}
+
+impl Rv32HintBufferAir {
+    /// Constraints on the first row of an instruction: the register reads, the initial values of
+    /// the per-row state and the execution interaction.
+    fn eval_instruction<AB: InteractionBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Rv32HintBufferCols<AB::Var>,
+    ) {
+        let is_start = local.is_start;
+        let start_timestamp = local.from_state.timestamp;
+        let reg_as = AB::F::from_canonical_u32(RV32_REGISTER_AS);
+
+        self.memory_bridge
+            .read(
+                MemoryAddress::new(reg_as, local.num_words_ptr),
+                local.num_words_data,
+                start_timestamp,
+                &local.num_words_aux_cols,
+            )
+            .eval(builder, is_start);
+        self.memory_bridge
+            .read(
+                MemoryAddress::new(reg_as, local.mem_ptr_ptr),
+                local.mem_ptr_data,
+                start_timestamp + AB::F::ONE,
+                &local.mem_ptr_aux_cols,
+            )
+            .eval(builder, is_start);
+
+        // num_words < 2^pointer_max_bits, so it does not overflow the field.
+        self.range_bus
+            .range_check(
+                local.num_words_data[RV32_REGISTER_NUM_LIMBS - 1],
+                self.pointer_max_bits - RV32_CELL_BITS * (RV32_REGISTER_NUM_LIMBS - 1),
+            )
+            .eval(builder, is_start);
+
+        let mut when_start = builder.when(is_start);
+        when_start.assert_eq(
+            local.rem_words,
+            abstract_compose::<AB::Expr, _>(local.num_words_data),
+        );
+        when_start.assert_eq(
+            local.timestamp,
+            start_timestamp + AB::F::from_canonical_usize(HINT_BUFFER_REGISTER_READS),
+        );
+        let byte_base = AB::F::from_canonical_u32(1 << RV32_CELL_BITS);
+        when_start.assert_eq(
+            local.mem_ptr_limbs[0],
+            local.mem_ptr_data[0] + local.mem_ptr_data[1] * byte_base,
+        );
+        when_start.assert_eq(
+            local.mem_ptr_limbs[1],
+            local.mem_ptr_data[2] + local.mem_ptr_data[3] * byte_base,
+        );
+
+        self.execution_bridge
+            .execute_and_increment_pc(
+                AB::Expr::from_canonical_usize(
+                    Rv32HintStoreOpcode::HINT_BUFFER as usize + self.offset,
+                ),
+                [
+                    local.num_words_ptr.into(),
+                    local.mem_ptr_ptr.into(),
+                    AB::Expr::ZERO,
+                    AB::Expr::from_canonical_u32(RV32_REGISTER_AS),
+                    AB::Expr::from_canonical_u32(RV32_MEMORY_AS),
+                ],
+                local.from_state,
+                AB::Expr::from_canonical_usize(HINT_BUFFER_REGISTER_READS) + local.rem_words,
+            )
+            .eval(builder, is_start);
+    }
+}
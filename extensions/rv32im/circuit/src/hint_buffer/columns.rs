@@ -0,0 +1,33 @@
+use openvm_circuit::{
+    arch::ExecutionState,
+    system::memory::offline_checker::{MemoryReadAuxCols, MemoryWriteAuxCols},
+};
+use openvm_circuit_primitives_derive::AlignedBorrow;
+
+use crate::adapters::RV32_REGISTER_NUM_LIMBS;
+
+#[repr(C)]
+#[derive(Debug, AlignedBorrow)]
+pub struct Rv32HintBufferCols<T> {
+    /// Whether the row writes a word to memory.
+    pub is_valid: T,
+    /// Whether the row is the first row of an instruction. The columns up to `rem_words` are only
+    /// used on such rows.
+    pub is_start: T,
+    pub from_state: ExecutionState<T>,
+    pub num_words_ptr: T,
+    pub num_words_data: [T; RV32_REGISTER_NUM_LIMBS],
+    pub num_words_aux_cols: MemoryReadAuxCols<T, RV32_REGISTER_NUM_LIMBS>,
+    pub mem_ptr_ptr: T,
+    pub mem_ptr_data: [T; RV32_REGISTER_NUM_LIMBS],
+    pub mem_ptr_aux_cols: MemoryReadAuxCols<T, RV32_REGISTER_NUM_LIMBS>,
+
+    /// Number of words left to write in the instruction, including the word of this row.
+    pub rem_words: T,
+    /// Timestamp of the write in this row.
+    pub timestamp: T,
+    /// Pointer of the write in this row as two 16-bit limbs, needed to range check it.
+    pub mem_ptr_limbs: [T; 2],
+    pub data: [T; RV32_REGISTER_NUM_LIMBS],
+    pub write_aux: MemoryWriteAuxCols<T, RV32_REGISTER_NUM_LIMBS>,
+}
@@ -0,0 +1,173 @@
+//! Chip for the `HINT_BUFFER` instruction, which writes a contiguous buffer of words from the hint
+//! stream to memory. Each written word takes one row, and only the first row of an instruction
+//! reads registers and interacts with the execution bus.
+use std::sync::{Arc, OnceLock};
+
+use openvm_circuit::{
+    arch::{
+        ExecutionBridge, ExecutionBus, ExecutionError, ExecutionState, InstructionExecutor, Streams,
+    },
+    system::{
+        memory::{MemoryControllerRef, MemoryReadRecord, MemoryWriteRecord},
+        program::ProgramBus,
+    },
+};
+use openvm_circuit_primitives::{
+    bitwise_op_lookup::BitwiseOperationLookupChip, var_range::VariableRangeCheckerChip,
+};
+use openvm_instructions::{instruction::Instruction, program::DEFAULT_PC_STEP, UsizeOpcode};
+use openvm_rv32im_transpiler::Rv32HintStoreOpcode;
+use openvm_stark_backend::p3_field::PrimeField32;
+use parking_lot::Mutex;
+
+use crate::adapters::{read_rv32_register, RV32_CELL_BITS, RV32_REGISTER_NUM_LIMBS};
+
+mod air;
+mod columns;
+mod trace;
+
+pub use air::*;
+pub use columns::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Number of register reads at the start of each instruction: `num_words` and `mem_ptr`.
+pub(super) const HINT_BUFFER_REGISTER_READS: usize = 2;
+
+#[derive(Clone, Debug)]
+pub struct Rv32HintBufferRecord<F> {
+    pub from_state: ExecutionState<u32>,
+    pub num_words_read: MemoryReadRecord<F, RV32_REGISTER_NUM_LIMBS>,
+    pub mem_ptr_read: MemoryReadRecord<F, RV32_REGISTER_NUM_LIMBS>,
+    /// One write per word, at consecutive pointers starting from the value of `mem_ptr_read`.
+    pub writes: Vec<MemoryWriteRecord<F, RV32_REGISTER_NUM_LIMBS>>,
+}
+
+#[derive(Debug)]
+pub struct Rv32HintBufferChip<F: PrimeField32> {
+    pub air: Rv32HintBufferAir,
+    pub records: Vec<Rv32HintBufferRecord<F>>,
+    pub streams: OnceLock<Arc<Mutex<Streams<F>>>>,
+    pub memory_controller: MemoryControllerRef<F>,
+    pub range_checker_chip: Arc<VariableRangeCheckerChip>,
+    pub bitwise_lookup_chip: Arc<BitwiseOperationLookupChip<RV32_CELL_BITS>>,
+    /// Total number of words written, i.e. the number of used rows.
+    num_rows: usize,
+}
+
+impl<F: PrimeField32> Rv32HintBufferChip<F> {
+    pub fn new(
+        execution_bus: ExecutionBus,
+        program_bus: ProgramBus,
+        memory_controller: MemoryControllerRef<F>,
+        range_checker_chip: Arc<VariableRangeCheckerChip>,
+        bitwise_lookup_chip: Arc<BitwiseOperationLookupChip<RV32_CELL_BITS>>,
+        offset: usize,
+    ) -> Self {
+        let pointer_max_bits = memory_controller.borrow().mem_config().pointer_max_bits;
+        let memory_bridge = memory_controller.borrow().memory_bridge();
+        assert!(range_checker_chip.range_max_bits() >= 2 * RV32_CELL_BITS);
+        assert!(
+            pointer_max_bits >= 3 * RV32_CELL_BITS,
+            "pointer_max_bits={pointer_max_bits} needs to be large enough for high limb range check"
+        );
+        Self {
+            air: Rv32HintBufferAir::new(
+                ExecutionBridge::new(execution_bus, program_bus),
+                memory_bridge,
+                range_checker_chip.bus(),
+                bitwise_lookup_chip.bus(),
+                pointer_max_bits,
+                offset,
+            ),
+            records: Vec::new(),
+            streams: OnceLock::new(),
+            memory_controller,
+            range_checker_chip,
+            bitwise_lookup_chip,
+            num_rows: 0,
+        }
+    }
+
+    pub fn set_streams(&mut self, streams: Arc<Mutex<Streams<F>>>) {
+        self.streams.set(streams).unwrap();
+    }
+}
+
+impl<F: PrimeField32> InstructionExecutor<F> for Rv32HintBufferChip<F> {
+    fn execute(
+        &mut self,
+        instruction: Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> Result<ExecutionState<u32>, ExecutionError> {
+        let Instruction {
+            opcode, a, b, d, e, ..
+        } = instruction;
+        let local_opcode =
+            Rv32HintStoreOpcode::from_usize(opcode.local_opcode_idx(self.air.offset));
+        debug_assert_eq!(local_opcode, Rv32HintStoreOpcode::HINT_BUFFER);
+
+        let mut memory = self.memory_controller.borrow_mut();
+        debug_assert_eq!(from_state.timestamp, memory.timestamp());
+
+        let (num_words_read, num_words) = read_rv32_register(&mut memory, d, a);
+        let (mem_ptr_read, mem_ptr) = read_rv32_register(&mut memory, d, b);
+        let pointer_max_bits = self.air.pointer_max_bits;
+        // Both values are guest controlled, so an empty or out of bounds buffer fails execution
+        // instead of panicking.
+        if num_words == 0
+            || (mem_ptr as u64) + (num_words as u64) * (RV32_REGISTER_NUM_LIMBS as u64)
+                > (1u64 << pointer_max_bits)
+        {
+            return Err(ExecutionError::Fail { pc: from_state.pc });
+        }
+
+        let mut streams = self.streams.get().unwrap().lock();
+        let num_cells = num_words as usize * RV32_REGISTER_NUM_LIMBS;
+        if streams.hint_stream.len() < num_cells {
+            return Err(ExecutionError::HintOutOfBounds { pc: from_state.pc });
+        }
+
+        self.range_checker_chip.add_count(
+            num_words >> (RV32_CELL_BITS * (RV32_REGISTER_NUM_LIMBS - 1)),
+            pointer_max_bits - RV32_CELL_BITS * (RV32_REGISTER_NUM_LIMBS - 1),
+        );
+        let mut writes = Vec::with_capacity(num_words as usize);
+        for word_idx in 0..num_words {
+            let data: [F; RV32_REGISTER_NUM_LIMBS] =
+                std::array::from_fn(|_| streams.hint_stream.pop_front().unwrap());
+            for pair in data.chunks_exact(2) {
+                self.bitwise_lookup_chip
+                    .request_range(pair[0].as_canonical_u32(), pair[1].as_canonical_u32());
+            }
+            let ptr = mem_ptr + word_idx * RV32_REGISTER_NUM_LIMBS as u32;
+            self.range_checker_chip
+                .add_count(ptr & 0xffff, RV32_CELL_BITS * 2);
+            self.range_checker_chip.add_count(
+                ptr >> (RV32_CELL_BITS * 2),
+                pointer_max_bits - RV32_CELL_BITS * 2,
+            );
+            writes.push(memory.write(e, F::from_canonical_u32(ptr), data));
+        }
+
+        if !memory.is_pure_execution() {
+            self.num_rows += writes.len();
+            self.records.push(Rv32HintBufferRecord {
+                from_state,
+                num_words_read,
+                mem_ptr_read,
+                writes,
+            });
+        }
+
+        Ok(ExecutionState {
+            pc: from_state.pc + DEFAULT_PC_STEP,
+            timestamp: memory.timestamp(),
+        })
+    }
+
+    fn get_opcode_name(&self, _: usize) -> String {
+        "HINT_BUFFER".to_string()
+    }
+}
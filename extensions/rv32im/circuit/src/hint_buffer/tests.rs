@@ -0,0 +1,226 @@
+use std::{borrow::BorrowMut, sync::Arc};
+
+use openvm_circuit::{
+    arch::{
+        testing::{memory::gen_pointer, VmChipTestBuilder},
+        ExecutionError, ExecutionState, InstructionExecutor, Streams, BITWISE_OP_LOOKUP_BUS,
+    },
+    utils::u32_into_limbs,
+};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, BitwiseOperationLookupChip,
+};
+use openvm_instructions::{instruction::Instruction, UsizeOpcode, VmOpcode};
+use openvm_rv32im_transpiler::Rv32HintStoreOpcode::{self, *};
+use openvm_stark_backend::{
+    p3_field::{AbstractField, PrimeField32},
+    p3_matrix::{
+        dense::{DenseMatrix, RowMajorMatrix},
+        Matrix,
+    },
+    utils::disable_debug_builder,
+    verifier::VerificationError,
+};
+use openvm_stark_sdk::{config::setup_tracing, p3_baby_bear::BabyBear, utils::create_seeded_rng};
+use parking_lot::Mutex;
+use rand::{rngs::StdRng, Rng};
+
+use super::{Rv32HintBufferChip, Rv32HintBufferCols};
+use crate::adapters::{RV32_CELL_BITS, RV32_REGISTER_NUM_LIMBS};
+
+const MAX_WORDS: u32 = 16;
+
+type F = BabyBear;
+
+fn setup_chip(
+    tester: &mut VmChipTestBuilder<F>,
+) -> (
+    Rv32HintBufferChip<F>,
+    Arc<BitwiseOperationLookupChip<RV32_CELL_BITS>>,
+) {
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = Arc::new(BitwiseOperationLookupChip::<RV32_CELL_BITS>::new(
+        bitwise_bus,
+    ));
+    let range_checker_chip = tester.memory_controller().borrow().range_checker.clone();
+    let mut chip = Rv32HintBufferChip::<F>::new(
+        tester.execution_bus(),
+        tester.program_bus(),
+        tester.memory_controller(),
+        range_checker_chip,
+        bitwise_chip.clone(),
+        Rv32HintStoreOpcode::default_offset(),
+    );
+    chip.set_streams(Arc::new(Mutex::new(Streams::default())));
+    (chip, bitwise_chip)
+}
+
+fn set_and_execute(
+    tester: &mut VmChipTestBuilder<F>,
+    chip: &mut Rv32HintBufferChip<F>,
+    rng: &mut StdRng,
+    num_words: Option<u32>,
+) {
+    let num_words = num_words.unwrap_or(rng.gen_range(1..=MAX_WORDS));
+    let pointer_max_bits = tester
+        .memory_controller()
+        .borrow()
+        .mem_config()
+        .pointer_max_bits;
+    let mem_ptr = rng.gen_range(0..(1 << (pointer_max_bits - 2)) - num_words) << 2;
+
+    let a = gen_pointer(rng, 4);
+    let b = gen_pointer(rng, 4);
+    if a == b {
+        return set_and_execute(tester, chip, rng, Some(num_words));
+    }
+    tester.write(
+        1,
+        a,
+        u32_into_limbs::<RV32_REGISTER_NUM_LIMBS, RV32_CELL_BITS>(num_words)
+            .map(F::from_canonical_u32),
+    );
+    tester.write(
+        1,
+        b,
+        u32_into_limbs::<RV32_REGISTER_NUM_LIMBS, RV32_CELL_BITS>(mem_ptr)
+            .map(F::from_canonical_u32),
+    );
+
+    let data: Vec<[F; RV32_REGISTER_NUM_LIMBS]> = (0..num_words)
+        .map(|_| {
+            std::array::from_fn(|_| F::from_canonical_u32(rng.gen_range(0..(1 << RV32_CELL_BITS))))
+        })
+        .collect();
+    chip.streams
+        .get()
+        .unwrap()
+        .lock()
+        .hint_stream
+        .extend(data.iter().flatten().copied());
+
+    tester.execute(
+        chip,
+        Instruction::from_usize(VmOpcode::with_default_offset(HINT_BUFFER), [a, b, 0, 1, 2]),
+    );
+
+    for (i, word) in data.into_iter().enumerate() {
+        let ptr = mem_ptr as usize + i * RV32_REGISTER_NUM_LIMBS;
+        assert_eq!(word, tester.read::<RV32_REGISTER_NUM_LIMBS>(2, ptr));
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////////////
+/// POSITIVE TESTS
+///
+/// Randomly generate computations and execute, ensuring that the generated trace
+/// passes all constraints.
+///////////////////////////////////////////////////////////////////////////////////////
+#[test]
+fn rand_hint_buffer_test() {
+    setup_tracing();
+    let mut rng = create_seeded_rng();
+    let mut tester = VmChipTestBuilder::default();
+    let (mut chip, bitwise_chip) = setup_chip(&mut tester);
+
+    let num_tests: usize = 20;
+    for _ in 0..num_tests {
+        set_and_execute(&mut tester, &mut chip, &mut rng, None);
+    }
+    // A single word buffer is both the first and the last row of its instruction.
+    set_and_execute(&mut tester, &mut chip, &mut rng, Some(1));
+
+    let tester = tester.build().load(chip).load(bitwise_chip).finalize();
+    tester.simple_test().expect("Verification failed");
+}
+
+//////////////////////////////////////////////////////////////////////////////////////
+// NEGATIVE TESTS
+//
+// Given a fake trace of a single operation, setup a chip and run the test. We replace
+// a part of the trace and check that the chip throws the expected error.
+//////////////////////////////////////////////////////////////////////////////////////
+
+fn run_negative_hint_buffer_test(
+    modify_row: impl Fn(&mut Rv32HintBufferCols<F>),
+    row_idx: usize,
+    expected_error: VerificationError,
+) {
+    let mut rng = create_seeded_rng();
+    let mut tester = VmChipTestBuilder::default();
+    let (mut chip, bitwise_chip) = setup_chip(&mut tester);
+
+    set_and_execute(&mut tester, &mut chip, &mut rng, Some(4));
+
+    let modify_trace = |trace: &mut DenseMatrix<BabyBear>| {
+        let mut values = trace.values.clone();
+        let width = trace.width();
+        let row: &mut Rv32HintBufferCols<F> =
+            values[row_idx * width..(row_idx + 1) * width].borrow_mut();
+        modify_row(row);
+        *trace = RowMajorMatrix::new(values, width);
+    };
+
+    disable_debug_builder();
+    let tester = tester
+        .build()
+        .load_and_prank_trace(chip, modify_trace)
+        .load(bitwise_chip)
+        .finalize();
+    tester.simple_test_with_expected_error(expected_error);
+}
+
+#[test]
+fn negative_hint_buffer_data_test() {
+    run_negative_hint_buffer_test(
+        |row| row.data[0] = F::from_canonical_u32(280),
+        1,
+        VerificationError::ChallengePhaseError,
+    );
+}
+
+#[test]
+fn negative_hint_buffer_write_test() {
+    // Memory must end up with the word that was taken from the hint stream.
+    run_negative_hint_buffer_test(
+        |row| row.data[2] = F::from_canonical_u32((row.data[2].as_canonical_u32() + 1) % 256),
+        2,
+        VerificationError::ChallengePhaseError,
+    );
+}
+
+#[test]
+fn hint_buffer_out_of_bounds_fails_test() {
+    let mut tester = VmChipTestBuilder::default();
+    let (mut chip, _) = setup_chip(&mut tester);
+    let pointer_max_bits = tester
+        .memory_controller()
+        .borrow()
+        .mem_config()
+        .pointer_max_bits;
+
+    // The last word would end past the addressable memory.
+    let (a, b) = (0, 4);
+    let num_words = 2;
+    let mem_ptr = (1u32 << pointer_max_bits) - RV32_REGISTER_NUM_LIMBS as u32;
+    tester.write(
+        1,
+        a,
+        u32_into_limbs::<RV32_REGISTER_NUM_LIMBS, RV32_CELL_BITS>(num_words)
+            .map(F::from_canonical_u32),
+    );
+    tester.write(
+        1,
+        b,
+        u32_into_limbs::<RV32_REGISTER_NUM_LIMBS, RV32_CELL_BITS>(mem_ptr)
+            .map(F::from_canonical_u32),
+    );
+
+    let pc = 0u32;
+    let timestamp = tester.memory_controller().borrow().timestamp();
+    let result = chip.execute(
+        Instruction::from_usize(VmOpcode::with_default_offset(HINT_BUFFER), [a, b, 0, 1, 2]),
+        ExecutionState::new(pc, timestamp),
+    );
+    assert!(matches!(result, Err(ExecutionError::Fail { pc: 0 })));
+}
@@ -0,0 +1,77 @@
+use std::{borrow::BorrowMut, sync::Arc};
+
+use openvm_stark_backend::{
+    config::{StarkGenericConfig, Val},
+    p3_air::BaseAir,
+    p3_field::{AbstractField, PrimeField32},
+    p3_matrix::dense::RowMajorMatrix,
+    prover::types::AirProofInput,
+    rap::{get_air_name, AnyRap},
+    Chip, ChipUsageGetter,
+};
+
+use super::{Rv32HintBufferChip, Rv32HintBufferCols};
+use crate::adapters::RV32_CELL_BITS;
+
+impl<SC: StarkGenericConfig> Chip<SC> for Rv32HintBufferChip<Val<SC>>
+where
+    Val<SC>: PrimeField32,
+{
+    fn air(&self) -> Arc<dyn AnyRap<SC>> {
+        Arc::new(self.air)
+    }
+
+    fn generate_air_proof_input(self) -> AirProofInput<SC> {
+        let air = self.air();
+        let width = self.trace_width();
+        let height = self.num_rows.next_power_of_two();
+        let mut values = Val::<SC>::zero_vec(height * width);
+        let aux_cols_factory = self.memory_controller.borrow().aux_cols_factory();
+
+        let mut rows = values.chunks_exact_mut(width);
+        for record in self.records {
+            let num_words = record.writes.len();
+            for (i, write) in record.writes.into_iter().enumerate() {
+                let row: &mut Rv32HintBufferCols<Val<SC>> = rows.next().unwrap().borrow_mut();
+                row.is_valid = Val::<SC>::ONE;
+                if i == 0 {
+                    row.is_start = Val::<SC>::ONE;
+                    row.from_state = record.from_state.map(Val::<SC>::from_canonical_u32);
+                    row.num_words_ptr = record.num_words_read.pointer;
+                    row.num_words_data = record.num_words_read.data;
+                    row.num_words_aux_cols =
+                        aux_cols_factory.make_read_aux_cols(record.num_words_read);
+                    row.mem_ptr_ptr = record.mem_ptr_read.pointer;
+                    row.mem_ptr_data = record.mem_ptr_read.data;
+                    row.mem_ptr_aux_cols = aux_cols_factory.make_read_aux_cols(record.mem_ptr_read);
+                }
+                row.rem_words = Val::<SC>::from_canonical_usize(num_words - i);
+                row.timestamp = Val::<SC>::from_canonical_u32(write.timestamp);
+                let ptr = write.pointer.as_canonical_u32();
+                row.mem_ptr_limbs = [
+                    ptr & ((1 << (RV32_CELL_BITS * 2)) - 1),
+                    ptr >> (RV32_CELL_BITS * 2),
+                ]
+                .map(Val::<SC>::from_canonical_u32);
+                row.data = write.data;
+                row.write_aux = aux_cols_factory.make_write_aux_cols(write);
+            }
+        }
+
+        AirProofInput::simple_no_pis(air, RowMajorMatrix::new(values, width))
+    }
+}
+
+impl<F: PrimeField32> ChipUsageGetter for Rv32HintBufferChip<F> {
+    fn air_name(&self) -> String {
+        get_air_name(&self.air)
+    }
+
+    fn current_trace_height(&self) -> usize {
+        self.num_rows
+    }
+
+    fn trace_width(&self) -> usize {
+        BaseAir::<F>::width(&self.air)
+    }
+}
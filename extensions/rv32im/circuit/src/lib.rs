@@ -5,6 +5,7 @@ mod base_alu;
 mod branch_eq;
 mod branch_lt;
 mod divrem;
+mod hint_buffer;
 mod hintstore;
 mod jal_lui;
 mod jalr;
@@ -20,6 +21,7 @@ pub use base_alu::*;
 pub use branch_eq::*;
 pub use branch_lt::*;
 pub use divrem::*;
+pub use hint_buffer::*;
 pub use hintstore::*;
 pub use jal_lui::*;
 pub use jalr::*;
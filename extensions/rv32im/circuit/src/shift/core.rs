@@ -173,11 +173,17 @@ where
 
         // Check that bit_shift and limb_shift are correct.
         let num_bits = AB::F::from_canonical_usize(NUM_LIMBS * LIMB_BITS);
+        let num_shift_limbs = num_shift_limbs::<NUM_LIMBS, LIMB_BITS>();
+        let shift_operand = (0..num_shift_limbs).fold(AB::Expr::ZERO, |acc, i| {
+            acc + c[i] * AB::F::from_canonical_usize(1 << (i * LIMB_BITS))
+        });
         self.range_bus
             .range_check(
-                (c[0] - limb_shift * AB::F::from_canonical_usize(LIMB_BITS) - bit_shift.clone())
+                (shift_operand
+                    - limb_shift * AB::F::from_canonical_usize(LIMB_BITS)
+                    - bit_shift.clone())
                     * num_bits.inverse(),
-                LIMB_BITS - ((NUM_LIMBS * LIMB_BITS) as u32).ilog2() as usize,
+                num_shift_limbs * LIMB_BITS - ((NUM_LIMBS * LIMB_BITS) as u32).ilog2() as usize,
             )
             .eval(builder, is_valid.clone());
 
@@ -256,6 +262,10 @@ impl<const NUM_LIMBS: usize, const LIMB_BITS: usize> ShiftCoreChip<NUM_LIMBS, LI
         offset: usize,
     ) -> Self {
         assert_eq!(NUM_LIMBS % 2, 0, "Number of limbs must be divisible by 2");
+        assert!(
+            (NUM_LIMBS * LIMB_BITS).is_power_of_two(),
+            "Number of bits must be a power of 2"
+        );
         Self {
             air: ShiftCoreAir {
                 bitwise_lookup_bus: bitwise_lookup_chip.bus(),
@@ -305,9 +315,11 @@ where
         }
 
         let num_bits_log = (NUM_LIMBS * LIMB_BITS).ilog2();
+        let num_shift_limbs = num_shift_limbs::<NUM_LIMBS, LIMB_BITS>();
         self.range_checker_chip.add_count(
-            (((c[0] as usize) - bit_shift - limb_shift * LIMB_BITS) >> num_bits_log) as u32,
-            LIMB_BITS - num_bits_log as usize,
+            ((shift_operand::<NUM_LIMBS, LIMB_BITS>(&c) - bit_shift - limb_shift * LIMB_BITS)
+                >> num_bits_log) as u32,
+            num_shift_limbs * LIMB_BITS - num_bits_log as usize,
         );
 
         for i in 0..(NUM_LIMBS / 2) {
@@ -422,8 +434,19 @@ fn run_shift_right<const NUM_LIMBS: usize, const LIMB_BITS: usize>(
 }
 
 fn get_shift<const NUM_LIMBS: usize, const LIMB_BITS: usize>(y: &[u32]) -> (usize, usize) {
-    // We assume `NUM_LIMBS * LIMB_BITS <= 2^LIMB_BITS` so so the shift is defined
-    // entirely in y[0].
-    let shift = (y[0] as usize) % (NUM_LIMBS * LIMB_BITS);
+    let shift = shift_operand::<NUM_LIMBS, LIMB_BITS>(y) % (NUM_LIMBS * LIMB_BITS);
     (shift / LIMB_BITS, shift % LIMB_BITS)
 }
+
+/// Number of low limbs of `c` that the shift amount is computed from. This is `1` as long as
+/// `NUM_LIMBS * LIMB_BITS <= 2^LIMB_BITS`, and more limbs are needed for wider integers.
+fn num_shift_limbs<const NUM_LIMBS: usize, const LIMB_BITS: usize>() -> usize {
+    ((NUM_LIMBS * LIMB_BITS).ilog2() as usize).div_ceil(LIMB_BITS)
+}
+
+/// Value of the low limbs of `y` that the shift amount is computed from.
+fn shift_operand<const NUM_LIMBS: usize, const LIMB_BITS: usize>(y: &[u32]) -> usize {
+    (0..num_shift_limbs::<NUM_LIMBS, LIMB_BITS>())
+        .map(|i| (y[i] as usize) << (i * LIMB_BITS))
+        .sum()
+}
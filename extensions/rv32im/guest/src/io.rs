@@ -15,6 +15,21 @@ macro_rules! hint_store_u32 {
     };
 }
 
+/// Store the next `4 * len` bytes from the hint stream to [[rd]..[rd] + 4 * len]_2.
+/// `len` must be nonzero.
+#[macro_export]
+macro_rules! hint_buffer_u32 {
+    ($x:expr, $len:expr) => {
+        openvm_platform::custom_insn_i!(
+            openvm_rv32im_guest::SYSTEM_OPCODE,
+            openvm_rv32im_guest::HINT_BUFFER_FUNCT3,
+            $x,
+            $len,
+            openvm_rv32im_guest::HINT_BUFFER_IMM
+        )
+    };
+}
+
 /// Reset the hint stream with the next hint.
 #[inline(always)]
 pub fn hint_input() {
@@ -27,6 +42,18 @@ pub fn hint_input() {
     );
 }
 
+/// Reset the hint stream with `4 * num_words` bytes from the host's random number generator.
+#[inline(always)]
+pub fn hint_random(num_words: usize) {
+    openvm_platform::custom_insn_i!(
+        SYSTEM_OPCODE,
+        PHANTOM_FUNCT3,
+        num_words,
+        "x0",
+        PhantomImm::HintRandom as u16
+    );
+}
+
 /// Store rs1 to [[rd] + imm]_2.
 #[macro_export]
 macro_rules! reveal {
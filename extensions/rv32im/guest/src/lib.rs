@@ -19,10 +19,17 @@ pub const REVEAL_FUNCT3: u8 = 0b010;
 pub const PHANTOM_FUNCT3: u8 = 0b011;
 pub const CSRRW_FUNCT3: u8 = 0b001;
 
+pub const HINT_BUFFER_FUNCT3: u8 = PHANTOM_FUNCT3;
+
+/// imm of the hint buffer instruction. It shares [HINT_BUFFER_FUNCT3] with the phantom
+/// instructions, so it must not collide with any [PhantomImm].
+pub const HINT_BUFFER_IMM: u16 = 0x100;
+
 /// imm options for system phantom instructions
 #[derive(Debug, Copy, Clone, PartialEq, Eq, FromRepr)]
 #[repr(u16)]
 pub enum PhantomImm {
     HintInput = 0,
     PrintStr,
+    HintRandom,
 }
@@ -0,0 +1,17 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+use openvm::io::read_vec;
+
+openvm::entry!(main);
+
+#[repr(align(4))]
+struct Aligned([u8; 32]);
+
+pub fn main() {
+    // The host passes the bytes it expects its seeded RNG to produce.
+    let expected = read_vec();
+    let mut buf = Aligned([0u8; 32]);
+    if getrandom::getrandom(&mut buf.0).is_err() || buf.0[..] != expected[..] {
+        openvm::process::panic();
+    }
+}
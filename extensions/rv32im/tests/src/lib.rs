@@ -2,7 +2,7 @@
 mod tests {
     use eyre::Result;
     use openvm_circuit::{
-        arch::{hasher::poseidon2::vm_poseidon2_hasher, VmExecutor},
+        arch::{hasher::poseidon2::vm_poseidon2_hasher, Streams, VmExecutor},
         system::memory::tree::public_values::UserPublicValuesProof,
         utils::{air_test, air_test_with_min_segments},
     };
@@ -19,6 +19,10 @@ mod tests {
     use openvm_transpiler::{
         elf::ELF_DEFAULT_MAX_NUM_PUBLIC_VALUES, transpiler::Transpiler, FromElf,
     };
+    use rand_chacha::{
+        rand_core::{RngCore, SeedableRng},
+        ChaCha20Rng,
+    };
     use test_case::test_case;
 
     type F = BabyBear;
@@ -166,6 +170,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_getrandom() -> Result<()> {
+        let elf = build_example_program_at_path_with_features(
+            get_programs_dir!(),
+            "getrandom",
+            ["getrandom"],
+        )?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        let config = Rv32IConfig::default();
+
+        let seed = 42;
+        let mut expected = [0u8; 32];
+        ChaCha20Rng::seed_from_u64(seed).fill_bytes(&mut expected);
+        let input = expected.into_iter().map(F::from_canonical_u8).collect();
+        let streams = Streams::new(vec![input]).with_rng_seed(seed);
+        air_test_with_min_segments(config.clone(), exe.clone(), streams.clone(), 1);
+
+        // A different seed produces different bytes, which the guest rejects.
+        let executor = VmExecutor::<F, _>::new(config);
+        assert!(executor
+            .execute(exe, streams.with_rng_seed(seed + 1))
+            .is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_tiny_mem_test() -> Result<()> {
         let elf = build_example_program_at_path_with_features(
@@ -166,6 +166,7 @@ pub enum DivRemOpcode {
 #[allow(non_camel_case_types)]
 pub enum Rv32HintStoreOpcode {
     HINT_STOREW,
+    HINT_BUFFER,
 }
 
 // =================================================================================================
@@ -179,4 +180,6 @@ pub enum Rv32Phantom {
     HintInput = 0x20,
     /// Peek string from memory and print it to stdout.
     PrintStr,
+    /// Prepare the given number of words from the host random number generator for hinting.
+    HintRandom,
 }
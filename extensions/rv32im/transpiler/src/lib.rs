@@ -5,8 +5,8 @@ use openvm_instructions::{
     VmOpcode,
 };
 use openvm_rv32im_guest::{
-    PhantomImm, CSRRW_FUNCT3, CSR_OPCODE, HINT_STORE_W_FUNCT3, PHANTOM_FUNCT3, REVEAL_FUNCT3,
-    RV32M_FUNCT7, RV32_ALU_OPCODE, SYSTEM_OPCODE, TERMINATE_FUNCT3,
+    PhantomImm, CSRRW_FUNCT3, CSR_OPCODE, HINT_BUFFER_FUNCT3, HINT_BUFFER_IMM, HINT_STORE_W_FUNCT3,
+    PHANTOM_FUNCT3, REVEAL_FUNCT3, RV32M_FUNCT7, RV32_ALU_OPCODE, SYSTEM_OPCODE, TERMINATE_FUNCT3,
 };
 use openvm_stark_backend::p3_field::PrimeField32;
 use openvm_transpiler::{
@@ -84,6 +84,12 @@ impl<F: PrimeField32> TranspilerExtension<F> for Rv32ITranspilerExtension {
                         F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rs1),
                         0,
                     ),
+                    PhantomImm::HintRandom => Instruction::phantom(
+                        PhantomDiscriminant(Rv32Phantom::HintRandom as u16),
+                        F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rd),
+                        F::ZERO,
+                        0,
+                    ),
                 })
             }
             (RV32_ALU_OPCODE, _) => {
@@ -142,7 +148,8 @@ impl<F: PrimeField32> TranspilerExtension<F> for Rv32IoTranspilerExtension {
         if opcode != SYSTEM_OPCODE {
             return None;
         }
-        if funct3 != HINT_STORE_W_FUNCT3 && funct3 != REVEAL_FUNCT3 {
+        if funct3 != HINT_STORE_W_FUNCT3 && funct3 != HINT_BUFFER_FUNCT3 && funct3 != REVEAL_FUNCT3
+        {
             return None;
         }
 
@@ -159,6 +166,22 @@ impl<F: PrimeField32> TranspilerExtension<F> for Rv32IoTranspilerExtension {
                     2,
                 ))
             }
+            HINT_BUFFER_FUNCT3 => {
+                let dec_insn = IType::new(instruction_u32);
+                // The other imm values of this funct3 are phantom instructions
+                if dec_insn.imm as u16 != HINT_BUFFER_IMM {
+                    return None;
+                }
+                // HINT_BUFFER writes [rs1] words to memory starting at [rd]
+                Some(Instruction::from_isize(
+                    VmOpcode::with_default_offset(Rv32HintStoreOpcode::HINT_BUFFER),
+                    (RV32_REGISTER_NUM_LIMBS * dec_insn.rs1) as isize,
+                    (RV32_REGISTER_NUM_LIMBS * dec_insn.rd) as isize,
+                    0,
+                    1,
+                    2,
+                ))
+            }
             REVEAL_FUNCT3 => {
                 let dec_insn = IType::new(instruction_u32);
                 let imm_u16 = (dec_insn.imm as u32) & 0xffff;
@@ -0,0 +1,483 @@
+use std::{array::from_fn, borrow::Borrow};
+
+use itertools::{izip, Itertools};
+use openvm_circuit::{
+    arch::{ExecutionBridge, ExecutionState},
+    system::memory::{offline_checker::MemoryBridge, MemoryAddress},
+};
+use openvm_circuit_primitives::{
+    bitwise_op_lookup::BitwiseOperationLookupBus,
+    utils::{assert_array_eq, not, select},
+};
+use openvm_instructions::riscv::{RV32_CELL_BITS, RV32_REGISTER_NUM_LIMBS};
+use openvm_rv32im_circuit::adapters::abstract_compose;
+use openvm_sha256_transpiler::Rv32Sha256Opcode;
+use openvm_stark_backend::{
+    interaction::InteractionBuilder,
+    p3_air::{Air, AirBuilder, BaseAir},
+    p3_field::AbstractField,
+    p3_matrix::Matrix,
+    rap::{BaseAirWithPublicValues, PartitionedBaseAir},
+};
+
+use super::{
+    columns::{Sha256VmCols, NUM_SHA256_VM_COLS},
+    utils::SHA256_K,
+    SHA256_BLOCK_WORDS, SHA256_NUM_ROUNDS, SHA256_TIMESTAMP_DELTA, SHA256_WORD_BITS,
+    SHA256_WORD_SIZE,
+};
+
+#[derive(Clone, Copy, Debug, derive_new::new)]
+pub struct Sha256VmAir {
+    pub execution_bridge: ExecutionBridge,
+    pub memory_bridge: MemoryBridge,
+    /// Bus to send 8-bit range checks to.
+    pub bitwise_lookup_bus: BitwiseOperationLookupBus,
+    /// Maximum number of bits allowed for an address pointer
+    pub ptr_max_bits: usize,
+    pub(super) offset: usize,
+}
+
+impl<F> BaseAirWithPublicValues<F> for Sha256VmAir {}
+impl<F> PartitionedBaseAir<F> for Sha256VmAir {}
+impl<F> BaseAir<F> for Sha256VmAir {
+    fn width(&self) -> usize {
+        NUM_SHA256_VM_COLS
+    }
+}
+
+impl<AB: InteractionBuilder> Air<AB> for Sha256VmAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Sha256VmCols<AB::Var> = (*local).borrow();
+        let next: &Sha256VmCols<AB::Var> = (*next).borrow();
+
+        self.constrain_flags(builder, local, next);
+        self.eval_round_functions(builder, local);
+        self.constrain_round_transition(builder, local, next);
+        self.constrain_consistency_across_rounds(builder, local, next);
+
+        // Interactions:
+        let start_read_timestamp = self.eval_instruction(builder, local);
+        let start_write_timestamp = self.constrain_input_read(builder, local, start_read_timestamp);
+        self.constrain_output_write(builder, local, start_write_timestamp);
+    }
+}
+
+impl Sha256VmAir {
+    /// A block is [SHA256_NUM_ROUNDS] round rows followed by a digest row. Blocks are contiguous
+    /// from the first row and followed by dummy rows.
+    pub fn constrain_flags<AB: AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Sha256VmCols<AB::Var>,
+        next: &Sha256VmCols<AB::Var>,
+    ) {
+        let flags = &local.flags;
+        for &flag in flags.round_flags.iter() {
+            builder.assert_bool(flag);
+        }
+        builder.assert_bool(flags.is_digest_row);
+        // At most one flag is set
+        builder.assert_bool(local.is_enabled::<AB::Expr>());
+
+        builder
+            .when_first_row()
+            .assert_eq(local.is_first_round(), local.is_enabled::<AB::Expr>());
+
+        let mut transition_builder = builder.when_transition();
+        for t in 1..SHA256_NUM_ROUNDS {
+            transition_builder.assert_eq(next.flags.round_flags[t], flags.round_flags[t - 1]);
+        }
+        transition_builder.assert_eq(
+            next.flags.is_digest_row,
+            flags.round_flags[SHA256_NUM_ROUNDS - 1],
+        );
+        // A new block can only start right after a digest row
+        transition_builder
+            .when(not::<AB::Expr>(flags.is_digest_row))
+            .assert_zero(next.is_first_round());
+
+        // The trace cannot end in the middle of a block
+        builder
+            .when_last_row()
+            .assert_zero(local.is_round::<AB::Expr>());
+    }
+
+    /// Constrains the bitwise functions of the round on every row. These are degree 3, so they
+    /// are materialized in columns to keep the round transition within degree 3 once it is
+    /// multiplied by the round flag.
+    pub fn eval_round_functions<AB: AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Sha256VmCols<AB::Var>,
+    ) {
+        let round = &local.round;
+        for bits in round.a.iter().chain(&round.e).chain(&round.w) {
+            for &bit in bits {
+                builder.assert_bool(bit);
+            }
+        }
+
+        let [a, b, c, _] = round.a;
+        let e = round.e[0];
+        let (w1, w14) = (round.w[1], round.w[14]);
+        for i in 0..SHA256_WORD_BITS {
+            builder.assert_eq(
+                round.big_sigma0[i],
+                xor3::<AB::Expr>(rotr(&a, 2, i), rotr(&a, 13, i), rotr(&a, 22, i)),
+            );
+            builder.assert_eq(
+                round.big_sigma1[i],
+                xor3::<AB::Expr>(rotr(&e, 6, i), rotr(&e, 11, i), rotr(&e, 25, i)),
+            );
+            builder.assert_eq(round.maj[i], maj::<AB::Expr>(a[i], b[i], c[i]));
+            builder.assert_eq(
+                round.small_sigma0[i],
+                xor3::<AB::Expr>(rotr(&w1, 7, i), rotr(&w1, 18, i), shr(&w1, 3, i)),
+            );
+            builder.assert_eq(
+                round.small_sigma1[i],
+                xor3::<AB::Expr>(rotr(&w14, 17, i), rotr(&w14, 19, i), shr(&w14, 10, i)),
+            );
+        }
+    }
+
+    /// Constrains the next row's working variables and message schedule from one round.
+    /// Additions are done on 16-bit limbs so that sums fit in the field.
+    pub fn constrain_round_transition<AB: InteractionBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Sha256VmCols<AB::Var>,
+        next: &Sha256VmCols<AB::Var>,
+    ) {
+        let is_round = local.is_round::<AB::Expr>();
+        let round = &local.round;
+        let [e, f, g, h] = round.e;
+        let ch: [AB::Expr; SHA256_WORD_BITS] = from_fn(|i| select(e[i], f[i], g[i]));
+        let k: [AB::Expr; 2] = from_fn(|limb| {
+            izip!(local.flags.round_flags, SHA256_K).fold(AB::Expr::ZERO, |acc, (flag, k)| {
+                acc + flag * AB::F::from_canonical_u32((k >> (16 * limb)) & 0xffff)
+            })
+        });
+        let ch = word_limbs::<AB::Expr>(ch);
+        let [d, h, w, big_sigma0, big_sigma1, maj] = [
+            round.a[3],
+            h,
+            round.w[0],
+            round.big_sigma0,
+            round.big_sigma1,
+            round.maj,
+        ]
+        .map(word_limbs::<AB::Expr>);
+
+        // T1 = h + Σ1(e) + Ch(e, f, g) + K_t + W_t and T2 = Σ0(a) + Maj(a, b, c), per limb
+        let t1: [AB::Expr; 2] = from_fn(|i| {
+            h[i].clone() + big_sigma1[i].clone() + ch[i].clone() + k[i].clone() + w[i].clone()
+        });
+        let t2: [AB::Expr; 2] = from_fn(|i| big_sigma0[i].clone() + maj[i].clone());
+        let next_a = word_limbs::<AB::Expr>(next.round.a[0]);
+        let next_e = word_limbs::<AB::Expr>(next.round.e[0]);
+        let limb_base = AB::F::from_canonical_u32(1 << 16);
+        let (a_carry, e_carry, w_carry) = (round.a_carry, round.e_carry, round.w_carry);
+
+        let mut round_builder = builder.when(is_round.clone());
+        // a' = T1 + T2
+        round_builder.assert_eq(
+            t1[0].clone() + t2[0].clone(),
+            next_a[0].clone() + a_carry[0] * limb_base,
+        );
+        round_builder.assert_eq(
+            t1[1].clone() + t2[1].clone() + a_carry[0],
+            next_a[1].clone() + a_carry[1] * limb_base,
+        );
+        // e' = d + T1
+        round_builder.assert_eq(
+            d[0].clone() + t1[0].clone(),
+            next_e[0].clone() + e_carry[0] * limb_base,
+        );
+        round_builder.assert_eq(
+            d[1].clone() + t1[1].clone() + e_carry[0],
+            next_e[1].clone() + e_carry[1] * limb_base,
+        );
+        // b' = a, c' = b, d' = c and f' = e, g' = f, h' = g
+        for i in 0..3 {
+            assert_array_eq(&mut round_builder, next.round.a[i + 1], round.a[i]);
+            assert_array_eq(&mut round_builder, next.round.e[i + 1], round.e[i]);
+        }
+
+        // The message schedule window moves by one word:
+        // W_{t + 16} = σ1(W_{t + 14}) + W_{t + 9} + σ0(W_{t + 1}) + W_t
+        for i in 0..SHA256_BLOCK_WORDS - 1 {
+            assert_array_eq(&mut round_builder, next.round.w[i], round.w[i + 1]);
+        }
+        let [small_sigma0, small_sigma1, w9] =
+            [round.small_sigma0, round.small_sigma1, round.w[9]].map(word_limbs::<AB::Expr>);
+        let next_w = word_limbs::<AB::Expr>(next.round.w[SHA256_BLOCK_WORDS - 1]);
+        round_builder.assert_eq(
+            small_sigma1[0].clone() + w9[0].clone() + small_sigma0[0].clone() + w[0].clone(),
+            next_w[0].clone() + w_carry[0] * limb_base,
+        );
+        round_builder.assert_eq(
+            small_sigma1[1].clone()
+                + w9[1].clone()
+                + small_sigma0[1].clone()
+                + w[1].clone()
+                + w_carry[0],
+            next_w[1].clone() + w_carry[1] * limb_base,
+        );
+
+        // The sums above are at most 2^19, so byte range checked carries cannot overflow the field.
+        for carry in [a_carry, e_carry, w_carry] {
+            self.bitwise_lookup_bus
+                .send_range(carry[0], carry[1])
+                .eval(builder, is_round.clone());
+        }
+    }
+
+    /// The instruction columns are expected to be the same on all rows of a block.
+    pub fn constrain_consistency_across_rounds<AB: AirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Sha256VmCols<AB::Var>,
+        next: &Sha256VmCols<AB::Var>,
+    ) {
+        let mut round_builder = builder.when(local.is_round::<AB::Expr>());
+        local
+            .instruction
+            .assert_eq(&mut round_builder, next.instruction);
+    }
+
+    pub fn eval_instruction<AB: InteractionBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Sha256VmCols<AB::Var>,
+    ) -> AB::Expr {
+        let instruction = local.instruction;
+        let should_receive = local.is_first_round();
+
+        let [dst_ptr, src_ptr, msg_ptr] = [
+            instruction.dst_ptr,
+            instruction.src_ptr,
+            instruction.msg_ptr,
+        ];
+        let reg_addr_sp = AB::F::ONE;
+        let operands: [AB::Expr; 5] = [
+            dst_ptr.into(),
+            src_ptr.into(),
+            msg_ptr.into(),
+            reg_addr_sp.into(),
+            instruction.e.into(),
+        ];
+        self.execution_bridge
+            .execute_and_increment_pc(
+                AB::Expr::from_canonical_usize(
+                    Rv32Sha256Opcode::SHA256_COMPRESS as usize + self.offset,
+                ),
+                operands,
+                ExecutionState::new(instruction.pc, instruction.start_timestamp),
+                AB::Expr::from_canonical_usize(SHA256_TIMESTAMP_DELTA),
+            )
+            .eval(builder, should_receive);
+
+        let mut timestamp: AB::Expr = instruction.start_timestamp.into();
+        for (ptr, value, aux) in izip!(
+            [dst_ptr, src_ptr, msg_ptr],
+            [instruction.dst, instruction.src, instruction.msg],
+            &local.mem_oc.register_aux,
+        ) {
+            self.memory_bridge
+                .read(
+                    MemoryAddress::new(reg_addr_sp, ptr),
+                    value,
+                    timestamp.clone(),
+                    aux,
+                )
+                .eval(builder, should_receive);
+
+            timestamp += AB::Expr::ONE;
+        }
+        let need_range_check = [
+            *instruction.dst.last().unwrap(),
+            *instruction.src.last().unwrap(),
+            *instruction.msg.last().unwrap(),
+            *instruction.msg.last().unwrap(),
+        ];
+        let limb_shift = AB::F::from_canonical_usize(
+            1 << (RV32_CELL_BITS * RV32_REGISTER_NUM_LIMBS - self.ptr_max_bits),
+        );
+        for pair in need_range_check.chunks_exact(2) {
+            self.bitwise_lookup_bus
+                .send_range(pair[0] * limb_shift, pair[1] * limb_shift)
+                .eval(builder, should_receive);
+        }
+
+        timestamp
+    }
+
+    /// On the first round, the working variables are the input state and the message schedule
+    /// is the message block.
+    pub fn constrain_input_read<AB: InteractionBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Sha256VmCols<AB::Var>,
+        start_read_timestamp: AB::Expr,
+    ) -> AB::Expr {
+        let instruction = local.instruction;
+        let is_first_round = local.is_first_round();
+        let src = abstract_compose::<AB::Expr, _>(instruction.src);
+        let msg = abstract_compose::<AB::Expr, _>(instruction.msg);
+
+        let mut timestamp = start_read_timestamp;
+        for (i, (bits, input_limbs, aux)) in izip!(
+            local.round.a.iter().chain(&local.round.e),
+            instruction.input_state,
+            &local.mem_oc.state_reads
+        )
+        .enumerate()
+        {
+            assert_array_eq(
+                &mut builder.when(is_first_round),
+                input_limbs,
+                word_limbs::<AB::Expr>(*bits),
+            );
+            self.memory_bridge
+                .read(
+                    MemoryAddress::new(
+                        instruction.e,
+                        src.clone() + AB::F::from_canonical_usize(i * SHA256_WORD_SIZE),
+                    ),
+                    word_be_bytes::<AB::Expr>(*bits),
+                    timestamp.clone(),
+                    aux,
+                )
+                .eval(builder, is_first_round);
+
+            timestamp += AB::Expr::ONE;
+        }
+        for (i, (bits, aux)) in izip!(local.round.w, &local.mem_oc.block_reads).enumerate() {
+            self.memory_bridge
+                .read(
+                    MemoryAddress::new(
+                        instruction.e,
+                        msg.clone() + AB::F::from_canonical_usize(i * SHA256_WORD_SIZE),
+                    ),
+                    word_be_bytes::<AB::Expr>(bits),
+                    timestamp.clone(),
+                    aux,
+                )
+                .eval(builder, is_first_round);
+
+            timestamp += AB::Expr::ONE;
+        }
+        timestamp
+    }
+
+    /// On the digest row, the output state is the input state plus the working variables.
+    pub fn constrain_output_write<AB: InteractionBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Sha256VmCols<AB::Var>,
+        start_write_timestamp: AB::Expr,
+    ) {
+        let instruction = local.instruction;
+        let is_digest_row = local.flags.is_digest_row;
+        let digest = &local.digest;
+        let dst = abstract_compose::<AB::Expr, _>(instruction.dst);
+        let limb_base = AB::F::from_canonical_u32(1 << 16);
+        let byte_base = AB::F::from_canonical_u32(1 << 8);
+
+        for (i, (bits, input_limbs, output, carry, aux)) in izip!(
+            local.round.a.iter().chain(&local.round.e),
+            instruction.input_state,
+            digest.output,
+            digest.carry,
+            &local.mem_oc.state_writes
+        )
+        .enumerate()
+        {
+            let vars = word_limbs::<AB::Expr>(*bits);
+            // Output bytes are big-endian
+            let output_limbs: [AB::Expr; 2] = [
+                output[3] + output[2] * byte_base,
+                output[1] + output[0] * byte_base,
+            ];
+            builder.assert_bool(carry[0]);
+            builder.assert_bool(carry[1]);
+            let mut digest_builder = builder.when(is_digest_row);
+            digest_builder.assert_eq(
+                input_limbs[0] + vars[0].clone(),
+                output_limbs[0].clone() + carry[0] * limb_base,
+            );
+            digest_builder.assert_eq(
+                input_limbs[1] + vars[1].clone() + carry[0],
+                output_limbs[1].clone() + carry[1] * limb_base,
+            );
+
+            self.memory_bridge
+                .write(
+                    MemoryAddress::new(
+                        instruction.e,
+                        dst.clone() + AB::F::from_canonical_usize(i * SHA256_WORD_SIZE),
+                    ),
+                    output,
+                    start_write_timestamp.clone() + AB::Expr::from_canonical_usize(i),
+                    aux,
+                )
+                .eval(builder, is_digest_row);
+        }
+        for (&x, &y) in digest.output.iter().flatten().tuples() {
+            self.bitwise_lookup_bus
+                .send_range(x, y)
+                .eval(builder, is_digest_row);
+        }
+    }
+}
+
+/// Bit `i` of `x` rotated right by `n`.
+fn rotr<E: AbstractField>(x: &[impl Into<E> + Copy; SHA256_WORD_BITS], n: usize, i: usize) -> E {
+    x[(i + n) % SHA256_WORD_BITS].into()
+}
+
+/// Bit `i` of `x` shifted right by `n`.
+fn shr<E: AbstractField>(x: &[impl Into<E> + Copy; SHA256_WORD_BITS], n: usize, i: usize) -> E {
+    if i + n < SHA256_WORD_BITS {
+        x[i + n].into()
+    } else {
+        E::ZERO
+    }
+}
+
+fn xor<E: AbstractField>(x: E, y: E) -> E {
+    x.clone() + y.clone() - (x * y).double()
+}
+
+fn xor3<E: AbstractField>(x: E, y: E, z: E) -> E {
+    xor(xor(x, y), z)
+}
+
+fn maj<E: AbstractField>(x: impl Into<E>, y: impl Into<E>, z: impl Into<E>) -> E {
+    let (x, y, z) = (x.into(), y.into(), z.into());
+    x.clone() * y.clone() + x.clone() * z.clone() + y.clone() * z.clone() - (x * y * z).double()
+}
+
+/// Low and high 16-bit limbs of a word given by its bits.
+fn word_limbs<E: AbstractField>(bits: [impl Into<E>; SHA256_WORD_BITS]) -> [E; 2] {
+    let mut limbs = [E::ZERO, E::ZERO];
+    for (i, bit) in bits.into_iter().enumerate() {
+        limbs[i / 16] += bit.into() * E::from_canonical_u32(1 << (i % 16));
+    }
+    limbs
+}
+
+/// Bytes of a word given by its bits, in the big-endian order the word is stored in memory.
+fn word_be_bytes<E: AbstractField>(
+    bits: [impl Into<E>; SHA256_WORD_BITS],
+) -> [E; SHA256_WORD_SIZE] {
+    let mut bytes = from_fn(|_| E::ZERO);
+    for (i, bit) in bits.into_iter().enumerate() {
+        bytes[SHA256_WORD_SIZE - 1 - i / 8] += bit.into() * E::from_canonical_u32(1 << (i % 8));
+    }
+    bytes
+}
@@ -0,0 +1,169 @@
+use core::mem::size_of;
+
+use openvm_circuit::system::memory::offline_checker::{MemoryReadAuxCols, MemoryWriteAuxCols};
+use openvm_circuit_primitives::utils::assert_array_eq;
+use openvm_circuit_primitives_derive::AlignedBorrow;
+use openvm_instructions::riscv::RV32_REGISTER_NUM_LIMBS;
+use openvm_stark_backend::{p3_air::AirBuilder, p3_field::AbstractField};
+
+use super::{
+    SHA256_BLOCK_READS, SHA256_BLOCK_WORDS, SHA256_NUM_ROUNDS, SHA256_REGISTER_READS,
+    SHA256_STATE_READS, SHA256_STATE_WORDS, SHA256_STATE_WRITES, SHA256_WORD_BITS,
+    SHA256_WORD_SIZE,
+};
+
+/// Every block takes [SHA256_ROWS_PER_BLOCK](super::SHA256_ROWS_PER_BLOCK) rows: one row per
+/// round, holding the working variables and message schedule before that round, and a digest row
+/// holding the working variables after the last round.
+///
+/// Words are stored as bits in little-endian order, i.e. bit `i` has weight `2^i`.
+#[repr(C)]
+#[derive(Debug, AlignedBorrow)]
+pub struct Sha256VmCols<T> {
+    pub flags: Sha256FlagCols<T>,
+    /// Columns for the compression rounds
+    pub round: Sha256RoundCols<T>,
+    /// Columns for instruction interface and register access
+    pub instruction: Sha256InstructionCols<T>,
+    /// Columns for the output state, only used on the digest row
+    pub digest: Sha256DigestCols<T>,
+    /// Auxiliary columns for offline memory checking
+    pub mem_oc: Sha256MemoryCols<T>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, AlignedBorrow)]
+pub struct Sha256FlagCols<T> {
+    /// `round_flags[t]` is true on the row of round `t` of a block.
+    /// All flags are false on the digest row and on dummy rows only used to pad the height.
+    pub round_flags: [T; SHA256_NUM_ROUNDS],
+    /// True on the last row of a block.
+    pub is_digest_row: T,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, AlignedBorrow)]
+pub struct Sha256RoundCols<T> {
+    /// Working variables `a, b, c, d`. Since `b, c, d` are `a` of the three previous rounds,
+    /// `a[i]` is `a` from `i` rounds ago.
+    pub a: [[T; SHA256_WORD_BITS]; 4],
+    /// Working variables `e, f, g, h`, similarly `e[i]` is `e` from `i` rounds ago.
+    pub e: [[T; SHA256_WORD_BITS]; 4],
+    /// Message schedule words `W_t, ..., W_{t + 15}` on the row of round `t`.
+    pub w: [[T; SHA256_WORD_BITS]; SHA256_BLOCK_WORDS],
+    /// `Σ0(a)`
+    pub big_sigma0: [T; SHA256_WORD_BITS],
+    /// `Σ1(e)`
+    pub big_sigma1: [T; SHA256_WORD_BITS],
+    /// `Maj(a, b, c)`
+    pub maj: [T; SHA256_WORD_BITS],
+    /// `σ0(W_{t + 1})`
+    pub small_sigma0: [T; SHA256_WORD_BITS],
+    /// `σ1(W_{t + 14})`
+    pub small_sigma1: [T; SHA256_WORD_BITS],
+    /// Carries out of the low and high 16-bit limbs when adding up the next `a`.
+    pub a_carry: [T; 2],
+    /// Carries out of the low and high 16-bit limbs when adding up the next `e`.
+    pub e_carry: [T; 2],
+    /// Carries out of the low and high 16-bit limbs when adding up `W_{t + 16}`.
+    pub w_carry: [T; 2],
+}
+
+/// Columns for SHA256_COMPRESS_RV32 instruction parsing.
+/// Includes columns for instruction execution and register reads.
+/// These are the same on all rows of a block.
+#[allow(clippy::too_many_arguments)]
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, AlignedBorrow, derive_new::new)]
+pub struct Sha256InstructionCols<T> {
+    /// Program counter
+    pub pc: T,
+    /// The starting timestamp of the instruction.
+    pub start_timestamp: T,
+    /// Pointer to address space 1 `dst` register
+    pub dst_ptr: T,
+    /// Pointer to address space 1 `src` register
+    pub src_ptr: T,
+    /// Pointer to address space 1 `msg` register
+    pub msg_ptr: T,
+    /// Memory address space
+    pub e: T,
+    // Register values
+    /// dst <- [dst_ptr:4]_1, where the output state is written
+    pub dst: [T; RV32_REGISTER_NUM_LIMBS],
+    /// src <- [src_ptr:4]_1, where the input state is read from
+    pub src: [T; RV32_REGISTER_NUM_LIMBS],
+    /// msg <- [msg_ptr:4]_1, where the message block is read from
+    pub msg: [T; RV32_REGISTER_NUM_LIMBS],
+    /// The input state as low and high 16-bit limbs of each word. It is added to the working
+    /// variables on the digest row.
+    pub input_state: [[T; 2]; SHA256_STATE_WORDS],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, AlignedBorrow)]
+pub struct Sha256DigestCols<T> {
+    /// Bytes of the output state words, big-endian as they are written to memory.
+    pub output: [[T; SHA256_WORD_SIZE]; SHA256_STATE_WORDS],
+    /// Carries out of the low and high 16-bit limbs when adding the working variables to the
+    /// input state.
+    pub carry: [[T; 2]; SHA256_STATE_WORDS],
+}
+
+#[repr(C)]
+#[derive(Clone, Debug, AlignedBorrow)]
+pub struct Sha256MemoryCols<T> {
+    pub register_aux: [MemoryReadAuxCols<T, RV32_REGISTER_NUM_LIMBS>; SHA256_REGISTER_READS],
+    pub state_reads: [MemoryReadAuxCols<T, SHA256_WORD_SIZE>; SHA256_STATE_READS],
+    pub block_reads: [MemoryReadAuxCols<T, SHA256_WORD_SIZE>; SHA256_BLOCK_READS],
+    pub state_writes: [MemoryWriteAuxCols<T, SHA256_WORD_SIZE>; SHA256_STATE_WRITES],
+}
+
+impl<T: Copy> Sha256VmCols<T> {
+    pub fn is_first_round(&self) -> T {
+        self.flags.round_flags[0]
+    }
+
+    /// True on the rows of the compression rounds, i.e. all rows of a block except the digest row.
+    pub fn is_round<E: AbstractField>(&self) -> E
+    where
+        T: Into<E>,
+    {
+        self.flags
+            .round_flags
+            .iter()
+            .fold(E::ZERO, |acc, &flag| acc + flag.into())
+    }
+
+    pub fn is_enabled<E: AbstractField>(&self) -> E
+    where
+        T: Into<E>,
+    {
+        self.is_round::<E>() + self.flags.is_digest_row.into()
+    }
+}
+
+impl<T: Copy> Sha256InstructionCols<T> {
+    pub fn assert_eq<AB: AirBuilder>(&self, builder: &mut AB, other: Self)
+    where
+        T: Into<AB::Expr>,
+    {
+        builder.assert_eq(self.pc, other.pc);
+        builder.assert_eq(self.start_timestamp, other.start_timestamp);
+        builder.assert_eq(self.dst_ptr, other.dst_ptr);
+        builder.assert_eq(self.src_ptr, other.src_ptr);
+        builder.assert_eq(self.msg_ptr, other.msg_ptr);
+        builder.assert_eq(self.e, other.e);
+        assert_array_eq(builder, self.dst, other.dst);
+        assert_array_eq(builder, self.src, other.src);
+        assert_array_eq(builder, self.msg, other.msg);
+        for (limbs, other_limbs) in self.input_state.into_iter().zip(other.input_state) {
+            assert_array_eq(builder, limbs, other_limbs);
+        }
+    }
+}
+
+pub const NUM_SHA256_VM_COLS: usize = size_of::<Sha256VmCols<u8>>();
+pub const NUM_SHA256_ROUND_COLS: usize = size_of::<Sha256RoundCols<u8>>();
+pub const NUM_SHA256_INSTRUCTION_COLS: usize = size_of::<Sha256InstructionCols<u8>>();
+pub const NUM_SHA256_MEMORY_COLS: usize = size_of::<Sha256MemoryCols<u8>>();
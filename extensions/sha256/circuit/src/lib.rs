@@ -0,0 +1,209 @@
+//! SHA-256 compression function chip. Applies the compression function to one 64-byte message
+//! block and a 32-byte state read from VM memory. Padding is left to the guest.
+use std::{array::from_fn, sync::Arc};
+
+use openvm_circuit_primitives::bitwise_op_lookup::BitwiseOperationLookupChip;
+use openvm_stark_backend::p3_field::PrimeField32;
+
+pub mod air;
+pub mod columns;
+pub mod trace;
+pub mod utils;
+
+mod extension;
+pub use extension::*;
+
+#[cfg(test)]
+mod tests;
+
+pub use air::Sha256VmAir;
+use openvm_circuit::{
+    arch::{ExecutionBridge, ExecutionBus, ExecutionError, ExecutionState, InstructionExecutor},
+    system::{
+        memory::{MemoryControllerRef, MemoryReadRecord, MemoryWriteRecord},
+        program::ProgramBus,
+    },
+};
+use openvm_instructions::{
+    instruction::Instruction, program::DEFAULT_PC_STEP, riscv::RV32_REGISTER_NUM_LIMBS, UsizeOpcode,
+};
+use openvm_rv32im_circuit::adapters::read_rv32_register;
+use openvm_sha256_transpiler::Rv32Sha256Opcode;
+use utils::sha256_compress;
+
+// ==== Constants for register/memory adapter ====
+/// Register reads to get dst, src, msg
+const SHA256_REGISTER_READS: usize = 3;
+/// Number of cells to read/write in a single memory access
+const SHA256_WORD_SIZE: usize = 4;
+/// Memory reads for the input state
+const SHA256_STATE_READS: usize = SHA256_STATE_WORDS;
+/// Memory reads for the message block
+const SHA256_BLOCK_READS: usize = SHA256_BLOCK_WORDS;
+/// Memory writes for the output state
+const SHA256_STATE_WRITES: usize = SHA256_STATE_WORDS;
+/// Timestamp change of one instruction: one per memory access.
+const SHA256_TIMESTAMP_DELTA: usize =
+    SHA256_REGISTER_READS + SHA256_STATE_READS + SHA256_BLOCK_READS + SHA256_STATE_WRITES;
+
+// ==== Do not change these constants! ====
+/// Number of bits in a SHA-256 word.
+pub const SHA256_WORD_BITS: usize = 32;
+/// Number of words in the SHA-256 state.
+pub const SHA256_STATE_WORDS: usize = 8;
+/// Number of words in a message block.
+pub const SHA256_BLOCK_WORDS: usize = 16;
+/// Number of rounds of the compression function.
+pub const SHA256_NUM_ROUNDS: usize = 64;
+/// One row per round, and one row for the output state.
+pub const SHA256_ROWS_PER_BLOCK: usize = SHA256_NUM_ROUNDS + 1;
+
+#[derive(Debug)]
+pub struct Sha256VmChip<F: PrimeField32> {
+    pub air: Sha256VmAir,
+    /// IO and memory data necessary for each opcode call
+    pub records: Vec<Sha256Record<F>>,
+    pub memory_controller: MemoryControllerRef<F>,
+    pub bitwise_lookup_chip: Arc<BitwiseOperationLookupChip<8>>,
+
+    offset: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Sha256Record<F> {
+    pub pc: F,
+    pub dst_read: MemoryReadRecord<F, RV32_REGISTER_NUM_LIMBS>,
+    pub src_read: MemoryReadRecord<F, RV32_REGISTER_NUM_LIMBS>,
+    pub msg_read: MemoryReadRecord<F, RV32_REGISTER_NUM_LIMBS>,
+    pub state_reads: [MemoryReadRecord<F, SHA256_WORD_SIZE>; SHA256_STATE_READS],
+    pub block_reads: [MemoryReadRecord<F, SHA256_WORD_SIZE>; SHA256_BLOCK_READS],
+    pub state_writes: [MemoryWriteRecord<F, SHA256_WORD_SIZE>; SHA256_STATE_WRITES],
+}
+
+impl<F: PrimeField32> Sha256VmChip<F> {
+    pub fn new(
+        execution_bus: ExecutionBus,
+        program_bus: ProgramBus,
+        memory_controller: MemoryControllerRef<F>,
+        bitwise_lookup_chip: Arc<BitwiseOperationLookupChip<8>>,
+        offset: usize,
+    ) -> Self {
+        let ptr_max_bits = memory_controller.borrow().mem_config().pointer_max_bits;
+        let memory_bridge = memory_controller.borrow().memory_bridge();
+        Self {
+            air: Sha256VmAir::new(
+                ExecutionBridge::new(execution_bus, program_bus),
+                memory_bridge,
+                bitwise_lookup_chip.bus(),
+                ptr_max_bits,
+                offset,
+            ),
+            memory_controller,
+            bitwise_lookup_chip,
+            records: Vec::new(),
+            offset,
+        }
+    }
+}
+
+impl<F: PrimeField32> InstructionExecutor<F> for Sha256VmChip<F> {
+    fn execute(
+        &mut self,
+        instruction: Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> Result<ExecutionState<u32>, ExecutionError> {
+        let Instruction {
+            opcode,
+            a,
+            b,
+            c,
+            d,
+            e,
+            ..
+        } = instruction;
+        let local_opcode = Rv32Sha256Opcode::from_usize(opcode.local_opcode_idx(self.offset));
+        debug_assert_eq!(local_opcode, Rv32Sha256Opcode::SHA256_COMPRESS);
+
+        let mut memory = self.memory_controller.borrow_mut();
+        debug_assert_eq!(from_state.timestamp, memory.timestamp());
+
+        let (dst_read, dst) = read_rv32_register(&mut memory, d, a);
+        let (src_read, src) = read_rv32_register(&mut memory, d, b);
+        let (msg_read, msg) = read_rv32_register(&mut memory, d, c);
+        #[cfg(debug_assertions)]
+        {
+            assert!(dst < (1 << self.air.ptr_max_bits));
+            assert!(src < (1 << self.air.ptr_max_bits));
+            assert!(msg < (1 << self.air.ptr_max_bits));
+        }
+
+        // The whole input is read before the output is written, so `dst` may equal `src`.
+        let state_reads: [_; SHA256_STATE_READS] = from_fn(|i| {
+            memory.read::<SHA256_WORD_SIZE>(
+                e,
+                F::from_canonical_usize(src as usize + i * SHA256_WORD_SIZE),
+            )
+        });
+        let block_reads: [_; SHA256_BLOCK_READS] = from_fn(|i| {
+            memory.read::<SHA256_WORD_SIZE>(
+                e,
+                F::from_canonical_usize(msg as usize + i * SHA256_WORD_SIZE),
+            )
+        });
+        let state = state_reads.map(|read| word_from_be_cells(read.data));
+        let block = block_reads.map(|read| word_from_be_cells(read.data));
+        let output = sha256_compress(state, block);
+
+        let dst = dst as usize;
+        let state_writes: [_; SHA256_STATE_WRITES] = from_fn(|i| {
+            memory.write::<SHA256_WORD_SIZE>(
+                e,
+                F::from_canonical_usize(dst + i * SHA256_WORD_SIZE),
+                output[i].to_be_bytes().map(F::from_canonical_u8),
+            )
+        });
+        tracing::trace!("[runtime] sha256 compress output: {:08x?}", output);
+
+        let record = Sha256Record {
+            pc: F::from_canonical_u32(from_state.pc),
+            dst_read,
+            src_read,
+            msg_read,
+            state_reads,
+            block_reads,
+            state_writes,
+        };
+
+        // Add the events to chip state for later trace generation usage
+        if !memory.is_pure_execution() {
+            self.records.push(record);
+        }
+
+        let to_timestamp = from_state.timestamp + SHA256_TIMESTAMP_DELTA as u32;
+        debug_assert_eq!(memory.timestamp(), to_timestamp);
+
+        Ok(ExecutionState {
+            pc: from_state.pc + DEFAULT_PC_STEP,
+            timestamp: to_timestamp,
+        })
+    }
+
+    fn get_opcode_name(&self, _: usize) -> String {
+        "SHA256_COMPRESS".to_string()
+    }
+}
+
+impl<F: Copy> Sha256Record<F> {
+    pub fn start_timestamp(&self) -> u32 {
+        self.dst_read.timestamp
+    }
+}
+
+/// Words are stored big-endian in memory, as the SHA-256 specification serializes them.
+fn word_from_be_cells<F: PrimeField32>(cells: [F; SHA256_WORD_SIZE]) -> u32 {
+    u32::from_be_bytes(cells.map(|x| {
+        x.as_canonical_u32()
+            .try_into()
+            .expect("Memory cell not a byte")
+    }))
+}
@@ -0,0 +1,153 @@
+use std::{borrow::BorrowMut, sync::Arc};
+
+use openvm_circuit::arch::{
+    testing::{VmChipTestBuilder, VmChipTester},
+    BITWISE_OP_LOOKUP_BUS,
+};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, BitwiseOperationLookupChip,
+};
+use openvm_instructions::{instruction::Instruction, VmOpcode};
+use openvm_sha256_transpiler::Rv32Sha256Opcode;
+use openvm_stark_backend::{
+    p3_field::AbstractField, utils::disable_debug_builder, verifier::VerificationError,
+};
+use openvm_stark_sdk::{
+    config::baby_bear_blake3::BabyBearBlake3Config, p3_baby_bear::BabyBear,
+    utils::create_seeded_rng,
+};
+use rand::Rng;
+use sha2::digest::generic_array::GenericArray;
+
+use super::{
+    columns::Sha256VmCols, Sha256VmChip, SHA256_NUM_ROUNDS, SHA256_ROWS_PER_BLOCK,
+    SHA256_STATE_WORDS, SHA256_WORD_SIZE,
+};
+
+type F = BabyBear;
+// io is vector of (state, block, prank_output) where prank_output is Some if the trace
+// will be replaced
+#[allow(clippy::type_complexity)]
+fn build_sha256_test(
+    io: Vec<([u32; 8], [u8; 64], Option<[u8; 32]>)>,
+) -> VmChipTester<BabyBearBlake3Config> {
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = Arc::new(BitwiseOperationLookupChip::<8>::new(bitwise_bus));
+
+    let mut tester = VmChipTestBuilder::default();
+    let mut chip = Sha256VmChip::new(
+        tester.execution_bus(),
+        tester.program_bus(),
+        tester.memory_controller(),
+        bitwise_chip.clone(),
+        0,
+    );
+
+    let mut dst = 0;
+    let src = 1 << 10;
+    let msg = 1 << 11;
+
+    for (state, block, prank_output) in &io {
+        let [a, b, c] = [0, 4, 8]; // space apart for register limbs
+        let [d, e] = [1, 2];
+
+        tester.write(d, a, (dst as u32).to_le_bytes().map(F::from_canonical_u8));
+        tester.write(d, b, (src as u32).to_le_bytes().map(F::from_canonical_u8));
+        tester.write(d, c, (msg as u32).to_le_bytes().map(F::from_canonical_u8));
+        for (i, word) in state.iter().enumerate() {
+            tester.write(
+                e,
+                src + i * SHA256_WORD_SIZE,
+                word.to_be_bytes().map(F::from_canonical_u8),
+            );
+        }
+        for (i, byte) in block.iter().enumerate() {
+            tester.write_cell(e, msg + i, F::from_canonical_u8(*byte));
+        }
+
+        tester.execute(
+            &mut chip,
+            Instruction::from_isize(
+                VmOpcode::from_usize(Rv32Sha256Opcode::SHA256_COMPRESS as usize),
+                a as isize,
+                b as isize,
+                c as isize,
+                d as isize,
+                e as isize,
+            ),
+        );
+
+        let mut expected = *state;
+        sha2::compress256(&mut expected, &[GenericArray::clone_from_slice(block)]);
+        for (i, word) in expected.iter().enumerate() {
+            for (j, byte) in word.to_be_bytes().into_iter().enumerate() {
+                assert_eq!(
+                    tester.read_cell(e, dst + i * SHA256_WORD_SIZE + j),
+                    F::from_canonical_u8(byte)
+                );
+            }
+        }
+        if let Some(output) = prank_output {
+            for (i, output_byte) in output.iter().enumerate() {
+                chip.records.last_mut().unwrap().state_writes[i / SHA256_WORD_SIZE].data
+                    [i % SHA256_WORD_SIZE] = F::from_canonical_u8(*output_byte);
+            }
+        }
+        // shift dst to not deal with timestamps for pranking
+        dst += SHA256_STATE_WORDS * SHA256_WORD_SIZE;
+    }
+    let mut tester = tester.build().load(chip).load(bitwise_chip).finalize();
+
+    let sha256_trace = tester.air_proof_inputs[2].raw.common_main.as_mut().unwrap();
+    for (block_idx, (_, _, prank_output)) in io.into_iter().enumerate() {
+        let Some(output) = prank_output else {
+            continue;
+        };
+        let digest_row: &mut Sha256VmCols<_> = sha256_trace
+            .row_mut(block_idx * SHA256_ROWS_PER_BLOCK + SHA256_NUM_ROUNDS)
+            .borrow_mut();
+        for (i, output_byte) in output.into_iter().enumerate() {
+            digest_row.digest.output[i / SHA256_WORD_SIZE][i % SHA256_WORD_SIZE] =
+                F::from_canonical_u8(output_byte);
+        }
+    }
+
+    tester
+}
+
+#[test]
+fn test_sha256_compress_positive() {
+    let mut rng = create_seeded_rng();
+    let io = (0..4)
+        .map(|_| (rng.gen(), std::array::from_fn(|_| rng.gen()), None))
+        .collect();
+    let tester = build_sha256_test(io);
+    tester.simple_test().expect("Verification failed");
+}
+
+#[test]
+fn test_sha256_compress_initial_state() {
+    // The single padded block of the empty message.
+    let mut block = [0u8; 64];
+    block[0] = 0x80;
+    let tester = build_sha256_test(vec![(super::utils::SHA256_H, block, None)]);
+    tester.simple_test().expect("Verification failed");
+}
+
+#[test]
+fn test_sha256_compress_negative() {
+    let mut rng = create_seeded_rng();
+    let state = rng.gen();
+    let block = std::array::from_fn(|_| rng.gen());
+    let mut expected = state;
+    sha2::compress256(&mut expected, &[GenericArray::clone_from_slice(&block)]);
+    let mut out: [u8; 32] =
+        std::array::from_fn(|i| expected[i / SHA256_WORD_SIZE].to_be_bytes()[i % SHA256_WORD_SIZE]);
+    out[0] = out[0].wrapping_add(1);
+    let tester = build_sha256_test(vec![(state, block, Some(out))]);
+    disable_debug_builder();
+    assert_eq!(
+        tester.simple_test().err(),
+        Some(VerificationError::OodEvaluationMismatch)
+    );
+}
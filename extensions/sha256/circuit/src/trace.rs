@@ -0,0 +1,186 @@
+use std::{array::from_fn, borrow::BorrowMut, sync::Arc};
+
+use openvm_circuit_primitives::utils::next_power_of_two_or_zero;
+use openvm_instructions::riscv::{RV32_CELL_BITS, RV32_REGISTER_NUM_LIMBS};
+use openvm_stark_backend::{
+    config::{StarkGenericConfig, Val},
+    p3_air::BaseAir,
+    p3_field::{AbstractField, PrimeField32},
+    p3_matrix::dense::RowMajorMatrix,
+    p3_maybe_rayon::prelude::*,
+    prover::types::AirProofInput,
+    rap::{get_air_name, AnyRap},
+    Chip, ChipUsageGetter,
+};
+
+use super::{
+    columns::{Sha256InstructionCols, Sha256RoundCols, Sha256VmCols},
+    utils::{
+        big_sigma0, big_sigma1, ch, limb_carries, maj, message_schedule, small_sigma0,
+        small_sigma1, working_variables, SHA256_K,
+    },
+    word_from_be_cells, Sha256VmChip, SHA256_BLOCK_WORDS, SHA256_NUM_ROUNDS, SHA256_ROWS_PER_BLOCK,
+    SHA256_STATE_WORDS, SHA256_WORD_BITS,
+};
+
+impl<SC: StarkGenericConfig> Chip<SC> for Sha256VmChip<Val<SC>>
+where
+    Val<SC>: PrimeField32,
+{
+    fn air(&self) -> Arc<dyn AnyRap<SC>> {
+        Arc::new(self.air)
+    }
+
+    fn generate_air_proof_input(self) -> AirProofInput<SC> {
+        let air = self.air();
+        let trace_width = self.trace_width();
+        let height = next_power_of_two_or_zero(self.current_trace_height());
+        let aux_cols_factory = self.memory_controller.borrow().aux_cols_factory();
+        let limb_shift_bits = RV32_CELL_BITS * RV32_REGISTER_NUM_LIMBS - self.air.ptr_max_bits;
+
+        // Rows past the last block are left as all zeros, which is a valid dummy row.
+        let mut trace = RowMajorMatrix::new(Val::<SC>::zero_vec(height * trace_width), trace_width);
+        trace
+            .values
+            .par_chunks_mut(trace_width * SHA256_ROWS_PER_BLOCK)
+            .zip(self.records.into_par_iter())
+            .for_each(|(rows, record)| {
+                let state = record.state_reads.map(|read| word_from_be_cells(read.data));
+                let block = record.block_reads.map(|read| word_from_be_cells(read.data));
+                let w = message_schedule(block);
+                let vars = working_variables(state, &w);
+
+                let instruction = Sha256InstructionCols {
+                    pc: record.pc,
+                    start_timestamp: Val::<SC>::from_canonical_u32(record.start_timestamp()),
+                    dst_ptr: record.dst_read.pointer,
+                    src_ptr: record.src_read.pointer,
+                    msg_ptr: record.msg_read.pointer,
+                    e: record.state_reads[0].address_space,
+                    dst: record.dst_read.data,
+                    src: record.src_read.data,
+                    msg: record.msg_read.data,
+                    input_state: state.map(|x| word_limbs(x).map(Val::<SC>::from_canonical_u32)),
+                };
+
+                for (t, row) in rows.chunks_exact_mut(trace_width).enumerate() {
+                    let row: &mut Sha256VmCols<Val<SC>> = row.borrow_mut();
+                    row.instruction = instruction;
+                    generate_round_cols(
+                        &mut row.round,
+                        vars[t],
+                        w[t..t + SHA256_BLOCK_WORDS].try_into().unwrap(),
+                    );
+                    if t < SHA256_NUM_ROUNDS {
+                        row.flags.round_flags[t] = Val::<SC>::ONE;
+                        let carries = round_carries(vars[t], &w[t..t + SHA256_BLOCK_WORDS], t);
+                        [row.round.a_carry, row.round.e_carry, row.round.w_carry] =
+                            carries.map(|c| c.map(Val::<SC>::from_canonical_u32));
+                        for [c0, c1] in carries {
+                            self.bitwise_lookup_chip.request_range(c0, c1);
+                        }
+                    } else {
+                        row.flags.is_digest_row = Val::<SC>::ONE;
+                    }
+                }
+
+                // Make memory access aux columns. Any aux column not explicitly defined defaults to all 0s
+                let first_row: &mut Sha256VmCols<Val<SC>> = rows[..trace_width].borrow_mut();
+                let register_reads = [record.dst_read, record.src_read, record.msg_read];
+                let need_range_check = [
+                    &register_reads[0], // dst
+                    &register_reads[1], // src
+                    &register_reads[2], // msg
+                    &register_reads[2],
+                ]
+                .map(|r| r.data.last().unwrap().as_canonical_u32());
+                for bytes in need_range_check.chunks(2) {
+                    self.bitwise_lookup_chip
+                        .request_range(bytes[0] << limb_shift_bits, bytes[1] << limb_shift_bits);
+                }
+                for (i, record) in register_reads.into_iter().enumerate() {
+                    first_row.mem_oc.register_aux[i] = aux_cols_factory.make_read_aux_cols(record);
+                }
+                for (i, record) in record.state_reads.into_iter().enumerate() {
+                    first_row.mem_oc.state_reads[i] = aux_cols_factory.make_read_aux_cols(record);
+                }
+                for (i, record) in record.block_reads.into_iter().enumerate() {
+                    first_row.mem_oc.block_reads[i] = aux_cols_factory.make_read_aux_cols(record);
+                }
+
+                let digest_row: &mut Sha256VmCols<Val<SC>> =
+                    rows[SHA256_NUM_ROUNDS * trace_width..].borrow_mut();
+                let last_vars = vars[SHA256_NUM_ROUNDS];
+                for i in 0..SHA256_STATE_WORDS {
+                    let output = state[i].wrapping_add(last_vars[i]).to_be_bytes();
+                    digest_row.digest.output[i] = output.map(Val::<SC>::from_canonical_u8);
+                    digest_row.digest.carry[i] =
+                        limb_carries(&[state[i], last_vars[i]]).map(Val::<SC>::from_canonical_u32);
+                    for bytes in output.chunks_exact(2) {
+                        self.bitwise_lookup_chip
+                            .request_range(bytes[0] as u32, bytes[1] as u32);
+                    }
+                }
+                for (i, record) in record.state_writes.into_iter().enumerate() {
+                    digest_row.mem_oc.state_writes[i] =
+                        aux_cols_factory.make_write_aux_cols(record);
+                }
+            });
+
+        AirProofInput::simple_no_pis(air, trace)
+    }
+}
+
+impl<F: PrimeField32> ChipUsageGetter for Sha256VmChip<F> {
+    fn air_name(&self) -> String {
+        get_air_name(&self.air)
+    }
+    fn current_trace_height(&self) -> usize {
+        self.records.len() * SHA256_ROWS_PER_BLOCK
+    }
+
+    fn trace_width(&self) -> usize {
+        BaseAir::<F>::width(&self.air)
+    }
+}
+
+/// Fills the working variables and message schedule window of a row, together with the bitwise
+/// functions of them. These are filled on every row of a block.
+fn generate_round_cols<F: AbstractField>(
+    cols: &mut Sha256RoundCols<F>,
+    vars: [u32; SHA256_STATE_WORDS],
+    w: [u32; SHA256_BLOCK_WORDS],
+) {
+    let [a, b, c, _, e, ..] = vars;
+    cols.a = from_fn(|i| word_bits(vars[i]));
+    cols.e = from_fn(|i| word_bits(vars[4 + i]));
+    cols.w = w.map(word_bits);
+    cols.big_sigma0 = word_bits(big_sigma0(a));
+    cols.big_sigma1 = word_bits(big_sigma1(e));
+    cols.maj = word_bits(maj(a, b, c));
+    cols.small_sigma0 = word_bits(small_sigma0(w[1]));
+    cols.small_sigma1 = word_bits(small_sigma1(w[14]));
+}
+
+/// Limb carries of the additions computing the next `a`, the next `e` and `W_{t + 16}` in round `t`.
+fn round_carries(vars: [u32; SHA256_STATE_WORDS], w: &[u32], t: usize) -> [[u32; 2]; 3] {
+    let [a, b, c, d, e, f, g, h] = vars;
+    let t1 = [h, big_sigma1(e), ch(e, f, g), SHA256_K[t], w[0]];
+    let t2 = [big_sigma0(a), maj(a, b, c)];
+    let a_terms: Vec<u32> = t1.iter().chain(&t2).copied().collect();
+    let e_terms: Vec<u32> = t1.iter().chain(&[d]).copied().collect();
+    let w_terms = [small_sigma1(w[14]), w[9], small_sigma0(w[1]), w[0]];
+    [
+        limb_carries(&a_terms),
+        limb_carries(&e_terms),
+        limb_carries(&w_terms),
+    ]
+}
+
+fn word_bits<F: AbstractField>(x: u32) -> [F; SHA256_WORD_BITS] {
+    from_fn(|i| F::from_bool((x >> i) & 1 == 1))
+}
+
+fn word_limbs(x: u32) -> [u32; 2] {
+    [x & 0xffff, x >> 16]
+}
@@ -0,0 +1,96 @@
+use super::{SHA256_BLOCK_WORDS, SHA256_NUM_ROUNDS, SHA256_ROWS_PER_BLOCK, SHA256_STATE_WORDS};
+
+/// SHA-256 round constants.
+pub const SHA256_K: [u32; SHA256_NUM_ROUNDS] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// SHA-256 initial hash value.
+pub const SHA256_H: [u32; SHA256_STATE_WORDS] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+pub fn big_sigma0(x: u32) -> u32 {
+    x.rotate_right(2) ^ x.rotate_right(13) ^ x.rotate_right(22)
+}
+
+pub fn big_sigma1(x: u32) -> u32 {
+    x.rotate_right(6) ^ x.rotate_right(11) ^ x.rotate_right(25)
+}
+
+pub fn small_sigma0(x: u32) -> u32 {
+    x.rotate_right(7) ^ x.rotate_right(18) ^ (x >> 3)
+}
+
+pub fn small_sigma1(x: u32) -> u32 {
+    x.rotate_right(17) ^ x.rotate_right(19) ^ (x >> 10)
+}
+
+pub fn ch(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) ^ (!x & z)
+}
+
+pub fn maj(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) ^ (x & z) ^ (y & z)
+}
+
+/// The message schedule `W_0, W_1, ...` of `block`. It is extended past the last round so that
+/// every row of a block, including the digest row, has a window of [SHA256_BLOCK_WORDS] words.
+pub fn message_schedule(
+    block: [u32; SHA256_BLOCK_WORDS],
+) -> [u32; SHA256_NUM_ROUNDS + SHA256_BLOCK_WORDS] {
+    let mut w = [0u32; SHA256_NUM_ROUNDS + SHA256_BLOCK_WORDS];
+    w[..SHA256_BLOCK_WORDS].copy_from_slice(&block);
+    for t in SHA256_BLOCK_WORDS..w.len() {
+        w[t] = small_sigma1(w[t - 2])
+            .wrapping_add(w[t - 7])
+            .wrapping_add(small_sigma0(w[t - 15]))
+            .wrapping_add(w[t - 16]);
+    }
+    w
+}
+
+/// The working variables `a, b, c, d, e, f, g, h` before every round, followed by the working
+/// variables after the last round.
+pub fn working_variables(
+    state: [u32; SHA256_STATE_WORDS],
+    w: &[u32; SHA256_NUM_ROUNDS + SHA256_BLOCK_WORDS],
+) -> [[u32; SHA256_STATE_WORDS]; SHA256_ROWS_PER_BLOCK] {
+    let mut vars = [state; SHA256_ROWS_PER_BLOCK];
+    for t in 0..SHA256_NUM_ROUNDS {
+        let [a, b, c, d, e, f, g, h] = vars[t];
+        let t1 = h
+            .wrapping_add(big_sigma1(e))
+            .wrapping_add(ch(e, f, g))
+            .wrapping_add(SHA256_K[t])
+            .wrapping_add(w[t]);
+        let t2 = big_sigma0(a).wrapping_add(maj(a, b, c));
+        vars[t + 1] = [t1.wrapping_add(t2), a, b, c, d.wrapping_add(t1), e, f, g];
+    }
+    vars
+}
+
+/// The SHA-256 compression function.
+pub fn sha256_compress(
+    state: [u32; SHA256_STATE_WORDS],
+    block: [u32; SHA256_BLOCK_WORDS],
+) -> [u32; SHA256_STATE_WORDS] {
+    let vars = working_variables(state, &message_schedule(block));
+    let last = vars[SHA256_NUM_ROUNDS];
+    std::array::from_fn(|i| state[i].wrapping_add(last[i]))
+}
+
+/// Carries of adding `terms` in 16-bit limbs: the carry out of the low limb and the carry out
+/// of the high limb.
+pub fn limb_carries(terms: &[u32]) -> [u32; 2] {
+    let lo: u32 = terms.iter().map(|x| x & 0xffff).sum();
+    let hi: u32 = terms.iter().map(|x| x >> 16).sum::<u32>() + (lo >> 16);
+    [lo >> 16, hi >> 16]
+}
@@ -0,0 +1,97 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// This is custom-0 defined in RISC-V spec document
+pub const OPCODE: u8 = 0x0b;
+pub const FUNCT3: u8 = 0b111;
+
+/// Number of bytes in a SHA-256 message block.
+pub const SHA256_BLOCK_BYTES: usize = 64;
+/// Number of bytes in the SHA-256 state, which is also the digest size.
+pub const SHA256_STATE_BYTES: usize = 32;
+
+/// The SHA-256 initial hash value. The state is stored as the big-endian bytes of its eight
+/// 32-bit words, so the final state is the digest.
+pub const SHA256_INIT: [u8; SHA256_STATE_BYTES] = [
+    0x6a, 0x09, 0xe6, 0x67, 0xbb, 0x67, 0xae, 0x85, 0x3c, 0x6e, 0xf3, 0x72, 0xa5, 0x4f, 0xf5, 0x3a,
+    0x51, 0x0e, 0x52, 0x7f, 0x9b, 0x05, 0x68, 0x8c, 0x1f, 0x83, 0xd9, 0xab, 0x5b, 0xe0, 0xcd, 0x19,
+];
+
+/// The sha256 cryptographic hash function.
+#[inline(always)]
+pub fn sha256(input: &[u8]) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    set_sha256(input, &mut output);
+    output
+}
+
+/// Sets `output` to the sha256 hash of `input`.
+pub fn set_sha256(input: &[u8], output: &mut [u8; 32]) {
+    #[cfg(not(target_os = "zkvm"))]
+    {
+        use sha2::Digest;
+        output.copy_from_slice(&sha2::Sha256::digest(input));
+    }
+    #[cfg(target_os = "zkvm")]
+    {
+        *output = SHA256_INIT;
+        let mut blocks = input.chunks_exact(SHA256_BLOCK_BYTES);
+        for block in &mut blocks {
+            let state = output.as_mut_ptr();
+            native_sha256_compress(state, state, block.as_ptr());
+        }
+        // Padding: a single 1 bit, zeros, and the message length in bits as a big-endian u64.
+        let remainder = blocks.remainder();
+        let mut last_blocks = [0u8; 2 * SHA256_BLOCK_BYTES];
+        last_blocks[..remainder.len()].copy_from_slice(remainder);
+        last_blocks[remainder.len()] = 0x80;
+        let padded_len = if remainder.len() + 9 <= SHA256_BLOCK_BYTES {
+            SHA256_BLOCK_BYTES
+        } else {
+            2 * SHA256_BLOCK_BYTES
+        };
+        let bit_len = (input.len() as u64) * 8;
+        last_blocks[padded_len - 8..padded_len].copy_from_slice(&bit_len.to_be_bytes());
+        for block in last_blocks[..padded_len].chunks_exact(SHA256_BLOCK_BYTES) {
+            let state = output.as_mut_ptr();
+            native_sha256_compress(state, state, block.as_ptr());
+        }
+    }
+}
+
+/// Applies the SHA-256 compression function to `state` with one message `block`.
+///
+/// No padding is applied: this is the building block for custom SHA-256 based constructions and
+/// for the `sys_sha_compress`/`sys_sha_buffer` syscalls.
+#[inline(always)]
+pub fn sha256_compress(state: &mut [u8; SHA256_STATE_BYTES], block: &[u8; SHA256_BLOCK_BYTES]) {
+    #[cfg(not(target_os = "zkvm"))]
+    {
+        let mut words: [u32; 8] = core::array::from_fn(|i| {
+            u32::from_be_bytes(state[4 * i..4 * i + 4].try_into().unwrap())
+        });
+        let block = sha2::digest::generic_array::GenericArray::from_slice(block);
+        sha2::compress256(&mut words, core::slice::from_ref(block));
+        for (bytes, word) in state.chunks_exact_mut(4).zip(words) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+    }
+    #[cfg(target_os = "zkvm")]
+    {
+        let state = state.as_mut_ptr();
+        native_sha256_compress(state, state, block.as_ptr());
+    }
+}
+
+/// Native hook for the SHA-256 compression function.
+///
+/// # Safety
+///
+/// The VM reads the input state and the block before writing the output state.
+/// - `state_out` and `state_in` must point to buffers at least 32-bytes long. They may be equal.
+/// - `block` must point to a buffer at least 64-bytes long.
+#[cfg(target_os = "zkvm")]
+#[inline(always)]
+#[no_mangle]
+extern "C" fn native_sha256_compress(state_out: *mut u8, state_in: *const u8, block: *const u8) {
+    openvm_platform::custom_insn_r!(OPCODE, FUNCT3, 0x0, state_out, state_in, block);
+}
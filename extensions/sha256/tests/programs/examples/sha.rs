@@ -0,0 +1,50 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::hint::black_box;
+
+use hex::FromHex;
+use openvm_sha256_guest::{sha256, sha256_compress, SHA256_INIT};
+
+openvm::entry!(main);
+
+pub fn main() {
+    // Covers the padding fitting in the last block (55 bytes), spilling into an extra block (56
+    // bytes) and a full last block (64 bytes).
+    let test_vectors = [
+        ("", "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855"),
+        ("616263", "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD"),
+        ("6162636462636465636465666465666765666768666768696768696A68696A6B696A6B6C6A6B6C6D6B6C6D6E6C6D6E6F6D6E6F706E6F7071", "248D6A61D20638B8E5C026930C3E6039A33CE45964FF2167F6ECEDD419DB06C1"),
+        ("61616161616161616161616161616161616161616161616161616161616161616161616161616161616161616161616161616161616161", "9F4390F8D30C2DD92EC9F095B65E2B9AE9B0A925A5258E241C9F1E910F734318"),
+        ("6161616161616161616161616161616161616161616161616161616161616161616161616161616161616161616161616161616161616161", "B35439A4AC6F0948B6D6F9E3C6AF0F5F590CE20F1BDE7090EF7970686EC6738A"),
+        ("61616161616161616161616161616161616161616161616161616161616161616161616161616161616161616161616161616161616161616161616161616161", "FFE054FE7AE0CB6DC65C3AF9B61D5209F439851DB43D0BA5997337DF154668EB"),
+    ];
+    for (input, expected_output) in test_vectors.iter() {
+        let input = Vec::from_hex(input).unwrap();
+        let expected_output = Vec::from_hex(expected_output).unwrap();
+        let output = sha256(&black_box(input));
+        if output != *expected_output {
+            panic!();
+        }
+    }
+
+    // Multi-block input.
+    let input: Vec<u8> = (0..=255u8).cycle().take(512).collect();
+    let expected_output =
+        Vec::from_hex("110009DCEE21620B166F3ABFECB5EFF7A873BE729D1C2D53822E7ACC5F34EB9B").unwrap();
+    if sha256(&black_box(input)) != *expected_output {
+        panic!();
+    }
+
+    // The compression function on its own: the padded empty message is a single block.
+    let mut state = SHA256_INIT;
+    let mut block = [0u8; 64];
+    block[0] = 0x80;
+    sha256_compress(&mut state, &black_box(block));
+    if state != sha256(&[]) {
+        panic!();
+    }
+}